@@ -0,0 +1,181 @@
+//! Compares the all-pairs deduplication path with the blocking strategies,
+//! and transitive clustering with and without a diameter bound.
+//!
+//! Run with `cargo bench --bench dedupe`.
+
+use biblib::Citation;
+use biblib::Date;
+use biblib::dedupe::{BlockingStrategy, Clustering, Deduplicator, DeduplicatorConfig};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+const CONSONANTS: [&str; 20] = [
+    "b", "c", "d", "f", "g", "h", "j", "k", "l", "m", "n", "p", "qu", "r", "s", "t", "v", "w", "x",
+    "z",
+];
+const VOWELS: [&str; 6] = ["a", "e", "i", "o", "u", "y"];
+
+/// Deterministic xorshift generator so every run uses the same records.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 16) as usize
+    }
+}
+
+/// `count` records of which about a third have a near-identical copy: a
+/// different case, a dropped trailing word or a missing DOI.
+fn citations(count: usize) -> Vec<Citation> {
+    let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+    let mut citations = Vec::with_capacity(count);
+    while citations.len() < count {
+        let id = citations.len();
+        let title = (0..8 + rng.next() % 8)
+            .map(|_| {
+                (0..1 + rng.next() % 3)
+                    .map(|_| {
+                        let consonant = CONSONANTS[rng.next() % CONSONANTS.len()];
+                        let vowel = VOWELS[rng.next() % VOWELS.len()];
+                        format!("{consonant}{vowel}")
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let citation = Citation {
+            title,
+            doi: Some(format!("10.1000/{id}")),
+            journal: Some(format!("Journal {}", rng.next() % 50)),
+            volume: Some((rng.next() % 40).to_string()),
+            pages: Some(format!("{}-{}", id, id + 9)),
+            date: Some(Date {
+                year: 2000 + (rng.next() % 20) as i32,
+                month: None,
+                day: None,
+                season: None,
+                end: None,
+            }),
+            ..Default::default()
+        };
+
+        if rng.next().is_multiple_of(3) {
+            citations.push(variant(&citation, &mut rng));
+        }
+        citations.push(citation);
+    }
+    citations.truncate(count);
+    citations
+}
+
+/// A near-identical copy of `citation`: a different case, a dropped
+/// trailing word or a missing DOI.
+fn variant(citation: &Citation, rng: &mut Rng) -> Citation {
+    let mut copy = citation.clone();
+    match rng.next() % 3 {
+        0 => copy.title = copy.title.to_uppercase(),
+        1 => copy
+            .title
+            .truncate(copy.title.rfind(' ').unwrap_or(copy.title.len())),
+        _ => copy.doi = None,
+    }
+    copy
+}
+
+/// `count` records in groups of up to 16 copies of one record, so
+/// transitive clustering builds large groups.
+fn grouped_citations(count: usize) -> Vec<Citation> {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+    let mut grouped = Vec::with_capacity(count);
+    for citation in citations(count) {
+        if grouped.len() >= count {
+            break;
+        }
+        for _ in 0..rng.next() % 16 {
+            grouped.push(variant(&citation, &mut rng));
+        }
+        grouped.push(citation);
+    }
+    grouped.truncate(count);
+    grouped
+}
+
+fn blocking(c: &mut Criterion) {
+    let strategies: [(&str, Vec<BlockingStrategy>); 5] = [
+        ("all_pairs", Vec::new()),
+        ("identifier", vec![BlockingStrategy::Identifier]),
+        (
+            "min_hash",
+            vec![BlockingStrategy::MinHash {
+                shingle: 3,
+                bands: 20,
+                rows: 3,
+            }],
+        ),
+        (
+            "sorted_neighbourhood",
+            vec![BlockingStrategy::SortedNeighbourhood { window: 10 }],
+        ),
+        ("recommended", BlockingStrategy::recommended()),
+    ];
+
+    let mut group = c.benchmark_group("dedupe");
+    group.sample_size(10);
+    for count in [1_000, 10_000] {
+        let records = citations(count);
+        for group_by_year in [false, true] {
+            for (name, blocking) in &strategies {
+                let config = DeduplicatorConfig {
+                    group_by_year,
+                    blocking: blocking.clone(),
+                    ..Default::default()
+                };
+                let id = if group_by_year {
+                    format!("{name}/by_year")
+                } else {
+                    (*name).to_string()
+                };
+                group.bench_with_input(BenchmarkId::new(id, count), &records, |b, records| {
+                    b.iter(|| {
+                        Deduplicator::new()
+                            .with_config(config.clone())
+                            .find_duplicates(records)
+                            .unwrap()
+                    });
+                });
+            }
+        }
+    }
+    group.finish();
+}
+
+fn clustering(c: &mut Criterion) {
+    let records = grouped_citations(10_000);
+    let mut group = c.benchmark_group("dedupe_clustering");
+    group.sample_size(10);
+    for (name, max_diameter) in [("transitive", None), ("max_diameter_2", Some(2))] {
+        let config = DeduplicatorConfig {
+            clustering: Clustering::Transitive { max_diameter },
+            blocking: BlockingStrategy::recommended(),
+            ..Default::default()
+        };
+        group.bench_with_input(
+            BenchmarkId::new(name, records.len()),
+            &records,
+            |b, records| {
+                b.iter(|| {
+                    Deduplicator::new()
+                        .with_config(config.clone())
+                        .find_duplicates(records)
+                        .unwrap()
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, blocking, clustering);
+criterion_main!(benches);
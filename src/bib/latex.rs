@@ -0,0 +1,529 @@
+//! LaTeX-to-Unicode decoding for `.bib` field values.
+//!
+//! BibTeX values are LaTeX source: `{\"u}ber`, `\'{e}`, `{\ss}`, `$\alpha$`
+//! and `\textit{...}` all appear in real exports, along with braces that only
+//! protect capitalisation. [`decode`] turns such a value into plain Unicode
+//! text so it compares equal to the same value from other formats.
+//!
+//! Decoding handles accent commands, special letters such as `\ss` and `\o`,
+//! escaped characters, common text and math symbols, the `--`, `---`, ``` `` ```
+//! and `''` ligatures, and ties. [`decode_number`] keeps `--` and `---` as
+//! written for fields such as `volume` that hold numbers or ranges. Formatting commands such as `\textit` and
+//! `\emph` are dropped and their argument kept; unknown commands are dropped
+//! the same way. All unescaped braces are removed.
+
+/// Accent commands, with the combining mark used when no precomposed
+/// character exists and pairs of base and precomposed characters.
+const ACCENTS: [(char, char, &str); 15] = [
+    (
+        '\'',
+        '\u{0301}',
+        "aáeéiíoóuúyýAÁEÉIÍOÓUÚYÝcćCĆgǵGǴlĺLĹnńNŃrŕRŔsśSŚzźZŹ",
+    ),
+    ('`', '\u{0300}', "aàeèiìoòuùAÀEÈIÌOÒUÙnǹNǸ"),
+    (
+        '^',
+        '\u{0302}',
+        "aâeêiîoôuûAÂEÊIÎOÔUÛcĉCĈgĝGĜhĥHĤjĵJĴsŝSŜwŵWŴyŷYŶ",
+    ),
+    ('"', '\u{0308}', "aäeëiïoöuüyÿAÄEËIÏOÖUÜYŸ"),
+    ('~', '\u{0303}', "aãiĩnñoõuũAÃIĨNÑOÕUŨ"),
+    ('=', '\u{0304}', "aāeēiīoōuūAĀEĒIĪOŌUŪ"),
+    ('.', '\u{0307}', "cċeėgġzżCĊEĖGĠIİZŻ"),
+    ('u', '\u{0306}', "aăeĕgğiĭoŏuŭAĂEĔGĞIĬOŎUŬ"),
+    ('v', '\u{030C}', "cčdďeěnňrřsštťzžCČDĎEĚNŇRŘSŠTŤZŽ"),
+    ('H', '\u{030B}', "oőuűOŐUŰ"),
+    ('c', '\u{0327}', "cçgģkķlļnņrŗsştţCÇGĢKĶLĻNŅRŖSŞTŢ"),
+    ('k', '\u{0328}', "aąeęiįuųAĄEĘIĮUŲ"),
+    ('r', '\u{030A}', "aåuůAÅUŮ"),
+    (
+        'd',
+        '\u{0323}',
+        "aạdḍeẹhḥiịlḷmṃnṇoọrṛsṣtṭuụzẓADḌEẸHḤIỊLḶMṂNṆOỌRṚSṢTṬUỤZẒ",
+    ),
+    ('b', '\u{0331}', "bḇdḏkḵlḻnṉrṟtṯzẕBḆDḎKḴLḺNṈRṞTṮZẔ"),
+];
+
+/// Special letter commands. Like accents, these swallow the spaces after
+/// them, so `Stra\ss e` is one word.
+const LETTERS: [(&str, &str); 22] = [
+    ("ss", "ß"),
+    ("SS", "SS"),
+    ("o", "ø"),
+    ("O", "Ø"),
+    ("ae", "æ"),
+    ("AE", "Æ"),
+    ("oe", "œ"),
+    ("OE", "Œ"),
+    ("aa", "å"),
+    ("AA", "Å"),
+    ("l", "ł"),
+    ("L", "Ł"),
+    ("i", "ı"),
+    ("j", "ȷ"),
+    ("dh", "ð"),
+    ("DH", "Ð"),
+    ("th", "þ"),
+    ("TH", "Þ"),
+    ("ng", "ŋ"),
+    ("NG", "Ŋ"),
+    ("dj", "đ"),
+    ("DJ", "Đ"),
+];
+
+/// Commands that stand for a fixed string, in text or math mode.
+const SYMBOLS: [(&str, &str); 99] = [
+    // Text symbols.
+    ("textendash", "–"),
+    ("textemdash", "—"),
+    ("ldots", "…"),
+    ("dots", "…"),
+    ("textellipsis", "…"),
+    ("textquoteleft", "‘"),
+    ("textquoteright", "’"),
+    ("textquotedblleft", "“"),
+    ("textquotedblright", "”"),
+    ("guillemotleft", "«"),
+    ("guillemotright", "»"),
+    ("textbackslash", "\\"),
+    ("textasciitilde", "~"),
+    ("textasciicircum", "^"),
+    ("textunderscore", "_"),
+    ("textbar", "|"),
+    ("textless", "<"),
+    ("textgreater", ">"),
+    ("S", "§"),
+    ("P", "¶"),
+    ("dag", "†"),
+    ("ddag", "‡"),
+    ("copyright", "©"),
+    ("textcopyright", "©"),
+    ("textregistered", "®"),
+    ("texttrademark", "™"),
+    ("pounds", "£"),
+    ("textsterling", "£"),
+    ("euro", "€"),
+    ("texteuro", "€"),
+    ("textdegree", "°"),
+    ("degree", "°"),
+    ("textperthousand", "‰"),
+    ("textbullet", "•"),
+    ("textmu", "µ"),
+    // Math symbols.
+    ("pm", "±"),
+    ("mp", "∓"),
+    ("times", "×"),
+    ("div", "÷"),
+    ("cdot", "·"),
+    ("cdots", "⋯"),
+    ("ast", "∗"),
+    ("star", "⋆"),
+    ("bullet", "•"),
+    ("circ", "∘"),
+    ("le", "≤"),
+    ("leq", "≤"),
+    ("ge", "≥"),
+    ("geq", "≥"),
+    ("ne", "≠"),
+    ("neq", "≠"),
+    ("ll", "≪"),
+    ("gg", "≫"),
+    ("approx", "≈"),
+    ("sim", "∼"),
+    ("simeq", "≃"),
+    ("cong", "≅"),
+    ("equiv", "≡"),
+    ("propto", "∝"),
+    ("infty", "∞"),
+    ("partial", "∂"),
+    ("nabla", "∇"),
+    ("sum", "∑"),
+    ("prod", "∏"),
+    ("int", "∫"),
+    ("surd", "√"),
+    ("sqrt", "√"),
+    ("to", "→"),
+    ("rightarrow", "→"),
+    ("leftarrow", "←"),
+    ("leftrightarrow", "↔"),
+    ("Rightarrow", "⇒"),
+    ("Leftarrow", "⇐"),
+    ("Leftrightarrow", "⇔"),
+    ("uparrow", "↑"),
+    ("downarrow", "↓"),
+    ("in", "∈"),
+    ("notin", "∉"),
+    ("subset", "⊂"),
+    ("supset", "⊃"),
+    ("subseteq", "⊆"),
+    ("supseteq", "⊇"),
+    ("cup", "∪"),
+    ("cap", "∩"),
+    ("emptyset", "∅"),
+    ("forall", "∀"),
+    ("exists", "∃"),
+    ("neg", "¬"),
+    ("wedge", "∧"),
+    ("vee", "∨"),
+    ("langle", "⟨"),
+    ("rangle", "⟩"),
+    ("prime", "′"),
+    ("hbar", "ℏ"),
+    ("ell", "ℓ"),
+    ("angle", "∠"),
+    ("perp", "⊥"),
+    ("parallel", "∥"),
+    ("mid", "∣"),
+];
+
+/// Greek letter commands, lowercase then uppercase.
+const GREEK: [(&str, char); 40] = [
+    ("alpha", 'α'),
+    ("beta", 'β'),
+    ("gamma", 'γ'),
+    ("delta", 'δ'),
+    ("epsilon", 'ϵ'),
+    ("varepsilon", 'ε'),
+    ("zeta", 'ζ'),
+    ("eta", 'η'),
+    ("theta", 'θ'),
+    ("vartheta", 'ϑ'),
+    ("iota", 'ι'),
+    ("kappa", 'κ'),
+    ("lambda", 'λ'),
+    ("mu", 'μ'),
+    ("nu", 'ν'),
+    ("xi", 'ξ'),
+    ("pi", 'π'),
+    ("varpi", 'ϖ'),
+    ("rho", 'ρ'),
+    ("varrho", 'ϱ'),
+    ("sigma", 'σ'),
+    ("varsigma", 'ς'),
+    ("tau", 'τ'),
+    ("upsilon", 'υ'),
+    ("phi", 'ϕ'),
+    ("varphi", 'φ'),
+    ("chi", 'χ'),
+    ("psi", 'ψ'),
+    ("omega", 'ω'),
+    ("Gamma", 'Γ'),
+    ("Delta", 'Δ'),
+    ("Theta", 'Θ'),
+    ("Lambda", 'Λ'),
+    ("Xi", 'Ξ'),
+    ("Pi", 'Π'),
+    ("Sigma", 'Σ'),
+    ("Upsilon", 'Υ'),
+    ("Phi", 'Φ'),
+    ("Psi", 'Ψ'),
+    ("Omega", 'Ω'),
+];
+
+/// Commands whose argument is a sort or case-protection hint rather than text.
+const DROPPED_ARGUMENT: [&str; 2] = ["noopsort", "sortkey"];
+
+/// Decode a LaTeX field value to plain Unicode text.
+pub(crate) fn decode(input: &str) -> String {
+    if !needs_decoding(input) {
+        return input.to_string();
+    }
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut output = String::with_capacity(input.len());
+    Decoder::new(&chars, false, true).run(&mut output);
+    output
+}
+
+/// Decode a LaTeX field value that holds a number or range, keeping `--` and
+/// `---` as written.
+pub(crate) fn decode_number(input: &str) -> String {
+    if !needs_decoding(input) {
+        return input.to_string();
+    }
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut output = String::with_capacity(input.len());
+    Decoder::new(&chars, false, false).run(&mut output);
+    output
+}
+
+fn needs_decoding(input: &str) -> bool {
+    input.contains(['\\', '{', '}', '$', '~'])
+        || input.contains("--")
+        || input.contains("``")
+        || input.contains("''")
+}
+
+struct Decoder<'a> {
+    chars: &'a [char],
+    pos: usize,
+    math: bool,
+    /// Whether `--` and `---` become dashes.
+    dashes: bool,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(chars: &'a [char], math: bool, dashes: bool) -> Self {
+        Self {
+            chars,
+            pos: 0,
+            math,
+            dashes,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn run(&mut self, output: &mut String) {
+        while let Some(ch) = self.peek() {
+            match ch {
+                '\\' => self.command(output),
+                '{' | '}' => self.pos += 1,
+                '$' => {
+                    self.math = !self.math;
+                    self.pos += 1;
+                }
+                '~' => {
+                    output.push(' ');
+                    self.pos += 1;
+                }
+                '^' | '_' if self.math => self.pos += 1,
+                '-' if self.dashes && !self.math && self.peek_at(1) == Some('-') => {
+                    if self.peek_at(2) == Some('-') {
+                        output.push('—');
+                        self.pos += 3;
+                    } else {
+                        output.push('–');
+                        self.pos += 2;
+                    }
+                }
+                '`' if !self.math => {
+                    if self.peek_at(1) == Some('`') {
+                        output.push('“');
+                        self.pos += 2;
+                    } else {
+                        output.push('‘');
+                        self.pos += 1;
+                    }
+                }
+                '\'' if !self.math && self.peek_at(1) == Some('\'') => {
+                    output.push('”');
+                    self.pos += 2;
+                }
+                _ => {
+                    output.push(ch);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    /// Decode the command starting at the backslash under the cursor.
+    fn command(&mut self, output: &mut String) {
+        self.pos += 1;
+        let Some(first) = self.peek() else {
+            return;
+        };
+
+        if !first.is_ascii_alphabetic() {
+            self.pos += 1;
+            match first {
+                '\'' | '`' | '^' | '"' | '~' | '=' | '.' => self.accent(first, output),
+                '\\' | ' ' | '\n' | '\t' | ',' | ';' | ':' => output.push(' '),
+                '-' | '/' | '!' | '@' => {}
+                _ => output.push(first),
+            }
+            return;
+        }
+
+        let start = self.pos;
+        while self.peek().is_some_and(|ch| ch.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        let name = self.chars[start..self.pos].iter().collect::<String>();
+
+        // Symbols keep the spaces after them, which a reader of the plain
+        // text expects around `≤` or `…`.
+        if let Some((_, text)) = SYMBOLS.iter().find(|(command, _)| *command == name) {
+            output.push_str(text);
+            return;
+        }
+        if let Some((_, letter)) = GREEK.iter().find(|(command, _)| *command == name) {
+            output.push(*letter);
+            return;
+        }
+
+        self.skip_spaces();
+        if let [mark] = name.as_bytes()
+            && ACCENTS
+                .iter()
+                .any(|(accent, _, _)| *accent == char::from(*mark))
+        {
+            self.accent(char::from(*mark), output);
+        } else if let Some((_, text)) = LETTERS.iter().find(|(command, _)| *command == name) {
+            output.push_str(text);
+        } else if name == "url" {
+            if let Some(argument) = self.take_group() {
+                output.extend(argument);
+            }
+        } else if DROPPED_ARGUMENT.contains(&name.as_str()) {
+            self.take_group();
+        }
+        // Formatting and unknown commands are dropped; their braced argument,
+        // if any, is decoded as ordinary text.
+    }
+
+    /// Apply an accent to the next character or group.
+    fn accent(&mut self, mark: char, output: &mut String) {
+        self.skip_spaces();
+        let mut argument = String::new();
+        match self.peek() {
+            Some('{') => {
+                if let Some(group) = self.take_group() {
+                    Decoder::new(group, self.math, self.dashes).run(&mut argument);
+                }
+            }
+            Some('\\') => self.command(&mut argument),
+            Some(ch) => {
+                argument.push(ch);
+                self.pos += 1;
+            }
+            None => {}
+        }
+
+        let mut rest = argument.chars();
+        match rest.next() {
+            Some(base) => {
+                output.push_str(&compose(mark, base));
+                output.extend(rest);
+            }
+            // `\~{}` and `\^{}` are the usual way to write a literal tilde or
+            // caret.
+            None if matches!(mark, '~' | '^') => output.push(mark),
+            None => {}
+        }
+    }
+
+    /// Take the braced group under the cursor, without its braces.
+    fn take_group(&mut self) -> Option<&'a [char]> {
+        if self.peek() != Some('{') {
+            return None;
+        }
+        let start = self.pos + 1;
+        let mut depth = 0usize;
+        while let Some(ch) = self.peek() {
+            match ch {
+                '\\' => self.pos += 1,
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.pos += 1;
+                        return Some(&self.chars[start..self.pos - 1]);
+                    }
+                }
+                _ => {}
+            }
+            self.pos += 1;
+        }
+        Some(&self.chars[start.min(self.chars.len())..])
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+}
+
+/// Combine an accent with its base character, preferring a precomposed
+/// character.
+fn compose(mark: char, base: char) -> String {
+    let base = match base {
+        'ı' => 'i',
+        'ȷ' => 'j',
+        other => other,
+    };
+    let Some((_, combining, pairs)) = ACCENTS.iter().find(|(accent, _, _)| *accent == mark) else {
+        return base.to_string();
+    };
+
+    let mut chars = pairs.chars();
+    while let (Some(plain), Some(composed)) = (chars.next(), chars.next()) {
+        if plain == base {
+            return composed.to_string();
+        }
+    }
+    [base, *combining].iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(r#"{\"u}ber"#, "über")]
+    #[case(r#"\"{u}ber"#, "über")]
+    #[case(r"Caf\'{e}", "Café")]
+    #[case(r"Caf\'e", "Café")]
+    #[case(r"Gau{\ss}", "Gauß")]
+    #[case(r"Stra\ss e", "Straße")]
+    #[case(r"{\O}rsted and {\aa}", "Ørsted and å")]
+    #[case(r"Ko{\l}odziej", "Kołodziej")]
+    #[case(r"Erd\H{o}s", "Erdős")]
+    #[case(r"Dvo\v{r}\'ak", "Dvořák")]
+    #[case(r"Gar\c{c}on", "Garçon")]
+    #[case(r#"na\"{\i}ve"#, "naïve")]
+    #[case(r"\`a la", "à la")]
+    #[case(r"\t{oo}", "oo")]
+    #[case(r"\v{q}", "q\u{030C}")]
+    fn test_decode_letters(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(decode(input), expected);
+    }
+
+    #[rstest]
+    #[case(r"The {$\alpha$}-helix", "The α-helix")]
+    #[case(r"$\beta$-blockers and $\Omega$", "β-blockers and Ω")]
+    #[case(r"CO$_2$ and $x^{2}$", "CO2 and x2")]
+    #[case(r"$p \leq 0.05$", "p ≤ 0.05")]
+    #[case(r"10\% of R\&D", "10% of R&D")]
+    #[case(r"pages 1--10 --- and ``quoted''", "pages 1–10 — and “quoted”")]
+    #[case(r"Smith~et~al.", "Smith et al.")]
+    #[case(r"a\textbackslash{}b \~{} c", r"a\b ~ c")]
+    fn test_decode_symbols(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(decode(input), expected);
+    }
+
+    #[rstest]
+    #[case(r"\textit{In vivo} study", "In vivo study")]
+    #[case(r"\emph{Drosophila} {\em genetics}", "Drosophila genetics")]
+    #[case(r"{{The}} {RNA} World", "The RNA World")]
+    #[case(r"\textbf{\textsc{Nested}} text", "Nested text")]
+    #[case(r"{\noopsort{b}}Beta", "Beta")]
+    #[case(r"\url{https://example.com/~user}", "https://example.com/~user")]
+    #[case(r"\unknowncommand{kept}", "kept")]
+    fn test_decode_formatting(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(decode(input), expected);
+    }
+
+    #[test]
+    fn test_decode_number_keeps_dashes() {
+        assert_eq!(decode_number(r"3--4"), "3--4");
+        assert_eq!(decode_number(r"{12}---\textit{13}"), "12---13");
+    }
+
+    #[test]
+    fn test_decode_plain_text_unchanged() {
+        assert_eq!(
+            decode("Plain title - with it's text"),
+            "Plain title - with it's text"
+        );
+    }
+}
@@ -21,23 +21,66 @@
 //! assert_eq!(citations[0].title, "Example Article");
 //! assert_eq!(citations[0].doi.as_deref(), Some("10.1000/example"));
 //! ```
+//!
+//! The citation key of each entry is kept in `extra_fields["entrykey"]`, which
+//! [`BibWriter`] reuses when writing the citation back out.
+//!
+//! Field values are decoded from LaTeX to plain Unicode: accents such as
+//! `{\"u}` and `\'{e}`, special letters such as `\ss`, math symbols such as
+//! `$\alpha$` and formatting commands such as `\textit{...}` are converted,
+//! and protective braces are removed. [`BibParser::with_raw_latex`] keeps the
+//! undecoded values as well.
 
+mod latex;
 mod parse;
+mod write;
 
 use crate::error::ParseError;
-use crate::{Citation, CitationParser};
+use crate::stream::parse_whole;
+use crate::{
+    Citation, CitationFormat, CitationParser, CitationWriter, ParseOptions, ParseReport,
+    collect_report,
+};
 pub(crate) use parse::looks_like_bib;
-use parse::parse_bib;
+use parse::{bib_records, bib_records_with_warnings, parse_bib};
+pub use write::{BibDialect, BibWriter, DEFAULT_KEY_PATTERN};
 
 /// Parser for BibTeX / BibLaTeX (`.bib`) files.
 #[derive(Debug, Clone, Default)]
-pub struct BibParser;
+pub struct BibParser {
+    keep_raw: bool,
+}
 
 impl BibParser {
     /// Creates a new `.bib` parser instance.
     #[must_use]
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Keep the value of each field that LaTeX decoding changes in
+    /// `extra_fields`, under the field name with a `.raw` suffix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{BibParser, CitationParser};
+    ///
+    /// let input = r#"@article{muller2020,
+    ///   title = {{\"U}ber {RNA}}
+    /// }"#;
+    ///
+    /// let citation = BibParser::new().with_raw_latex(true).parse(input).unwrap().remove(0);
+    /// assert_eq!(citation.title, "Über RNA");
+    /// assert_eq!(
+    ///     citation.extra_fields["title.raw"],
+    ///     vec![r#"{\"U}ber {RNA}"#.to_string()]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_raw_latex(mut self, keep: bool) -> Self {
+        self.keep_raw = keep;
+        self
     }
 }
 
@@ -47,16 +90,46 @@ impl CitationParser for BibParser {
             return Ok(Vec::new());
         }
 
-        parse_bib(input)
+        parse_bib(input, self.keep_raw)
+    }
+
+    /// Reads the whole input, then yields one result per entry.
+    fn parse_reader<'r, R>(
+        &self,
+        reader: R,
+    ) -> Box<dyn Iterator<Item = Result<Citation, ParseError>> + 'r>
+    where
+        R: std::io::Read + 'r,
+    {
+        let keep_raw = self.keep_raw;
+        parse_whole(reader, CitationFormat::Bib, move |input| {
+            bib_records(input, keep_raw)
+        })
+    }
+
+    /// Reports unparseable `date`, `year` and `urldate` values and invalid
+    /// `doi` values as warnings.
+    fn parse_with_options(
+        &self,
+        input: &str,
+        options: &ParseOptions,
+    ) -> Result<ParseReport, ParseError> {
+        collect_report(bib_records_with_warnings(input, self.keep_raw), options)
+    }
+}
+
+impl CitationWriter for BibWriter {
+    fn write(&self, citations: &[Citation]) -> String {
+        self.write_entries(citations)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::CitationFormat;
     #[cfg(feature = "diagnostics")]
     use crate::parse_with_diagnostics;
+    use crate::{CitationFormat, ContributorRole, DateKind};
 
     #[test]
     fn test_parse_simple_article() {
@@ -110,12 +183,11 @@ mod tests {
 
         let citation = BibParser::new().parse(input).unwrap().remove(0);
         assert_eq!(citation.title, "Main Title: Practical Guide");
-        assert_eq!(citation.authors.len(), 1);
-        assert_eq!(citation.authors[0].name, "Doe");
-        assert_eq!(
-            citation.extra_fields.get("editor"),
-            Some(&vec!["Doe, Jane".to_string()])
-        );
+        assert!(citation.authors.is_empty());
+        assert_eq!(citation.contributors.len(), 1);
+        assert_eq!(citation.contributors[0].name, "Doe");
+        assert_eq!(citation.contributors[0].role, ContributorRole::Editor);
+        assert!(!citation.extra_fields.contains_key("editor"));
     }
 
     #[test]
@@ -255,6 +327,36 @@ mod tests {
         assert!(err.span.is_some());
     }
 
+    #[test]
+    fn test_parse_with_options_reports_warnings() {
+        use crate::{OnError, ParseOptions, WarningKind};
+
+        let input = "@article{first, title = {First}, year = 2020}\n\n@article{second,\n  title = {Second},\n  year = {someday},\n  doi = {not a doi}\n}\n";
+        for on_error in [OnError::Fail, OnError::Collect] {
+            let options = ParseOptions::new().with_on_error(on_error);
+            let report = BibParser::new()
+                .parse_with_options(input, &options)
+                .unwrap();
+
+            assert_eq!(report.citations.len(), 2);
+            let kinds: Vec<_> = report.warnings.iter().map(|w| &w.kind).collect();
+            assert_eq!(
+                kinds,
+                vec![
+                    &WarningKind::UnparseableDate {
+                        key: "year".to_string(),
+                        value: "someday".to_string()
+                    },
+                    &WarningKind::InvalidDoi {
+                        key: "doi".to_string(),
+                        value: "not a doi".to_string()
+                    },
+                ]
+            );
+            assert_eq!(report.warnings[0].line, Some(3));
+        }
+    }
+
     #[cfg(feature = "diagnostics")]
     #[test]
     fn test_bib_diagnostics() {
@@ -265,4 +367,177 @@ mod tests {
         assert!(diag.contains("refs.bib"));
         assert!(diag.contains("Bib"));
     }
+
+    #[test]
+    fn test_parse_preserves_entry_key() {
+        let input = "@article{smith2024,\n  title = {Example}\n}";
+        let citation = BibParser::new().parse(input).unwrap().remove(0);
+        assert_eq!(
+            citation.extra_fields.get("entrykey"),
+            Some(&vec!["smith2024".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_parse_normalizes_double_dash_pages() {
+        let input = "@article{pages,\n  title = {Example},\n  pages = {100--110}\n}";
+        let citation = BibParser::new().parse(input).unwrap().remove(0);
+        assert_eq!(citation.pages.as_deref(), Some("100-110"));
+    }
+
+    #[test]
+    fn test_parse_converts_dashes_in_prose_only() {
+        let input = "@article{dashes,\n  title = {Pre--post analysis},\n  volume = {3--4},\n  number = {{1--2}},\n  note = {See pages 5--6}\n}";
+        let citation = BibParser::new().parse(input).unwrap().remove(0);
+        assert_eq!(citation.title, "Pre–post analysis");
+        assert_eq!(citation.volume.as_deref(), Some("3--4"));
+        assert_eq!(citation.issue.as_deref(), Some("1--2"));
+        assert_eq!(
+            citation.extra_fields.get("note"),
+            Some(&vec!["See pages 5–6".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_parse_decodes_latex() {
+        let input = r#"@article{muller2020,
+  title = {{\"U}ber das {$\alpha$}-Helix {Modell} in \textit{E. coli}},
+  author = {M{\"u}ller, J{\"o}rg and Gau{\ss}, Carl Friedrich and {\O}rsted, Hans and {Universit\'{e} de Montr\'eal}},
+  journal = {Revue de l'Acad\'emie},
+  keywords = {R\&D; {\ss}-Laute},
+  url = {https://example.com/~muller},
+  doi = {10.1000/x\_y}
+}"#;
+
+        let citation = BibParser::new().parse(input).unwrap().remove(0);
+        assert_eq!(citation.title, "Über das α-Helix Modell in E. coli");
+        assert_eq!(citation.authors[0].name, "Müller");
+        assert_eq!(citation.authors[0].given_name.as_deref(), Some("Jörg"));
+        assert_eq!(citation.authors[1].name, "Gauß");
+        assert_eq!(citation.authors[2].name, "Ørsted");
+        assert_eq!(citation.authors[3].name, "Université de Montréal");
+        assert_eq!(citation.authors[3].given_name, None);
+        assert_eq!(citation.journal.as_deref(), Some("Revue de l'Académie"));
+        assert_eq!(citation.keywords, vec!["R&D", "ß-Laute"]);
+        assert_eq!(citation.urls, vec!["https://example.com/~muller"]);
+        assert!(!citation.extra_fields.contains_key("title.raw"));
+    }
+
+    #[test]
+    fn test_parse_with_raw_latex_keeps_changed_values() {
+        let input = r#"@article{muller2020,
+  title = {Gene {\"U}bersicht},
+  author = {M{\"u}ller, J.},
+  journal = {Plain Journal},
+  note = {See \emph{supplement}},
+  pages = {1--10}
+}"#;
+
+        let citation = BibParser::new()
+            .with_raw_latex(true)
+            .parse(input)
+            .unwrap()
+            .remove(0);
+        assert_eq!(citation.title, "Gene Übersicht");
+        assert_eq!(
+            citation.extra_fields.get("title.raw"),
+            Some(&vec![r#"Gene {\"U}bersicht"#.to_string()])
+        );
+        assert_eq!(
+            citation.extra_fields.get("author.raw"),
+            Some(&vec![r#"M{\"u}ller, J."#.to_string()])
+        );
+        assert_eq!(
+            citation.extra_fields.get("note"),
+            Some(&vec!["See supplement".to_string()])
+        );
+        assert_eq!(
+            citation.extra_fields.get("note.raw"),
+            Some(&vec![r"See \emph{supplement}".to_string()])
+        );
+        assert!(!citation.extra_fields.contains_key("journal.raw"));
+        assert!(!citation.extra_fields.contains_key("pages.raw"));
+
+        let output = BibWriter::new().write(&[citation]);
+        assert!(!output.contains(".raw"));
+    }
+
+    #[test]
+    fn test_write_round_trip() {
+        let input = r#"@article{smith2024,
+  title = {Example Article},
+  author = {Smith, John A and Doe, Jane and {World Health Organization}},
+  journal = {Journal of Examples},
+  shortjournal = {J Ex},
+  year = {2024},
+  month = may,
+  volume = {12},
+  number = {3},
+  pages = {100--110},
+  publisher = {Example Press},
+  language = {english},
+  abstract = {An abstract.},
+  keywords = {alpha, beta},
+  doi = {10.1000/example},
+  url = {https://example.com/a},
+  urldate = {2024-06-01},
+  issn = {1234-5678},
+  pmid = {12345},
+  note = {Extra note}
+}
+
+@book{doe2020,
+  title = {A Book},
+  editor = {Doe, Jane},
+  translator = {M{\"u}ller, Hans},
+  date = {2020}
+}"#;
+        let citations = BibParser::new().parse(input).unwrap();
+        let output = BibWriter::new().write(&citations);
+        let reparsed = BibParser::new().parse(&output).unwrap();
+
+        assert_eq!(reparsed, citations);
+        assert!(output.starts_with("@article{smith2024,\n"));
+        assert!(output.contains("@book{doe2020,\n"));
+        assert!(output.contains("  editor = {Doe, Jane},\n"));
+        assert!(output.contains("  translator = {Müller, Hans},\n"));
+        assert!(output.contains("  urldate = {2024-06-01},\n"));
+        let accessed = citations[0].date_of(DateKind::Accessed).unwrap();
+        assert_eq!(
+            (accessed.year, accessed.month, accessed.day),
+            (2024, Some(6), Some(1))
+        );
+        assert!(!citations[0].extra_fields.contains_key("urldate"));
+    }
+
+    #[test]
+    fn test_write_biblatex_round_trip() {
+        let input = r#"@inproceedings{conf,
+  title = {Talk},
+  author = {Smith, John},
+  booktitle = {Proceedings},
+  date = {2023-05-09/2023-05-11}
+}"#;
+        let citations = BibParser::new().parse(input).unwrap();
+        let output = BibWriter::new()
+            .with_dialect(BibDialect::BibLatex)
+            .write(&citations);
+
+        assert!(output.contains("  date = {2023-05-09/2023-05-11}\n"));
+        assert!(output.contains("  booktitle = {Proceedings},\n"));
+        assert_eq!(BibParser::new().parse(&output).unwrap(), citations);
+    }
+
+    #[test]
+    fn test_parse_reader_yields_one_result_per_entry() {
+        let input = r#"@article{first, title = {First}}
+@misc{empty, note = {Only a note}}
+@book{third, title = {Third}}"#;
+        let streamed: Vec<_> = BibParser::new().parse_reader(input.as_bytes()).collect();
+
+        assert_eq!(streamed.len(), 3);
+        assert_eq!(streamed[0].as_ref().unwrap().title, "First");
+        assert_eq!(streamed[1].as_ref().unwrap_err().line, Some(2));
+        assert_eq!(streamed[2].as_ref().unwrap().title, "Third");
+    }
 }
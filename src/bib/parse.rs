@@ -1,7 +1,37 @@
-use crate::error::{ParseError, SourceSpan, ValueError, fields as error_fields};
-use crate::{Author, Citation, CitationFormat};
+use crate::bib::latex;
+use crate::error::{
+    ParseError, ParseWarning, SourceSpan, ValueError, WarningKind, fields as error_fields,
+};
+use crate::utils::{detect_identifier, partition_contributors, sort_serial_numbers};
+use crate::{Author, Citation, CitationFormat, ContributorRole, Identifier, Scheme};
 use std::collections::{HashMap, HashSet};
 
+/// `extra_fields` key under which the original citation key is preserved.
+///
+/// Named after the BibLaTeX `entrykey` field so it cannot collide with a
+/// regular data field.
+pub(crate) const ENTRY_KEY_FIELD: &str = "entrykey";
+
+/// Suffix of the `extra_fields` key under which a field's value is kept
+/// before LaTeX decoding, for example `title.raw`.
+///
+/// BibTeX field names cannot contain a `.`, so these keys cannot collide
+/// with a regular data field.
+pub(crate) const RAW_FIELD_SUFFIX: &str = ".raw";
+
+/// Fields that hold identifiers, dates or verbatim text rather than LaTeX
+/// text, and are therefore not decoded.
+const NON_TEXT_FIELDS: [&str; 19] = [
+    "url", "doi", "eprint", "file", "verba", "verbb", "verbc", "pages", "crossref", "xdata", "ids",
+    "date", "year", "month", "urldate", "issn", "isbn", "pmid", "pmcid",
+];
+
+/// Text fields that hold numbers or ranges, where `--` is not a dash.
+/// `pages` is one too, but is not decoded at all.
+const NUMBER_FIELDS: [&str; 8] = [
+    "volume", "volumes", "number", "issue", "edition", "chapter", "part", "eid",
+];
+
 #[derive(Debug, Clone)]
 pub(crate) struct MacroDef {
     expr: FieldExpr,
@@ -52,6 +82,7 @@ struct ResolvedField {
 #[derive(Debug, Clone)]
 struct ResolvedEntry {
     entry_type: String,
+    key: String,
     fields: HashMap<String, Vec<ResolvedField>>,
     start_line: usize,
     span: SourceSpan,
@@ -78,15 +109,38 @@ pub(crate) fn looks_like_bib(content: &str) -> bool {
     matches!(remainder.chars().next(), Some('{') | Some('('))
 }
 
-pub(crate) fn parse_bib(content: &str) -> Result<Vec<Citation>, ParseError> {
+pub(crate) fn parse_bib(content: &str, keep_raw: bool) -> Result<Vec<Citation>, ParseError> {
+    bib_records(content, keep_raw).into_iter().collect()
+}
+
+/// Parse a `.bib` document into one result per entry. A syntax error in the
+/// document is returned as the only item.
+///
+/// With `keep_raw`, the value of each field changed by LaTeX decoding is also
+/// kept in `extra_fields` under [`RAW_FIELD_SUFFIX`].
+pub(crate) fn bib_records(content: &str, keep_raw: bool) -> Vec<Result<Citation, ParseError>> {
+    bib_records_with_warnings(content, keep_raw)
+        .into_iter()
+        .map(|record| record.map(|(citation, _)| citation))
+        .collect()
+}
+
+/// [`bib_records`], keeping the warnings for each citation.
+pub(crate) fn bib_records_with_warnings(
+    content: &str,
+    keep_raw: bool,
+) -> Vec<Result<(Citation, Vec<ParseWarning>), ParseError>> {
     let mut parser = Parser::new(content);
-    let document = parser.parse_document()?;
+    let document = match parser.parse_document() {
+        Ok(document) => document,
+        Err(err) => return vec![Err(err)],
+    };
 
     if document.entries.is_empty() {
-        return Ok(Vec::new());
+        return Vec::new();
     }
 
-    Resolver::new(content, document).into_citations()
+    Resolver::new(document, keep_raw).into_citations()
 }
 
 struct Parser<'a> {
@@ -545,10 +599,11 @@ struct Resolver {
     entry_lookup: HashMap<String, usize>,
     macro_cache: HashMap<String, ResolvedText>,
     entry_cache: HashMap<usize, ResolvedEntry>,
+    keep_raw: bool,
 }
 
 impl Resolver {
-    fn new(_source: &str, document: ParsedDocument) -> Self {
+    fn new(document: ParsedDocument, keep_raw: bool) -> Self {
         let mut entry_lookup = HashMap::new();
         for (index, entry) in document.entries.iter().enumerate() {
             entry_lookup
@@ -562,19 +617,20 @@ impl Resolver {
             entry_lookup,
             macro_cache: HashMap::new(),
             entry_cache: HashMap::new(),
+            keep_raw,
         }
     }
 
-    fn into_citations(mut self) -> Result<Vec<Citation>, ParseError> {
+    fn into_citations(mut self) -> Vec<Result<(Citation, Vec<ParseWarning>), ParseError>> {
         let mut citations = Vec::with_capacity(self.entries.len());
         for index in 0..self.entries.len() {
             if self.entries[index].entry_type.eq_ignore_ascii_case("xdata") {
                 continue;
             }
             let resolved = self.resolve_entry(index, &mut Vec::new());
-            citations.push(self.build_citation(resolved)?);
+            citations.push(self.build_citation(resolved));
         }
-        Ok(citations)
+        citations
     }
 
     fn resolve_entry(&mut self, index: usize, stack: &mut Vec<usize>) -> ResolvedEntry {
@@ -615,6 +671,7 @@ impl Resolver {
 
         let resolved = ResolvedEntry {
             entry_type: raw_entry.entry_type,
+            key: raw_entry.key,
             fields,
             start_line: raw_entry.start_line,
             span: raw_entry.span,
@@ -689,23 +746,68 @@ impl Resolver {
         }
     }
 
-    fn build_citation(&self, resolved: ResolvedEntry) -> Result<Citation, ParseError> {
+    fn build_citation(
+        &self,
+        resolved: ResolvedEntry,
+    ) -> Result<(Citation, Vec<ParseWarning>), ParseError> {
         let ResolvedEntry {
             entry_type,
+            key,
             mut fields,
             start_line,
             span,
         } = resolved;
+        let mut warnings = Vec::new();
+        let mut warn = |kind| {
+            warnings.push(
+                ParseWarning::at_line(start_line, CitationFormat::Bib, kind)
+                    .with_span(span.clone()),
+            );
+        };
+
+        let raw_values = if self.keep_raw {
+            raw_latex_values(&fields)
+        } else {
+            HashMap::new()
+        };
+        // Names are split on braces and `and` before their parts are decoded.
+        let (authors, contributors) = take_contributors(&mut fields);
+        decode_latex_fields(&mut fields);
 
         let title = take_title(&mut fields);
-        let authors = take_authors(&mut fields);
         let journal = take_preferred_value(&mut fields, &["journaltitle", "journal", "booktitle"]);
         let journal_abbr = take_preferred_value(&mut fields, &["shortjournal", "journalabbr"]);
         let date = take_date(&mut fields);
+        if date.is_none()
+            && let Some((key, value)) = ["date", "year"]
+                .into_iter()
+                .find_map(|key| Some((key, first_text(&fields, key)?)))
+        {
+            warn(WarningKind::UnparseableDate {
+                key: key.to_string(),
+                value,
+            });
+        }
+        let url_date = take_url_date(&mut fields);
+        if url_date.is_none()
+            && let Some(value) = first_text(&fields, "urldate")
+        {
+            warn(WarningKind::UnparseableDate {
+                key: "urldate".to_string(),
+                value,
+            });
+        }
+        let dates = url_date
+            .map(|date| crate::TypedDate {
+                kind: crate::DateKind::Accessed,
+                date,
+            })
+            .into_iter()
+            .collect();
         let volume = take_first_value(&mut fields, "volume");
         let issue = take_preferred_value(&mut fields, &["number", "issue"]);
         let pages = take_first_value(&mut fields, "pages")
-            .map(|pages| crate::utils::format_page_numbers(&pages));
+            .map(|pages| crate::utils::format_page_numbers(&pages.replace("--", "-")));
         let publisher = take_first_value(&mut fields, "publisher");
         let language = take_preferred_value(&mut fields, &["language", "langid"]);
         let abstract_text = take_joined_value(&mut fields, "abstract");
@@ -719,10 +821,14 @@ impl Resolver {
         }
 
         let mut doi = None;
-        if let Some(candidate) = take_first_value(&mut fields, "doi")
-            && let Some(formatted) = crate::utils::format_doi(&candidate)
-        {
-            doi = Some(formatted);
+        if let Some(candidate) = take_first_value(&mut fields, "doi") {
+            doi = crate::utils::format_doi(&candidate);
+            if doi.is_none() {
+                warn(WarningKind::InvalidDoi {
+                    key: "doi".to_string(),
+                    value: candidate,
+                });
+            }
         }
 
         let urls = take_all_values(&mut fields, "url");
@@ -735,17 +841,32 @@ impl Resolver {
             }
         }
 
-        let mut issn = take_identifier_values(&mut fields, "issn");
-        issn.extend(take_identifier_values(&mut fields, "isbn"));
+        let has_eprint = has_non_empty_field(&fields, "eprint");
+        let mut identifiers: Vec<Identifier> = accession_number
+            .as_deref()
+            .and_then(detect_identifier)
+            .into_iter()
+            .collect();
+        // Either field may hold ISSNs and ISBNs; values with a wrong check
+        // digit stay with the ISSNs, where the writer tells them apart again.
+        let mut issn = Vec::new();
+        for key in ["issn", "isbn"] {
+            for value in take_identifier_values(&mut fields, key) {
+                sort_serial_numbers(&value, &mut issn, &mut identifiers);
+            }
+        }
+        let issn = dedupe_preserve_order(issn);
+        identifiers.extend(take_arxiv_id(&mut fields));
 
         let has_identity = !title.trim().is_empty()
             || !authors.is_empty()
+            || !contributors.is_empty()
             || doi.is_some()
             || !urls.is_empty()
             || accession_number.is_some()
             || pmid.is_some()
             || pmc_id.is_some()
-            || has_non_empty_field(&fields, "eprint");
+            || has_eprint;
 
         if !has_identity {
             let err = ParseError::new(
@@ -760,13 +881,19 @@ impl Resolver {
             return Err(err.with_span(span));
         }
 
-        Ok(Citation {
+        let mut extra_fields = remaining_extra_fields(fields);
+        extra_fields.extend(raw_values);
+        extra_fields.insert(ENTRY_KEY_FIELD.to_string(), vec![key]);
+
+        let citation = Citation {
             citation_type: vec![entry_type.to_ascii_lowercase()],
             title,
             authors,
+            contributors,
             journal,
             journal_abbr,
             date,
+            dates,
             volume,
             issue,
             pages,
@@ -775,14 +902,16 @@ impl Resolver {
             accession_number,
             pmid,
             pmc_id,
+            identifiers,
             abstract_text,
             keywords,
             urls: dedupe_preserve_order(urls),
             language,
             mesh_terms: Vec::new(),
             publisher,
-            extra_fields: remaining_extra_fields(fields),
-        })
+            extra_fields,
+        };
+        Ok((citation, warnings))
     }
 }
 
@@ -828,6 +957,52 @@ fn collect_reference_keys(values: Option<&Vec<ResolvedField>>) -> Vec<String> {
     keys
 }
 
+fn is_text_field(name: &str) -> bool {
+    !NON_TEXT_FIELDS.contains(&name)
+}
+
+/// Decode the LaTeX in a value of the text field `name`. Dash ligatures are
+/// only converted in prose fields.
+fn decode_field(name: &str, value: &str) -> String {
+    if NUMBER_FIELDS.contains(&name) {
+        latex::decode_number(value)
+    } else {
+        latex::decode(value)
+    }
+}
+
+/// Decode the LaTeX in every text field.
+fn decode_latex_fields(fields: &mut HashMap<String, Vec<ResolvedField>>) {
+    for (name, values) in fields.iter_mut() {
+        if !is_text_field(name) {
+            continue;
+        }
+        for value in values {
+            value.value = decode_field(name, &value.value);
+        }
+    }
+}
+
+/// The text field values that LaTeX decoding changes, keyed by field name
+/// plus [`RAW_FIELD_SUFFIX`].
+fn raw_latex_values(fields: &HashMap<String, Vec<ResolvedField>>) -> HashMap<String, Vec<String>> {
+    let mut raw = HashMap::new();
+    for (name, values) in fields {
+        if !is_text_field(name) {
+            continue;
+        }
+        let changed = values
+            .iter()
+            .map(ResolvedField::canonical_text)
+            .filter(|value| decode_field(name, value) != *value)
+            .collect::<Vec<_>>();
+        if !changed.is_empty() {
+            raw.insert(format!("{name}{RAW_FIELD_SUFFIX}"), changed);
+        }
+    }
+    raw
+}
+
 fn take_title(fields: &mut HashMap<String, Vec<ResolvedField>>) -> String {
     let mut title = take_first_value(fields, "title").unwrap_or_default();
     if let Some(subtitle) = take_first_value(fields, "subtitle") {
@@ -841,16 +1016,24 @@ fn take_title(fields: &mut HashMap<String, Vec<ResolvedField>>) -> String {
     title
 }
 
-fn take_authors(fields: &mut HashMap<String, Vec<ResolvedField>>) -> Vec<Author> {
-    if let Some(author_text) = take_first_value(fields, "author") {
-        return parse_people_list(&author_text);
-    }
+/// Fields holding name lists, and the role of the people in them.
+const CONTRIBUTOR_FIELDS: [(&str, ContributorRole); 3] = [
+    ("author", ContributorRole::Author),
+    ("editor", ContributorRole::Editor),
+    ("translator", ContributorRole::Translator),
+];
 
-    fields
-        .get("editor")
-        .map(|values| join_field_text(values).as_str().to_string())
-        .map(|text| parse_people_list(&text))
-        .unwrap_or_default()
+/// Take the name list fields, returning the authors and other contributors.
+fn take_contributors(
+    fields: &mut HashMap<String, Vec<ResolvedField>>,
+) -> (Vec<Author>, Vec<Author>) {
+    let mut people = Vec::new();
+    for (field, role) in CONTRIBUTOR_FIELDS {
+        if let Some(text) = take_first_value(fields, field) {
+            people.extend(parse_people_list(&text, role));
+        }
+    }
+    partition_contributors(people)
 }
 
 fn take_date(fields: &mut HashMap<String, Vec<ResolvedField>>) -> Option<crate::Date> {
@@ -895,6 +1078,30 @@ fn take_date(fields: &mut HashMap<String, Vec<ResolvedField>>) -> Option<crate::
     None
 }
 
+/// The first non-empty value of `key`, leaving it in place.
+fn first_text(fields: &HashMap<String, Vec<ResolvedField>>, key: &str) -> Option<String> {
+    fields.get(key).and_then(|values| {
+        values
+            .iter()
+            .map(ResolvedField::canonical_text)
+            .find(|value| !value.trim().is_empty())
+    })
+}
+
+/// Take the `urldate` access date when it parses; otherwise it stays in
+/// `extra_fields`.
+fn take_url_date(fields: &mut HashMap<String, Vec<ResolvedField>>) -> Option<crate::Date> {
+    let date = fields.get("urldate").and_then(|values| {
+        values
+            .iter()
+            .map(ResolvedField::canonical_text)
+            .find(|value| !value.trim().is_empty())
+            .and_then(|value| crate::utils::parse_bib_date(&value))
+    })?;
+    fields.remove("urldate");
+    Some(date)
+}
+
 fn take_keywords(fields: &mut HashMap<String, Vec<ResolvedField>>) -> Vec<String> {
     let Some(values) = fields.remove("keywords") else {
         return Vec::new();
@@ -935,20 +1142,38 @@ fn take_identifier_values(
         if text.trim().is_empty() {
             continue;
         }
-        if key == "issn" {
-            let split = crate::utils::split_issns(&text);
-            if split.is_empty() {
-                identifiers.push(text);
-            } else {
-                identifiers.extend(split);
-            }
-        } else {
-            identifiers.push(text);
-        }
+        identifiers.push(text);
     }
     dedupe_preserve_order(identifiers)
 }
 
+/// An arXiv `eprint`, marked by `eprinttype` (BibLaTeX) or `archiveprefix`
+/// (BibTeX) or by an `arXiv:` prefix. The fields are removed when the ID is
+/// valid; other e-prints are left in place.
+fn take_arxiv_id(fields: &mut HashMap<String, Vec<ResolvedField>>) -> Option<Identifier> {
+    let is_arxiv = ["eprinttype", "archiveprefix"].iter().any(|key| {
+        fields.get(*key).is_some_and(|values| {
+            values
+                .iter()
+                .any(|value| value.canonical_text().trim().eq_ignore_ascii_case("arxiv"))
+        })
+    });
+    let eprint = fields
+        .get("eprint")?
+        .iter()
+        .map(ResolvedField::canonical_text)
+        .find(|value| !value.trim().is_empty())?;
+    let identifier = if is_arxiv {
+        Identifier::new(Scheme::Arxiv, &eprint)
+    } else {
+        Identifier::detect(&eprint).filter(|identifier| identifier.scheme == Scheme::Arxiv)
+    }?;
+    for key in ["eprint", "eprinttype", "archiveprefix"] {
+        fields.remove(key);
+    }
+    Some(identifier)
+}
+
 fn take_joined_value(
     fields: &mut HashMap<String, Vec<ResolvedField>>,
     key: &str,
@@ -1040,10 +1265,10 @@ impl ResolvedField {
     }
 }
 
-fn parse_people_list(value: &str) -> Vec<Author> {
+fn parse_people_list(value: &str, role: ContributorRole) -> Vec<Author> {
     split_top_level_and(value)
         .into_iter()
-        .filter_map(|person| parse_person(&person))
+        .filter_map(|person| parse_person(&person, role))
         .collect()
 }
 
@@ -1081,7 +1306,7 @@ fn split_top_level_and(value: &str) -> Vec<String> {
     parts
 }
 
-fn parse_person(person: &str) -> Option<Author> {
+fn parse_person(person: &str, role: ContributorRole) -> Option<Author> {
     let trimmed = person.trim();
     if trimmed.is_empty() {
         return None;
@@ -1089,10 +1314,13 @@ fn parse_person(person: &str) -> Option<Author> {
 
     if let Some(name) = strip_wrapping_braces(trimmed) {
         return Some(Author {
-            name,
+            name: latex::decode(&name).trim().to_string(),
             given_name: None,
             middle_name: None,
             affiliations: Vec::new(),
+            role,
+            orcid: None,
+            is_organization: false,
         });
     }
 
@@ -1114,8 +1342,8 @@ fn parse_person(person: &str) -> Option<Author> {
         ),
     };
 
-    let family = family.trim().to_string();
-    let given = given.trim().to_string();
+    let family = latex::decode(&family).trim().to_string();
+    let given = latex::decode(&given).trim().to_string();
     let (given_name, middle_name) = if given.is_empty() {
         (None, None)
     } else {
@@ -1127,6 +1355,9 @@ fn parse_person(person: &str) -> Option<Author> {
         given_name,
         middle_name,
         affiliations: Vec::new(),
+        role,
+        orcid: None,
+        is_organization: false,
     })
 }
 
@@ -1184,15 +1415,6 @@ fn strip_wrapping_braces(value: &str) -> Option<String> {
     Some(value[1..value.len() - 1].trim().to_string())
 }
 
-fn join_field_text(values: &[ResolvedField]) -> String {
-    values
-        .iter()
-        .map(ResolvedField::canonical_text)
-        .filter(|value| !value.trim().is_empty())
-        .collect::<Vec<_>>()
-        .join(" and ")
-}
-
 fn line_and_column_at(source: &str, pos: usize) -> (usize, usize) {
     let mut line = 1usize;
     let mut column = 1usize;
@@ -0,0 +1,726 @@
+//! BibTeX / BibLaTeX writing implementation.
+//!
+//! Citations are written as `@type{key, field = {value}, ...}` entries. Field
+//! values are treated as plain text and LaTeX special characters are escaped,
+//! except for verbatim fields such as `url` and `doi`.
+
+use crate::bib::parse::{ENTRY_KEY_FIELD, RAW_FIELD_SUFFIX};
+use crate::{Author, Citation, ContributorRole, Date, DateKind, Scheme, Season};
+use std::collections::HashSet;
+
+/// Default citation key pattern used when a citation has no original key.
+pub const DEFAULT_KEY_PATTERN: &str = "{author}{year}{titleword}";
+
+/// Words skipped when picking the `{titleword}` for a generated key.
+const TITLE_STOPWORDS: [&str; 12] = [
+    "a", "an", "and", "at", "for", "from", "in", "of", "on", "the", "to", "with",
+];
+
+const MONTH_MACROS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Field naming conventions used when writing `.bib` output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BibDialect {
+    /// Classic BibTeX: `journal`, `year` + `month`, `phdthesis`, `techreport`.
+    #[default]
+    BibTex,
+    /// BibLaTeX: `journaltitle`, `date`, `thesis`, `report`.
+    BibLatex,
+}
+
+/// Writer for BibTeX / BibLaTeX (`.bib`) files.
+///
+/// The original citation key recorded by [`crate::BibParser`] is reused when
+/// present. Other citations get a key generated from the configured pattern,
+/// which supports the `{author}`, `{year}` and `{titleword}` placeholders.
+/// Keys are unique within one [`crate::CitationWriter::write`] call: repeated
+/// keys get an `a`, `b`, ... suffix in input order.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::{BibDialect, BibWriter, Citation, CitationWriter, Date};
+///
+/// let citation = Citation {
+///     citation_type: vec!["Journal Article".to_string()],
+///     title: "Deep Learning for Cats".to_string(),
+///     date: Some(Date { year: 2024, month: None, day: None, season: None, end: None }),
+///     ..Default::default()
+/// };
+///
+/// let output = BibWriter::new()
+///     .with_dialect(BibDialect::BibLatex)
+///     .with_key_pattern("{titleword}{year}")
+///     .write(&[citation]);
+/// assert!(output.starts_with("@article{deep2024,"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BibWriter {
+    dialect: BibDialect,
+    key_pattern: String,
+}
+
+impl Default for BibWriter {
+    fn default() -> Self {
+        Self {
+            dialect: BibDialect::default(),
+            key_pattern: DEFAULT_KEY_PATTERN.to_string(),
+        }
+    }
+}
+
+impl BibWriter {
+    /// Creates a new `.bib` writer using BibTeX conventions and the default key pattern.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the field naming dialect.
+    #[must_use]
+    pub fn with_dialect(mut self, dialect: BibDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Sets the pattern used to generate citation keys.
+    #[must_use]
+    pub fn with_key_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.key_pattern = pattern.into();
+        self
+    }
+
+    pub(crate) fn write_entries(&self, citations: &[Citation]) -> String {
+        let keys = self.assign_keys(citations);
+        let mut out = String::new();
+        for (citation, key) in citations.iter().zip(keys) {
+            self.write_entry(&mut out, citation, &key);
+        }
+        out
+    }
+
+    /// Assign one unique key per citation.
+    ///
+    /// Original keys are reserved first so that a generated key never takes a
+    /// key that a later citation already owns.
+    fn assign_keys(&self, citations: &[Citation]) -> Vec<String> {
+        let mut used = HashSet::new();
+        let mut keys: Vec<Option<String>> = citations
+            .iter()
+            .map(|citation| {
+                original_key(citation)
+                    .filter(|key| used.insert(key.to_ascii_lowercase()))
+                    .map(str::to_string)
+            })
+            .collect();
+
+        for (citation, key) in citations.iter().zip(keys.iter_mut()) {
+            if key.is_some() {
+                continue;
+            }
+            let base = original_key(citation)
+                .map(str::to_string)
+                .unwrap_or_else(|| generate_key(&self.key_pattern, citation));
+            let mut candidate = base.clone();
+            let mut suffix = 0;
+            while !used.insert(candidate.to_ascii_lowercase()) {
+                suffix += 1;
+                candidate = format!("{}{}", base, alpha_suffix(suffix));
+            }
+            *key = Some(candidate);
+        }
+
+        keys.into_iter().flatten().collect()
+    }
+
+    fn write_entry(&self, out: &mut String, citation: &Citation, key: &str) {
+        let entry_type = entry_type_for(citation, self.dialect);
+        let biblatex = self.dialect == BibDialect::BibLatex;
+        let mut fields = FieldList::default();
+
+        let authors = format_people(&citation.authors);
+        if !authors.is_empty() {
+            fields.push("author", format!("{{{}}}", authors));
+        }
+        for (name, role) in [
+            ("editor", ContributorRole::Editor),
+            ("translator", ContributorRole::Translator),
+        ] {
+            let people = format_people(citation.contributors.iter().filter(|c| c.role == role));
+            if !people.is_empty() {
+                fields.push(name, format!("{{{}}}", people));
+            }
+        }
+        fields.text("title", &citation.title);
+        if let Some(journal) = citation.journal.as_deref() {
+            let name = match entry_type {
+                "inproceedings" | "incollection" | "inbook" => "booktitle",
+                _ if biblatex => "journaltitle",
+                _ => "journal",
+            };
+            fields.text(name, journal);
+        }
+        if let Some(abbr) = citation.journal_abbr.as_deref() {
+            fields.text("shortjournal", abbr);
+        }
+        if let Some(date) = &citation.date {
+            if biblatex {
+                fields.raw("date", &format_iso_date(date));
+            } else {
+                fields.raw("year", &date.year.to_string());
+                if let Some(month) = date
+                    .month
+                    .and_then(|m| MONTH_MACROS.get(usize::from(m).wrapping_sub(1)))
+                {
+                    fields.bare("month", month);
+                }
+            }
+        }
+        if let Some(date) = citation.date_of(DateKind::Accessed) {
+            fields.raw("urldate", &format_iso_date(date));
+        }
+        fields.opt_text("volume", citation.volume.as_deref());
+        fields.opt_text("number", citation.issue.as_deref());
+        if let Some(pages) = citation.pages.as_deref() {
+            fields.text("pages", &pages.replacen('-', "--", 1));
+        }
+        fields.opt_text("publisher", citation.publisher.as_deref());
+        fields.opt_text("language", citation.language.as_deref());
+        fields.opt_text("abstract", citation.abstract_text.as_deref());
+        if !citation.keywords.is_empty() {
+            let separator = if citation.keywords.iter().any(|k| k.contains(',')) {
+                "; "
+            } else {
+                ", "
+            };
+            fields.text("keywords", &citation.keywords.join(separator));
+        }
+        fields.opt_raw("doi", citation.doi.as_deref());
+        if let Some(url) = citation.urls.first() {
+            fields.raw("url", url);
+        }
+
+        let (issns, isbns): (Vec<&String>, Vec<&String>) = citation
+            .issn
+            .iter()
+            .partition(|value| crate::utils::split_issns(value).len() == 1);
+        if !issns.is_empty() {
+            fields.raw("issn", &join_refs(&issns));
+        }
+        let isbns: Vec<&str> = citation
+            .identifiers_of(Scheme::Isbn)
+            .chain(isbns.into_iter().map(String::as_str))
+            .collect();
+        if !isbns.is_empty() {
+            fields.raw("isbn", &isbns.join(", "));
+        }
+        if let Some(arxiv) = citation.identifier(Scheme::Arxiv) {
+            fields.raw("eprint", arxiv);
+            if biblatex {
+                fields.raw("eprinttype", "arxiv");
+            } else {
+                fields.raw("archiveprefix", "arXiv");
+            }
+        }
+
+        fields.opt_raw("pmid", citation.pmid.as_deref());
+        fields.opt_raw("pmcid", citation.pmc_id.as_deref());
+        // The parser falls back to PMID / PMCID for the accession number, so
+        // only write it when it carries something else.
+        if let Some(accession) = citation.accession_number.as_deref()
+            && citation.pmid.as_deref() != Some(accession)
+            && citation.pmc_id.as_deref() != Some(accession)
+        {
+            fields.raw("accessionnumber", accession);
+        }
+
+        let mut extra_keys: Vec<&String> = citation
+            .extra_fields
+            .keys()
+            .filter(|name| {
+                is_field_name(name)
+                    && name.as_str() != ENTRY_KEY_FIELD
+                    && !name.ends_with(RAW_FIELD_SUFFIX)
+            })
+            .collect();
+        extra_keys.sort();
+        for name in extra_keys {
+            let name_lower = name.to_ascii_lowercase();
+            if fields.contains(&name_lower) {
+                continue;
+            }
+            let value = citation.extra_fields[name].join(" and ");
+            if is_verbatim_field(&name_lower) {
+                fields.raw(&name_lower, &value);
+            } else {
+                fields.text(&name_lower, &value);
+            }
+        }
+
+        out.push('@');
+        out.push_str(entry_type);
+        out.push('{');
+        out.push_str(key);
+        out.push_str(",\n");
+        let count = fields.entries.len();
+        for (index, (name, value)) in fields.entries.iter().enumerate() {
+            out.push_str("  ");
+            out.push_str(name);
+            out.push_str(" = ");
+            out.push_str(value);
+            if index + 1 < count {
+                out.push(',');
+            }
+            out.push('\n');
+        }
+        out.push_str("}\n\n");
+    }
+}
+
+/// Ordered `name = value` pairs for one entry, with values already delimited.
+#[derive(Default)]
+struct FieldList {
+    entries: Vec<(String, String)>,
+}
+
+impl FieldList {
+    fn contains(&self, name: &str) -> bool {
+        self.entries.iter().any(|(existing, _)| existing == name)
+    }
+
+    fn push(&mut self, name: &str, value: String) {
+        if !self.contains(name) {
+            self.entries.push((name.to_string(), value));
+        }
+    }
+
+    /// Add a plain-text value, escaping LaTeX special characters.
+    fn text(&mut self, name: &str, value: &str) {
+        let value = value.trim();
+        if !value.is_empty() {
+            self.push(name, format!("{{{}}}", escape_latex(value)));
+        }
+    }
+
+    fn opt_text(&mut self, name: &str, value: Option<&str>) {
+        if let Some(value) = value {
+            self.text(name, value);
+        }
+    }
+
+    /// Add a verbatim value; only braces are escaped so the value stays balanced.
+    fn raw(&mut self, name: &str, value: &str) {
+        let value = value.trim();
+        if !value.is_empty() {
+            let escaped = value.replace('{', "\\{").replace('}', "\\}");
+            self.push(name, format!("{{{}}}", escaped));
+        }
+    }
+
+    fn opt_raw(&mut self, name: &str, value: Option<&str>) {
+        if let Some(value) = value {
+            self.raw(name, value);
+        }
+    }
+
+    /// Add an undelimited value such as a month macro.
+    fn bare(&mut self, name: &str, value: &str) {
+        self.push(name, value.to_string());
+    }
+}
+
+/// Escape characters that have special meaning in LaTeX.
+pub(crate) fn escape_latex(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '{' | '}' | '&' | '%' | '$' | '#' | '_' => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\n' | '\r' => escaped.push(' '),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Format a date as BibLaTeX `YYYY-MM-DD`, with a season as codes `21` to
+/// `24` and a range as `start/end`.
+fn format_iso_date(date: &Date) -> String {
+    let mut value = format!("{:04}", date.year);
+    if let Some(month) = date.month {
+        value.push_str(&format!("-{:02}", month));
+        if let Some(day) = date.day {
+            value.push_str(&format!("-{:02}", day));
+        }
+    } else if let Some(season) = date.season {
+        let code = match season {
+            Season::Spring => 21,
+            Season::Summer => 22,
+            Season::Autumn => 23,
+            Season::Winter => 24,
+        };
+        value.push_str(&format!("-{}", code));
+    }
+    if let Some(end) = &date.end {
+        value.push('/');
+        value.push_str(&format_iso_date(end));
+    }
+    value
+}
+
+/// Format authors as `Family, Given Middle and ...`, escaping each name part.
+///
+/// Single-part names containing spaces or commas (usually organizations) are
+/// wrapped in braces so BibTeX keeps them as one family name.
+fn format_people<'a>(authors: impl IntoIterator<Item = &'a Author>) -> String {
+    authors
+        .into_iter()
+        .map(|author| {
+            let given = [author.given_name.as_deref(), author.middle_name.as_deref()]
+                .into_iter()
+                .flatten()
+                .filter(|part| !part.trim().is_empty())
+                .map(escape_latex)
+                .collect::<Vec<_>>()
+                .join(" ");
+            let name = escape_latex(author.name.trim());
+            let family = if name.contains(',')
+                || name.contains(" and ")
+                || (given.is_empty() && name.contains(char::is_whitespace))
+            {
+                format!("{{{}}}", name)
+            } else {
+                name
+            };
+            if given.is_empty() {
+                family
+            } else {
+                format!("{}, {}", family, given)
+            }
+        })
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+/// Pick the `.bib` entry type for a citation.
+///
+/// Types that are already `.bib` entry types (as produced by
+/// [`crate::BibParser`]) are kept; common descriptive types from the other
+/// parsers are mapped; anything else becomes `misc`.
+fn entry_type_for(citation: &Citation, dialect: BibDialect) -> &'static str {
+    let biblatex = dialect == BibDialect::BibLatex;
+    for citation_type in &citation.citation_type {
+        let lower = citation_type.trim().to_ascii_lowercase();
+        let entry_type = match lower.as_str() {
+            "article" | "journal article" | "jour" | "magazine article" | "newspaper" => "article",
+            "book" | "edited book" => "book",
+            "booklet" | "pamphlet" => "booklet",
+            "inbook" => "inbook",
+            "incollection" | "book chapter" | "book section" | "chap" => "incollection",
+            "inproceedings"
+            | "conference"
+            | "conference paper"
+            | "conference proceeding"
+            | "conf" => "inproceedings",
+            "proceedings" => "proceedings",
+            "manual" => "manual",
+            "mastersthesis" => "mastersthesis",
+            "phdthesis" | "thesis" | "thesis/dissertation" | "dissertation" | "thes" => {
+                if biblatex { "thesis" } else { "phdthesis" }
+            }
+            "techreport" | "report" | "rprt" => {
+                if biblatex {
+                    "report"
+                } else {
+                    "techreport"
+                }
+            }
+            "unpublished" | "unpublished work" | "unpb" => "unpublished",
+            "online" | "electronic citation" | "web page" | "elec" if biblatex => "online",
+            "dataset" | "data file" if biblatex => "dataset",
+            "software" | "computer program" if biblatex => "software",
+            "patent" if biblatex => "patent",
+            "misc" => "misc",
+            _ => continue,
+        };
+        return entry_type;
+    }
+    "misc"
+}
+
+/// The citation key recorded by the `.bib` parser, if any.
+fn original_key(citation: &Citation) -> Option<&str> {
+    citation
+        .extra_fields
+        .get(ENTRY_KEY_FIELD)
+        .and_then(|values| values.first())
+        .map(|key| key.trim())
+        .filter(|key| !key.is_empty() && !key.contains([',', '{', '}', ' ']))
+}
+
+/// Expand a key pattern for one citation.
+fn generate_key(pattern: &str, citation: &Citation) -> String {
+    let author = citation
+        .authors
+        .first()
+        .map(|author| key_token(&author.name))
+        .unwrap_or_default();
+    let year = citation
+        .date
+        .as_ref()
+        .map(|date| date.year.to_string())
+        .unwrap_or_default();
+    let titleword = citation
+        .title
+        .split(|c: char| !c.is_alphanumeric())
+        .map(key_token)
+        .find(|word| !word.is_empty() && !TITLE_STOPWORDS.contains(&word.as_str()))
+        .unwrap_or_default();
+
+    let key = pattern
+        .replace("{author}", &author)
+        .replace("{year}", &year)
+        .replace("{titleword}", &titleword);
+    let key: String = key
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | ':' | '.'))
+        .collect();
+
+    if key.is_empty() {
+        "ref".to_string()
+    } else {
+        key
+    }
+}
+
+/// Lowercase ASCII token for use in a key, folding common Latin accents.
+fn key_token(value: &str) -> String {
+    value
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(fold_accent)
+        .filter(char::is_ascii_alphanumeric)
+        .collect()
+}
+
+fn fold_accent(ch: char) -> char {
+    match ch {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ą' => 'a',
+        'ç' | 'ć' | 'č' => 'c',
+        'ď' => 'd',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' | 'ě' => 'e',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' => 'i',
+        'ł' => 'l',
+        'ñ' | 'ń' | 'ň' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => 'o',
+        'ř' => 'r',
+        'ś' | 'š' | 'ş' => 's',
+        'ť' | 'ţ' => 't',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        other => other,
+    }
+}
+
+/// Suffix for the `n`th repeat of a key: `a`..`z`, then `aa`, `ab`, ...
+fn alpha_suffix(mut n: usize) -> String {
+    let mut suffix = Vec::new();
+    while n > 0 {
+        n -= 1;
+        suffix.push(b'a' + (n % 26) as u8);
+        n /= 26;
+    }
+    suffix.reverse();
+    String::from_utf8(suffix).unwrap_or_default()
+}
+
+fn is_field_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | ':' | '.'))
+        && name.starts_with(|c: char| c.is_ascii_alphabetic())
+}
+
+fn is_verbatim_field(name: &str) -> bool {
+    matches!(
+        name,
+        "url" | "doi" | "eprint" | "file" | "verba" | "verbb" | "verbc"
+    )
+}
+
+fn join_refs(values: &[&String]) -> String {
+    values
+        .iter()
+        .map(|value| value.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Date;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn citation(title: &str, family: &str, year: i32) -> Citation {
+        Citation {
+            title: title.to_string(),
+            authors: vec![Author {
+                name: family.to_string(),
+                given_name: Some("Ann".to_string()),
+                middle_name: None,
+                affiliations: Vec::new(),
+                role: ContributorRole::Author,
+                orcid: None,
+                is_organization: false,
+            }],
+            date: Some(Date {
+                year,
+                month: None,
+                day: None,
+                season: None,
+                end: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case("Fish & Chips", "Fish \\& Chips")]
+    #[case("100% of $5 #1 a_b", "100\\% of \\$5 \\#1 a\\_b")]
+    #[case(
+        "{x} ~ ^ \\",
+        "\\{x\\} \\textasciitilde{} \\textasciicircum{} \\textbackslash{}"
+    )]
+    fn test_escape_latex(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(escape_latex(input), expected);
+    }
+
+    #[rstest]
+    #[case(1, "a")]
+    #[case(26, "z")]
+    #[case(27, "aa")]
+    fn test_alpha_suffix(#[case] n: usize, #[case] expected: &str) {
+        assert_eq!(alpha_suffix(n), expected);
+    }
+
+    #[test]
+    fn test_generate_key_default_pattern() {
+        let citation = citation("The Müller Effect on Cells", "Müller", 2021);
+        assert_eq!(
+            generate_key(DEFAULT_KEY_PATTERN, &citation),
+            "muller2021muller"
+        );
+    }
+
+    #[test]
+    fn test_generate_key_empty_falls_back() {
+        assert_eq!(generate_key("{author}", &Citation::default()), "ref");
+    }
+
+    #[test]
+    fn test_keys_are_unique_and_respect_original_keys() {
+        let first = citation("Cells", "Smith", 2020);
+        let second = citation("Cells", "Smith", 2020);
+        let mut third = citation("Other", "Doe", 2019);
+        third.extra_fields.insert(
+            ENTRY_KEY_FIELD.to_string(),
+            vec!["smith2020cells".to_string()],
+        );
+
+        let keys = BibWriter::new().assign_keys(&[first, second, third]);
+        assert_eq!(
+            keys,
+            vec!["smith2020cellsa", "smith2020cellsb", "smith2020cells"]
+        );
+    }
+
+    #[rstest]
+    #[case("Journal Article", BibDialect::BibTex, "article")]
+    #[case("Book Chapter", BibDialect::BibTex, "incollection")]
+    #[case("Thesis/Dissertation", BibDialect::BibTex, "phdthesis")]
+    #[case("Thesis/Dissertation", BibDialect::BibLatex, "thesis")]
+    #[case("Electronic Citation", BibDialect::BibTex, "misc")]
+    #[case("Electronic Citation", BibDialect::BibLatex, "online")]
+    #[case("inproceedings", BibDialect::BibTex, "inproceedings")]
+    #[case("Clinical Trial", BibDialect::BibTex, "misc")]
+    fn test_entry_type_for(
+        #[case] citation_type: &str,
+        #[case] dialect: BibDialect,
+        #[case] expected: &str,
+    ) {
+        let citation = Citation {
+            citation_type: vec![citation_type.to_string()],
+            ..Default::default()
+        };
+        assert_eq!(entry_type_for(&citation, dialect), expected);
+    }
+
+    #[test]
+    fn test_format_people() {
+        let authors = vec![
+            Author {
+                name: "Smith".to_string(),
+                given_name: Some("John".to_string()),
+                middle_name: Some("A".to_string()),
+                affiliations: Vec::new(),
+                role: ContributorRole::Author,
+                orcid: None,
+                is_organization: false,
+            },
+            Author {
+                name: "World Health Organization".to_string(),
+                given_name: None,
+                middle_name: None,
+                affiliations: Vec::new(),
+                role: ContributorRole::Author,
+                orcid: None,
+                is_organization: false,
+            },
+        ];
+        assert_eq!(
+            format_people(&authors),
+            "Smith, John A and {World Health Organization}"
+        );
+    }
+
+    #[test]
+    fn test_write_bibtex_entry() {
+        let mut citation = citation("Fish & Chips", "Smith", 2020);
+        citation.citation_type = vec!["Journal Article".to_string()];
+        citation.journal = Some("Food Journal".to_string());
+        citation.date.as_mut().unwrap().month = Some(3);
+        citation.pages = Some("10-20".to_string());
+        citation.doi = Some("10.1000/a_b".to_string());
+        citation
+            .extra_fields
+            .insert("note".to_string(), vec!["50% off".to_string()]);
+
+        assert_eq!(
+            BibWriter::new().write_entries(&[citation]),
+            "@article{smith2020fish,
+  author = {Smith, Ann},
+  title = {Fish \\& Chips},
+  journal = {Food Journal},
+  year = {2020},
+  month = mar,
+  pages = {10--20},
+  doi = {10.1000/a_b},
+  note = {50\\% off}
+}
+
+"
+        );
+    }
+}
@@ -0,0 +1,295 @@
+//! CSL-JSON parser and writer implementation.
+//!
+//! CSL-JSON is the item format used by citeproc processors, Zotero and Pandoc.
+//! Documents are JSON arrays of item objects; a single item object is also
+//! accepted by the parser.
+//!
+//! # Example
+//!
+//! ```
+//! use biblib::{CitationParser, CitationWriter, CslJsonParser, CslJsonWriter};
+//!
+//! let input = r#"[{
+//!   "id": "smith2024",
+//!   "type": "article-journal",
+//!   "title": "Example Article",
+//!   "author": [{"family": "Smith", "given": "John"}],
+//!   "issued": {"date-parts": [[2024, 5, 2]]},
+//!   "DOI": "10.1000/example"
+//! }]"#;
+//!
+//! let citations = CslJsonParser::new().parse(input).unwrap();
+//! assert_eq!(citations[0].title, "Example Article");
+//! assert_eq!(citations[0].doi.as_deref(), Some("10.1000/example"));
+//!
+//! let output = CslJsonWriter::new().write(&citations);
+//! assert_eq!(CslJsonParser::new().parse(&output).unwrap(), citations);
+//! ```
+
+mod parse;
+mod write;
+
+use crate::error::ParseError;
+use crate::stream::parse_whole;
+use crate::{
+    Citation, CitationFormat, CitationParser, CitationWriter, ParseOptions, ParseReport,
+    collect_report,
+};
+pub(crate) use parse::looks_like_csl_json;
+use parse::{csl_json_records, csl_json_records_with_warnings, parse_csl_json};
+use write::write_csl_json;
+
+/// Parser for CSL-JSON documents.
+///
+/// Item variables are mapped as follows:
+///
+/// - `type` → `citation_type`, kept as the CSL type name
+/// - `author` → `authors`, from `family` / `given` or `literal` names
+/// - `issued` → `date`, from `date-parts` or a `raw` date string
+/// - `container-title` / `container-title-short` → `journal` / `journal_abbr`
+/// - `DOI`, `PMID`, `PMCID`, `ISSN`, `URL`, `abstract`, `keyword`, `volume`,
+///   `issue`, `page`, `language` and `publisher` → the matching fields
+/// - `ISBN` → `identifiers`; values with a wrong check digit stay in
+///   `extra_fields`
+///
+/// Other string and number variables, including the item `id`, are kept in
+/// `extra_fields` under their CSL name.
+#[derive(Debug, Clone, Default)]
+pub struct CslJsonParser;
+
+impl CslJsonParser {
+    /// Creates a new CSL-JSON parser instance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CitationParser for CslJsonParser {
+    fn parse(&self, input: &str) -> Result<Vec<Citation>, ParseError> {
+        if input.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        parse_csl_json(input)
+    }
+
+    /// Reads the whole input, then yields one result per item.
+    fn parse_reader<'r, R>(
+        &self,
+        reader: R,
+    ) -> Box<dyn Iterator<Item = Result<Citation, ParseError>> + 'r>
+    where
+        R: std::io::Read + 'r,
+    {
+        parse_whole(reader, CitationFormat::CslJson, csl_json_records)
+    }
+
+    /// Reports unparseable `accessed` dates and invalid `DOI` values as
+    /// warnings, without line numbers.
+    fn parse_with_options(
+        &self,
+        input: &str,
+        options: &ParseOptions,
+    ) -> Result<ParseReport, ParseError> {
+        collect_report(csl_json_records_with_warnings(input), options)
+    }
+}
+
+/// Writer for CSL-JSON documents.
+///
+/// Writes a pretty-printed array with one item per citation, using the same
+/// variables [`CslJsonParser`] reads. The item `id` is taken from
+/// `extra_fields["id"]`, then from a `.bib` citation key, and is otherwise
+/// generated from the citation's position. Citation types from other formats
+/// are mapped to CSL types. Only the first URL is written, since CSL `URL` holds
+/// a single value, and `accession_number` and `mesh_terms` have no CSL variable.
+#[derive(Debug, Clone, Default)]
+pub struct CslJsonWriter;
+
+impl CslJsonWriter {
+    /// Creates a new CSL-JSON writer instance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CitationWriter for CslJsonWriter {
+    fn write(&self, citations: &[Citation]) -> String {
+        write_csl_json(citations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContributorRole, DateKind, Scheme, Season, ValueError};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_item() {
+        let input = r#"[{
+  "id": 42,
+  "type": "article-journal",
+  "title": "Example Article",
+  "author": [
+    {"family": "Smith", "given": "John A"},
+    {"literal": "World Health Organization"}
+  ],
+  "issued": {"date-parts": [["2024", "5"]]},
+  "container-title": "Journal of Tests",
+  "container-title-short": "J Tests",
+  "volume": 12,
+  "page": "100–10",
+  "ISSN": "1234-5678",
+  "DOI": "https://doi.org/10.1000/EXAMPLE",
+  "PMID": "12345678",
+  "PMCID": "PMC1234567",
+  "keyword": "alpha, beta",
+  "URL": "https://example.com",
+  "note": "A note",
+  "editor": [{"family": "Doe", "given": "Jane"}]
+}]"#;
+        let citations = CslJsonParser::new().parse(input).unwrap();
+        assert_eq!(citations.len(), 1);
+        let citation = &citations[0];
+        assert_eq!(citation.citation_type, vec!["article-journal"]);
+        assert_eq!(citation.authors.len(), 2);
+        assert_eq!(citation.authors[0].middle_name.as_deref(), Some("A"));
+        assert_eq!(citation.authors[1].name, "World Health Organization");
+        assert_eq!(citation.date.as_ref().unwrap().month, Some(5));
+        assert_eq!(citation.journal_abbr.as_deref(), Some("J Tests"));
+        assert_eq!(citation.volume.as_deref(), Some("12"));
+        assert_eq!(citation.pages.as_deref(), Some("100-110"));
+        assert_eq!(citation.issn, vec!["1234-5678"]);
+        assert_eq!(citation.doi.as_deref(), Some("10.1000/example"));
+        assert_eq!(citation.pmc_id.as_deref(), Some("PMC1234567"));
+        assert_eq!(citation.keywords, vec!["alpha", "beta"]);
+        assert_eq!(citation.extra_fields["id"], vec!["42"]);
+        assert_eq!(citation.extra_fields["note"], vec!["A note"]);
+        assert!(!citation.extra_fields.contains_key("editor"));
+        assert_eq!(citation.contributors.len(), 1);
+        assert_eq!(citation.contributors[0].name, "Doe");
+        assert_eq!(citation.contributors[0].role, ContributorRole::Editor);
+    }
+
+    #[test]
+    fn test_parse_single_object() {
+        let citations = CslJsonParser::new()
+            .parse(r#"{"type": "book", "title": "Only"}"#)
+            .unwrap();
+        assert_eq!(citations[0].title, "Only");
+    }
+
+    #[test]
+    fn test_missing_title_is_error() {
+        let err = CslJsonParser::new()
+            .parse(r#"[{"id": "a", "type": "book"}]"#)
+            .unwrap_err();
+        assert_eq!(err.format, CitationFormat::CslJson);
+        assert!(matches!(
+            err.error,
+            ValueError::MissingValue { key: "title", .. }
+        ));
+    }
+
+    #[test]
+    fn test_write_round_trip() {
+        let input = r#"[
+  {
+    "id": "doe2021",
+    "type": "chapter",
+    "title": "Second",
+    "author": [{"family": "van der Valk", "given": "Jan"}],
+    "editor": [{"family": "Doe", "given": "Jane"}],
+    "collection-editor": [{"family": "Brown", "given": "Alex"}],
+    "translator": [{"family": "Helper", "given": "Sam"}],
+    "issued": {"date-parts": [[2021], [2022]], "season": 3},
+    "publisher": "Test Press",
+    "ISBN": "978-3-16-148410-0, 978-3-16-148410-9"
+  },
+  {
+    "id": "smith2024",
+    "type": "article-journal",
+    "title": "Example Article",
+    "author": [{"family": "Smith", "given": "John A"}, {"literal": "WHO"}],
+    "issued": {"date-parts": [[2024, 5, 2]]},
+    "accessed": {"date-parts": [[2024, 6, 1]]},
+    "container-title": "Journal of Tests",
+    "container-title-short": "J Tests",
+    "volume": "12",
+    "issue": "3",
+    "page": "100-110",
+    "ISSN": "1234-5678",
+    "DOI": "10.1000/example",
+    "PMID": "12345678",
+    "PMCID": "PMC1234567",
+    "abstract": "An abstract.",
+    "keyword": "alpha, beta",
+    "URL": "https://example.com",
+    "language": "en"
+  }
+]"#;
+        let citations = CslJsonParser::new().parse(input).unwrap();
+        let output = CslJsonWriter::new().write(&citations);
+        assert_eq!(CslJsonParser::new().parse(&output).unwrap(), citations);
+        assert_eq!(citations[0].identifier(Scheme::Isbn), Some("9783161484100"));
+        assert_eq!(citations[0].extra_fields["ISBN"], vec!["978-3-16-148410-9"]);
+        let issued = citations[0].date.as_ref().unwrap();
+        assert_eq!(issued.season, Some(Season::Autumn));
+        assert_eq!(issued.end.as_ref().map(|end| end.year), Some(2022));
+        let accessed = citations[1].date_of(DateKind::Accessed).unwrap();
+        assert_eq!(
+            (accessed.year, accessed.month, accessed.day),
+            (2024, Some(6), Some(1))
+        );
+    }
+
+    #[test]
+    fn test_write_empty() {
+        assert_eq!(CslJsonWriter::new().write(&[]), "[]\n");
+    }
+
+    #[test]
+    fn test_parse_reader_yields_one_result_per_item() {
+        let input = r#"[{"type": "book", "title": "First"}, {"type": "book"}, {"type": "book", "title": "Third"}]"#;
+        let streamed: Vec<_> = CslJsonParser::new()
+            .parse_reader(input.as_bytes())
+            .collect();
+
+        assert_eq!(streamed.len(), 3);
+        assert_eq!(streamed[0].as_ref().unwrap().title, "First");
+        assert!(streamed[1].is_err());
+        assert_eq!(streamed[2].as_ref().unwrap().title, "Third");
+    }
+
+    #[test]
+    fn test_parse_with_options_reports_warnings() {
+        use crate::{OnError, ParseOptions, WarningKind};
+
+        let input = r#"[{"type": "book", "title": "First"}, {"type": "book", "title": "Second", "accessed": {"raw": "someday"}, "DOI": "not a doi"}]"#;
+        for on_error in [OnError::Fail, OnError::Collect] {
+            let options = ParseOptions::new().with_on_error(on_error);
+            let report = CslJsonParser::new()
+                .parse_with_options(input, &options)
+                .unwrap();
+
+            assert_eq!(report.citations.len(), 2);
+            let kinds: Vec<_> = report.warnings.iter().map(|w| &w.kind).collect();
+            assert_eq!(
+                kinds,
+                vec![
+                    &WarningKind::UnparseableDate {
+                        key: "accessed".to_string(),
+                        value: r#"{"raw":"someday"}"#.to_string()
+                    },
+                    &WarningKind::InvalidDoi {
+                        key: "DOI".to_string(),
+                        value: "not a doi".to_string()
+                    },
+                ]
+            );
+        }
+    }
+}
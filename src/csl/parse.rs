@@ -0,0 +1,497 @@
+//! CSL-JSON parsing implementation.
+//!
+//! Items are read from a JSON array (or a single item object) and mapped onto
+//! [`Citation`] fields. Unmapped string and number variables are kept in
+//! `extra_fields` under their CSL name.
+
+use crate::error::{ParseError, ParseWarning, ValueError, WarningKind, fields};
+use crate::utils::{
+    format_doi, format_page_numbers, parse_bib_date, partition_contributors, sort_serial_numbers,
+    split_given_and_middle,
+};
+use crate::{
+    Author, Citation, CitationFormat, ContributorRole, Date, DateKind, Identifier, Scheme, Season,
+    TypedDate,
+};
+use serde::Deserialize;
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// CSL name variables, and the role of the people in them.
+pub(crate) const NAME_VARIABLES: [(&str, ContributorRole); 4] = [
+    ("author", ContributorRole::Author),
+    ("editor", ContributorRole::Editor),
+    ("collection-editor", ContributorRole::SeriesEditor),
+    ("translator", ContributorRole::Translator),
+];
+
+/// Quick check used by format auto-detection: the first item of the array
+/// (or the single item object) has a `type` and at least one of `title`,
+/// `author`, `issued` or `id`. Only the first item is read.
+pub(crate) fn looks_like_csl_json(content: &str) -> bool {
+    let mut first = None;
+    let mut deserializer = serde_json::Deserializer::from_str(content.trim_start());
+    // Stopping after the first item leaves the rest of an array unread,
+    // which the deserializer reports as an error; the item is kept anyway.
+    let _ = FirstItem(&mut first).deserialize(&mut deserializer);
+    first.is_some_and(|item| {
+        item.contains_key("type")
+            && ["title", "author", "issued", "id"]
+                .iter()
+                .any(|key| item.contains_key(*key))
+    })
+}
+
+/// Reads the first object of a JSON array, or a single object, into the
+/// borrowed slot.
+struct FirstItem<'a>(&'a mut Option<Map<String, Value>>);
+
+impl<'de> DeserializeSeed<'de> for FirstItem<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FirstItem<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a CSL item or an array of CSL items")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        if let Some(Value::Object(item)) = seq.next_element()? {
+            *self.0 = Some(item);
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<(), A::Error> {
+        *self.0 = Some(Map::deserialize(MapAccessDeserializer::new(map))?);
+        Ok(())
+    }
+}
+
+/// Parse a CSL-JSON document into citations.
+pub(crate) fn parse_csl_json(input: &str) -> Result<Vec<Citation>, ParseError> {
+    csl_json_records(input).into_iter().collect()
+}
+
+/// Parse a CSL-JSON document into one result per item. Invalid JSON, or a
+/// document that is neither an array nor an item, is returned as the only
+/// item.
+pub(crate) fn csl_json_records(input: &str) -> Vec<Result<Citation, ParseError>> {
+    csl_json_records_with_warnings(input)
+        .into_iter()
+        .map(|record| record.map(|(citation, _)| citation))
+        .collect()
+}
+
+/// [`csl_json_records`], keeping the warnings for each citation.
+pub(crate) fn csl_json_records_with_warnings(
+    input: &str,
+) -> Vec<Result<(Citation, Vec<ParseWarning>), ParseError>> {
+    let document: Value = match serde_json::from_str(input) {
+        Ok(document) => document,
+        Err(err) => {
+            return vec![Err(ParseError::at_position(
+                err.line(),
+                err.column(),
+                CitationFormat::CslJson,
+                ValueError::Syntax(err.to_string()),
+            ))];
+        }
+    };
+
+    match document {
+        Value::Array(items) => items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| parse_item(item, index))
+            .collect(),
+        item @ Value::Object(_) => vec![parse_item(item, 0)],
+        _ => vec![Err(ParseError::without_position(
+            CitationFormat::CslJson,
+            ValueError::Syntax("expected an array of CSL items".to_string()),
+        ))],
+    }
+}
+
+fn parse_item(item: Value, index: usize) -> Result<(Citation, Vec<ParseWarning>), ParseError> {
+    let Value::Object(mut item) = item else {
+        return Err(ParseError::without_position(
+            CitationFormat::CslJson,
+            ValueError::Syntax(format!("item {} is not a JSON object", index + 1)),
+        ));
+    };
+
+    let title = take_string(&mut item, "title").ok_or_else(|| {
+        ParseError::without_position(
+            CitationFormat::CslJson,
+            ValueError::MissingValue {
+                field: fields::TITLE,
+                key: "title",
+            },
+        )
+    })?;
+
+    let date = match item.remove("issued") {
+        Some(issued) => Some(parse_date(&issued).ok_or_else(|| {
+            ParseError::without_position(
+                CitationFormat::CslJson,
+                ValueError::BadValue {
+                    field: fields::DATE,
+                    key: "issued",
+                    value: issued.to_string(),
+                    reason: "expected date-parts or a raw date".to_string(),
+                },
+            )
+        })?),
+        None => None,
+    };
+    // CSL-JSON has no line numbers to report once it has been read.
+    let mut warnings = Vec::new();
+    let mut warn = |kind| warnings.push(ParseWarning::new(None, CitationFormat::CslJson, kind));
+    let dates = item
+        .remove("accessed")
+        .and_then(|accessed| {
+            let date = parse_date(&accessed);
+            if date.is_none() {
+                warn(WarningKind::UnparseableDate {
+                    key: "accessed".to_string(),
+                    value: accessed.to_string(),
+                });
+            }
+            date
+        })
+        .map(|date| TypedDate {
+            kind: DateKind::Accessed,
+            date,
+        })
+        .into_iter()
+        .collect();
+
+    let mut people = Vec::new();
+    for (variable, role) in NAME_VARIABLES {
+        if let Some(Value::Array(names)) = item.remove(variable) {
+            people.extend(names.iter().filter_map(|name| parse_name(name, role)));
+        }
+    }
+    let (authors, contributors) = partition_contributors(people);
+
+    // ISBNs with a wrong check digit are kept as they were written.
+    let mut identifiers = Vec::new();
+    let mut invalid_isbns = Vec::new();
+    for isbn in take_strings(&mut item, "ISBN")
+        .iter()
+        .flat_map(|isbns| isbns.split([',', ';']))
+        .map(str::trim)
+        .filter(|isbn| !isbn.is_empty())
+    {
+        match Identifier::new(Scheme::Isbn, isbn) {
+            Some(identifier) => identifiers.push(identifier),
+            None => invalid_isbns.push(Value::String(isbn.to_string())),
+        }
+    }
+    if !invalid_isbns.is_empty() {
+        item.insert("ISBN".to_string(), Value::Array(invalid_isbns));
+    }
+    // ISBNs given as `ISSN` belong with the others.
+    let mut issn = Vec::new();
+    for value in take_strings(&mut item, "ISSN") {
+        sort_serial_numbers(&value, &mut issn, &mut identifiers);
+    }
+
+    let doi = take_string(&mut item, "DOI").and_then(|value| {
+        let doi = format_doi(&value);
+        if doi.is_none() {
+            warn(WarningKind::InvalidDoi {
+                key: "DOI".to_string(),
+                value,
+            });
+        }
+        doi
+    });
+
+    let citation = Citation {
+        citation_type: take_string(&mut item, "type").into_iter().collect(),
+        title,
+        authors,
+        contributors,
+        journal: take_string(&mut item, "container-title"),
+        journal_abbr: take_string(&mut item, "container-title-short")
+            .or_else(|| take_string(&mut item, "journalAbbreviation")),
+        date,
+        dates,
+        volume: take_string(&mut item, "volume"),
+        issue: take_string(&mut item, "issue"),
+        pages: take_string(&mut item, "page")
+            .map(|pages| format_page_numbers(&pages.replace('–', "-"))),
+        issn,
+        doi,
+        accession_number: None,
+        pmid: take_string(&mut item, "PMID"),
+        pmc_id: take_string(&mut item, "PMCID"),
+        identifiers,
+        abstract_text: take_string(&mut item, "abstract"),
+        keywords: take_strings(&mut item, "keyword")
+            .iter()
+            .flat_map(|keywords| keywords.split([',', ';']))
+            .map(str::trim)
+            .filter(|keyword| !keyword.is_empty())
+            .map(String::from)
+            .collect(),
+        urls: take_strings(&mut item, "URL"),
+        language: take_string(&mut item, "language"),
+        mesh_terms: Vec::new(),
+        publisher: take_string(&mut item, "publisher"),
+        extra_fields: item
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let values = scalar_strings(&value);
+                (!values.is_empty()).then_some((key, values))
+            })
+            .collect(),
+    };
+    Ok((citation, warnings))
+}
+
+/// Parse a CSL name object into an [`Author`] with `role`.
+///
+/// Personal names use `family` / `given` (with any `non-dropping-particle`
+/// prepended to the family name); institutional names use `literal` and are
+/// flagged as organizations, authors among them as corporate authors.
+fn parse_name(name: &Value, role: ContributorRole) -> Option<Author> {
+    let name = name.as_object()?;
+    let text = |key: &str| {
+        name.get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+
+    let family = match (text("non-dropping-particle"), text("family")) {
+        (Some(particle), Some(family)) => format!("{} {}", particle, family),
+        (None, Some(family)) => family.to_string(),
+        _ => {
+            return Some(Author::organization(text("literal")?, role));
+        }
+    };
+    let (given_name, middle_name) = text("given")
+        .map(split_given_and_middle)
+        .unwrap_or((None, None));
+
+    Some(Author {
+        name: family,
+        given_name,
+        middle_name,
+        affiliations: Vec::new(),
+        role,
+        orcid: None,
+        is_organization: false,
+    })
+}
+
+/// Parse a CSL date object, preferring `date-parts` over a `raw` date string.
+fn parse_date(issued: &Value) -> Option<Date> {
+    if let Some(ranges) = issued
+        .get("date-parts")
+        .and_then(Value::as_array)
+        .filter(|ranges| ranges.first().is_some_and(Value::is_array))
+    {
+        let mut date = parse_date_parts(&ranges[0])?;
+        if date.month.is_none() {
+            date.season = issued.get("season").and_then(parse_season);
+        }
+        date.end = ranges.get(1).and_then(parse_date_parts).map(Box::new);
+        return Some(date);
+    }
+
+    issued
+        .get("raw")
+        .or_else(|| issued.get("literal"))
+        .and_then(Value::as_str)
+        .and_then(parse_bib_date)
+}
+
+/// Parse one `[year, month, day]` entry of `date-parts`.
+fn parse_date_parts(parts: &Value) -> Option<Date> {
+    let parts = parts.as_array()?;
+    let part = |index: usize| {
+        parts.get(index).and_then(|value| match value {
+            Value::Number(n) => n.as_i64(),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        })
+    };
+    let year = i32::try_from(part(0)?).ok()?;
+    let month = part(1)
+        .and_then(|m| u8::try_from(m).ok())
+        .filter(|m| (1..=12).contains(m));
+    let day = month
+        .and(part(2))
+        .and_then(|d| u8::try_from(d).ok())
+        .filter(|d| (1..=31).contains(d));
+    Some(Date {
+        year,
+        month,
+        day,
+        season: None,
+        end: None,
+    })
+}
+
+/// Parse a CSL `season`, numbered 1 (spring) to 4 (winter) or given by name.
+fn parse_season(season: &Value) -> Option<Season> {
+    let number = match season {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    };
+    match number {
+        Some(1) => Some(Season::Spring),
+        Some(2) => Some(Season::Summer),
+        Some(3) => Some(Season::Autumn),
+        Some(4) => Some(Season::Winter),
+        Some(_) => None,
+        None => season.as_str().and_then(Season::from_name),
+    }
+}
+
+/// Remove a variable and return it as a trimmed, non-empty string.
+fn take_string(item: &mut Map<String, Value>, key: &str) -> Option<String> {
+    item.remove(key)
+        .and_then(|value| scalar_strings(&value).into_iter().next())
+}
+
+/// Remove a variable that may hold one value or an array of values.
+fn take_strings(item: &mut Map<String, Value>, key: &str) -> Vec<String> {
+    item.remove(key)
+        .map(|value| scalar_strings(&value))
+        .unwrap_or_default()
+}
+
+/// String and number values as trimmed strings; objects and nulls are skipped.
+fn scalar_strings(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) if !s.trim().is_empty() => vec![s.trim().to_string()],
+        Value::Number(n) => vec![n.to_string()],
+        Value::Array(values) => values.iter().flat_map(scalar_strings).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_date_parts() {
+        assert_eq!(
+            parse_date(&json!({"date-parts": [[2020, 6, 9]]})),
+            Some(Date {
+                year: 2020,
+                month: Some(6),
+                day: Some(9),
+                season: None,
+                end: None
+            })
+        );
+        assert_eq!(
+            parse_date(&json!({"date-parts": [["2019"]]})),
+            Some(Date {
+                year: 2019,
+                month: None,
+                day: None,
+                season: None,
+                end: None
+            })
+        );
+        assert_eq!(
+            parse_date(&json!({"raw": "2021-03"})),
+            Some(Date {
+                year: 2021,
+                month: Some(3),
+                day: None,
+                season: None,
+                end: None
+            })
+        );
+        assert_eq!(parse_date(&json!({"season": 1})), None);
+    }
+
+    #[test]
+    fn test_parse_name() {
+        let author = parse_name(
+            &json!({
+                "family": "Valk",
+                "non-dropping-particle": "van der",
+                "given": "Jan P M"
+            }),
+            ContributorRole::Author,
+        )
+        .unwrap();
+        assert_eq!(author.name, "van der Valk");
+        assert_eq!(author.given_name.as_deref(), Some("Jan"));
+        assert_eq!(author.middle_name.as_deref(), Some("P M"));
+
+        let org = parse_name(&json!({"literal": "WHO"}), ContributorRole::Editor).unwrap();
+        assert_eq!(org.name, "WHO");
+        assert_eq!(org.given_name, None);
+        assert_eq!(org.role, ContributorRole::Editor);
+        assert!(org.is_organization);
+
+        let org = parse_name(&json!({"literal": "WHO"}), ContributorRole::Author).unwrap();
+        assert_eq!(org.role, ContributorRole::CorporateAuthor);
+
+        assert_eq!(
+            parse_name(&json!({"given": "Only"}), ContributorRole::Author),
+            None
+        );
+    }
+
+    #[test]
+    fn test_looks_like_csl_json() {
+        assert!(looks_like_csl_json(
+            "[{\"type\": \"book\", \"title\": \"A\"}]"
+        ));
+        assert!(looks_like_csl_json(
+            "  {\"id\": \"a\", \"type\": \"book\", \"author\": []}"
+        ));
+        // Only the first item is read.
+        assert!(looks_like_csl_json(
+            "[{\"type\": \"book\", \"issued\": {}}, {\"title\": }"
+        ));
+        assert!(!looks_like_csl_json("@article{a, title={A}}"));
+        assert!(!looks_like_csl_json("[1, 2, 3]"));
+    }
+
+    #[test]
+    fn test_looks_like_csl_json_rejects_other_json() {
+        // `title` or `type` alone is not enough.
+        assert!(!looks_like_csl_json("  {\"title\": \"A\"}"));
+        assert!(!looks_like_csl_json(
+            "{\"type\": \"FeatureCollection\", \"features\": []}"
+        ));
+        // The keys must be on the item itself, not nested inside it.
+        assert!(!looks_like_csl_json(
+            "[{\"name\": \"pkg\", \"meta\": {\"type\": \"module\", \"title\": \"A\"}}]"
+        ));
+        assert!(!looks_like_csl_json("[]"));
+        assert!(!looks_like_csl_json("[{\"type\": \"book\""));
+    }
+
+    #[test]
+    fn test_syntax_error_has_position() {
+        let err = parse_csl_json("[\n  {\"title\": }\n]").unwrap_err();
+        assert_eq!(err.format, CitationFormat::CslJson);
+        assert_eq!(err.line, Some(2));
+        assert!(matches!(err.error, ValueError::Syntax(_)));
+    }
+}
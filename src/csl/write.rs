@@ -0,0 +1,363 @@
+//! CSL-JSON writing implementation.
+//!
+//! Each citation becomes one CSL item object in a pretty-printed JSON array.
+
+use super::parse::NAME_VARIABLES;
+use crate::{Author, Citation, Date, DateKind, Scheme, Season};
+use serde_json::{Map, Value, json};
+
+/// Standard CSL string and number variables that can be written back from
+/// `extra_fields` when they are not filled from a dedicated `Citation` field.
+const PASSTHROUGH_VARIABLES: &[&str] = &[
+    "annote",
+    "archive",
+    "archive_collection",
+    "archive_location",
+    "archive-place",
+    "authority",
+    "call-number",
+    "chapter-number",
+    "citation-key",
+    "citation-label",
+    "collection-number",
+    "collection-title",
+    "dimensions",
+    "division",
+    "edition",
+    "event",
+    "event-place",
+    "event-title",
+    "genre",
+    "jurisdiction",
+    "license",
+    "medium",
+    "note",
+    "number",
+    "number-of-pages",
+    "number-of-volumes",
+    "original-publisher",
+    "original-publisher-place",
+    "original-title",
+    "part",
+    "part-title",
+    "publisher-place",
+    "references",
+    "reviewed-genre",
+    "reviewed-title",
+    "scale",
+    "section",
+    "source",
+    "status",
+    "supplement",
+    "title-short",
+    "version",
+    "volume-title",
+    "volume-title-short",
+    "year-suffix",
+];
+
+/// Serialize citations into a CSL-JSON array.
+pub(crate) fn write_csl_json(citations: &[Citation]) -> String {
+    let items: Vec<Value> = citations
+        .iter()
+        .enumerate()
+        .map(|(index, citation)| Value::Object(to_item(citation, index)))
+        .collect();
+    let mut out = serde_json::to_string_pretty(&items).unwrap_or_default();
+    out.push('\n');
+    out
+}
+
+fn to_item(citation: &Citation, index: usize) -> Map<String, Value> {
+    let mut item = Map::new();
+    let id = first_extra(citation, "id")
+        .or_else(|| first_extra(citation, "entrykey"))
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("item-{}", index + 1));
+    item.insert("id".to_string(), Value::String(id));
+    let csl_type = citation
+        .citation_type
+        .first()
+        .map_or("article", |t| csl_type(t));
+    item.insert("type".to_string(), Value::String(csl_type.to_string()));
+    insert(&mut item, "title", Some(&citation.title));
+
+    for (variable, role) in NAME_VARIABLES {
+        let names: Vec<Value> = if role.is_author() {
+            citation.authors.iter().filter_map(name_object).collect()
+        } else {
+            citation
+                .contributors
+                .iter()
+                .filter(|contributor| contributor.role == role)
+                .filter_map(name_object)
+                .collect()
+        };
+        if !names.is_empty() {
+            item.insert(variable.to_string(), Value::Array(names));
+        }
+    }
+    if let Some(date) = &citation.date {
+        item.insert("issued".to_string(), date_value(date));
+    }
+    if let Some(date) = citation.date_of(DateKind::Accessed) {
+        item.insert("accessed".to_string(), date_value(date));
+    }
+
+    insert(&mut item, "container-title", citation.journal.as_ref());
+    insert(
+        &mut item,
+        "container-title-short",
+        citation.journal_abbr.as_ref(),
+    );
+    insert(&mut item, "volume", citation.volume.as_ref());
+    insert(&mut item, "issue", citation.issue.as_ref());
+    insert(&mut item, "page", citation.pages.as_ref());
+    insert(&mut item, "ISSN", Some(&citation.issn.join(", ")));
+    let isbns: Vec<&str> = citation
+        .identifiers_of(Scheme::Isbn)
+        .chain(
+            citation
+                .extra_fields
+                .get("ISBN")
+                .into_iter()
+                .flatten()
+                .map(String::as_str),
+        )
+        .collect();
+    insert(&mut item, "ISBN", Some(&isbns.join(", ")));
+    insert(&mut item, "DOI", citation.doi.as_ref());
+    insert(&mut item, "PMID", citation.pmid.as_ref());
+    insert(&mut item, "PMCID", citation.pmc_id.as_ref());
+    insert(&mut item, "abstract", citation.abstract_text.as_ref());
+    insert(&mut item, "keyword", Some(&citation.keywords.join(", ")));
+    insert(&mut item, "URL", citation.urls.first());
+    insert(&mut item, "language", citation.language.as_ref());
+    insert(&mut item, "publisher", citation.publisher.as_ref());
+
+    for (key, values) in &citation.extra_fields {
+        if PASSTHROUGH_VARIABLES.contains(&key.as_str()) {
+            insert(&mut item, key, Some(&values.join("; ")));
+        }
+    }
+    item
+}
+
+/// Build a CSL name object: `family` / `given` for people, `literal` for
+/// organizations.
+fn name_object(author: &Author) -> Option<Value> {
+    let family = author.name.trim();
+    if family.is_empty() {
+        return None;
+    }
+    let given = [author.given_name.as_deref(), author.middle_name.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if author.is_organization {
+        Some(json!({ "literal": family }))
+    } else if given.is_empty() {
+        Some(json!({ "family": family }))
+    } else {
+        Some(json!({ "family": family, "given": given }))
+    }
+}
+
+/// Build a CSL date object with `date-parts` for the date and the end of a
+/// range, and the season number when there is no month.
+fn date_value(date: &Date) -> Value {
+    let parts = |date: &Date| {
+        let mut parts = vec![json!(date.year)];
+        if let Some(month) = date.month {
+            parts.push(json!(month));
+            if let Some(day) = date.day {
+                parts.push(json!(day));
+            }
+        }
+        Value::Array(parts)
+    };
+    let ranges: Vec<Value> = std::iter::once(date)
+        .chain(date.end.as_deref())
+        .map(parts)
+        .collect();
+    let mut value = json!({ "date-parts": ranges });
+    if let Some(season) = date.season.filter(|_| date.month.is_none()) {
+        let number = match season {
+            Season::Spring => 1,
+            Season::Summer => 2,
+            Season::Autumn => 3,
+            Season::Winter => 4,
+        };
+        value["season"] = json!(number);
+    }
+    value
+}
+
+fn first_extra<'a>(citation: &'a Citation, key: &str) -> Option<&'a str> {
+    citation
+        .extra_fields
+        .get(key)
+        .and_then(|values| values.first())
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+}
+
+/// Insert a string variable, skipping blank values.
+fn insert(item: &mut Map<String, Value>, key: &str, value: Option<&String>) {
+    if let Some(value) = value.map(|v| v.trim()).filter(|v| !v.is_empty()) {
+        item.insert(key.to_string(), Value::String(value.to_string()));
+    }
+}
+
+/// CSL item types, written unchanged when a citation already uses one.
+const CSL_TYPES: &[&str] = &[
+    "article",
+    "article-journal",
+    "article-magazine",
+    "article-newspaper",
+    "bill",
+    "book",
+    "broadcast",
+    "chapter",
+    "classic",
+    "collection",
+    "dataset",
+    "document",
+    "entry",
+    "entry-dictionary",
+    "entry-encyclopedia",
+    "event",
+    "figure",
+    "graphic",
+    "hearing",
+    "interview",
+    "legal_case",
+    "legislation",
+    "manuscript",
+    "map",
+    "motion_picture",
+    "musical_score",
+    "pamphlet",
+    "paper-conference",
+    "patent",
+    "performance",
+    "periodical",
+    "personal_communication",
+    "post",
+    "post-weblog",
+    "regulation",
+    "report",
+    "review",
+    "review-book",
+    "software",
+    "song",
+    "speech",
+    "standard",
+    "thesis",
+    "treaty",
+    "webpage",
+];
+
+/// Map a citation type from any supported format to a CSL item type.
+///
+/// CSL type names are kept as-is; unknown types fall back to `article`.
+fn csl_type(citation_type: &str) -> &'static str {
+    let lower = citation_type.trim().to_ascii_lowercase();
+    if let Some(known) = CSL_TYPES.iter().find(|t| **t == lower) {
+        return known;
+    }
+    match lower.as_str() {
+        "journal article" | "jour" => "article-journal",
+        "magazine article" | "mgzn" => "article-magazine",
+        "newspaper article" | "news" | "newspaper" => "article-newspaper",
+        "edited book" | "edbook" | "book-volume" => "book",
+        "book section" | "book chapter" | "chap" | "incollection" | "inbook" => "chapter",
+        "conference paper"
+        | "conference proceedings"
+        | "conference proceeding"
+        | "conf"
+        | "cpaper"
+        | "inproceedings" => "paper-conference",
+        "thesis/dissertation" | "thes" | "phdthesis" | "mastersthesis" => "thesis",
+        "rprt" | "techreport" => "report",
+        "web page" | "elec" | "online" => "webpage",
+        "data" | "data file" => "dataset",
+        "pat" => "patent",
+        "computer program" | "comp" => "software",
+        "unpublished work" | "unpb" | "unpublished" => "manuscript",
+        _ => "article",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ContributorRole;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Journal Article", "article-journal")]
+    #[case("JOUR", "article-journal")]
+    #[case("inproceedings", "paper-conference")]
+    #[case("article", "article")]
+    #[case("Book Chapter", "chapter")]
+    #[case("legal_case", "legal_case")]
+    #[case("Clinical Trial", "article")]
+    fn test_csl_type(#[case] citation_type: &str, #[case] expected: &str) {
+        assert_eq!(csl_type(citation_type), expected);
+    }
+
+    #[test]
+    fn test_write_item() {
+        let citation = Citation {
+            citation_type: vec!["Journal Article".to_string()],
+            title: "Example".to_string(),
+            authors: vec![
+                Author {
+                    name: "Smith".to_string(),
+                    given_name: Some("John".to_string()),
+                    middle_name: Some("A".to_string()),
+                    affiliations: Vec::new(),
+                    role: ContributorRole::Author,
+                    orcid: None,
+                    is_organization: false,
+                },
+                Author {
+                    name: "World Health Organization".to_string(),
+                    given_name: None,
+                    middle_name: None,
+                    affiliations: Vec::new(),
+                    role: ContributorRole::CorporateAuthor,
+                    orcid: None,
+                    is_organization: true,
+                },
+            ],
+            date: Some(crate::Date {
+                year: 2020,
+                month: Some(6),
+                day: None,
+                season: None,
+                end: None,
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Value::Object(to_item(&citation, 0)),
+            json!({
+                "id": "item-1",
+                "type": "article-journal",
+                "title": "Example",
+                "author": [
+                    {"family": "Smith", "given": "John A"},
+                    {"literal": "World Health Organization"}
+                ],
+                "issued": {"date-parts": [[2020, 6]]}
+            })
+        );
+    }
+}
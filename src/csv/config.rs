@@ -25,6 +25,7 @@ pub(crate) const DEFAULT_HEADERS: &[(&str, &[&str])] = &[
     ("abstract", &["abstract", "summary"]),
     ("keywords", &["keywords", "tags"]),
     ("issn", &["issn"]),
+    ("isbn", &["isbn"]),
     ("language", &["language", "lang"]),
     ("publisher", &["publisher"]),
     ("url", &["url", "link", "web link"]),
@@ -72,6 +73,8 @@ pub struct CsvConfig {
     pub(crate) flexible: bool,
     /// Whether to store original record for debugging (memory optimization)
     pub(crate) store_original_record: bool,
+    /// Separator between values in multi-value columns (authors, keywords, URLs)
+    pub(crate) multi_value_separator: char,
 }
 
 impl Default for CsvConfig {
@@ -93,6 +96,7 @@ impl CsvConfig {
             trim: true,
             flexible: false,
             store_original_record: false,
+            multi_value_separator: ';',
         };
         config.set_default_headers();
         config
@@ -172,6 +176,13 @@ impl CsvConfig {
         self
     }
 
+    /// Sets the separator between values in multi-value columns such as authors,
+    /// keywords and URLs
+    pub fn set_multi_value_separator(&mut self, separator: char) -> &mut Self {
+        self.multi_value_separator = separator;
+        self
+    }
+
     /// Finds the field name for a given header using O(1) lookup
     pub(crate) fn get_field_for_header(&self, header: &str) -> Option<&str> {
         let header_lower = header.to_lowercase();
@@ -325,7 +336,8 @@ mod tests {
             .set_quote(b'\'')
             .set_trim(false)
             .set_flexible(true)
-            .set_store_original_record(true);
+            .set_store_original_record(true)
+            .set_multi_value_separator('|');
 
         assert_eq!(config.delimiter, b';');
         assert!(!config.has_header);
@@ -333,5 +345,6 @@ mod tests {
         assert!(!config.trim);
         assert!(config.flexible);
         assert!(config.store_original_record);
+        assert_eq!(config.multi_value_separator, '|');
     }
 }
@@ -15,19 +15,26 @@
 //! let citations = parser.parse(input).unwrap();
 //! assert_eq!(citations[0].title, "Example Paper");
 //! ```
+//!
+//! [`CsvWriter`] writes citations back out using the same [`CsvConfig`].
 
 mod config;
 mod parse;
 mod structure;
+mod write;
 
+use crate::error::{ParseError, ParseWarning, WarningKind};
 #[allow(deprecated)]
 pub use crate::ictrp::csv::IctrpCsvParser;
 pub(crate) use crate::ictrp::looks_like_ictrp_csv;
-use crate::{Citation, CitationFormat, CitationParser};
+use crate::{Citation, CitationFormat, CitationParser, ParseOptions, ParseReport, collect_report};
 pub use config::CsvConfig;
 use parse::csv_parse;
-pub(crate) use parse::csv_parse_with_format;
+pub(crate) use parse::{CsvRecords, csv_parse_with_format};
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Read};
 pub(crate) use structure::RawCsvData;
+pub use write::CsvWriter;
 
 /// Parser for CSV-formatted citation data with configurable mappings.
 ///
@@ -38,7 +45,7 @@ pub(crate) use structure::RawCsvData;
 ///
 /// - Custom header mappings with O(1) lookup performance
 /// - Configurable delimiters, quotes, and trimming
-/// - Multiple author parsing (semicolon-separated)
+/// - Multiple author parsing (semicolon-separated by default)
 /// - Support for extra fields not covered by standard citation fields
 /// - Automatic delimiter detection
 /// - Enhanced error reporting with line numbers
@@ -190,31 +197,112 @@ impl CitationParser for CsvParser {
 
         let mut citations = Vec::with_capacity(raw_citations.len());
         for raw in raw_citations {
-            // Convert the citation, handling potential errors
-            let citation = raw
-                .into_citation_with_config(&config)
-                .map_err(|citation_err| {
-                    // Convert CitationError to ParseError
-                    match citation_err {
-                        crate::error::CitationError::Parse(parse_err) => parse_err,
-                        crate::error::CitationError::UnknownFormat => {
-                            crate::error::ParseError::without_position(
-                                CitationFormat::Csv,
-                                crate::error::ValueError::Syntax("Unknown format".to_string()),
-                            )
-                        }
-                    }
-                })?;
-            citations.push(citation);
+            citations.push(into_citation(raw, &config)?);
         }
 
         Ok(citations)
     }
+
+    /// Reads one CSV record at a time. With auto-detection enabled, the
+    /// delimiter and header row are detected from the first five lines.
+    fn parse_reader<'r, R>(
+        &self,
+        reader: R,
+    ) -> Box<dyn Iterator<Item = std::result::Result<Citation, crate::error::ParseError>> + 'r>
+    where
+        R: Read + 'r,
+    {
+        let mut reader = BufReader::new(reader);
+        let mut sample = String::new();
+        if self.auto_detect {
+            for _ in 0..5 {
+                match reader.read_line(&mut sample) {
+                    Ok(0) => break,
+                    Ok(_) => {}
+                    Err(err) => {
+                        return Box::new(std::iter::once(Err(crate::error::ParseError::at_line(
+                            sample.lines().count() + 1,
+                            CitationFormat::Csv,
+                            crate::error::ValueError::Io(err),
+                        ))));
+                    }
+                }
+            }
+        }
+
+        let config = self.auto_detect_format(&sample);
+        let input = std::io::Cursor::new(sample.into_bytes()).chain(reader);
+        match CsvRecords::new(input, config.clone(), CitationFormat::Csv) {
+            Ok(records) => {
+                Box::new(records.map(move |raw| raw.and_then(|raw| into_citation(raw, &config))))
+            }
+            Err(err) => Box::new(std::iter::once(Err(err))),
+        }
+    }
+
+    /// Reports unparseable years, invalid DOIs, columns kept in
+    /// `extra_fields` and columns that map to an already filled field as
+    /// warnings. Each unknown column is reported once, at the first record
+    /// with a value for it.
+    fn parse_with_options(
+        &self,
+        input: &str,
+        options: &ParseOptions,
+    ) -> std::result::Result<ParseReport, ParseError> {
+        if input.trim().is_empty() {
+            return Ok(ParseReport::default());
+        }
+
+        let config = self.auto_detect_format(input);
+        let records = match CsvRecords::new(input.as_bytes(), config.clone(), CitationFormat::Csv) {
+            Ok(records) => records,
+            Err(err) => return collect_report(std::iter::once(Err(err)), options),
+        };
+
+        let mut reported = HashSet::new();
+        let records = records.map(|raw| {
+            let (citation, mut warnings) =
+                raw.and_then(|raw| into_citation_with_warnings(raw, &config))?;
+            warnings.retain(|warning| match &warning.kind {
+                WarningKind::UnknownTag { tag } => reported.insert(tag.clone()),
+                _ => true,
+            });
+            Ok((citation, warnings))
+        });
+        collect_report(records, options)
+    }
+}
+
+/// Convert a raw record, reporting conversion failures as a `ParseError`.
+fn into_citation(raw: RawCsvData, config: &CsvConfig) -> std::result::Result<Citation, ParseError> {
+    raw.into_citation_with_config(config)
+        .map_err(into_parse_error)
+}
+
+/// Convert a raw record, keeping its warnings.
+fn into_citation_with_warnings(
+    raw: RawCsvData,
+    config: &CsvConfig,
+) -> std::result::Result<(Citation, Vec<ParseWarning>), ParseError> {
+    raw.into_citation_with_warnings(config)
+        .map_err(into_parse_error)
+}
+
+fn into_parse_error(citation_err: crate::error::CitationError) -> ParseError {
+    // Convert CitationError to ParseError
+    match citation_err {
+        crate::error::CitationError::Parse(parse_err) => parse_err,
+        crate::error::CitationError::UnknownFormat => ParseError::without_position(
+            CitationFormat::Csv,
+            crate::error::ValueError::Syntax("Unknown format".to_string()),
+        ),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::CitationWriter;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -405,6 +493,31 @@ Another Paper,Doe J,2024";
         assert!(citations[0].keywords.contains(&"keyword1".to_string()));
     }
 
+    #[test]
+    fn test_isbn_column_fills_identifiers() {
+        use crate::{Scheme, WarningKind};
+
+        let input = "Title,ISBN\nTest Book,\"978-0-306-40615-7; 0-306-40615-1\"\n";
+        let report = CsvParser::new()
+            .parse_with_options(input, &ParseOptions::new())
+            .unwrap();
+
+        let isbns: Vec<_> = report.citations[0].identifiers_of(Scheme::Isbn).collect();
+        assert_eq!(isbns, vec!["9780306406157"]);
+        assert_eq!(report.citations[0].issn, vec!["0-306-40615-1"]);
+        assert_eq!(
+            report.warnings[0].kind,
+            WarningKind::InvalidCheckDigit {
+                key: "isbn".to_string(),
+                value: "0-306-40615-1".to_string()
+            }
+        );
+
+        let output = CsvWriter::new().write(&report.citations);
+        assert!(output.starts_with("title,author,journal,year,volume,issue,pages,issn,isbn,"));
+        assert!(output.contains(",0-306-40615-1,9780306406157,"));
+    }
+
     #[test]
     fn test_empty_input() {
         let parser = CsvParser::new();
@@ -498,4 +611,110 @@ Another Paper,Doe J,2024";
         assert_eq!(raw[1].line_number, 3);
         assert_eq!(raw[2].line_number, 4);
     }
+
+    #[test]
+    fn test_write_round_trip() {
+        let input = "\
+Title,Authors,Year,Journal,Volume,Pages,DOI,Keywords,URL,Label,Custom Field
+First Paper,\"Smith, John A; Doe, Jane\",2023,Test Journal,12,100-110,10.1000/one,alpha; beta,https://a.example; https://b.example,include,x
+\"Second, \"\"quoted\"\" paper\",Brown,2021,,,,,,,,
+";
+        let parser = CsvParser::new();
+        let citations = parser.parse(input).unwrap();
+
+        let output = CsvWriter::new().with_extra_fields(true).write(&citations);
+        assert_eq!(parser.parse(&output).unwrap(), citations);
+        assert!(output.starts_with("title,author,journal,year,volume,"));
+    }
+
+    #[test]
+    fn test_write_round_trip_tsv_custom_config() {
+        let mut config = CsvConfig::new();
+        config
+            .set_delimiter(b'\t')
+            .set_multi_value_separator('|')
+            .set_header_mapping("title", vec!["Article Name".to_string()])
+            .set_header_mapping("authors", vec!["Writers".to_string()])
+            .set_header_mapping("pmid", vec!["PMID".to_string()]);
+        let input = "Article Name\tWriters\tPMID\tKeywords\nA paper\tSmith, J | Doe, Jane\t123\tone | two; three\n";
+        let parser = CsvParser::with_config(config.clone());
+        let citations = parser.parse(input).unwrap();
+        assert_eq!(citations[0].keywords, vec!["one", "two; three"]);
+
+        let writer =
+            CsvWriter::with_config(config).with_columns(["pmid", "title", "authors", "keywords"]);
+        let output = writer.write(&citations);
+        assert_eq!(
+            output,
+            "PMID\tArticle Name\tWriters\tkeywords\n123\tA paper\tSmith, J| Doe, Jane\tone| two; three\n"
+        );
+        assert_eq!(parser.parse(&output).unwrap(), citations);
+    }
+
+    #[test]
+    fn test_parse_reader_matches_parse() {
+        let input = "Title,Author,Year\nFirst,Smith J,2020\nSecond,Doe J,2021\nThird,Roe R,2022\nFourth,Poe P,2023\nFifth,Moe M,2024\n";
+        let parser = CsvParser::new();
+
+        let streamed: Result<Vec<_>, _> = parser.parse_reader(input.as_bytes()).collect();
+        let streamed = streamed.unwrap();
+        assert_eq!(streamed.len(), 5);
+        assert_eq!(streamed, parser.parse(input).unwrap());
+    }
+
+    #[test]
+    fn test_parse_reader_continues_after_bad_row() {
+        let input = "Title,Author,Year\nFirst,Smith J,2020\n,Doe J,2021\nThird,Roe R,2022\n";
+        let parser = CsvParser::new();
+        let expected = parser.parse(input).unwrap_err();
+
+        let streamed: Vec<_> = parser.parse_reader(input.as_bytes()).collect();
+        assert_eq!(streamed.len(), 3);
+        assert_eq!(streamed[2].as_ref().unwrap().title, "Third");
+        let err = streamed[1].as_ref().unwrap_err();
+        assert_eq!(err.line, expected.line);
+        assert_eq!(err.line, Some(3));
+    }
+
+    #[test]
+    fn test_parse_with_options_reports_warnings() {
+        use crate::WarningKind;
+
+        let input = "Title,Year,DOI,Notes\nFirst,2020,10.1000/a,one\nSecond,n.d.,none,two\n";
+        let report = CsvParser::new()
+            .parse_with_options(input, &ParseOptions::new())
+            .unwrap();
+
+        assert_eq!(report.citations.len(), 2);
+        let warnings: Vec<_> = report
+            .warnings
+            .iter()
+            .map(|w| (w.line, w.kind.clone()))
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                (
+                    Some(2),
+                    WarningKind::UnknownTag {
+                        tag: "Notes".to_string()
+                    }
+                ),
+                (
+                    Some(3),
+                    WarningKind::UnparseableDate {
+                        key: "year".to_string(),
+                        value: "n.d.".to_string()
+                    }
+                ),
+                (
+                    Some(3),
+                    WarningKind::InvalidDoi {
+                        key: "doi".to_string(),
+                        value: "none".to_string()
+                    }
+                ),
+            ]
+        );
+    }
 }
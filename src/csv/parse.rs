@@ -6,7 +6,8 @@ use crate::CitationFormat;
 use crate::csv::config::CsvConfig;
 use crate::csv::structure::RawCsvData;
 use crate::error::{ParseError, ValueError};
-use csv::ReaderBuilder;
+use csv::{ReaderBuilder, StringRecord};
+use std::io::Read;
 
 /// Parse the content of a CSV formatted file, returning structured data.
 pub fn csv_parse<S: AsRef<str>>(
@@ -28,107 +29,158 @@ pub(crate) fn csv_parse_with_format<S: AsRef<str>>(
         return Ok(Vec::new());
     }
 
-    // Validate configuration
-    config.validate().map_err(|msg| {
-        ParseError::without_position(
-            format.clone(),
-            ValueError::Syntax(format!("Invalid CSV configuration: {}", msg)),
-        )
-    })?;
-
-    let mut reader = ReaderBuilder::new()
-        .delimiter(config.delimiter)
-        .has_headers(config.has_header)
-        .quote(config.quote)
-        .trim(if config.trim {
-            csv::Trim::All
-        } else {
-            csv::Trim::None
-        })
-        .flexible(config.flexible)
-        .from_reader(text.as_bytes());
+    CsvRecords::new(text.as_bytes(), config.clone(), format)?.collect()
+}
 
-    let headers: Vec<String> = if config.has_header {
-        reader
-            .headers()
-            .map_err(|e| {
-                ParseError::without_position(
-                    format.clone(),
-                    ValueError::Syntax(format!("Header parsing error: {}", e)),
-                )
-            })?
-            .iter()
-            .map(String::from)
-            .collect()
-    } else {
-        // Use column numbers as headers if no headers present
-        let first_record = reader.headers().map_err(|e| {
+/// Reads CSV records one at a time.
+///
+/// Iteration ends after the first error from the underlying CSV reader;
+/// records that are read but cannot be mapped yield an error and iteration
+/// continues.
+pub(crate) struct CsvRecords<R> {
+    reader: csv::Reader<R>,
+    headers: Vec<String>,
+    config: CsvConfig,
+    format: CitationFormat,
+    line_number: usize,
+    done: bool,
+}
+
+impl<R: Read> CsvRecords<R> {
+    /// Validate the configuration and read the header row.
+    pub(crate) fn new(
+        input: R,
+        config: CsvConfig,
+        format: CitationFormat,
+    ) -> Result<Self, ParseError> {
+        // Validate configuration
+        config.validate().map_err(|msg| {
             ParseError::without_position(
                 format.clone(),
-                ValueError::Syntax(format!("Failed to read first record: {}", e)),
+                ValueError::Syntax(format!("Invalid CSV configuration: {}", msg)),
             )
         })?;
-        (0..first_record.len())
-            .map(|i| format!("Column{}", i + 1))
-            .collect()
-    };
-
-    if headers.is_empty() {
-        return Err(ParseError::without_position(
-            format.clone(),
-            ValueError::Syntax("No headers found in CSV".to_string()),
-        ));
-    }
-
-    let mut raw_citations = Vec::new();
-    let mut line_number = if config.has_header { 2 } else { 1 }; // Start counting from data lines
 
-    for result in reader.records() {
-        let record = result.map_err(|e| {
-            // Extract position information from csv::Error if available
-            if let Some(position) = e.position() {
-                ParseError::at_line(
-                    position.line() as usize,
-                    format.clone(),
-                    ValueError::Syntax(format!("CSV parsing error: {}", e)),
-                )
+        let mut reader = ReaderBuilder::new()
+            .delimiter(config.delimiter)
+            .has_headers(config.has_header)
+            .quote(config.quote)
+            .trim(if config.trim {
+                csv::Trim::All
             } else {
-                ParseError::at_line(
-                    line_number,
+                csv::Trim::None
+            })
+            .flexible(config.flexible)
+            .from_reader(input);
+
+        let headers: Vec<String> = if config.has_header {
+            reader
+                .headers()
+                .map_err(|e| {
+                    ParseError::without_position(
+                        format.clone(),
+                        ValueError::Syntax(format!("Header parsing error: {}", e)),
+                    )
+                })?
+                .iter()
+                .map(String::from)
+                .collect()
+        } else {
+            // Use column numbers as headers if no headers present
+            let first_record = reader.headers().map_err(|e| {
+                ParseError::without_position(
                     format.clone(),
-                    ValueError::Syntax(format!("CSV parsing error: {}", e)),
+                    ValueError::Syntax(format!("Failed to read first record: {}", e)),
                 )
-            }
-        })?;
-
-        if record.is_empty() {
-            line_number += 1;
-            continue;
+            })?;
+            (0..first_record.len())
+                .map(|i| format!("Column{}", i + 1))
+                .collect()
+        };
+
+        // Empty input has no header row and no records.
+        let done = headers.is_empty() && reader.is_done();
+        if headers.is_empty() && !done {
+            return Err(ParseError::without_position(
+                format.clone(),
+                ValueError::Syntax("No headers found in CSV".to_string()),
+            ));
         }
 
-        let byte_offset = record.position().map(|p| p.byte() as usize).unwrap_or(0);
+        let line_number = if config.has_header { 2 } else { 1 }; // Start counting from data lines
 
-        let raw_citation =
-            RawCsvData::from_record(&headers, &record, config, line_number, byte_offset, &format)?;
+        Ok(Self {
+            reader,
+            headers,
+            config,
+            format,
+            line_number,
+            done,
+        })
+    }
+}
+
+impl<R: Read> Iterator for CsvRecords<R> {
+    type Item = Result<RawCsvData, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let format = &self.format;
+        let mut record = StringRecord::new();
+
+        while !self.done {
+            let line_number = self.line_number;
+            match self.reader.read_record(&mut record) {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.done = true;
+                    break;
+                }
+                Err(e) => {
+                    self.done = true;
+                    // Extract position information from csv::Error if available
+                    let line = e
+                        .position()
+                        .map_or(line_number, |position| position.line() as usize);
+                    return Some(Err(ParseError::at_line(
+                        line,
+                        format.clone(),
+                        ValueError::Syntax(format!("CSV parsing error: {}", e)),
+                    )));
+                }
+            }
+            self.line_number += 1;
+
+            if record.is_empty() {
+                continue;
+            }
 
-        if raw_citation.has_content() {
-            raw_citations.push(raw_citation);
-        } else if !config.flexible {
-            return Err(ParseError::at_line(
+            let byte_offset = record.position().map(|p| p.byte() as usize).unwrap_or(0);
+
+            let raw_citation = match RawCsvData::from_record(
+                &self.headers,
+                &record,
+                &self.config,
                 line_number,
-                format.clone(),
-                ValueError::Syntax("Record contains no meaningful content".to_string()),
-            ));
+                byte_offset,
+                format,
+            ) {
+                Ok(raw_citation) => raw_citation,
+                Err(err) => return Some(Err(err)),
+            };
+
+            if raw_citation.has_content() {
+                return Some(Ok(raw_citation));
+            } else if !self.config.flexible {
+                return Some(Err(ParseError::at_line(
+                    line_number,
+                    format.clone(),
+                    ValueError::Syntax("Record contains no meaningful content".to_string()),
+                )));
+            }
         }
 
-        line_number += 1;
+        None
     }
-
-    if raw_citations.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    Ok(raw_citations)
 }
 
 /// Detect CSV delimiter by analyzing the content.
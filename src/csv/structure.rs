@@ -3,8 +3,9 @@
 //! This module defines intermediate data structures used during CSV parsing.
 
 use crate::csv::config::CsvConfig;
-use crate::error::{ParseError, SourceSpan, ValueError, fields};
-use crate::{Author, CitationFormat};
+use crate::error::{ParseError, ParseWarning, SourceSpan, ValueError, WarningKind, fields};
+use crate::utils::{detect_identifier, sort_serial_numbers};
+use crate::{Author, CitationFormat, Identifier};
 use csv::StringRecord;
 use std::collections::HashMap;
 
@@ -19,12 +20,14 @@ pub(crate) struct RawCsvData {
     pub(crate) keywords: Vec<String>,
     /// URLs parsed from URL fields
     pub(crate) urls: Vec<String>,
-    /// ISSN values parsed from ISSN fields
+    /// Values of ISSN fields, sorted into ISSNs and ISBNs on conversion
     pub(crate) issn: Vec<String>,
     /// Line number for error reporting
     pub(crate) line_number: usize,
     /// Byte offset of the record start in the source text.
     pub(crate) byte_offset: usize,
+    /// Data dropped while reading the record.
+    pub(crate) warnings: Vec<ParseWarning>,
     /// Original record for debugging (optional for memory efficiency)
     #[allow(dead_code)]
     pub(crate) original_record: Option<Vec<String>>,
@@ -45,6 +48,7 @@ impl RawCsvData {
         let mut keywords = Vec::new();
         let mut urls = Vec::new();
         let mut issn = Vec::new();
+        let mut warnings = Vec::new();
 
         // Store original record for debugging if enabled
         let original_record = if config.store_original_record {
@@ -79,7 +83,7 @@ impl RawCsvData {
             if let Some(field) = config.get_field_for_header(header) {
                 match field {
                     "authors" => {
-                        for author_str in value.split(';') {
+                        for author_str in value.split(config.multi_value_separator) {
                             let author_str = author_str.trim();
                             if !author_str.is_empty() {
                                 let (family, given) = crate::utils::parse_author_name(author_str);
@@ -93,6 +97,9 @@ impl RawCsvData {
                                     given_name: given_opt,
                                     middle_name: middle_opt,
                                     affiliations: Vec::new(),
+                                    role: crate::ContributorRole::Author,
+                                    orcid: None,
+                                    is_organization: false,
                                 });
                             }
                         }
@@ -100,20 +107,40 @@ impl RawCsvData {
                     "keywords" => {
                         keywords.extend(
                             value
-                                .split(';')
+                                .split(config.multi_value_separator)
                                 .map(str::trim)
                                 .filter(|s| !s.is_empty())
                                 .map(String::from),
                         );
                     }
                     "url" => {
-                        urls.push(value.to_string());
+                        urls.extend(
+                            value
+                                .split(config.multi_value_separator)
+                                .map(str::trim)
+                                .filter(|s| !s.is_empty())
+                                .map(String::from),
+                        );
                     }
                     "issn" => {
-                        issn.extend(crate::utils::split_issns(value));
+                        issn.push(value.to_string());
                     }
                     _ => {
-                        fields.insert(field.to_string(), value.to_string());
+                        // Several headers can map to the same field; the last one wins.
+                        if let Some(previous) = fields.insert(field.to_string(), value.to_string())
+                        {
+                            warnings.push(
+                                ParseWarning::at_line(
+                                    line_number,
+                                    format.clone(),
+                                    WarningKind::DroppedValue {
+                                        key: field.to_string(),
+                                        value: previous,
+                                    },
+                                )
+                                .with_span(SourceSpan::new(byte_offset, byte_offset)),
+                            );
+                        }
                     }
                 }
             } else {
@@ -130,6 +157,7 @@ impl RawCsvData {
             issn,
             line_number,
             byte_offset,
+            warnings,
             original_record,
         })
     }
@@ -139,6 +167,17 @@ impl RawCsvData {
         self,
         config: &CsvConfig,
     ) -> Result<crate::Citation, crate::error::CitationError> {
+        self.into_citation_with_warnings(config)
+            .map(|(citation, _)| citation)
+    }
+
+    /// Convert to Citation, returning the warnings for data that was dropped
+    /// or coerced along the way. Every column kept in `extra_fields` is
+    /// reported as an unknown tag.
+    pub(crate) fn into_citation_with_warnings(
+        mut self,
+        config: &CsvConfig,
+    ) -> Result<(crate::Citation, Vec<ParseWarning>), crate::error::CitationError> {
         let title = self.get_field("title").cloned().ok_or_else(|| {
             ParseError::at_line(
                 self.line_number,
@@ -155,9 +194,16 @@ impl RawCsvData {
         let journal_abbr = self.get_field("journal_abbr").cloned();
 
         // Parse date/year
-        let date = self
-            .get_field("year")
-            .and_then(|year_str| crate::utils::parse_year_only(year_str));
+        let date = self.get_field("year").cloned().and_then(|year_str| {
+            let date = crate::utils::parse_year_only(&year_str);
+            if date.is_none() {
+                self.warn(WarningKind::UnparseableDate {
+                    key: "year".to_string(),
+                    value: year_str,
+                });
+            }
+            date
+        });
 
         let volume = self.get_field("volume").cloned();
         let issue = self.get_field("issue").cloned();
@@ -166,9 +212,36 @@ impl RawCsvData {
             .get_field("pages")
             .map(|p| crate::utils::format_page_numbers(p));
 
-        let doi = self
-            .get_field("doi")
-            .and_then(|doi_str| crate::utils::format_doi(doi_str));
+        let doi = self.get_field("doi").cloned().and_then(|doi_str| {
+            let doi = crate::utils::format_doi(&doi_str);
+            if doi.is_none() {
+                self.warn(WarningKind::InvalidDoi {
+                    key: "doi".to_string(),
+                    value: doi_str,
+                });
+            }
+            doi
+        });
+
+        let accession_number = self.get_field("accession_number").cloned();
+        let mut identifiers: Vec<Identifier> = accession_number
+            .as_deref()
+            .and_then(detect_identifier)
+            .into_iter()
+            .collect();
+        let mut issn = Vec::new();
+        let serial_numbers = std::mem::take(&mut self.issn)
+            .into_iter()
+            .map(|value| ("issn", value))
+            .chain(self.get_field("isbn").cloned().map(|value| ("isbn", value)));
+        for (key, value) in serial_numbers.collect::<Vec<_>>() {
+            for invalid in sort_serial_numbers(&value, &mut issn, &mut identifiers) {
+                self.warn(WarningKind::InvalidCheckDigit {
+                    key: key.to_string(),
+                    value: invalid,
+                });
+            }
+        }
 
         let abstract_text = self.get_field("abstract").cloned();
         let language = self.get_field("language").cloned();
@@ -182,22 +255,30 @@ impl RawCsvData {
 
         // Properly extract extra fields using the config
         let extra_fields = self.get_extra_fields(config);
+        let mut unknown: Vec<_> = extra_fields.keys().cloned().collect();
+        unknown.sort();
+        for tag in unknown {
+            self.warn(WarningKind::UnknownTag { tag });
+        }
 
-        Ok(crate::Citation {
+        let citation = crate::Citation {
             citation_type,
             title,
             authors: self.authors.clone(),
+            contributors: Vec::new(),
             journal,
             journal_abbr,
             date: date.clone(),
+            dates: Vec::new(),
             volume,
             issue,
             pages,
-            issn: self.issn.clone(),
+            issn,
             doi,
-            accession_number: self.get_field("accession_number").cloned(),
+            accession_number,
             pmid: self.get_field("pmid").cloned(),
             pmc_id: self.get_field("pmc_id").cloned(),
+            identifiers,
             abstract_text,
             keywords: self.keywords.clone(),
             urls: self.urls.clone(),
@@ -205,7 +286,16 @@ impl RawCsvData {
             mesh_terms: Vec::new(), // CSV typically doesn't have MeSH terms
             publisher,
             extra_fields,
-        })
+        };
+        Ok((citation, self.warnings))
+    }
+
+    /// Add a warning located at the start of this record.
+    fn warn(&mut self, kind: WarningKind) {
+        self.warnings.push(
+            ParseWarning::at_line(self.line_number, CitationFormat::Csv, kind)
+                .with_span(SourceSpan::new(self.byte_offset, self.byte_offset)),
+        );
     }
 
     /// Get a field value by name.
@@ -234,7 +324,7 @@ impl RawCsvData {
 }
 
 /// Check if a field name corresponds to a standard citation field.
-fn is_standard_field(field_name: &str, config: &CsvConfig) -> bool {
+pub(crate) fn is_standard_field(field_name: &str, config: &CsvConfig) -> bool {
     const STANDARD_FIELDS: &[&str] = &[
         "title",
         "authors",
@@ -251,6 +341,7 @@ fn is_standard_field(field_name: &str, config: &CsvConfig) -> bool {
         "abstract",
         "keywords",
         "issn",
+        "isbn",
         "language",
         "publisher",
         "type",
@@ -328,6 +419,24 @@ mod tests {
         assert!(raw.keywords.contains(&"keyword1".to_string()));
     }
 
+    #[test]
+    fn test_from_record_custom_multi_value_separator() {
+        let headers = vec!["Authors".to_string(), "URL".to_string()];
+        let record = create_test_record(&[
+            "Smith, John | Doe, Jane",
+            "https://a.example | https://b.example",
+        ]);
+        let mut config = CsvConfig::new();
+        config.set_multi_value_separator('|');
+
+        let raw = RawCsvData::from_record(&headers, &record, &config, 1, 0, &CitationFormat::Csv)
+            .unwrap();
+
+        assert_eq!(raw.authors.len(), 2);
+        assert_eq!(raw.authors[1].given_name.as_deref(), Some("Jane"));
+        assert_eq!(raw.urls, vec!["https://a.example", "https://b.example"]);
+    }
+
     #[test]
     fn test_from_record_too_many_fields_strict() {
         let headers = vec!["Title".to_string()];
@@ -0,0 +1,354 @@
+//! CSV format writing implementation.
+//!
+//! Columns are named after the first alias of each field in a [`CsvConfig`],
+//! so output written with a configuration parses back with the same one.
+
+use crate::csv::config::CsvConfig;
+use crate::csv::structure::is_standard_field;
+use crate::utils::format_author_name;
+use crate::{Citation, CitationWriter, Scheme};
+use csv::{QuoteStyle, WriterBuilder};
+use std::collections::BTreeSet;
+
+/// Fields written by default, in column order.
+///
+/// Only fields that the configuration maps to a column name are written, so
+/// the default configuration skips `type`, `journal_abbr`, `pmid` and `pmc_id`.
+const DEFAULT_COLUMNS: &[&str] = &[
+    "type",
+    "title",
+    "authors",
+    "journal",
+    "journal_abbr",
+    "year",
+    "volume",
+    "issue",
+    "pages",
+    "issn",
+    "isbn",
+    "doi",
+    "accession_number",
+    "pmid",
+    "pmc_id",
+    "abstract",
+    "keywords",
+    "url",
+    "language",
+    "publisher",
+];
+
+/// Writer for CSV (and other delimited) citation data.
+///
+/// The writer is driven by the same [`CsvConfig`] as [`super::CsvParser`]:
+///
+/// - each field is written under the first alias in its header mapping
+/// - the delimiter and quote character are taken from the configuration, so
+///   `set_delimiter(b'\t')` produces TSV
+/// - authors, keywords, URLs, ISSNs and ISBNs are joined with the multi-value
+///   separator followed by a space
+/// - the header row is omitted when `has_header` is disabled
+///
+/// Only the year of a citation's date and its first citation type are written,
+/// matching what the parser reads back.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::csv::{CsvConfig, CsvParser, CsvWriter};
+/// use biblib::{Citation, CitationParser, CitationWriter};
+///
+/// let mut config = CsvConfig::new();
+/// config
+///     .set_delimiter(b'\t')
+///     .set_header_mapping("title", vec!["Article Title".to_string()]);
+///
+/// let citation = Citation {
+///     title: "Example Paper".to_string(),
+///     keywords: vec!["alpha".to_string(), "beta".to_string()],
+///     ..Default::default()
+/// };
+///
+/// let writer = CsvWriter::with_config(config.clone()).with_columns(["title", "keywords"]);
+/// let output = writer.write(&[citation]);
+/// assert_eq!(output, "Article Title\tkeywords\nExample Paper\talpha; beta\n");
+///
+/// let parsed = CsvParser::with_config(config).parse(&output).unwrap();
+/// assert_eq!(parsed[0].keywords, vec!["alpha", "beta"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CsvWriter {
+    config: CsvConfig,
+    columns: Option<Vec<String>>,
+    include_extra_fields: bool,
+}
+
+impl CsvWriter {
+    /// Creates a new CSV writer with default configuration
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new CSV writer with custom configuration
+    #[must_use]
+    pub fn with_config(config: CsvConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the fields to write, in column order.
+    ///
+    /// Field names are the keys of the configuration's header mappings (for
+    /// example `"title"` or `"authors"`). Names that are not citation fields are
+    /// looked up in `extra_fields`.
+    #[must_use]
+    pub fn with_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets whether `extra_fields` are written as additional columns.
+    ///
+    /// Extra columns follow the configured ones in key order. Keys that would
+    /// be read back as a standard field are skipped.
+    #[must_use]
+    pub fn with_extra_fields(mut self, include: bool) -> Self {
+        self.include_extra_fields = include;
+        self
+    }
+
+    /// Gets a reference to the current configuration
+    pub fn config(&self) -> &CsvConfig {
+        &self.config
+    }
+
+    /// Resolve the `(field, header)` pairs to write, in column order.
+    fn resolve_columns(&self, citations: &[Citation]) -> Vec<(String, String)> {
+        let fields: Vec<String> = match &self.columns {
+            Some(columns) => columns.clone(),
+            None => DEFAULT_COLUMNS
+                .iter()
+                .filter(|field| self.config.header_map.contains_key(**field))
+                .map(|field| field.to_string())
+                .collect(),
+        };
+        let mut columns: Vec<(String, String)> = fields
+            .into_iter()
+            .map(|field| {
+                let header = self.header_for(&field);
+                (field, header)
+            })
+            .collect();
+
+        if self.include_extra_fields {
+            let keys: BTreeSet<&String> = citations
+                .iter()
+                .flat_map(|citation| citation.extra_fields.keys())
+                .collect();
+            for key in keys {
+                let header = self.header_for(key);
+                let maps_elsewhere = self
+                    .config
+                    .get_field_for_header(&header)
+                    .is_some_and(|field| field != key);
+                let taken = columns.iter().any(|(field, existing)| {
+                    field == key || existing.eq_ignore_ascii_case(&header)
+                });
+                if !maps_elsewhere && !taken && !is_standard_field(&header, &self.config) {
+                    columns.push((key.clone(), header));
+                }
+            }
+        }
+        columns
+    }
+
+    /// The column name for a field: the first alias in its header mapping, or
+    /// the field name itself when it has none.
+    fn header_for(&self, field: &str) -> String {
+        self.config
+            .header_map
+            .get(field)
+            .and_then(|aliases| aliases.first())
+            .cloned()
+            .unwrap_or_else(|| field.to_string())
+    }
+
+    /// The value written for `field`, or an empty string.
+    fn field_value(&self, citation: &Citation, field: &str) -> String {
+        let separator = format!("{} ", self.config.multi_value_separator);
+        let single = |value: Option<&String>| value.cloned().unwrap_or_default();
+        match field {
+            "type" => single(citation.citation_type.first()),
+            "title" => citation.title.clone(),
+            "authors" => citation
+                .authors
+                .iter()
+                .map(format_author_name)
+                .collect::<Vec<_>>()
+                .join(&separator),
+            "journal" => single(citation.journal.as_ref()),
+            "journal_abbr" => single(citation.journal_abbr.as_ref()),
+            "year" => citation
+                .date
+                .as_ref()
+                .map(|date| date.year.to_string())
+                .unwrap_or_default(),
+            "volume" => single(citation.volume.as_ref()),
+            "issue" => single(citation.issue.as_ref()),
+            "pages" => single(citation.pages.as_ref()),
+            "issn" => citation.issn.join(&separator),
+            "isbn" => citation
+                .identifiers_of(Scheme::Isbn)
+                .collect::<Vec<_>>()
+                .join(&separator),
+            "doi" => single(citation.doi.as_ref()),
+            "accession_number" => single(citation.accession_number.as_ref()),
+            "pmid" => single(citation.pmid.as_ref()),
+            "pmc_id" => single(citation.pmc_id.as_ref()),
+            "abstract" => single(citation.abstract_text.as_ref()),
+            "keywords" => citation.keywords.join(&separator),
+            "url" => citation.urls.join(&separator),
+            "language" => single(citation.language.as_ref()),
+            "publisher" => single(citation.publisher.as_ref()),
+            other => citation
+                .extra_fields
+                .get(other)
+                .map(|values| values.join(&separator))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl CitationWriter for CsvWriter {
+    fn write(&self, citations: &[Citation]) -> String {
+        let columns = self.resolve_columns(citations);
+        let mut writer = WriterBuilder::new()
+            .delimiter(self.config.delimiter)
+            .quote(self.config.quote)
+            .quote_style(QuoteStyle::Necessary)
+            .from_writer(Vec::new());
+
+        // Writing to an in-memory buffer only fails on I/O errors, which a
+        // `Vec` never produces, and every record has the same length.
+        if self.config.has_header {
+            writer
+                .write_record(columns.iter().map(|(_, header)| header))
+                .expect("writing CSV to memory cannot fail");
+        }
+        for citation in citations {
+            writer
+                .write_record(
+                    columns
+                        .iter()
+                        .map(|(field, _)| self.field_value(citation, field)),
+                )
+                .expect("writing CSV to memory cannot fail");
+        }
+        let bytes = writer
+            .into_inner()
+            .expect("writing CSV to memory cannot fail");
+        String::from_utf8(bytes).expect("CSV output is built from UTF-8 strings")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Author, ContributorRole, Date};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_default_columns_follow_config() {
+        let writer = CsvWriter::new();
+        let headers: Vec<String> = writer
+            .resolve_columns(&[])
+            .into_iter()
+            .map(|(_, header)| header)
+            .collect();
+
+        assert_eq!(headers.first().map(String::as_str), Some("title"));
+        assert!(headers.contains(&"author".to_string()));
+        assert!(!headers.contains(&"pmid".to_string()));
+    }
+
+    #[test]
+    fn test_field_values() {
+        let citation = Citation {
+            title: "Example".to_string(),
+            authors: vec![
+                Author {
+                    name: "Smith".to_string(),
+                    given_name: Some("John".to_string()),
+                    middle_name: None,
+                    affiliations: Vec::new(),
+                    role: ContributorRole::Author,
+                    orcid: None,
+                    is_organization: false,
+                },
+                Author {
+                    name: "Doe".to_string(),
+                    given_name: None,
+                    middle_name: None,
+                    affiliations: Vec::new(),
+                    role: ContributorRole::Author,
+                    orcid: None,
+                    is_organization: false,
+                },
+            ],
+            date: Some(Date {
+                year: 2023,
+                month: Some(5),
+                day: None,
+                season: None,
+                end: None,
+            }),
+            ..Default::default()
+        };
+        let writer = CsvWriter::new();
+
+        assert_eq!(writer.field_value(&citation, "authors"), "Smith, John; Doe");
+        assert_eq!(writer.field_value(&citation, "year"), "2023");
+        assert_eq!(writer.field_value(&citation, "missing"), "");
+    }
+
+    #[test]
+    fn test_extra_columns_skip_standard_fields() {
+        let mut citation = Citation {
+            title: "Example".to_string(),
+            ..Default::default()
+        };
+        citation
+            .extra_fields
+            .insert("Title".to_string(), vec!["Shadow".to_string()]);
+        citation
+            .extra_fields
+            .insert("Notes".to_string(), vec!["A note".to_string()]);
+
+        let writer = CsvWriter::new()
+            .with_columns(["title"])
+            .with_extra_fields(true);
+
+        assert_eq!(writer.write(&[citation]), "title,Notes\nExample,A note\n");
+    }
+
+    #[test]
+    fn test_without_header_row() {
+        let mut config = CsvConfig::new();
+        config.set_has_header(false);
+        let citation = Citation {
+            title: "Example, with comma".to_string(),
+            ..Default::default()
+        };
+
+        let output = CsvWriter::with_config(config)
+            .with_columns(["title", "year"])
+            .write(&[citation]);
+        assert_eq!(output, "\"Example, with comma\",\n");
+    }
+}
@@ -9,18 +9,22 @@
 //! - Flexible deduplication based on multiple citation fields
 //! - Smart matching of journal names and abbreviations
 //! - Support for DOI and non-DOI based citations
-//! - Optional year-based grouping for improved performance
+//! - Optional year-based grouping for improved performance, comparing
+//!   neighbouring years and undated citations
 //! - Parallel processing support
 //! - Unicode character handling
-//! - Configurable matching thresholds
+//! - Configurable matching thresholds, similarity metrics and rules
 //! - Source-aware deduplication with preferences
+//! - Match explanations and near-miss pairs for review
+//! - Order-independent transitive clustering with an optional diameter limit
+//! - Blocking strategies that avoid comparing every pair in large datasets
 //!
 //! ## Usage
 //!
 //! ### Basic Deduplication
 //!
 //! ```rust
-//! use biblib::{dedupe::Deduplicator, Citation, Author, Date};
+//! use biblib::{dedupe::Deduplicator, Citation, Author, ContributorRole, Date};
 //!
 //! // Create some sample citations
 //! let citations = vec![
@@ -32,10 +36,13 @@
 //!                 given_name: Some("John".to_string()),
 //!                 middle_name: None,
 //!                 affiliations: vec![],
+//!                 role: ContributorRole::Author,
+//!                 orcid: None,
+//!                 is_organization: false,
 //!             }
 //!         ],
 //!         doi: Some("10.1234/ml.2023.001".to_string()),
-//!         date: Some(Date { year: 2023, month: None, day: None }),
+//!         date: Some(Date { year: 2023, month: None, day: None, season: None, end: None }),
 //!         ..Default::default()
 //!     },
 //!     // Duplicate citation with slightly different title
@@ -47,10 +54,13 @@
 //!                 given_name: Some("John".to_string()),
 //!                 middle_name: None,
 //!                 affiliations: vec![],
+//!                 role: ContributorRole::Author,
+//!                 orcid: None,
+//!                 is_organization: false,
 //!             }
 //!         ],
 //!         doi: Some("10.1234/ml.2023.001".to_string()),
-//!         date: Some(Date { year: 2023, month: None, day: None }),
+//!         date: Some(Date { year: 2023, month: None, day: None, season: None, end: None }),
 //!         ..Default::default()
 //!     },
 //! ];
@@ -113,6 +123,7 @@
 //!     group_by_year: false,     // Disable year-based grouping
 //!     run_in_parallel: true,    // Enable parallel processing
 //!     source_preferences: vec!["PubMed".to_string(), "CrossRef".to_string()],
+//!     ..Default::default()
 //! };
 //!
 //! let deduplicator = Deduplicator::new().with_config(config);
@@ -120,7 +131,8 @@
 //!
 //! ## Matching Criteria
 //!
-//! Citations are considered duplicates based on the following criteria:
+//! Citations are considered duplicates when any of the configured
+//! [`MatchRule`]s matches. The default rules are:
 //!
 //! 1. With DOIs:
 //!    - Matching DOIs and high title similarity (≥ 0.85)
@@ -130,19 +142,38 @@
 //!    - Very high title similarity (≥ 0.93)
 //!    - Matching volume or page numbers
 //!    - Matching journal names or ISSNs
+//!
+//! The thresholds, the title similarity metric and the rules themselves can
+//! be changed through [`DeduplicatorConfig`]:
+//!
+//! ```rust
+//! use biblib::dedupe::{
+//!     DeduplicatorConfig, DoiScope, MatchField, MatchRule, SimilarityMetric, TitleThreshold,
+//! };
+//!
+//! let mut config = DeduplicatorConfig::default();
+//! // Require closer titles before records without DOIs are merged
+//! config.thresholds.no_doi_title = 0.96;
+//! // Also merge records with the same DOI and year whose titles differ in word order
+//! config.rules.push(MatchRule {
+//!     name: "same-doi-token-set".to_string(),
+//!     scope: DoiScope::BothHaveDoi,
+//!     metric: SimilarityMetric::TokenSet,
+//!     title_threshold: TitleThreshold::Value(0.9),
+//!     all_of: vec![MatchField::Doi, MatchField::Year],
+//!     any_of: Vec::new(),
+//! });
+//! ```
 
-use crate::regex::Regex;
 use crate::{Citation, DuplicateGroup};
-use std::collections::HashMap;
-use std::sync::LazyLock;
-use strsim::jaro;
-use strsim::jaro_winkler;
+use either::Either;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::ops::Bound::{Excluded, Included};
+use strsim::{jaro, jaro_winkler, normalized_levenshtein};
 
 const DOI_TITLE_SIMILARITY_THRESHOLD: f64 = 0.85;
 const NO_DOI_TITLE_SIMILARITY_THRESHOLD: f64 = 0.93;
-
-static UNICODE_REGEX: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"<U\+([0-9A-Fa-f]+)>").unwrap());
+const STRICT_TITLE_SIMILARITY_THRESHOLD: f64 = 0.99;
 
 const HTML_REPLACEMENTS: [(&str, &str); 13] = [
     ("&lt;", "<"),
@@ -175,6 +206,7 @@ const HTML_REPLACEMENTS: [(&str, &str); 13] = [
 ///     group_by_year: true,    // Enable year-based grouping
 ///     run_in_parallel: true,  // Enable parallel processing
 ///     source_preferences: vec!["PubMed".to_string(), "Google Scholar      ".to_string()],
+///     ..Default::default()
 /// };
 /// ```
 ///
@@ -187,7 +219,7 @@ const HTML_REPLACEMENTS: [(&str, &str); 13] = [
 ///
 /// - When `group_by_year` is false, `run_in_parallel` is automatically disabled
 /// - Year grouping is recommended for datasets with > 1000 citations
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct DeduplicatorConfig {
     /// Whether to group citations by year before processing.
     /// This can significantly improve performance for large datasets.
@@ -195,9 +227,425 @@ pub struct DeduplicatorConfig {
     /// Whether to use parallel processing for year groups.
     /// Most effective when combined with `group_by_year = true`.
     pub run_in_parallel: bool,
+    /// When grouping by year, how many following years each year's
+    /// citations are also compared with, so that an epub-ahead-of-print
+    /// record can match its print version. `0` compares each year only
+    /// with itself. Citations without a year are compared with every year.
+    /// Default: 1.
+    pub year_window: u32,
     /// Ordered list of preferred sources for unique citations.
     /// First source in the list has highest priority.
     pub source_preferences: Vec<String>,
+    /// Title similarity thresholds that rules refer to by name.
+    pub thresholds: MatchThresholds,
+    /// Rules deciding whether two citations are duplicates. A pair is a
+    /// duplicate when any rule matches. Defaults to [`MatchRule::default_rules`].
+    pub rules: Vec<MatchRule>,
+    /// How far below a rule's title threshold a pair may fall and still be
+    /// reported as a [`NearMiss`] by [`Deduplicator::explain_duplicates`].
+    /// `None`, the default, reports no near misses.
+    pub near_miss_margin: Option<f64>,
+    /// How matching pairs are combined into groups. Default:
+    /// [`Clustering::FirstSeed`].
+    pub clustering: Clustering,
+    /// Strategies proposing which pairs to compare. Empty, the default,
+    /// compares every pair (limited by year when grouping by year).
+    pub blocking: Vec<BlockingStrategy>,
+}
+
+impl Default for DeduplicatorConfig {
+    fn default() -> Self {
+        Self {
+            group_by_year: false,
+            run_in_parallel: false,
+            year_window: 1,
+            source_preferences: Vec::new(),
+            thresholds: MatchThresholds::default(),
+            rules: MatchRule::default_rules(),
+            near_miss_margin: None,
+            clustering: Clustering::default(),
+            blocking: Vec::new(),
+        }
+    }
+}
+
+/// How matching pairs of citations are combined into duplicate groups.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Clustering {
+    /// Each citation not yet in a group starts one and takes every
+    /// remaining citation that matches it directly. Fast, but if A matches
+    /// B and B matches C while A does not match C, whether C joins depends
+    /// on the input order.
+    #[default]
+    FirstSeed,
+    /// Groups are the connected components of the graph of all matching
+    /// pairs, so A, B and C above form one group whatever the input order.
+    Transitive {
+        /// Guards against chaining unrelated records through intermediate
+        /// ones: two citations in a group may be at most this many matches
+        /// apart. `Some(1)` is complete linkage, where every pair in a group
+        /// must match. Pairs are merged from the most to the least similar,
+        /// with ties broken by input position.
+        max_diameter: Option<usize>,
+    },
+}
+
+/// A way of choosing which pairs of citations to compare, so large
+/// datasets need not compare every pair.
+///
+/// With [`DeduplicatorConfig::blocking`] set, only pairs proposed by at
+/// least one strategy are compared against the rules. Pairs no strategy
+/// proposes are never matched, so strategies are usually combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlockingStrategy {
+    /// Citations sharing a DOI or PMID, ignoring case.
+    Identifier,
+    /// MinHash locality-sensitive hashing of the normalized title's
+    /// character shingles. Titles whose shingle sets overlap by about
+    /// `(1 / bands)^(1 / rows)` (Jaccard similarity) or more are likely to
+    /// share a band and be compared. 3-character shingles with 20 bands of
+    /// 3 rows catch nearly all titles that the default rules match.
+    MinHash {
+        /// Characters per shingle.
+        shingle: usize,
+        /// Number of bands; more bands propose more pairs.
+        bands: usize,
+        /// Hashes per band; more rows propose fewer, closer pairs.
+        rows: usize,
+    },
+    /// Sorts citations by normalized title and compares each with the
+    /// next `window - 1` citations, which catches titles that differ only
+    /// towards the end.
+    SortedNeighbourhood {
+        /// Number of consecutive citations compared with each other.
+        window: usize,
+    },
+}
+
+impl BlockingStrategy {
+    /// Identifier blocks, MinHash with 3-character shingles and 20 bands
+    /// of 3 rows, and a sorted neighbourhood of 10.
+    #[must_use]
+    pub fn recommended() -> Vec<BlockingStrategy> {
+        vec![
+            BlockingStrategy::Identifier,
+            BlockingStrategy::MinHash {
+                shingle: 3,
+                bands: 20,
+                rows: 3,
+            },
+            BlockingStrategy::SortedNeighbourhood { window: 10 },
+        ]
+    }
+}
+
+/// Title similarity thresholds used by the default [`MatchRule`]s.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchThresholds {
+    /// Minimum title similarity for citations sharing a DOI and a journal
+    /// or ISSN. Default: 0.85.
+    pub doi_title: f64,
+    /// Minimum title similarity for citations without DOIs that share a
+    /// journal or ISSN and a volume or pages. Default: 0.93.
+    pub no_doi_title: f64,
+    /// Minimum title similarity for the rules that rely on the title with
+    /// fewer matching fields. Default: 0.99.
+    pub strict_title: f64,
+}
+
+impl Default for MatchThresholds {
+    fn default() -> Self {
+        Self {
+            doi_title: DOI_TITLE_SIMILARITY_THRESHOLD,
+            no_doi_title: NO_DOI_TITLE_SIMILARITY_THRESHOLD,
+            strict_title: STRICT_TITLE_SIMILARITY_THRESHOLD,
+        }
+    }
+}
+
+/// String similarity measure used to compare normalized titles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SimilarityMetric {
+    /// Jaro similarity.
+    Jaro,
+    /// Jaro-Winkler similarity, which gives more weight to a shared prefix.
+    JaroWinkler,
+    /// Levenshtein edit distance scaled to 0.0-1.0.
+    NormalizedLevenshtein,
+    /// Normalized Levenshtein similarity of the titles' sorted word sets,
+    /// so word order does not matter and a title whose words are all in the
+    /// other title scores 1.0.
+    TokenSet,
+}
+
+impl SimilarityMetric {
+    /// All metrics, in declaration order.
+    const ALL: [SimilarityMetric; 4] = [
+        SimilarityMetric::Jaro,
+        SimilarityMetric::JaroWinkler,
+        SimilarityMetric::NormalizedLevenshtein,
+        SimilarityMetric::TokenSet,
+    ];
+
+    /// Similarity of two strings, from 0.0 to 1.0. [`TokenSet`](Self::TokenSet)
+    /// splits them into words on whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::dedupe::SimilarityMetric;
+    ///
+    /// let metric = SimilarityMetric::TokenSet;
+    /// assert_eq!(metric.similarity("deep learning review", "review deep learning"), 1.0);
+    /// ```
+    #[must_use]
+    pub fn similarity(self, a: &str, b: &str) -> f64 {
+        match self {
+            SimilarityMetric::Jaro => jaro(a, b),
+            SimilarityMetric::JaroWinkler => jaro_winkler(a, b),
+            SimilarityMetric::NormalizedLevenshtein => normalized_levenshtein(a, b),
+            SimilarityMetric::TokenSet => token_set_similarity(a, b),
+        }
+    }
+}
+
+/// Token set similarity: the best normalized Levenshtein similarity between
+/// the sorted shared words and the sorted shared words followed by either
+/// string's remaining words.
+fn token_set_similarity(a: &str, b: &str) -> f64 {
+    let words_a: BTreeSet<&str> = a.split_whitespace().collect();
+    let words_b: BTreeSet<&str> = b.split_whitespace().collect();
+    if words_a.is_empty() || words_b.is_empty() {
+        return normalized_levenshtein(a, b);
+    }
+
+    let join = |words: Vec<&str>| words.join(" ");
+    let shared = join(words_a.intersection(&words_b).copied().collect());
+    let with_rest = |rest: Vec<&str>| {
+        let rest = join(rest);
+        match (shared.is_empty(), rest.is_empty()) {
+            (true, _) => rest,
+            (_, true) => shared.clone(),
+            _ => format!("{shared} {rest}"),
+        }
+    };
+    let combined_a = with_rest(words_a.difference(&words_b).copied().collect());
+    let combined_b = with_rest(words_b.difference(&words_a).copied().collect());
+
+    let mut best = normalized_levenshtein(&combined_a, &combined_b);
+    if !shared.is_empty() {
+        best = best
+            .max(normalized_levenshtein(&shared, &combined_a))
+            .max(normalized_levenshtein(&shared, &combined_b));
+    }
+    best
+}
+
+/// 64-bit FNV-1a hash of `bytes`, which unlike the std hashers stays the
+/// same across Rust releases, so MinHash signatures do too.
+fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+/// SplitMix64 finalizer, used to derive independent MinHash permutations.
+fn mix_hash(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// A field compared between two citations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatchField {
+    /// Both have the same non-empty DOI.
+    Doi,
+    /// Full journal names or abbreviations match after normalization.
+    Journal,
+    /// They share a normalized ISSN.
+    Issn,
+    /// Both have the same volume number.
+    Volume,
+    /// Both have the same pages.
+    Pages,
+    /// Both have the same publication year, or neither has one.
+    Year,
+    /// The first authors are the same person by
+    /// [`Author::is_same_person`](crate::Author::is_same_person), which
+    /// compares ORCID iDs when both have one.
+    FirstAuthor,
+}
+
+impl MatchField {
+    /// All fields, in declaration order.
+    const ALL: [MatchField; 7] = [
+        MatchField::Doi,
+        MatchField::Journal,
+        MatchField::Issn,
+        MatchField::Volume,
+        MatchField::Pages,
+        MatchField::Year,
+        MatchField::FirstAuthor,
+    ];
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+
+    fn from_bits(bits: u8) -> Vec<MatchField> {
+        Self::ALL
+            .into_iter()
+            .filter(|field| bits & field.bit() != 0)
+            .collect()
+    }
+}
+
+/// Which pairs of citations a [`MatchRule`] applies to, by whether they
+/// have DOIs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DoiScope {
+    /// Both citations have a DOI, equal or not.
+    BothHaveDoi,
+    /// At least one citation has no DOI.
+    EitherLacksDoi,
+    /// Any pair.
+    Any,
+}
+
+/// The minimum title similarity of a [`MatchRule`], either one of the
+/// configured [`MatchThresholds`] or a fixed value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TitleThreshold {
+    /// [`MatchThresholds::doi_title`].
+    Doi,
+    /// [`MatchThresholds::no_doi_title`].
+    NoDoi,
+    /// [`MatchThresholds::strict_title`].
+    Strict,
+    /// A fixed threshold.
+    Value(f64),
+}
+
+impl TitleThreshold {
+    fn resolve(self, thresholds: &MatchThresholds) -> f64 {
+        match self {
+            TitleThreshold::Doi => thresholds.doi_title,
+            TitleThreshold::NoDoi => thresholds.no_doi_title,
+            TitleThreshold::Strict => thresholds.strict_title,
+            TitleThreshold::Value(value) => value,
+        }
+    }
+}
+
+/// A declarative rule for deciding that two citations are duplicates.
+///
+/// A rule matches a pair in its [`scope`](Self::scope) when the titles are
+/// at least [`title_threshold`](Self::title_threshold) similar under
+/// [`metric`](Self::metric), every field in [`all_of`](Self::all_of)
+/// matches, and at least one field of each group in
+/// [`any_of`](Self::any_of) matches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchRule {
+    /// Name identifying the rule.
+    pub name: String,
+    /// Pairs the rule applies to.
+    pub scope: DoiScope,
+    /// Metric used to compare titles.
+    pub metric: SimilarityMetric,
+    /// Minimum title similarity.
+    pub title_threshold: TitleThreshold,
+    /// Fields that must all match.
+    pub all_of: Vec<MatchField>,
+    /// Groups of fields, each of which needs at least one matching field.
+    pub any_of: Vec<Vec<MatchField>>,
+}
+
+impl MatchRule {
+    /// The rules used by default, which compare titles with Jaro similarity
+    /// when both citations have a DOI and with Jaro-Winkler otherwise:
+    ///
+    /// - `same-doi-journal`: same DOI, title ≥ `doi_title`, journal or ISSN
+    /// - `same-doi-volume-pages`: same DOI, title ≥ `strict_title`, volume or pages
+    /// - `different-doi-strict`: title ≥ `strict_title`, year, volume or
+    ///   pages, and journal or ISSN
+    /// - `no-doi-journal`: title ≥ `no_doi_title`, volume or pages, and
+    ///   journal or ISSN
+    /// - `no-doi-strict`: title ≥ `strict_title`, year, volume and pages
+    #[must_use]
+    pub fn default_rules() -> Vec<MatchRule> {
+        use MatchField::{Doi, Issn, Journal, Pages, Volume, Year};
+
+        let rule = |name: &str,
+                    scope: DoiScope,
+                    title_threshold: TitleThreshold,
+                    all_of: Vec<MatchField>,
+                    any_of: Vec<Vec<MatchField>>| MatchRule {
+            name: name.to_string(),
+            scope,
+            metric: match scope {
+                DoiScope::BothHaveDoi => SimilarityMetric::Jaro,
+                _ => SimilarityMetric::JaroWinkler,
+            },
+            title_threshold,
+            all_of,
+            any_of,
+        };
+        vec![
+            rule(
+                "same-doi-journal",
+                DoiScope::BothHaveDoi,
+                TitleThreshold::Doi,
+                vec![Doi],
+                vec![vec![Journal, Issn]],
+            ),
+            rule(
+                "same-doi-volume-pages",
+                DoiScope::BothHaveDoi,
+                TitleThreshold::Strict,
+                vec![Doi],
+                vec![vec![Volume, Pages]],
+            ),
+            rule(
+                "different-doi-strict",
+                DoiScope::BothHaveDoi,
+                TitleThreshold::Strict,
+                vec![Year],
+                vec![vec![Volume, Pages], vec![Journal, Issn]],
+            ),
+            rule(
+                "no-doi-journal",
+                DoiScope::EitherLacksDoi,
+                TitleThreshold::NoDoi,
+                Vec::new(),
+                vec![vec![Volume, Pages], vec![Journal, Issn]],
+            ),
+            rule(
+                "no-doi-strict",
+                DoiScope::EitherLacksDoi,
+                TitleThreshold::Strict,
+                vec![Year, Volume, Pages],
+                Vec::new(),
+            ),
+        ]
+    }
+
+    /// Whether the rule's field conditions hold for a pair with these
+    /// matching fields.
+    fn fields_match(&self, both_have_doi: bool, matched: u8) -> bool {
+        let in_scope = match self.scope {
+            DoiScope::BothHaveDoi => both_have_doi,
+            DoiScope::EitherLacksDoi => !both_have_doi,
+            DoiScope::Any => true,
+        };
+        in_scope
+            && self.all_of.iter().all(|field| matched & field.bit() != 0)
+            && self
+                .any_of
+                .iter()
+                .all(|group| group.iter().any(|field| matched & field.bit() != 0))
+    }
 }
 
 /// Core deduplication engine for finding duplicate citations.
@@ -208,7 +656,8 @@ pub struct DeduplicatorConfig {
 ///
 /// # Algorithm
 ///
-/// Citations are considered duplicates based on these criteria:
+/// Citations are considered duplicates when any of the configured [`MatchRule`]s
+/// matches. The default rules use these criteria:
 ///
 /// 1. **With DOIs**:
 ///    - Matching DOIs and high title similarity (≥ 0.85)
@@ -231,6 +680,7 @@ pub struct DeduplicatorConfig {
 ///     group_by_year: true,
 ///     run_in_parallel: true,
 ///     source_preferences: vec!["PubMed".to_string(), "Embase".to_string()],
+///     ..Default::default()
 /// };
 /// let deduplicator = Deduplicator::new().with_config(config);
 /// ```
@@ -238,23 +688,178 @@ pub struct DeduplicatorConfig {
 /// # Performance
 ///
 /// - Time complexity: O(n²) without year grouping
-/// - With year grouping: O(Σ n_y²) where n_y is citations per year
+/// - With year grouping: O(Σ n_y²) where n_y is citations per year, plus the
+///   comparisons with neighbouring years and undated citations
 /// - Parallel processing available when using year grouping
 #[derive(Debug, Default, Clone)]
 pub struct Deduplicator {
     config: DeduplicatorConfig,
 }
 
+/// Why a citation was added to a duplicate group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchExplanation {
+    /// Index of the citation in the input.
+    pub citation: usize,
+    /// Index of the citation it was matched against.
+    pub matched_with: usize,
+    /// Name of the [`MatchRule`] that matched.
+    pub rule: String,
+    /// Title similarity under the rule's metric.
+    pub title_similarity: f64,
+    /// Fields that matched.
+    pub matched_fields: Vec<MatchField>,
+}
+
+/// A pair of citations whose title similarity fell just short of a rule's
+/// threshold while the rule's other conditions held.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NearMiss {
+    /// Index of the first citation in the input.
+    pub first: usize,
+    /// Index of the second citation in the input.
+    pub second: usize,
+    /// Name of the [`MatchRule`] the pair came closest to matching.
+    pub rule: String,
+    /// Title similarity under the rule's metric.
+    pub title_similarity: f64,
+    /// The rule's title threshold.
+    pub threshold: f64,
+    /// Fields that matched.
+    pub matched_fields: Vec<MatchField>,
+}
+
+/// A [`DuplicateGroup`] with the input indices of its citations and the
+/// matches that formed it.
+#[derive(Debug, Clone)]
+pub struct ExplainedGroup {
+    /// The duplicate group.
+    pub group: DuplicateGroup,
+    /// Index of the unique citation in the input.
+    pub unique_index: usize,
+    /// Indices of the duplicates in the input, in the order of
+    /// `group.duplicates`.
+    pub duplicate_indices: Vec<usize>,
+    /// One explanation for each citation matched into the group. The
+    /// group's unique citation may be on either side of a match.
+    pub matches: Vec<MatchExplanation>,
+}
+
+/// Duplicate groups with explanations, returned by
+/// [`Deduplicator::explain_duplicates`].
+#[derive(Debug, Clone, Default)]
+pub struct DedupeReport {
+    /// Groups of duplicates, including single-citation groups.
+    pub groups: Vec<ExplainedGroup>,
+    /// Pairs just below a rule's title threshold. Empty unless
+    /// [`DeduplicatorConfig::near_miss_margin`] is set.
+    pub near_misses: Vec<NearMiss>,
+}
+
+impl DedupeReport {
+    /// Drops the explanations, keeping the duplicate groups.
+    #[must_use]
+    pub fn into_groups(self) -> Vec<DuplicateGroup> {
+        self.groups.into_iter().map(|group| group.group).collect()
+    }
+}
+
 #[derive(Debug)]
 struct PreprocessedCitation<'a> {
     original: &'a Citation,
     normalized_title: String,
+    /// Normalized title words separated by single spaces, for
+    /// [`SimilarityMetric::TokenSet`].
+    title_words: String,
     normalized_journal: Option<String>,
     normalized_journal_abbr: Option<String>,
     normalized_issn: Vec<String>,
     normalized_volume: String,
 }
 
+/// Result of comparing two citations against the configured rules.
+enum PairOutcome<'r> {
+    Match {
+        rule: &'r MatchRule,
+        similarity: f64,
+        matched: u8,
+    },
+    NearMiss {
+        rule: &'r MatchRule,
+        similarity: f64,
+        threshold: f64,
+        matched: u8,
+    },
+    NoMatch,
+}
+
+/// Citations grouped together, as input indices.
+struct Cluster {
+    members: Vec<usize>,
+    matches: Vec<MatchExplanation>,
+}
+
+/// Input indices of citations to compare with each other, and with the
+/// `across` citations, which are compared with each other in other units.
+struct ComparisonUnit {
+    within: Vec<usize>,
+    across: Vec<usize>,
+}
+
+/// Matching and near-miss pairs found in a [`ComparisonUnit`].
+#[derive(Default)]
+struct PairResults {
+    matches: Vec<MatchExplanation>,
+    near_misses: Vec<NearMiss>,
+}
+
+/// Input indices of citations by publication year.
+#[derive(Debug, Default)]
+struct YearGroups {
+    by_year: BTreeMap<i32, Vec<usize>>,
+    undated: Vec<usize>,
+}
+
+/// Disjoint sets of citation positions, tracking each set's members.
+struct UnionFind {
+    parent: Vec<usize>,
+    members: Vec<Vec<usize>>,
+}
+
+impl UnionFind {
+    fn new(count: usize) -> Self {
+        Self {
+            parent: (0..count).collect(),
+            members: (0..count).map(|idx| vec![idx]).collect(),
+        }
+    }
+
+    fn find(&mut self, idx: usize) -> usize {
+        let mut root = idx;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut node = idx;
+        while self.parent[node] != root {
+            node = std::mem::replace(&mut self.parent[node], root);
+        }
+        root
+    }
+
+    /// Joins two roots, keeping the larger set's root, and returns it.
+    fn union(&mut self, a: usize, b: usize) -> usize {
+        let (root, child) = if self.members[a].len() >= self.members[b].len() {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[child] = root;
+        let mut moved = std::mem::take(&mut self.members[child]);
+        self.members[root].append(&mut moved);
+        root
+    }
+}
+
 /// Error types for dedupe operations
 #[derive(Debug, thiserror::Error)]
 pub enum DedupeError {
@@ -285,8 +890,7 @@ impl Deduplicator {
         Self {
             config: DeduplicatorConfig {
                 group_by_year: true,
-                run_in_parallel: false,
-                source_preferences: Vec::new(),
+                ..Default::default()
             },
         }
     }
@@ -307,7 +911,8 @@ impl Deduplicator {
     /// let config = DeduplicatorConfig {
     ///     group_by_year: true,
     ///     run_in_parallel: true,
-    ///     source_preferences: vec!["PubMed".to_string(), "Google Scholar".to_string()],   
+    ///     source_preferences: vec!["PubMed".to_string(), "Google Scholar".to_string()],
+    ///     ..Default::default()
     /// };
     /// let deduplicator = Deduplicator::new().with_config(config);
     /// ```
@@ -405,8 +1010,65 @@ impl Deduplicator {
         citations: &[Citation],
         sources: &[&str],
     ) -> Result<Vec<DuplicateGroup>, DedupeError> {
+        self.explain_duplicates(citations, sources)
+            .map(DedupeReport::into_groups)
+    }
+
+    /// Finds duplicates like [`find_duplicates_with_sources`](Self::find_duplicates_with_sources)
+    /// and reports why each citation was put in its group.
+    ///
+    /// Each [`ExplainedGroup`] lists the pairs that were matched, with the
+    /// rule that fired, the title similarity and the fields that matched.
+    /// When [`DeduplicatorConfig::near_miss_margin`] is set, pairs whose
+    /// title similarity fell just short of a rule's threshold are returned
+    /// in [`DedupeReport::near_misses`] for manual review.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::dedupe::{Deduplicator, DeduplicatorConfig, MatchField};
+    /// use biblib::Citation;
+    ///
+    /// let citations = vec![
+    ///     Citation {
+    ///         title: "Example Title".to_string(),
+    ///         doi: Some("10.1234/example".to_string()),
+    ///         journal: Some("Journal of Examples".to_string()),
+    ///         ..Default::default()
+    ///     },
+    ///     Citation {
+    ///         title: "Example Title".to_string(),
+    ///         doi: Some("10.1234/example".to_string()),
+    ///         journal: Some("Journal of Examples".to_string()),
+    ///         ..Default::default()
+    ///     },
+    /// ];
+    ///
+    /// let config = DeduplicatorConfig {
+    ///     near_miss_margin: Some(0.05),
+    ///     ..Default::default()
+    /// };
+    /// let report = Deduplicator::new()
+    ///     .with_config(config)
+    ///     .explain_duplicates(&citations, &[])
+    ///     .unwrap();
+    ///
+    /// let explanation = &report.groups[0].matches[0];
+    /// assert_eq!((explanation.citation, explanation.matched_with), (1, 0));
+    /// assert_eq!(explanation.rule, "same-doi-journal");
+    /// assert_eq!(explanation.title_similarity, 1.0);
+    /// assert!(explanation.matched_fields.contains(&MatchField::Doi));
+    /// assert!(report.near_misses.is_empty());
+    /// ```
+    pub fn explain_duplicates(
+        self,
+        citations: &[Citation],
+        sources: &[&str],
+    ) -> Result<DedupeReport, DedupeError> {
+        self.validate_config()?;
+
         if citations.is_empty() {
-            return Ok(Vec::new());
+            return Ok(DedupeReport::default());
         }
 
         // Validate input - warn if sources length exceeds citations
@@ -431,72 +1093,146 @@ impl Deduplicator {
             .map(|((idx, _citation), source)| (idx, source))
             .collect();
 
-        // Create global mapping from citation pointers to original indices
-        let global_ptr_to_index: HashMap<*const Citation, usize> = citations
-            .iter()
-            .enumerate()
-            .map(|(i, citation)| (citation as *const Citation, i))
-            .collect();
+        let units = self.comparison_units(citations);
+        let (preprocessed, results) = if self.config.run_in_parallel {
+            use rayon::prelude::*;
+
+            let preprocessed = citations
+                .par_iter()
+                .map(Self::preprocess)
+                .collect::<Result<Vec<_>, _>>()?;
+            let results: Vec<PairResults> = units
+                .par_iter()
+                .map(|unit| self.compare_unit(&preprocessed, unit))
+                .collect();
+            (preprocessed, results)
+        } else {
+            let preprocessed = citations
+                .iter()
+                .map(Self::preprocess)
+                .collect::<Result<Vec<_>, _>>()?;
+            let results: Vec<PairResults> = units
+                .iter()
+                .map(|unit| self.compare_unit(&preprocessed, unit))
+                .collect();
+            (preprocessed, results)
+        };
 
-        if self.config.group_by_year {
-            let year_groups = Self::group_by_year_with_indices(citations);
-            if self.config.run_in_parallel {
-                use rayon::prelude::*;
-
-                let duplicate_groups: Result<Vec<_>, _> = year_groups
-                    .par_iter()
-                    .map(|(_, citations_with_indices)| {
-                        let citations_in_year: Vec<&Citation> = citations_with_indices
-                            .iter()
-                            .map(|(citation, _)| *citation)
-                            .collect();
-                        // Create a local mapping for this year group
-                        let local_to_global: HashMap<*const Citation, usize> =
-                            citations_with_indices
-                                .iter()
-                                .map(|(citation, global_idx)| {
-                                    (*citation as *const Citation, *global_idx)
-                                })
-                                .collect();
-                        self.process_citation_group_with_sources(
-                            &citations_in_year,
-                            &source_map,
-                            &local_to_global,
-                        )
-                    })
-                    .collect();
-
-                // Flatten results
-                Ok(duplicate_groups?.into_iter().flatten().collect())
-            } else {
-                let mut duplicate_groups = Vec::new();
-
-                for citations_with_indices in year_groups.values() {
-                    let citations_in_year: Vec<&Citation> = citations_with_indices
-                        .iter()
-                        .map(|(citation, _)| *citation)
-                        .collect();
-                    // Create a local mapping for this year group
-                    let local_to_global: HashMap<*const Citation, usize> = citations_with_indices
-                        .iter()
-                        .map(|(citation, global_idx)| (*citation as *const Citation, *global_idx))
-                        .collect();
-                    duplicate_groups.extend(self.process_citation_group_with_sources(
-                        &citations_in_year,
-                        &source_map,
-                        &local_to_global,
-                    )?);
-                }
-                Ok(duplicate_groups)
+        let mut matches = Vec::new();
+        let mut report = DedupeReport::default();
+        for result in results {
+            matches.extend(result.matches);
+            report.near_misses.extend(result.near_misses);
+        }
+        matches.sort_by_key(|m| (m.matched_with, m.citation));
+        report
+            .near_misses
+            .sort_by_key(|near_miss| (near_miss.first, near_miss.second));
+
+        let clusters = match self.config.clustering {
+            Clustering::FirstSeed => Self::seed_clusters(preprocessed.len(), matches),
+            Clustering::Transitive { max_diameter } => {
+                Self::transitive_clusters(preprocessed.len(), matches, max_diameter)
             }
-        } else {
-            let citations_refs: Vec<&Citation> = citations.iter().collect();
-            self.process_citation_group_with_sources(
-                &citations_refs,
+        };
+
+        for cluster in clusters {
+            let group_citations: Vec<&Citation> = cluster
+                .members
+                .iter()
+                .map(|&idx| preprocessed[idx].original)
+                .collect();
+
+            let unique = self.select_unique_citation_with_sources(
+                &group_citations,
+                &cluster.members,
                 &source_map,
-                &global_ptr_to_index,
-            )
+            );
+            let unique_position = group_citations
+                .iter()
+                .position(|c| std::ptr::eq(*c, unique))
+                .unwrap_or(0);
+
+            let (duplicate_indices, duplicates): (Vec<usize>, Vec<Citation>) = cluster
+                .members
+                .iter()
+                .zip(&group_citations)
+                .enumerate()
+                .filter(|(position, _)| *position != unique_position)
+                .map(|(_, (&idx, c))| (idx, (*c).clone()))
+                .unzip();
+
+            report.groups.push(ExplainedGroup {
+                group: DuplicateGroup {
+                    unique: unique.clone(),
+                    duplicates,
+                },
+                unique_index: cluster.members[unique_position],
+                duplicate_indices,
+                matches: cluster.matches,
+            });
+        }
+
+        Ok(report)
+    }
+
+    /// Checks that every title threshold is between 0.0 and 1.0.
+    fn validate_config(&self) -> Result<(), DedupeError> {
+        let thresholds = &self.config.thresholds;
+        let named = [
+            ("doi_title", thresholds.doi_title),
+            ("no_doi_title", thresholds.no_doi_title),
+            ("strict_title", thresholds.strict_title),
+        ];
+        for (name, value) in named {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(DedupeError::ConfigError(format!(
+                    "Threshold {name} ({value}) must be between 0.0 and 1.0"
+                )));
+            }
+        }
+        if let Some(margin) = self.config.near_miss_margin
+            && !(0.0..=1.0).contains(&margin)
+        {
+            return Err(DedupeError::ConfigError(format!(
+                "Near-miss margin ({margin}) must be between 0.0 and 1.0"
+            )));
+        }
+        if let Clustering::Transitive {
+            max_diameter: Some(0),
+        } = self.config.clustering
+        {
+            return Err(DedupeError::ConfigError(
+                "Maximum cluster diameter must be at least 1".to_string(),
+            ));
         }
+        for strategy in &self.config.blocking {
+            let valid = match *strategy {
+                BlockingStrategy::Identifier => true,
+                BlockingStrategy::MinHash {
+                    shingle,
+                    bands,
+                    rows,
+                } => shingle > 0 && bands > 0 && rows > 0,
+                BlockingStrategy::SortedNeighbourhood { window } => window > 1,
+            };
+            if !valid {
+                return Err(DedupeError::ConfigError(format!(
+                    "Invalid blocking strategy {strategy:?}"
+                )));
+            }
+        }
+        for rule in &self.config.rules {
+            if let TitleThreshold::Value(value) = rule.title_threshold
+                && !(0.0..=1.0).contains(&value)
+            {
+                return Err(DedupeError::ConfigError(format!(
+                    "Title threshold of rule '{}' ({value}) must be between 0.0 and 1.0",
+                    rule.name
+                )));
+            }
+        }
+        Ok(())
     }
 
     /// Get the year from a citation.
@@ -554,191 +1290,562 @@ impl Deduplicator {
         self.select_unique_citation(citations)
     }
 
-    fn process_citation_group_with_sources(
-        &self,
-        citations: &[&Citation],
-        source_map: &HashMap<usize, Option<&str>>,
-        global_ptr_to_index: &HashMap<*const Citation, usize>,
-    ) -> Result<Vec<DuplicateGroup>, DedupeError> {
-        let mut duplicate_groups = Vec::new();
+    fn preprocess(citation: &Citation) -> Result<PreprocessedCitation<'_>, DedupeError> {
+        Ok(PreprocessedCitation {
+            original: citation,
+            normalized_title: Self::normalize_string(&Self::convert_unicode_string(
+                &citation.title,
+            ))
+            .ok_or_else(|| DedupeError::ProcessingError("Failed to normalize title".to_string()))?,
+            title_words: Self::normalize_words(&Self::convert_unicode_string(&citation.title)),
+            normalized_journal: Self::format_journal_name(citation.journal.as_deref()),
+            normalized_journal_abbr: Self::format_journal_name(citation.journal_abbr.as_deref()),
+            normalized_volume: citation
+                .volume
+                .as_deref()
+                .map_or(String::new(), Deduplicator::normalize_volume),
+            normalized_issn: citation
+                .issn
+                .iter()
+                .filter_map(|issn| Deduplicator::format_issn(issn))
+                .collect(),
+        })
+    }
+
+    /// Splits the citations into units that can be compared independently:
+    /// one unit without year grouping, otherwise one per year, compared with
+    /// the following `year_window` years and with undated citations, and
+    /// one for the undated citations.
+    fn comparison_units(&self, citations: &[Citation]) -> Vec<ComparisonUnit> {
+        if !self.config.group_by_year {
+            return vec![ComparisonUnit {
+                within: (0..citations.len()).collect(),
+                across: Vec::new(),
+            }];
+        }
 
-        // Preprocess all citations in this group
-        let preprocessed: Vec<PreprocessedCitation> = citations
+        let YearGroups { by_year, undated } = Self::group_by_year_with_indices(citations);
+        let window = i32::try_from(self.config.year_window).unwrap_or(i32::MAX);
+        let mut units: Vec<ComparisonUnit> = by_year
             .iter()
-            .map(|c| {
-                Ok(PreprocessedCitation {
-                    original: c,
-                    normalized_title: Self::normalize_string(&Self::convert_unicode_string(
-                        &c.title,
-                    ))
-                    .ok_or_else(|| {
-                        DedupeError::ProcessingError("Failed to normalize title".to_string())
-                    })?,
-                    normalized_journal: Self::format_journal_name(c.journal.as_deref()),
-                    normalized_journal_abbr: Self::format_journal_name(c.journal_abbr.as_deref()),
-                    normalized_volume: c
-                        .volume
-                        .as_deref()
-                        .map_or(String::new(), Deduplicator::normalize_volume),
-                    normalized_issn: c
-                        .issn
-                        .iter()
-                        .filter_map(|issn| Deduplicator::format_issn(issn))
-                        .collect(),
-                })
+            .map(|(&year, within)| ComparisonUnit {
+                within: within.clone(),
+                across: by_year
+                    .range((Excluded(year), Included(year.saturating_add(window))))
+                    .flat_map(|(_, indices)| indices)
+                    .chain(&undated)
+                    .copied()
+                    .collect(),
             })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let mut processed_indices = std::collections::HashSet::new();
+            .collect();
+        if !undated.is_empty() {
+            units.push(ComparisonUnit {
+                within: undated,
+                across: Vec::new(),
+            });
+        }
+        units
+    }
 
-        for i in 0..preprocessed.len() {
-            if processed_indices.contains(&i) {
-                continue;
+    /// Compares each citation in `unit.within` with the later ones and with
+    /// every citation in `unit.across`, or with the candidates the blocking
+    /// strategies propose among them.
+    fn compare_unit(
+        &self,
+        preprocessed: &[PreprocessedCitation],
+        unit: &ComparisonUnit,
+    ) -> PairResults {
+        let members: Vec<usize> = unit.within.iter().chain(&unit.across).copied().collect();
+        let citations: Vec<&PreprocessedCitation> =
+            members.iter().map(|&idx| &preprocessed[idx]).collect();
+        let candidates = self.candidate_pairs(&citations);
+        let mut results = PairResults::default();
+
+        for a in 0..unit.within.len() {
+            for b in Self::candidates_of(candidates.as_deref(), members.len(), a).filter(|&b| b > a)
+            {
+                let (i, j) = (members[a].min(members[b]), members[a].max(members[b]));
+                match self.evaluate_pair(&preprocessed[i], &preprocessed[j]) {
+                    PairOutcome::Match {
+                        rule,
+                        similarity,
+                        matched,
+                    } => results.matches.push(MatchExplanation {
+                        citation: j,
+                        matched_with: i,
+                        rule: rule.name.clone(),
+                        title_similarity: similarity,
+                        matched_fields: MatchField::from_bits(matched),
+                    }),
+                    PairOutcome::NearMiss {
+                        rule,
+                        similarity,
+                        threshold,
+                        matched,
+                    } => results.near_misses.push(NearMiss {
+                        first: i,
+                        second: j,
+                        rule: rule.name.clone(),
+                        title_similarity: similarity,
+                        threshold,
+                        matched_fields: MatchField::from_bits(matched),
+                    }),
+                    PairOutcome::NoMatch => {}
+                }
             }
+        }
 
-            let mut group_citations = vec![preprocessed[i].original];
-            let mut group_indices = vec![i];
-            let current = &preprocessed[i];
-
-            for (j, other) in preprocessed.iter().enumerate() {
-                if i == j || processed_indices.contains(&j) {
-                    continue;
-                }
+        results
+    }
 
-                let journal_match = Self::journals_match(
-                    &current.normalized_journal,
-                    &current.normalized_journal_abbr,
-                    &other.normalized_journal,
-                    &other.normalized_journal_abbr,
-                );
-                let issns_match =
-                    Self::match_issns(&current.normalized_issn, &other.normalized_issn);
-                let volumes_match = !current.normalized_volume.is_empty()
-                    && !other.normalized_volume.is_empty()
-                    && current.normalized_volume == other.normalized_volume;
-                let pages_match = current.original.pages.is_some()
-                    && other.original.pages.is_some()
-                    && current.original.pages == other.original.pages;
-                let years_match = Self::get_citation_year(current.original)
-                    == Self::get_citation_year(other.original);
-
-                let is_duplicate = match (&current.original.doi, &other.original.doi) {
-                    // With DOIs
-                    (Some(doi1), Some(doi2)) if !doi1.is_empty() && !doi2.is_empty() => {
-                        let title_similarity =
-                            jaro(&current.normalized_title, &other.normalized_title);
-
-                        // With Journal/ISSN match
-                        (doi1 == doi2 && title_similarity >= DOI_TITLE_SIMILARITY_THRESHOLD && (journal_match || issns_match))
-                        // Without Journal/ISSN match: only when we have same DOI (and we use volume/pages instead)
-                        || (doi1 == doi2 && title_similarity >= 0.99 && (volumes_match || pages_match))
-                        // Without DOI match: only when we have a very high title similarity and all other fields match
-                        || (title_similarity >= 0.99 && years_match && (volumes_match || pages_match) && (journal_match || issns_match))
-                    }
-                    // Without DOIs
-                    _ => {
-                        let title_similarity =
-                            jaro_winkler(&current.normalized_title, &other.normalized_title);
-
-                        // With Journal/ISSN match
-                        (title_similarity >= NO_DOI_TITLE_SIMILARITY_THRESHOLD && (volumes_match || pages_match) && (journal_match || issns_match))
-                        // Without Journal/ISSN match: only when we have a very high title similarity and all other fields match
-                        || (title_similarity >= 0.99 && years_match && (volumes_match && pages_match))
-                    }
-                };
+    /// Groups each citation not yet in a group with every unassigned
+    /// citation that matches it directly, in input order.
+    fn seed_clusters(count: usize, matches: Vec<MatchExplanation>) -> Vec<Cluster> {
+        let mut neighbours: Vec<Vec<MatchExplanation>> = vec![Vec::new(); count];
+        for explanation in matches {
+            neighbours[explanation.matched_with].push(explanation);
+        }
 
-                if is_duplicate {
-                    group_citations.push(other.original);
-                    group_indices.push(j);
-                    processed_indices.insert(j);
-                }
+        let mut assigned = vec![false; count];
+        let mut clusters = Vec::new();
+        for i in 0..count {
+            if assigned[i] {
+                continue;
             }
-
-            if group_citations.len() > 1 {
-                // Convert citation indices to original indices for source lookup
-                let original_indices: Vec<usize> = group_indices
-                    .iter()
-                    .map(|&local_idx| {
-                        let citation_ptr = preprocessed[local_idx].original as *const Citation;
-                        global_ptr_to_index[&citation_ptr]
-                    })
-                    .collect();
-
-                let unique = self.select_unique_citation_with_sources(
-                    &group_citations,
-                    &original_indices,
-                    source_map,
-                );
-
-                let duplicates: Vec<Citation> = group_citations
-                    .into_iter()
-                    .filter(|c| !std::ptr::eq(*c, unique))
-                    .map(|c| (*c).clone())
-                    .collect();
-
-                duplicate_groups.push(DuplicateGroup {
-                    unique: unique.clone(),
-                    duplicates,
-                });
-                processed_indices.insert(i);
-            } else {
-                duplicate_groups.push(DuplicateGroup {
-                    unique: current.original.clone(),
-                    duplicates: Vec::new(),
-                });
+            assigned[i] = true;
+
+            let mut members = vec![i];
+            let mut cluster_matches = Vec::new();
+            for explanation in std::mem::take(&mut neighbours[i]) {
+                if !assigned[explanation.citation] {
+                    assigned[explanation.citation] = true;
+                    members.push(explanation.citation);
+                    cluster_matches.push(explanation);
+                }
             }
+            clusters.push(Cluster {
+                members,
+                matches: cluster_matches,
+            });
         }
 
-        Ok(duplicate_groups)
+        clusters
     }
 
-    fn group_by_year_with_indices(citations: &[Citation]) -> HashMap<i32, Vec<(&Citation, usize)>> {
-        let mut year_map: HashMap<i32, Vec<(&Citation, usize)>> = HashMap::new();
+    /// Groups citations into the connected components of the graph of all
+    /// matching pairs. With `max_diameter`, pairs are merged from the most
+    /// to the least similar, skipping those that would join two groups into
+    /// one whose members are more than `max_diameter` matches apart.
+    fn transitive_clusters(
+        count: usize,
+        mut matches: Vec<MatchExplanation>,
+        max_diameter: Option<usize>,
+    ) -> Vec<Cluster> {
+        let mut neighbours = vec![Vec::new(); count];
+        for explanation in &matches {
+            neighbours[explanation.matched_with].push(explanation.citation);
+            neighbours[explanation.citation].push(explanation.matched_with);
+        }
 
-        // TODO: handle citations without a year when grouping by year
-        for (index, citation) in citations.iter().enumerate() {
-            let year = Self::get_citation_year_static(citation).unwrap_or(0);
-            year_map.entry(year).or_default().push((citation, index));
+        // Most similar pairs first, then by position, so the guard keeps the
+        // strongest matches
+        matches.sort_by(|a, b| {
+            b.title_similarity
+                .total_cmp(&a.title_similarity)
+                .then((a.matched_with, a.citation).cmp(&(b.matched_with, b.citation)))
+        });
+
+        let mut components = UnionFind::new(count);
+        let mut cluster_matches: Vec<Vec<MatchExplanation>> = vec![Vec::new(); count];
+        for explanation in matches {
+            let root_i = components.find(explanation.matched_with);
+            let root_j = components.find(explanation.citation);
+            if root_i == root_j {
+                continue;
+            }
+            if let Some(max_diameter) = max_diameter {
+                let (small, large) =
+                    if components.members[root_i].len() <= components.members[root_j].len() {
+                        (root_i, root_j)
+                    } else {
+                        (root_j, root_i)
+                    };
+                if !Self::diameter_within(&mut components, small, large, &neighbours, max_diameter)
+                {
+                    continue;
+                }
+            }
+
+            let root = components.union(root_i, root_j);
+            let mut merged_matches = std::mem::take(&mut cluster_matches[root_i]);
+            merged_matches.append(&mut cluster_matches[root_j]);
+            merged_matches.push(explanation);
+            cluster_matches[root] = merged_matches;
         }
 
-        year_map
-    }
-    /// Static version of get_citation_year for use in static contexts
-    fn get_citation_year_static(citation: &Citation) -> Option<i32> {
-        citation.date.as_ref().map(|d| d.year)
+        let roots: Vec<usize> = (0..count)
+            .filter(|&idx| components.find(idx) == idx)
+            .collect();
+        let mut clusters: Vec<Cluster> = roots
+            .into_iter()
+            .map(|root| {
+                let mut members = std::mem::take(&mut components.members[root]);
+                members.sort_unstable();
+                let mut matches = std::mem::take(&mut cluster_matches[root]);
+                matches.sort_by_key(|m| (m.matched_with, m.citation));
+                Cluster { members, matches }
+            })
+            .collect();
+        clusters.sort_by_key(|cluster| cluster.members[0]);
+        clusters
     }
 
-    fn convert_unicode_string(input: &str) -> String {
-        UNICODE_REGEX
-            .replace_all(input, |caps: &crate::regex::Captures| {
-                u32::from_str_radix(&caps[1], 16)
-                    .ok()
-                    .and_then(char::from_u32)
-                    .map(|c| c.to_string())
-                    .unwrap_or_else(|| caps[0].to_string())
-            })
-            .to_string()
+    /// The citations to compare with citation `i`: its candidates, or every
+    /// citation without blocking.
+    fn candidates_of(
+        candidates: Option<&[Vec<usize>]>,
+        count: usize,
+        i: usize,
+    ) -> impl Iterator<Item = usize> + '_ {
+        match candidates {
+            Some(candidates) => Either::Left(candidates[i].iter().copied()),
+            None => Either::Right(0..count),
+        }
     }
 
-    fn normalize_string(string: &str) -> Option<String> {
-        if string.is_empty() {
+    /// For each citation, the sorted positions of the citations the
+    /// blocking strategies pair it with, or `None` without blocking.
+    fn candidate_pairs(&self, preprocessed: &[&PreprocessedCitation]) -> Option<Vec<Vec<usize>>> {
+        if self.config.blocking.is_empty() {
             return None;
         }
 
-        let mut result = String::with_capacity(string.len());
-        let mut s = string.trim().to_lowercase();
+        let mut candidates = vec![Vec::new(); preprocessed.len()];
+        let mut add_block = |block: &[usize]| {
+            for (k, &a) in block.iter().enumerate() {
+                for &b in &block[k + 1..] {
+                    candidates[a].push(b);
+                    candidates[b].push(a);
+                }
+            }
+        };
 
-        for replacement in HTML_REPLACEMENTS.iter() {
-            s = s.replace(replacement.0, replacement.1);
+        for strategy in &self.config.blocking {
+            match *strategy {
+                BlockingStrategy::Identifier => {
+                    for block in Self::identifier_blocks(preprocessed) {
+                        add_block(&block);
+                    }
+                }
+                BlockingStrategy::MinHash {
+                    shingle,
+                    bands,
+                    rows,
+                } => {
+                    for block in Self::min_hash_blocks(preprocessed, shingle, bands, rows) {
+                        add_block(&block);
+                    }
+                }
+                BlockingStrategy::SortedNeighbourhood { window } => {
+                    let mut order: Vec<usize> = (0..preprocessed.len()).collect();
+                    order.sort_by(|&a, &b| {
+                        preprocessed[a]
+                            .normalized_title
+                            .cmp(&preprocessed[b].normalized_title)
+                            .then(a.cmp(&b))
+                    });
+                    for start in 0..order.len() {
+                        let end = (start + window).min(order.len());
+                        for &other in &order[start + 1..end] {
+                            add_block(&[order[start], other]);
+                        }
+                    }
+                }
+            }
         }
 
-        s.chars()
+        for neighbours in &mut candidates {
+            neighbours.sort_unstable();
+            neighbours.dedup();
+        }
+        Some(candidates)
+    }
+
+    /// Citations sharing a lowercased DOI or PMID.
+    fn identifier_blocks(preprocessed: &[&PreprocessedCitation]) -> Vec<Vec<usize>> {
+        let mut blocks: HashMap<(bool, String), Vec<usize>> = HashMap::new();
+        for (idx, citation) in preprocessed.iter().enumerate() {
+            let identifiers = [
+                (true, citation.original.doi.as_deref()),
+                (false, citation.original.pmid.as_deref()),
+            ];
+            for (is_doi, value) in identifiers {
+                let value = value.map(str::trim).filter(|value| !value.is_empty());
+                if let Some(value) = value {
+                    blocks
+                        .entry((is_doi, value.to_lowercase()))
+                        .or_default()
+                        .push(idx);
+                }
+            }
+        }
+        blocks
+            .into_values()
+            .filter(|block| block.len() > 1)
+            .collect()
+    }
+
+    /// Citations whose title MinHash signatures agree on a whole band.
+    fn min_hash_blocks(
+        preprocessed: &[&PreprocessedCitation],
+        shingle: usize,
+        bands: usize,
+        rows: usize,
+    ) -> Vec<Vec<usize>> {
+        let seeds: Vec<u64> = (0..bands * rows)
+            .map(|seed| mix_hash(seed as u64))
+            .collect();
+        let mut blocks: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+
+        for (idx, citation) in preprocessed.iter().enumerate() {
+            let chars: Vec<char> = citation.normalized_title.chars().collect();
+            if chars.is_empty() {
+                continue;
+            }
+            let shingles: Vec<u64> = chars
+                .windows(shingle.min(chars.len()))
+                .map(|window| fnv1a(window.iter().flat_map(|&c| u32::from(c).to_le_bytes())))
+                .collect();
+            let signature: Vec<u64> = seeds
+                .iter()
+                .map(|&seed| {
+                    shingles
+                        .iter()
+                        .map(|&shingle| mix_hash(shingle ^ seed))
+                        .min()
+                        .unwrap_or(0)
+                })
+                .collect();
+            for (band, rows) in signature.chunks(rows).enumerate() {
+                blocks
+                    .entry((band, fnv1a(rows.iter().flat_map(|row| row.to_le_bytes()))))
+                    .or_default()
+                    .push(idx);
+            }
+        }
+
+        blocks
+            .into_values()
+            .filter(|block| block.len() > 1)
+            .collect()
+    }
+
+    /// Whether joining the groups rooted at `small` and `large` keeps every
+    /// pair of members connected by at most `max_diameter` matches between
+    /// members.
+    ///
+    /// Both groups are already within the bound, and joining them can only
+    /// shorten the paths inside each, so only pairs across the two are
+    /// checked: a search from each member of the smaller group, stopping at
+    /// `max_diameter` matches or once every member of the larger is reached.
+    fn diameter_within(
+        components: &mut UnionFind,
+        small: usize,
+        large: usize,
+        neighbours: &[Vec<usize>],
+        max_diameter: usize,
+    ) -> bool {
+        let starts = components.members[small].clone();
+        let large_len = components.members[large].len();
+        starts.into_iter().all(|start| {
+            let mut distances = HashMap::from([(start, 0)]);
+            let mut queue = VecDeque::from([start]);
+            let mut reached = 0;
+            while let Some(node) = queue.pop_front() {
+                let distance = distances[&node];
+                if distance == max_diameter {
+                    continue;
+                }
+                for &next in &neighbours[node] {
+                    if distances.contains_key(&next) {
+                        continue;
+                    }
+                    let root = components.find(next);
+                    if root == large {
+                        reached += 1;
+                        if reached == large_len {
+                            return true;
+                        }
+                    } else if root != small {
+                        continue;
+                    }
+                    distances.insert(next, distance + 1);
+                    queue.push_back(next);
+                }
+            }
+            false
+        })
+    }
+
+    /// Finds the first configured rule that matches the pair or, failing
+    /// that, the rule it came closest to matching within the near-miss margin.
+    fn evaluate_pair(&self, a: &PreprocessedCitation, b: &PreprocessedCitation) -> PairOutcome<'_> {
+        let (both_have_doi, matched) = Self::matched_fields(a, b);
+        let mut similarities = [None; SimilarityMetric::ALL.len()];
+        let mut closest: Option<(&MatchRule, f64, f64)> = None;
+
+        for rule in &self.config.rules {
+            if !rule.fields_match(both_have_doi, matched) {
+                continue;
+            }
+            let similarity = *similarities[rule.metric as usize]
+                .get_or_insert_with(|| Self::title_similarity(rule.metric, a, b));
+            let threshold = rule.title_threshold.resolve(&self.config.thresholds);
+            if similarity >= threshold {
+                return PairOutcome::Match {
+                    rule,
+                    similarity,
+                    matched,
+                };
+            }
+            let is_near = self
+                .config
+                .near_miss_margin
+                .is_some_and(|margin| similarity >= threshold - margin);
+            if is_near && closest.is_none_or(|(_, s, t)| threshold - similarity < t - s) {
+                closest = Some((rule, similarity, threshold));
+            }
+        }
+
+        match closest {
+            Some((rule, similarity, threshold)) => PairOutcome::NearMiss {
+                rule,
+                similarity,
+                threshold,
+                matched,
+            },
+            None => PairOutcome::NoMatch,
+        }
+    }
+
+    /// Whether both citations have a DOI, and the [`MatchField`]s that
+    /// match as a bit set.
+    fn matched_fields(a: &PreprocessedCitation, b: &PreprocessedCitation) -> (bool, u8) {
+        let doi_a = a.original.doi.as_deref().filter(|doi| !doi.is_empty());
+        let doi_b = b.original.doi.as_deref().filter(|doi| !doi.is_empty());
+        let both_have_doi = doi_a.is_some() && doi_b.is_some();
+
+        let checks = [
+            (MatchField::Doi, both_have_doi && doi_a == doi_b),
+            (
+                MatchField::Journal,
+                Self::journals_match(
+                    &a.normalized_journal,
+                    &a.normalized_journal_abbr,
+                    &b.normalized_journal,
+                    &b.normalized_journal_abbr,
+                ),
+            ),
+            (
+                MatchField::Issn,
+                Self::match_issns(&a.normalized_issn, &b.normalized_issn),
+            ),
+            (
+                MatchField::Volume,
+                !a.normalized_volume.is_empty() && a.normalized_volume == b.normalized_volume,
+            ),
+            (
+                MatchField::Pages,
+                a.original.pages.is_some() && a.original.pages == b.original.pages,
+            ),
+            (
+                MatchField::Year,
+                Self::get_citation_year(a.original) == Self::get_citation_year(b.original),
+            ),
+            (
+                MatchField::FirstAuthor,
+                a.original
+                    .authors
+                    .first()
+                    .zip(b.original.authors.first())
+                    .is_some_and(|(author_a, author_b)| author_a.is_same_person(author_b)),
+            ),
+        ];
+        let matched = checks
+            .iter()
+            .filter(|(_, is_match)| *is_match)
+            .fold(0, |bits, (field, _)| bits | field.bit());
+
+        (both_have_doi, matched)
+    }
+
+    fn title_similarity(
+        metric: SimilarityMetric,
+        a: &PreprocessedCitation,
+        b: &PreprocessedCitation,
+    ) -> f64 {
+        match metric {
+            SimilarityMetric::TokenSet => metric.similarity(&a.title_words, &b.title_words),
+            _ => metric.similarity(&a.normalized_title, &b.normalized_title),
+        }
+    }
+
+    fn group_by_year_with_indices(citations: &[Citation]) -> YearGroups {
+        let mut groups = YearGroups::default();
+
+        for (index, citation) in citations.iter().enumerate() {
+            match Self::get_citation_year_static(citation) {
+                Some(year) => groups.by_year.entry(year).or_default().push(index),
+                None => groups.undated.push(index),
+            }
+        }
+
+        groups
+    }
+
+    /// Static version of get_citation_year for use in static contexts
+    fn get_citation_year_static(citation: &Citation) -> Option<i32> {
+        citation.date.as_ref().map(|d| d.year)
+    }
+
+    fn convert_unicode_string(input: &str) -> String {
+        crate::text::decode_unicode_escapes(input)
+    }
+
+    fn normalize_string(string: &str) -> Option<String> {
+        if string.is_empty() {
+            return None;
+        }
+
+        let mut result = String::with_capacity(string.len());
+        let mut s = string.trim().to_lowercase();
+
+        for replacement in HTML_REPLACEMENTS.iter() {
+            s = s.replace(replacement.0, replacement.1);
+        }
+
+        s.chars()
             .filter(|c| c.is_alphanumeric())
             .for_each(|c| result.push(c));
 
         Some(result)
     }
 
+    /// Like [`normalize_string`](Self::normalize_string) but keeps word
+    /// boundaries as single spaces.
+    fn normalize_words(string: &str) -> String {
+        let mut s = string.trim().to_lowercase();
+
+        for replacement in HTML_REPLACEMENTS.iter() {
+            s = s.replace(replacement.0, replacement.1);
+        }
+
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     fn normalize_volume(volume: &str) -> String {
         if volume.is_empty() {
             return String::new();
@@ -797,29 +1904,8 @@ impl Deduplicator {
     }
 
     fn format_issn(issn_str: &str) -> Option<String> {
-        // Remove common suffixes and extra text
-        let clean_issn = issn_str
-            .trim()
-            .replace("(Electronic)", "")
-            .replace("(Linking)", "")
-            .replace("(Print)", "")
-            .replace(|c: char| !c.is_ascii_digit() && c != '-' && c != 'X', "")
-            .trim()
-            .to_string();
-
-        // Extract all digits and X
-        let digits: String = clean_issn
-            .chars()
-            .filter(|c| c.is_ascii_digit() || *c == 'X')
-            .collect();
-
-        // Validate format
-        match (clean_issn.len(), digits.len()) {
-            // Valid formats: "1234-5678" (9 chars with hyphen) or "12345678" (8 chars without hyphen)
-            (9, 8) if clean_issn.chars().nth(4) == Some('-') => Some(clean_issn),
-            (8, 8) => Some(format!("{}-{}", &digits[..4], &digits[4..])),
-            _ => None,
-        }
+        // ISBNs and other text kept in `issn` are not ISSN-shaped and never match.
+        crate::utils::normalize_issn(issn_str)
     }
 
     fn match_issns(list1: &[String], list2: &[String]) -> bool {
@@ -832,6 +1918,8 @@ impl Deduplicator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Author, ContributorRole};
+    use rstest::rstest;
 
     #[test]
     fn test_group_by_year() {
@@ -845,6 +1933,8 @@ mod tests {
                     year: 2020,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 volume: None,
                 abstract_text: None,
@@ -865,8 +1955,9 @@ mod tests {
         ];
 
         let grouped = Deduplicator::group_by_year_with_indices(&citations);
-        assert_eq!(grouped.get(&2020).unwrap().len(), 1);
-        assert_eq!(grouped.get(&0).unwrap().len(), 1);
+        assert_eq!(grouped.by_year.get(&2020), Some(&vec![0]));
+        assert_eq!(grouped.by_year.len(), 1);
+        assert_eq!(grouped.undated, vec![1]);
     }
 
     #[test]
@@ -878,6 +1969,8 @@ mod tests {
                     year: 2020,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 doi: Some("10.1234/abc".to_string()),
                 journal: Some("Journal 1".to_string()),
@@ -889,6 +1982,8 @@ mod tests {
                     year: 2020,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 doi: Some("10.1234/abc".to_string()),
                 journal: Some("Journal 1".to_string()),
@@ -900,6 +1995,8 @@ mod tests {
                     year: 2020,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 doi: Some("10.1234/def".to_string()),
                 journal: Some("Journal 2".to_string()),
@@ -931,6 +2028,8 @@ mod tests {
                     year: 2020,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 doi: Some("10.1234/abc".to_string()),
                 journal: Some("Journal 1".to_string()),
@@ -943,6 +2042,8 @@ mod tests {
                     year: 2020,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 doi: Some("".to_string()),
                 journal: Some("Journal 1".to_string()),
@@ -955,6 +2056,8 @@ mod tests {
                     year: 2020,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 doi: Some("".to_string()),
                 journal: Some("Journal 2".to_string()),
@@ -1135,6 +2238,11 @@ mod tests {
         assert_eq!(Deduplicator::format_issn("invalid"), None);
         assert_eq!(Deduplicator::format_issn("1234-56789"), None);
         assert_eq!(Deduplicator::format_issn("123-45678"), None);
+        assert_eq!(
+            Deduplicator::format_issn("1234-567x"),
+            Some("1234-567X".to_string())
+        );
+        assert_eq!(Deduplicator::format_issn("978-3-8309-1689-5"), None);
     }
 
     #[test]
@@ -1146,6 +2254,8 @@ mod tests {
                     year: 2020,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 doi: Some("10.1234/abc".to_string()),
                 journal: Some("Journal 1".to_string()),
@@ -1157,6 +2267,8 @@ mod tests {
                     year: 2019, // Different year
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 doi: Some("10.1234/abc".to_string()),
                 journal: Some("Journal 1".to_string()),
@@ -1174,10 +2286,22 @@ mod tests {
         assert_eq!(duplicate_groups.len(), 1);
         assert_eq!(duplicate_groups[0].duplicates.len(), 1);
 
-        // Test with default year grouping (should not find duplicates across years)
+        // Test with default year grouping (compares neighbouring years)
         let deduplicator = Deduplicator::new();
         let duplicate_groups = deduplicator.find_duplicates(&citations).unwrap();
 
+        assert_eq!(duplicate_groups.len(), 1);
+        assert_eq!(duplicate_groups[0].duplicates.len(), 1);
+
+        // Test with year grouping without a window (should not find duplicates across years)
+        let config = DeduplicatorConfig {
+            group_by_year: true,
+            year_window: 0,
+            ..Default::default()
+        };
+        let deduplicator = Deduplicator::new().with_config(config);
+        let duplicate_groups = deduplicator.find_duplicates(&citations).unwrap();
+
         assert_eq!(duplicate_groups.len(), 2);
         assert!(duplicate_groups.iter().all(|g| g.duplicates.is_empty()));
     }
@@ -1193,6 +2317,8 @@ mod tests {
                     year: 2020,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 ..Default::default()
             },
@@ -1204,6 +2330,8 @@ mod tests {
                     year: 2020,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 ..Default::default()
             },
@@ -1239,6 +2367,8 @@ mod tests {
                     year: 2020,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 ..Default::default()
             },
@@ -1251,6 +2381,8 @@ mod tests {
                     year: 2020,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 ..Default::default()
             },
@@ -1277,6 +2409,8 @@ mod tests {
                     year: 2020,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 ..Default::default()
             },
@@ -1288,6 +2422,8 @@ mod tests {
                     year: 2020,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 ..Default::default()
             },
@@ -1299,6 +2435,8 @@ mod tests {
                     year: 2021,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 ..Default::default()
             },
@@ -1310,6 +2448,8 @@ mod tests {
                     year: 2021,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
                 ..Default::default()
             },
@@ -1322,6 +2462,7 @@ mod tests {
             group_by_year: true, // This is the key - enable year grouping
             run_in_parallel: false,
             source_preferences: vec!["PubMed".to_string(), "Embase".to_string()],
+            ..Default::default()
         };
 
         let deduplicator = Deduplicator::new().with_config(config);
@@ -1347,4 +2488,778 @@ mod tests {
             assert_eq!(group.duplicates.len(), 1);
         }
     }
+
+    fn match_citation(title: &str, doi: Option<&str>) -> Citation {
+        Citation {
+            title: title.to_string(),
+            doi: doi.map(str::to_string),
+            journal: Some("Journal of Testing".to_string()),
+            volume: Some("12".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_similarity_metrics() {
+        for metric in SimilarityMetric::ALL {
+            assert_eq!(metric.similarity("same title", "same title"), 1.0);
+        }
+        assert_eq!(
+            SimilarityMetric::TokenSet.similarity("image analysis review", "review image analysis"),
+            1.0
+        );
+        assert_eq!(
+            SimilarityMetric::TokenSet.similarity("image analysis", "a review of image analysis"),
+            1.0
+        );
+        assert!(SimilarityMetric::NormalizedLevenshtein.similarity("abcd", "abce") < 1.0);
+        assert_eq!(SimilarityMetric::TokenSet.similarity("", ""), 1.0);
+        assert_eq!(SimilarityMetric::TokenSet.similarity("title", ""), 0.0);
+    }
+
+    #[test]
+    fn test_normalize_words() {
+        assert_eq!(
+            Deduplicator::normalize_words("  Machine-Learning:  A <sup>Test</sup> "),
+            "machine learning a test"
+        );
+    }
+
+    #[test]
+    fn test_default_config_rules() {
+        let config = DeduplicatorConfig::default();
+        assert_eq!(config.thresholds, MatchThresholds::default());
+        assert_eq!(config.thresholds.doi_title, 0.85);
+        assert_eq!(config.thresholds.no_doi_title, 0.93);
+        assert_eq!(config.thresholds.strict_title, 0.99);
+        assert_eq!(config.rules, MatchRule::default_rules());
+        assert_eq!(config.rules.len(), 5);
+    }
+
+    #[test]
+    fn test_custom_thresholds() {
+        let citations = vec![
+            match_citation("Machine learning in medicine", None),
+            match_citation("Machine learning in medicines", None),
+        ];
+
+        let groups = Deduplicator::new().find_duplicates(&citations).unwrap();
+        assert_eq!(groups.len(), 1);
+
+        let mut config = DeduplicatorConfig::default();
+        config.thresholds.no_doi_title = 1.0;
+        let groups = Deduplicator::new()
+            .with_config(config)
+            .find_duplicates(&citations)
+            .unwrap();
+        assert_eq!(groups.len(), 2);
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let citations = vec![
+            Citation {
+                title: "Image analysis for deep learning".to_string(),
+                doi: Some("10.1234/abc".to_string()),
+                ..Default::default()
+            },
+            Citation {
+                title: "Deep learning for image analysis".to_string(),
+                doi: Some("10.1234/abc".to_string()),
+                ..Default::default()
+            },
+        ];
+
+        let groups = Deduplicator::new().find_duplicates(&citations).unwrap();
+        assert_eq!(groups.len(), 2);
+
+        let config = DeduplicatorConfig {
+            rules: vec![MatchRule {
+                name: "same-doi-token-set".to_string(),
+                scope: DoiScope::BothHaveDoi,
+                metric: SimilarityMetric::TokenSet,
+                title_threshold: TitleThreshold::Value(0.95),
+                all_of: vec![MatchField::Doi],
+                any_of: Vec::new(),
+            }],
+            ..Default::default()
+        };
+        let groups = Deduplicator::new()
+            .with_config(config)
+            .find_duplicates(&citations)
+            .unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].duplicates.len(), 1);
+    }
+
+    #[test]
+    fn test_first_author_matches_by_orcid() {
+        let author = |name: &str, given: &str, orcid: Option<&str>| Author {
+            name: name.to_string(),
+            given_name: Some(given.to_string()),
+            middle_name: None,
+            affiliations: Vec::new(),
+            role: ContributorRole::Author,
+            orcid: orcid.map(str::to_string),
+            is_organization: false,
+        };
+        let with_authors = |authors| Citation {
+            authors,
+            ..match_citation("Machine learning in medicine", None)
+        };
+        let config = DeduplicatorConfig {
+            rules: vec![MatchRule {
+                name: "first-author".to_string(),
+                scope: DoiScope::Any,
+                metric: SimilarityMetric::JaroWinkler,
+                title_threshold: TitleThreshold::NoDoi,
+                all_of: vec![MatchField::FirstAuthor],
+                any_of: Vec::new(),
+            }],
+            ..Default::default()
+        };
+        let groups = |citations: &[Citation]| {
+            Deduplicator::new()
+                .with_config(config.clone())
+                .find_duplicates(citations)
+                .unwrap()
+                .len()
+        };
+
+        // Names differ, but the ORCID iDs say it is the same person.
+        let orcid = Some("0000-0002-1825-0097");
+        assert_eq!(
+            groups(&[
+                with_authors(vec![author("Carberry", "Josiah", orcid)]),
+                with_authors(vec![author("Carberry-Smith", "Jo", orcid)]),
+            ]),
+            1
+        );
+        // Names match, but the ORCID iDs differ.
+        assert_eq!(
+            groups(&[
+                with_authors(vec![author("Carberry", "Josiah", orcid)]),
+                with_authors(vec![author(
+                    "Carberry",
+                    "Josiah",
+                    Some("0000-0001-5109-3700")
+                )]),
+            ]),
+            2
+        );
+        // Without ORCID iDs the names decide.
+        assert_eq!(
+            groups(&[
+                with_authors(vec![author("Carberry", "Josiah", None)]),
+                with_authors(vec![author("Carberry", "J", None)]),
+            ]),
+            1
+        );
+        assert_eq!(
+            groups(&[with_authors(Vec::new()), with_authors(Vec::new())]),
+            2
+        );
+    }
+
+    #[test]
+    fn test_rule_scope() {
+        let citations = vec![
+            match_citation("Machine learning in medicine", Some("10.1234/abc")),
+            match_citation("Machine learning in medicine", None),
+        ];
+        let rule = |scope| MatchRule {
+            name: "journal".to_string(),
+            scope,
+            metric: SimilarityMetric::Jaro,
+            title_threshold: TitleThreshold::Doi,
+            all_of: vec![MatchField::Journal],
+            any_of: Vec::new(),
+        };
+
+        for (scope, expected_groups) in [
+            (DoiScope::BothHaveDoi, 2),
+            (DoiScope::EitherLacksDoi, 1),
+            (DoiScope::Any, 1),
+        ] {
+            let config = DeduplicatorConfig {
+                rules: vec![rule(scope)],
+                ..Default::default()
+            };
+            let groups = Deduplicator::new()
+                .with_config(config)
+                .find_duplicates(&citations)
+                .unwrap();
+            assert_eq!(groups.len(), expected_groups, "{scope:?}");
+        }
+    }
+
+    #[test]
+    fn test_no_rules_finds_no_duplicates() {
+        let citations = vec![
+            match_citation("Machine learning in medicine", Some("10.1234/abc")),
+            match_citation("Machine learning in medicine", Some("10.1234/abc")),
+        ];
+        let config = DeduplicatorConfig {
+            rules: Vec::new(),
+            ..Default::default()
+        };
+
+        let groups = Deduplicator::new()
+            .with_config(config)
+            .find_duplicates(&citations)
+            .unwrap();
+        assert_eq!(groups.len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_thresholds() {
+        let mut config = DeduplicatorConfig::default();
+        config.thresholds.doi_title = 1.5;
+        let result = Deduplicator::new().with_config(config).find_duplicates(&[]);
+        assert!(matches!(result, Err(DedupeError::ConfigError(_))));
+
+        let mut config = DeduplicatorConfig::default();
+        config.rules[0].title_threshold = TitleThreshold::Value(-0.1);
+        let result = Deduplicator::new().with_config(config).find_duplicates(&[]);
+        assert!(matches!(result, Err(DedupeError::ConfigError(_))));
+    }
+
+    #[test]
+    fn test_explain_duplicates() {
+        let citations = vec![
+            match_citation("Machine learning in medicine", None),
+            match_citation("Deep learning", None),
+            Citation {
+                pages: Some("1-10".to_string()),
+                ..match_citation("Machine learning in medicine", None)
+            },
+        ];
+        let sources = ["Embase", "Embase", "PubMed"];
+        let config = DeduplicatorConfig {
+            source_preferences: vec!["PubMed".to_string()],
+            ..Default::default()
+        };
+
+        let report = Deduplicator::new()
+            .with_config(config)
+            .explain_duplicates(&citations, &sources)
+            .unwrap();
+
+        assert_eq!(report.groups.len(), 2);
+        let group = report
+            .groups
+            .iter()
+            .find(|group| !group.matches.is_empty())
+            .unwrap();
+        assert_eq!(group.unique_index, 2);
+        assert_eq!(group.duplicate_indices, vec![0]);
+        assert_eq!(group.group.unique, citations[2]);
+        assert_eq!(
+            group.matches,
+            vec![MatchExplanation {
+                citation: 2,
+                matched_with: 0,
+                rule: "no-doi-journal".to_string(),
+                title_similarity: 1.0,
+                matched_fields: vec![MatchField::Journal, MatchField::Volume, MatchField::Year],
+            }]
+        );
+        assert!(report.near_misses.is_empty());
+    }
+
+    #[test]
+    fn test_explain_duplicates_near_misses() {
+        let citations = vec![
+            match_citation("Machine learning in medicine", None),
+            match_citation("Machine learning in medicines", None),
+            match_citation("Deep learning", None),
+        ];
+        let mut config = DeduplicatorConfig {
+            near_miss_margin: Some(0.05),
+            ..Default::default()
+        };
+        config.thresholds.no_doi_title = 1.0;
+
+        let report = Deduplicator::new()
+            .with_config(config)
+            .explain_duplicates(&citations, &[])
+            .unwrap();
+
+        assert_eq!(report.groups.len(), 3);
+        assert_eq!(report.near_misses.len(), 1);
+        let near_miss = &report.near_misses[0];
+        assert_eq!((near_miss.first, near_miss.second), (0, 1));
+        assert_eq!(near_miss.rule, "no-doi-journal");
+        assert_eq!(near_miss.threshold, 1.0);
+        assert!(near_miss.title_similarity >= 0.95 && near_miss.title_similarity < 1.0);
+    }
+
+    #[test]
+    fn test_explain_duplicates_with_year_grouping() {
+        let dated = |title: &str, year| Citation {
+            date: Some(crate::Date {
+                year,
+                month: None,
+                day: None,
+                season: None,
+                end: None,
+            }),
+            ..match_citation(title, Some("10.1234/abc"))
+        };
+        let citations = vec![
+            dated("Machine learning in medicine", 2020),
+            dated("Deep learning", 2021),
+            dated("Machine learning in medicine", 2020),
+            dated("Deep learning", 2021),
+        ];
+        let config = DeduplicatorConfig {
+            group_by_year: true,
+            run_in_parallel: true,
+            ..Default::default()
+        };
+
+        let report = Deduplicator::new()
+            .with_config(config)
+            .explain_duplicates(&citations, &[])
+            .unwrap();
+
+        let mut pairs: Vec<_> = report
+            .groups
+            .iter()
+            .flat_map(|group| &group.matches)
+            .map(|explanation| (explanation.matched_with, explanation.citation))
+            .collect();
+        pairs.sort_unstable();
+        assert_eq!(pairs, vec![(0, 2), (1, 3)]);
+        assert_eq!(report.clone().into_groups().len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_near_miss_margin() {
+        let config = DeduplicatorConfig {
+            near_miss_margin: Some(2.0),
+            ..Default::default()
+        };
+        let result = Deduplicator::new()
+            .with_config(config)
+            .explain_duplicates(&[], &[]);
+        assert!(matches!(result, Err(DedupeError::ConfigError(_))));
+    }
+
+    /// Input indices of each group's citations, sorted.
+    fn group_members(report: &DedupeReport) -> Vec<Vec<usize>> {
+        let mut members: Vec<Vec<usize>> = report
+            .groups
+            .iter()
+            .map(|group| {
+                let mut indices = group.duplicate_indices.clone();
+                indices.push(group.unique_index);
+                indices.sort_unstable();
+                indices
+            })
+            .collect();
+        members.sort();
+        members
+    }
+
+    /// A matches B and B matches C, but A does not match C.
+    fn chained_citations() -> Vec<Citation> {
+        let title = "Machine learning in medicine".to_string();
+        vec![
+            Citation {
+                title: title.clone(),
+                journal: Some("Journal of Testing".to_string()),
+                volume: Some("12".to_string()),
+                ..Default::default()
+            },
+            Citation {
+                title: title.clone(),
+                journal: Some("Journal of Testing".to_string()),
+                volume: Some("12".to_string()),
+                pages: Some("10-20".to_string()),
+                issn: vec!["1234-5678".to_string()],
+                ..Default::default()
+            },
+            Citation {
+                title,
+                pages: Some("10-20".to_string()),
+                issn: vec!["1234-5678".to_string()],
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn group_sizes(citations: &[Citation], clustering: Clustering) -> Vec<usize> {
+        let config = DeduplicatorConfig {
+            clustering,
+            ..Default::default()
+        };
+        let groups = Deduplicator::new()
+            .with_config(config)
+            .find_duplicates(citations)
+            .unwrap();
+        let mut sizes: Vec<usize> = groups
+            .iter()
+            .map(|group| group.duplicates.len() + 1)
+            .collect();
+        sizes.sort_unstable();
+        sizes
+    }
+
+    #[test]
+    fn test_first_seed_clustering_depends_on_order() {
+        let citations = chained_citations();
+        assert_eq!(group_sizes(&citations, Clustering::FirstSeed), vec![1, 2]);
+
+        let reordered = vec![
+            citations[1].clone(),
+            citations[0].clone(),
+            citations[2].clone(),
+        ];
+        assert_eq!(group_sizes(&reordered, Clustering::FirstSeed), vec![3]);
+    }
+
+    #[test]
+    fn test_transitive_clustering() {
+        let citations = chained_citations();
+        let transitive = Clustering::Transitive { max_diameter: None };
+
+        for order in [[0, 1, 2], [1, 0, 2], [2, 0, 1], [0, 2, 1]] {
+            let ordered: Vec<Citation> = order.iter().map(|&i| citations[i].clone()).collect();
+            assert_eq!(group_sizes(&ordered, transitive), vec![3], "{order:?}");
+        }
+
+        let config = DeduplicatorConfig {
+            clustering: transitive,
+            ..Default::default()
+        };
+        let report = Deduplicator::new()
+            .with_config(config)
+            .explain_duplicates(&citations, &[])
+            .unwrap();
+        assert_eq!(report.groups.len(), 1);
+        let pairs: Vec<_> = report.groups[0]
+            .matches
+            .iter()
+            .map(|m| (m.matched_with, m.citation))
+            .collect();
+        assert_eq!(pairs, vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn test_transitive_clustering_max_diameter() {
+        let citations = chained_citations();
+
+        let complete = Clustering::Transitive {
+            max_diameter: Some(1),
+        };
+        assert_eq!(group_sizes(&citations, complete), vec![1, 2]);
+
+        let diameter_two = Clustering::Transitive {
+            max_diameter: Some(2),
+        };
+        assert_eq!(group_sizes(&citations, diameter_two), vec![3]);
+    }
+
+    #[test]
+    fn test_transitive_clustering_with_year_grouping() {
+        let mut citations = chained_citations();
+        citations.extend(chained_citations());
+        for (idx, citation) in citations.iter_mut().enumerate() {
+            citation.date = Some(crate::Date {
+                year: if idx < 3 { 2020 } else { 2022 },
+                month: None,
+                day: None,
+                season: None,
+                end: None,
+            });
+        }
+        let config = DeduplicatorConfig {
+            group_by_year: true,
+            run_in_parallel: true,
+            clustering: Clustering::Transitive { max_diameter: None },
+            ..Default::default()
+        };
+
+        let report = Deduplicator::new()
+            .with_config(config)
+            .explain_duplicates(&citations, &[])
+            .unwrap();
+
+        assert_eq!(group_members(&report), vec![vec![0, 1, 2], vec![3, 4, 5]]);
+    }
+
+    #[test]
+    fn test_invalid_max_diameter() {
+        let config = DeduplicatorConfig {
+            clustering: Clustering::Transitive {
+                max_diameter: Some(0),
+            },
+            ..Default::default()
+        };
+        let result = Deduplicator::new().with_config(config).find_duplicates(&[]);
+        assert!(matches!(result, Err(DedupeError::ConfigError(_))));
+    }
+
+    fn report_with_blocking(
+        citations: &[Citation],
+        blocking: Vec<BlockingStrategy>,
+        clustering: Clustering,
+    ) -> DedupeReport {
+        let config = DeduplicatorConfig {
+            blocking,
+            clustering,
+            ..Default::default()
+        };
+        Deduplicator::new()
+            .with_config(config)
+            .explain_duplicates(citations, &[])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_blocking_finds_all_pairs_duplicates() {
+        let citations = vec![
+            match_citation("Machine learning in medicine", Some("10.1234/a")),
+            match_citation("Deep learning for image analysis", None),
+            match_citation("MACHINE LEARNING IN MEDICINE", None),
+            match_citation("A survey of reinforcement learning", Some("10.1234/b")),
+            match_citation("Deep learning for image analyses", None),
+            match_citation("Zebrafish models of heart regeneration", None),
+            match_citation("A survey of reinforcement learning", Some("10.1234/b")),
+            match_citation("Machine learning in medicine", Some("10.1234/a")),
+        ];
+
+        for clustering in [
+            Clustering::FirstSeed,
+            Clustering::Transitive { max_diameter: None },
+        ] {
+            let all_pairs = report_with_blocking(&citations, Vec::new(), clustering);
+            let blocked =
+                report_with_blocking(&citations, BlockingStrategy::recommended(), clustering);
+            assert_eq!(
+                group_members(&blocked),
+                group_members(&all_pairs),
+                "{clustering:?}"
+            );
+        }
+        assert_eq!(
+            group_members(&report_with_blocking(
+                &citations,
+                BlockingStrategy::recommended(),
+                Clustering::Transitive { max_diameter: None },
+            )),
+            vec![vec![0, 2, 7], vec![1, 4], vec![3, 6], vec![5]]
+        );
+    }
+
+    #[test]
+    fn test_identifier_blocking() {
+        let mut citations = vec![
+            match_citation("Machine learning in medicine", None),
+            match_citation("Machine learning in medicine", None),
+        ];
+        let identifier = || vec![BlockingStrategy::Identifier];
+
+        let report = report_with_blocking(&citations, identifier(), Clustering::FirstSeed);
+        assert_eq!(report.groups.len(), 2);
+
+        citations[0].pmid = Some("12345".to_string());
+        citations[1].pmid = Some("12345".to_string());
+        let report = report_with_blocking(&citations, identifier(), Clustering::FirstSeed);
+        assert_eq!(report.groups.len(), 1);
+    }
+
+    #[test]
+    fn test_sorted_neighbourhood_blocking() {
+        let citations = vec![
+            match_citation("Machine learning in medicine", None),
+            match_citation("Machine learning in medicines", None),
+            match_citation("Machine learning in medicine.", None),
+        ];
+        let window = |window| vec![BlockingStrategy::SortedNeighbourhood { window }];
+        let transitive = Clustering::Transitive { max_diameter: None };
+
+        // Sorted by title, citations 0 and 2 are adjacent and 1 comes last
+        assert_eq!(
+            group_members(&report_with_blocking(&citations, window(2), transitive)),
+            vec![vec![0, 1, 2]]
+        );
+        let report = report_with_blocking(&citations, window(2), Clustering::FirstSeed);
+        let pairs: Vec<_> = report
+            .groups
+            .iter()
+            .flat_map(|group| &group.matches)
+            .map(|m| (m.matched_with, m.citation))
+            .collect();
+        assert_eq!(pairs, vec![(0, 2)]);
+    }
+
+    #[test]
+    fn test_invalid_blocking() {
+        for strategy in [
+            BlockingStrategy::SortedNeighbourhood { window: 1 },
+            BlockingStrategy::MinHash {
+                shingle: 0,
+                bands: 20,
+                rows: 3,
+            },
+        ] {
+            let config = DeduplicatorConfig {
+                blocking: vec![strategy],
+                ..Default::default()
+            };
+            let result = Deduplicator::new().with_config(config).find_duplicates(&[]);
+            assert!(matches!(result, Err(DedupeError::ConfigError(_))));
+        }
+    }
+
+    fn citation_in_year(title: &str, year: Option<i32>) -> Citation {
+        Citation {
+            date: year.map(|year| crate::Date {
+                year,
+                month: None,
+                day: None,
+                season: None,
+                end: None,
+            }),
+            ..match_citation(title, None)
+        }
+    }
+
+    #[test]
+    fn test_comparison_units() {
+        let citations = vec![
+            citation_in_year("A", Some(2020)),
+            citation_in_year("B", None),
+            citation_in_year("C", Some(2019)),
+            citation_in_year("D", Some(2022)),
+            citation_in_year("E", Some(2020)),
+        ];
+        let deduplicator = Deduplicator::new();
+
+        let units: Vec<_> = deduplicator
+            .comparison_units(&citations)
+            .into_iter()
+            .map(|unit| (unit.within, unit.across))
+            .collect();
+        assert_eq!(
+            units,
+            vec![
+                (vec![2], vec![0, 4, 1]),
+                (vec![0, 4], vec![1]),
+                (vec![3], vec![1]),
+                (vec![1], vec![]),
+            ]
+        );
+
+        let config = DeduplicatorConfig {
+            group_by_year: true,
+            year_window: 3,
+            ..Default::default()
+        };
+        let units = Deduplicator::new()
+            .with_config(config)
+            .comparison_units(&citations);
+        assert_eq!(units[0].across, vec![0, 4, 3, 1]);
+        assert_eq!(units[1].across, vec![3, 1]);
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn test_undated_citations_compared_with_all_years(#[case] run_in_parallel: bool) {
+        let citations = vec![
+            citation_in_year("Machine learning in medicine", Some(2020)),
+            citation_in_year("Deep learning", Some(2015)),
+            citation_in_year("Machine learning in medicine", None),
+            citation_in_year("Deep learning", None),
+            citation_in_year("Zebrafish models", None),
+        ];
+        let config = DeduplicatorConfig {
+            group_by_year: true,
+            run_in_parallel,
+            year_window: 0,
+            ..Default::default()
+        };
+
+        let report = Deduplicator::new()
+            .with_config(config)
+            .explain_duplicates(&citations, &[])
+            .unwrap();
+
+        assert_eq!(
+            group_members(&report),
+            vec![vec![0, 2], vec![1, 3], vec![4]]
+        );
+    }
+
+    #[test]
+    fn test_year_window() {
+        let citations = vec![
+            citation_in_year("Machine learning in medicine", Some(2020)),
+            citation_in_year("Machine learning in medicine", Some(2018)),
+            citation_in_year("Deep learning", Some(2021)),
+            citation_in_year("Deep learning", Some(2020)),
+        ];
+        let members = |year_window| {
+            let config = DeduplicatorConfig {
+                group_by_year: true,
+                run_in_parallel: true,
+                year_window,
+                ..Default::default()
+            };
+            let report = Deduplicator::new()
+                .with_config(config)
+                .explain_duplicates(&citations, &[])
+                .unwrap();
+            group_members(&report)
+        };
+
+        assert_eq!(members(0), vec![vec![0], vec![1], vec![2], vec![3]]);
+        assert_eq!(members(1), vec![vec![0], vec![1], vec![2, 3]]);
+        assert_eq!(members(2), vec![vec![0, 1], vec![2, 3]]);
+    }
+
+    #[test]
+    fn test_year_grouping_matches_all_pairs_within_window() {
+        let citations = vec![
+            citation_in_year("Machine learning in medicine", Some(2020)),
+            citation_in_year("Deep learning", None),
+            citation_in_year("Machine learning in medicine", Some(2021)),
+            citation_in_year("Deep learning", Some(2019)),
+            citation_in_year("Machine learning in medicines", None),
+        ];
+        let report = |group_by_year| {
+            let config = DeduplicatorConfig {
+                group_by_year,
+                run_in_parallel: group_by_year,
+                near_miss_margin: Some(0.1),
+                ..Default::default()
+            };
+            Deduplicator::new()
+                .with_config(config)
+                .explain_duplicates(&citations, &[])
+                .unwrap()
+        };
+
+        let (grouped, all_pairs) = (report(true), report(false));
+        assert_eq!(group_members(&grouped), group_members(&all_pairs));
+        assert_eq!(grouped.near_misses, all_pairs.near_misses);
+        let matches = |report: &DedupeReport| -> Vec<MatchExplanation> {
+            report
+                .groups
+                .iter()
+                .flat_map(|group| group.matches.clone())
+                .collect()
+        };
+        assert_eq!(matches(&grouped), matches(&all_pairs));
+    }
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(*b""), 0xCBF2_9CE4_8422_2325);
+        assert_eq!(fnv1a(*b"a"), 0xAF63_DC4C_8601_EC8C);
+        assert_eq!(fnv1a(*b"foobar"), 0x8594_4171_F739_67E8);
+    }
 }
@@ -1,7 +1,8 @@
 //! Pretty diagnostic reporting using [ariadne].
 //!
-//! This module provides rich, human-readable error output for [`ParseError`]
-//! values, rendered with source-code context, underlines, and labels.  It
+//! This module provides rich, human-readable output for [`ParseError`] and
+//! [`ParseWarning`] values, rendered with source-code context, underlines, and
+//! labels.  It
 //! is only compiled when the `diagnostics` Cargo feature is enabled:
 //!
 //! ```toml
@@ -20,8 +21,21 @@
 //!     Err(e) => eprintln!("{}", e.to_diagnostic("input.ris", source)),
 //! }
 //! ```
+//!
+//! Warnings from [`crate::CitationParser::parse_with_options`] render the same
+//! way:
+//!
+//! ```rust,ignore
+//! use biblib::{CitationParser, ParseOptions, RisParser};
+//!
+//! let source = "TY  - JOUR\nTI  - Title\nPY  - someday\nER  -";
+//! let report = RisParser::new().parse_with_options(source, &ParseOptions::new())?;
+//! for warning in &report.warnings {
+//!     eprintln!("{}", warning.to_diagnostic("input.ris", source));
+//! }
+//! ```
 
-use crate::error::ParseError;
+use crate::error::{ParseError, ParseWarning, SourceSpan};
 
 #[cfg(feature = "diagnostics")]
 use ariadne::{Color, Label, Report, ReportKind, Source};
@@ -44,7 +58,7 @@ impl ParseError {
         // Ariadne 0.6+: Report::build takes a Span directly.
         // We use (filename, range) as our span type, where range is the
         // portion of the source that triggered the error.
-        let primary_range = self.primary_byte_range(source);
+        let primary_range = primary_byte_range(self.span.as_ref(), self.line, source);
         let header_span = (filename, primary_range.clone());
 
         let mut report =
@@ -64,34 +78,71 @@ impl ParseError {
 
         String::from_utf8_lossy(&buf).into_owned()
     }
+}
 
-    /// Compute a byte-range into `source` that best represents the error
-    /// location, used for Ariadne label placement.
+#[cfg(feature = "diagnostics")]
+impl ParseWarning {
+    /// Render this warning as a pretty Ariadne diagnostic.
+    ///
+    /// Same as [`ParseError::to_diagnostic`], but reported as a warning.
+    ///
+    /// # Arguments
     ///
-    /// Priority: explicit `span` > line-derived range > whole-file fallback.
-    #[cfg(feature = "diagnostics")]
-    fn primary_byte_range(&self, source: &str) -> std::ops::Range<usize> {
-        if let Some(ref span) = self.span {
-            return span.start..span.end;
-        }
-        if let Some(line) = self.line {
-            let line_start: usize = source
-                .lines()
-                .take(line.saturating_sub(1))
-                .map(|l| l.len() + 1) // +1 for '\n'
-                .sum();
-            let line_len = source
-                .lines()
-                .nth(line.saturating_sub(1))
-                .map(|l| l.len())
-                .unwrap_or(0);
-            return line_start..line_start + line_len;
-        }
-        // No position info — point at offset 0 (shows the first line).
-        0..0
+    /// * `filename` – Label shown in the report header (e.g. `"citations.ris"`).
+    /// * `source`   – The original source text that was parsed.
+    pub fn to_diagnostic(&self, filename: &str, source: &str) -> String {
+        let mut buf = Vec::new();
+
+        let primary_range = primary_byte_range(self.span.as_ref(), self.line, source);
+        let header_span = (filename, primary_range.clone());
+
+        let report =
+            Report::build(ReportKind::Warning, header_span).with_message(format!("{}", self));
+
+        report
+            .with_label(
+                Label::new((filename, primary_range))
+                    .with_message(format!("{}", self.kind))
+                    .with_color(Color::Yellow),
+            )
+            .finish()
+            .write((filename, Source::from(source)), &mut buf)
+            .unwrap();
+
+        String::from_utf8_lossy(&buf).into_owned()
     }
 }
 
+/// Compute a byte-range into `source` that best represents the error or
+/// warning location, used for Ariadne label placement.
+///
+/// Priority: explicit `span` > line-derived range > whole-file fallback.
+#[cfg(feature = "diagnostics")]
+fn primary_byte_range(
+    span: Option<&SourceSpan>,
+    line: Option<usize>,
+    source: &str,
+) -> std::ops::Range<usize> {
+    if let Some(span) = span {
+        return span.start..span.end;
+    }
+    if let Some(line) = line {
+        let line_start: usize = source
+            .lines()
+            .take(line.saturating_sub(1))
+            .map(|l| l.len() + 1) // +1 for '\n'
+            .sum();
+        let line_len = source
+            .lines()
+            .nth(line.saturating_sub(1))
+            .map(|l| l.len())
+            .unwrap_or(0);
+        return line_start..line_start + line_len;
+    }
+    // No position info — point at offset 0 (shows the first line).
+    0..0
+}
+
 /// Parse a citation string and, on failure, return a pretty Ariadne diagnostic
 /// instead of a raw [`ParseError`].
 ///
@@ -253,4 +304,23 @@ mod tests {
             "format name should appear in the diagnostic"
         );
     }
+
+    #[test]
+    fn test_warning_to_diagnostic() {
+        use crate::error::{ParseWarning, WarningKind};
+
+        let source = "TY  - JOUR\nTI  - Hello\n!! stray\nER  -\n";
+        let warning = ParseWarning::at_line(
+            3,
+            CitationFormat::Ris,
+            WarningKind::IgnoredLine {
+                line: "!! stray".into(),
+            },
+        )
+        .with_span(SourceSpan::new(22, 30));
+        let diag = warning.to_diagnostic("test.ris", source);
+        assert!(diag.contains("test.ris"));
+        assert!(diag.contains("Warning"));
+        assert!(diag.contains("!! stray"));
+    }
 }
@@ -0,0 +1,287 @@
+//! Character encoding detection for byte input.
+//!
+//! Citation exports are not always UTF-8: EndNote on Windows writes UTF-16,
+//! older Ovid and CINAHL exports use Windows-1252, and many tools prepend a
+//! byte order mark. [`decode`] turns such input into a `String` for the
+//! `&str` parsers and reports which encoding it used.
+//!
+//! The encoding is chosen in this order:
+//!
+//! 1. A UTF-8, UTF-16LE or UTF-16BE byte order mark, which is removed.
+//! 2. UTF-16 without a byte order mark, recognised by NUL bytes in every
+//!    other position at the start of the input.
+//! 3. The `encoding` attribute of an XML declaration.
+//! 4. UTF-8, when the input is valid UTF-8.
+//! 5. Windows-1252, which decodes any byte sequence.
+//!
+//! # Example
+//!
+//! ```
+//! use biblib::encoding::{TextEncoding, decode};
+//!
+//! let bytes = b"TY  - JOUR\nTI  - \xc9tude\nER  -\n";
+//! let (text, encoding) = decode(bytes).unwrap();
+//!
+//! assert_eq!(encoding, TextEncoding::Windows1252);
+//! assert!(text.contains("Étude"));
+//! ```
+
+use crate::CitationFormat;
+use crate::error::{ParseError, SourceSpan, ValueError};
+
+/// Number of leading bytes inspected for UTF-16 and XML declaration sniffing.
+const SNIFF_LEN: usize = 1024;
+
+/// The character encoding [`decode`] used for byte input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    /// UTF-8, with or without a byte order mark.
+    Utf8 {
+        /// Whether the input started with a byte order mark.
+        bom: bool,
+    },
+    /// UTF-16, little-endian.
+    Utf16Le,
+    /// UTF-16, big-endian.
+    Utf16Be,
+    /// Windows-1252, a superset of ISO-8859-1.
+    Windows1252,
+    /// Another encoding named by an XML declaration, by its WHATWG name
+    /// (for example `"ISO-8859-2"` or `"Shift_JIS"`).
+    Other(&'static str),
+}
+
+impl TextEncoding {
+    /// The WHATWG name of the encoding.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 { .. } => "UTF-8",
+            TextEncoding::Utf16Le => "UTF-16LE",
+            TextEncoding::Utf16Be => "UTF-16BE",
+            TextEncoding::Windows1252 => "windows-1252",
+            TextEncoding::Other(name) => name,
+        }
+    }
+
+    fn from_encoding(encoding: &'static encoding_rs::Encoding) -> Self {
+        if encoding == encoding_rs::UTF_8 {
+            TextEncoding::Utf8 { bom: false }
+        } else if encoding == encoding_rs::UTF_16LE {
+            TextEncoding::Utf16Le
+        } else if encoding == encoding_rs::UTF_16BE {
+            TextEncoding::Utf16Be
+        } else if encoding == encoding_rs::WINDOWS_1252 {
+            TextEncoding::Windows1252
+        } else {
+            TextEncoding::Other(encoding.name())
+        }
+    }
+}
+
+impl std::fmt::Display for TextEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Decode byte input to a `String`, detecting its encoding.
+///
+/// A byte order mark is removed, so line numbers and spans reported for the
+/// decoded text count from the first character after it.
+///
+/// # Errors
+///
+/// Returns a [`ValueError::Encoding`] error when the input has a byte order
+/// mark or XML declaration for an encoding it is not valid in, or looks like
+/// UTF-16 but is not.
+pub fn decode(bytes: &[u8]) -> Result<(String, TextEncoding), ParseError> {
+    decode_with_format(bytes, CitationFormat::Unknown)
+}
+
+/// [`decode`], attributing errors to `format`.
+pub(crate) fn decode_with_format(
+    bytes: &[u8],
+    format: CitationFormat,
+) -> Result<(String, TextEncoding), ParseError> {
+    if let Some((encoding, bom_len)) = encoding_rs::Encoding::for_bom(bytes) {
+        let detected = match TextEncoding::from_encoding(encoding) {
+            TextEncoding::Utf8 { .. } => TextEncoding::Utf8 { bom: true },
+            detected => detected,
+        };
+        return decode_strict(&bytes[bom_len..], encoding, detected, format);
+    }
+
+    if let Some(encoding) = sniff_utf16(bytes) {
+        return decode_strict(
+            bytes,
+            encoding,
+            TextEncoding::from_encoding(encoding),
+            format,
+        );
+    }
+
+    if let Some(encoding) = xml_declared_encoding(bytes) {
+        return decode_strict(
+            bytes,
+            encoding,
+            TextEncoding::from_encoding(encoding),
+            format,
+        );
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Ok((text.to_string(), TextEncoding::Utf8 { bom: false })),
+        Err(_) => {
+            let (text, _) = encoding_rs::WINDOWS_1252.decode_without_bom_handling(bytes);
+            Ok((text.into_owned(), TextEncoding::Windows1252))
+        }
+    }
+}
+
+/// Decode `bytes` as `encoding`, failing on malformed sequences.
+fn decode_strict(
+    bytes: &[u8],
+    encoding: &'static encoding_rs::Encoding,
+    detected: TextEncoding,
+    format: CitationFormat,
+) -> Result<(String, TextEncoding), ParseError> {
+    if encoding == encoding_rs::UTF_8 {
+        return match std::str::from_utf8(bytes) {
+            Ok(text) => Ok((text.to_string(), detected)),
+            Err(err) => {
+                let offset = err.valid_up_to();
+                Err(ParseError::without_position(
+                    format,
+                    ValueError::Encoding {
+                        encoding: detected.as_str(),
+                    },
+                )
+                .with_span(SourceSpan::new(offset, offset)))
+            }
+        };
+    }
+
+    match encoding.decode_without_bom_handling_and_without_replacement(bytes) {
+        Some(text) => Ok((text.into_owned(), detected)),
+        None => Err(ParseError::without_position(
+            format,
+            ValueError::Encoding {
+                encoding: detected.as_str(),
+            },
+        )),
+    }
+}
+
+/// Recognise UTF-16 without a byte order mark from ASCII text stored as
+/// 16-bit units: every other byte at the start of the input is NUL.
+fn sniff_utf16(bytes: &[u8]) -> Option<&'static encoding_rs::Encoding> {
+    let head = &bytes[..bytes.len().min(SNIFF_LEN)];
+    if head.len() < 4 {
+        return None;
+    }
+
+    let units = head.len() / 2;
+    let even_nuls = head.iter().step_by(2).filter(|&&b| b == 0).count();
+    let odd_nuls = head.iter().skip(1).step_by(2).filter(|&&b| b == 0).count();
+
+    // Mostly-ASCII text leaves one byte of nearly every unit NUL.
+    if odd_nuls * 10 >= units * 9 && even_nuls == 0 {
+        Some(encoding_rs::UTF_16LE)
+    } else if even_nuls * 10 >= units * 9 && odd_nuls == 0 {
+        Some(encoding_rs::UTF_16BE)
+    } else {
+        None
+    }
+}
+
+/// The encoding named by a leading `<?xml … encoding="…"?>` declaration.
+fn xml_declared_encoding(bytes: &[u8]) -> Option<&'static encoding_rs::Encoding> {
+    let head = &bytes[..bytes.len().min(SNIFF_LEN)];
+    let start = head.iter().position(|b| !b.is_ascii_whitespace())?;
+    let head = head[start..].strip_prefix(b"<?xml")?;
+    let end = head.windows(2).position(|w| w == b"?>")?;
+    let declaration = std::str::from_utf8(&head[..end]).ok()?;
+
+    let (_, rest) = declaration.split_once("encoding")?;
+    let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let label = rest[1..].split(quote).next()?;
+
+    let encoding = encoding_rs::Encoding::for_label(label.as_bytes())?;
+    // A UTF-16 declaration in a file without NUL bytes is wrong; trust the
+    // bytes instead.
+    if encoding == encoding_rs::UTF_16LE || encoding == encoding_rs::UTF_16BE {
+        return None;
+    }
+    Some(encoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    fn utf16le(text: &str, bom: bool) -> Vec<u8> {
+        let mut bytes = if bom { vec![0xFF, 0xFE] } else { Vec::new() };
+        bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        bytes
+    }
+
+    fn utf16be(text: &str, bom: bool) -> Vec<u8> {
+        let mut bytes = if bom { vec![0xFE, 0xFF] } else { Vec::new() };
+        bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+        bytes
+    }
+
+    #[rstest]
+    #[case(utf16le("TY  - JOUR\nTI  - Étude\n", true), TextEncoding::Utf16Le)]
+    #[case(utf16le("TY  - JOUR\nTI  - Étude\n", false), TextEncoding::Utf16Le)]
+    #[case(utf16be("TY  - JOUR\nTI  - Étude\n", true), TextEncoding::Utf16Be)]
+    #[case(utf16be("TY  - JOUR\nTI  - Étude\n", false), TextEncoding::Utf16Be)]
+    #[case(
+        [b"\xEF\xBB\xBF".as_slice(), "TY  - JOUR\nTI  - Étude\n".as_bytes()].concat(),
+        TextEncoding::Utf8 { bom: true }
+    )]
+    #[case("TY  - JOUR\nTI  - Étude\n".as_bytes().to_vec(), TextEncoding::Utf8 { bom: false })]
+    #[case(b"TY  - JOUR\nTI  - \xC9tude\n".to_vec(), TextEncoding::Windows1252)]
+    fn test_decode(#[case] bytes: Vec<u8>, #[case] expected: TextEncoding) {
+        let (text, encoding) = decode(&bytes).unwrap();
+        assert_eq!(encoding, expected);
+        assert_eq!(text, "TY  - JOUR\nTI  - Étude\n");
+    }
+
+    #[test]
+    fn test_decode_xml_declaration() {
+        let bytes = b"<?xml version=\"1.0\" encoding=\"ISO-8859-2\"?>\n<xml>\xA9</xml>";
+        let (text, encoding) = decode(bytes).unwrap();
+        // WHATWG treats ISO-8859-2 as its own encoding, unlike ISO-8859-1.
+        assert_eq!(encoding, TextEncoding::Other("ISO-8859-2"));
+        assert!(text.ends_with("<xml>Š</xml>"));
+    }
+
+    #[test]
+    fn test_decode_xml_declaration_utf8_is_checked() {
+        let bytes = b"<?xml version='1.0' encoding='UTF-8'?>\n<xml>\xC9</xml>";
+        let err = decode(bytes).unwrap_err();
+        assert!(matches!(
+            err.error,
+            ValueError::Encoding { encoding: "UTF-8" }
+        ));
+        assert_eq!(err.span, Some(SourceSpan::new(44, 44)));
+    }
+
+    #[test]
+    fn test_decode_invalid_utf16() {
+        // An unpaired high surrogate.
+        let mut bytes = utf16le("TY  - JOUR\n", true);
+        bytes.extend([0x00, 0xD8]);
+        let err = decode(&bytes).unwrap_err();
+        assert!(matches!(
+            err.error,
+            ValueError::Encoding {
+                encoding: "UTF-16LE"
+            }
+        ));
+    }
+}
@@ -47,10 +47,17 @@
 //! ```
 
 mod parse;
+mod write;
 
 use crate::error::ParseError;
-use crate::{Citation, CitationParser};
-use parse::parse_endnote_xml;
+use crate::stream::chunk::parse_chunks;
+use crate::stream::element::ElementChunks;
+use crate::{
+    Citation, CitationFormat, CitationParser, CitationWriter, OnError, ParseOptions, ParseReport,
+    collect_report,
+};
+use parse::{parse_endnote_xml, parse_endnote_xml_with_warnings};
+use write::write_endnote_xml;
 
 /// Parser for EndNote XML format citations.
 ///
@@ -107,11 +114,140 @@ impl CitationParser for EndNoteXmlParser {
 
         parse_endnote_xml(input)
     }
+
+    /// Reads one `<record>` element at a time.
+    fn parse_reader<'r, R>(
+        &self,
+        reader: R,
+    ) -> Box<dyn Iterator<Item = Result<Citation, ParseError>> + 'r>
+    where
+        R: std::io::Read + 'r,
+    {
+        let chunks = ElementChunks::new(reader, CitationFormat::EndNoteXml, "record");
+        Box::new(parse_chunks(chunks, parse_endnote_xml))
+    }
+
+    /// Reports unparseable `<year>` dates and `<electronic-resource-num>`
+    /// values that are not DOIs as warnings.
+    fn parse_with_options(
+        &self,
+        input: &str,
+        options: &ParseOptions,
+    ) -> Result<ParseReport, ParseError> {
+        if options.on_error == OnError::Fail {
+            let records = parse_endnote_xml_with_warnings(input)?;
+            return collect_report(records.into_iter().map(Ok), options);
+        }
+
+        let chunks = ElementChunks::new(input.as_bytes(), CitationFormat::EndNoteXml, "record");
+        collect_report(
+            parse_chunks(chunks, parse_endnote_xml_with_warnings),
+            options,
+        )
+    }
+}
+
+/// Writer for EndNote XML format citations.
+///
+/// Writes the record layout understood by [`EndNoteXmlParser`]. Citation types
+/// are mapped to EndNote `ref-type` names and numbers; unknown types keep their
+/// name with the Generic number. Contributors are written to the group for
+/// their role, such as `<secondary-authors>` for editors. ENW-tagged
+/// `extra_fields` entries that have an EndNote XML element (such as `%C` for
+/// `<pub-location>`) are written to that element.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::{Citation, CitationParser, CitationWriter, EndNoteXmlParser, EndNoteXmlWriter};
+///
+/// let citation = Citation {
+///     citation_type: vec!["Journal Article".to_string()],
+///     title: "Example Title".to_string(),
+///     journal: Some("Journal of Examples".to_string()),
+///     ..Default::default()
+/// };
+///
+/// let xml = EndNoteXmlWriter::new().write(&[citation.clone()]);
+/// assert!(xml.contains(r#"<ref-type name="Journal Article">17</ref-type>"#));
+/// assert_eq!(EndNoteXmlParser::new().parse(&xml).unwrap(), vec![citation]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EndNoteXmlWriter;
+
+impl EndNoteXmlWriter {
+    /// Creates a new EndNote XML writer instance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CitationWriter for EndNoteXmlWriter {
+    fn write(&self, citations: &[Citation]) -> String {
+        write_endnote_xml(citations)
+    }
 }
 
 #[cfg(test)]
 mod integration_tests {
     use super::*;
+    use crate::ContributorRole;
+
+    #[test]
+    fn test_parse_reader_matches_parse() {
+        let xml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?><xml><records><record><titles><title>First</title></titles></record><record>\n<titles><title>Second</title></titles>\n<contributors><authors><author>Smith, John</author></authors></contributors></record></records></xml>";
+        let parser = EndNoteXmlParser::new();
+
+        let streamed: Result<Vec<_>, _> = parser.parse_reader(xml.as_bytes()).collect();
+        assert_eq!(streamed.unwrap(), parser.parse(xml).unwrap());
+    }
+
+    #[test]
+    fn test_parse_reader_reports_positions_in_whole_input() {
+        let xml = "<xml><records>\n<record>\n<titles><title>First</title></titles>\n</record>\n<record>\n<titles><title>Second</titl></titles>\n</record>\n</records></xml>";
+        let parser = EndNoteXmlParser::new();
+        let expected = parser.parse(xml).unwrap_err();
+
+        let streamed: Vec<_> = parser.parse_reader(xml.as_bytes()).collect();
+        assert_eq!(streamed.len(), 2);
+        assert!(streamed[0].is_ok());
+
+        let err = streamed[1].as_ref().unwrap_err();
+        assert_eq!(err.line, expected.line);
+        assert_eq!(err.span, expected.span);
+    }
+
+    #[test]
+    fn test_parse_with_options_reports_warnings() {
+        use crate::{OnError, ParseOptions, WarningKind};
+
+        let xml = "<xml><records>\n<record><titles><title>First</title></titles><dates><year>2020</year></dates></record>\n<record>\n<titles><title>Second</title></titles>\n<electronic-resource-num>not a doi</electronic-resource-num>\n<dates><year>someday</year></dates>\n</record>\n</records></xml>";
+        for on_error in [OnError::Fail, OnError::Collect] {
+            let options = ParseOptions::new().with_on_error(on_error);
+            let report = EndNoteXmlParser::new()
+                .parse_with_options(xml, &options)
+                .unwrap();
+
+            assert_eq!(report.citations.len(), 2);
+            let kinds: Vec<_> = report.warnings.iter().map(|w| &w.kind).collect();
+            assert_eq!(
+                kinds,
+                vec![
+                    &WarningKind::InvalidDoi {
+                        key: "electronic-resource-num".to_string(),
+                        value: "not a doi".to_string()
+                    },
+                    &WarningKind::UnparseableDate {
+                        key: "year".to_string(),
+                        value: "someday".to_string()
+                    },
+                ]
+            );
+            assert_eq!(report.warnings[0].line, Some(5));
+            assert_eq!(report.warnings[1].line, Some(6));
+        }
+    }
 
     #[test]
     fn test_complete_endnote_xml() {
@@ -334,6 +470,41 @@ mod integration_tests {
         assert_eq!(citation.keywords, vec!["keyword1", "keyword2"]);
     }
 
+    #[test]
+    fn test_contributor_roles() {
+        let xml = r#"<xml><records><record>
+          <contributors>
+            <authors><author>Smith, John</author></authors>
+            <secondary-authors><author>Doe, Jane</author></secondary-authors>
+            <tertiary-authors><author>Brown, Alex</author></tertiary-authors>
+            <subsidiary-authors><author>Helper, Sam</author></subsidiary-authors>
+            <translated-authors><author>Smit, Johan</author></translated-authors>
+          </contributors>
+          <titles><title>Example</title></titles>
+        </record></records></xml>"#;
+
+        let citation = &parse_endnote_xml(xml).unwrap()[0];
+        assert_eq!(citation.authors.len(), 1);
+        assert_eq!(citation.authors[0].role, ContributorRole::Author);
+        let roles: Vec<_> = citation
+            .contributors
+            .iter()
+            .map(|c| (c.name.as_str(), c.role))
+            .collect();
+        assert_eq!(
+            roles,
+            vec![
+                ("Doe", ContributorRole::Editor),
+                ("Brown", ContributorRole::SeriesEditor),
+                ("Helper", ContributorRole::Translator),
+            ]
+        );
+        assert_eq!(
+            citation.extra_fields.get("%H"),
+            Some(&vec!["Smit, Johan".to_string()])
+        );
+    }
+
     #[test]
     fn test_malformed_xml_error() {
         let xml = r#"
@@ -479,7 +650,7 @@ mod integration_tests {
             println!("Error at line {}: {}", line, parse_error.error);
             // The empty record starts around line 4, buffer position captured earlier
             assert!(
-                line >= 3 && line <= 7,
+                (3..=7).contains(&line),
                 "Line number should be around line 3-7, got {}",
                 line
             );
@@ -520,4 +691,68 @@ mod integration_tests {
         let result = parser.parse(xml).unwrap();
         assert_eq!(result.len(), 0);
     }
+
+    #[test]
+    fn test_write_round_trip() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xml><records><record>
+  <ref-type name="Journal Article">17</ref-type>
+  <contributors><authors>
+    <author>Doe, John A</author>
+    <author>Smith, Jane</author>
+    <author>World Health Organization,</author>
+  </authors><secondary-authors>
+    <author>Brown, Alex</author>
+  </secondary-authors><subsidiary-authors>
+    <author>Helper, Sam</author>
+  </subsidiary-authors></contributors>
+  <titles>
+    <title>Sample &amp; Research</title>
+    <secondary-title>Journal of Science</secondary-title>
+    <alt-title>J Sci</alt-title>
+  </titles>
+  <pages>123-135</pages>
+  <volume>15</volume>
+  <number>3</number>
+  <keywords><keyword>alpha</keyword><keyword>beta</keyword></keywords>
+  <dates><year year="2023" month="5" day="9">2023</year></dates>
+  <publisher>Science Press</publisher>
+  <isbn>1234-5678</isbn>
+  <accession-num>ACC-1</accession-num>
+  <abstract>An abstract.</abstract>
+  <electronic-resource-num>10.1234/example</electronic-resource-num>
+  <urls><related-urls><url>https://example.com/a</url></related-urls></urls>
+  <custom2>PMC12345</custom2>
+  <language>eng</language>
+</record></records></xml>"#;
+
+        let citations = EndNoteXmlParser::new().parse(xml).unwrap();
+        let output = EndNoteXmlWriter::new().write(&citations);
+        let reparsed = EndNoteXmlParser::new().parse(&output).unwrap();
+
+        assert_eq!(reparsed, citations);
+        let organization = &citations[0].authors[2];
+        assert_eq!(organization.name, "World Health Organization");
+        assert_eq!(organization.role, ContributorRole::CorporateAuthor);
+        assert!(organization.is_organization);
+        assert!(output.contains("<author>World Health Organization,</author>"));
+    }
+
+    #[cfg(feature = "enw")]
+    #[test]
+    fn test_write_enw_extras_to_elements() {
+        let input = "%0 Edited Book\n%E Handke, Jürgen\n%T Campus\n%C Münster\n";
+        let citations = crate::EnwParser::new().parse(input).unwrap();
+        let output = EndNoteXmlWriter::new().write(&citations);
+
+        assert!(output.contains("<ref-type name=\"Edited Book\">28</ref-type>"));
+        assert!(
+            output.contains("<secondary-authors>\n          <author>Handke, Jürgen</author>\n")
+        );
+        assert!(!output.contains("<authors>"));
+        assert!(output.contains("<pub-location>Münster</pub-location>"));
+
+        let reparsed = EndNoteXmlParser::new().parse(&output).unwrap();
+        assert_eq!(reparsed[0].contributors, citations[0].contributors);
+    }
 }
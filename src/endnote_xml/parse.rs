@@ -2,8 +2,11 @@
 //!
 //! This module provides the core parsing logic for EndNote XML format.
 
-use crate::error::{ParseError, SourceSpan, ValueError};
-use crate::{Author, Citation, CitationFormat};
+use crate::error::{ParseError, ParseWarning, SourceSpan, ValueError, WarningKind};
+use crate::utils::{
+    detect_identifier, parse_endnote_name, partition_contributors, sort_serial_numbers,
+};
+use crate::{Citation, CitationFormat, ContributorRole};
 use quick_xml::Reader;
 use quick_xml::events::Event;
 use quick_xml::name::QName;
@@ -88,6 +91,17 @@ fn extract_text_with_position<B: BufRead>(
 ///
 /// ```
 pub(crate) fn parse_endnote_xml(content: &str) -> Result<Vec<Citation>, ParseError> {
+    Ok(parse_endnote_xml_with_warnings(content)?
+        .into_iter()
+        .map(|(citation, _)| citation)
+        .collect())
+}
+
+/// Parse EndNote XML content into citations, keeping the warnings for each
+/// citation.
+pub(crate) fn parse_endnote_xml_with_warnings(
+    content: &str,
+) -> Result<Vec<(Citation, Vec<ParseWarning>)>, ParseError> {
     if content.trim().is_empty() {
         return Ok(Vec::new());
     }
@@ -123,12 +137,15 @@ pub(crate) fn parse_endnote_xml(content: &str) -> Result<Vec<Citation>, ParseErr
     Ok(citations)
 }
 
-/// Extracts date components (year, month, day) from a year element
+/// Extracts date components (year, month, day) from a year element, adding a
+/// warning when the element has a year that is not a number
 fn extract_date_from_year_element<B: BufRead>(
     reader: &mut Reader<B>,
     e: &quick_xml::events::BytesStart,
     content: &str,
+    warnings: &mut Vec<ParseWarning>,
 ) -> Result<(Option<i32>, Option<u8>, Option<u8>), ParseError> {
+    let mut year_text = String::new();
     let mut year_val = None;
     let mut month_val = None;
     let mut day_val = None;
@@ -147,6 +164,7 @@ fn extract_date_from_year_element<B: BufRead>(
         match attr.key.as_ref() {
             b"year" => {
                 if let Ok(year_str) = std::str::from_utf8(&attr.value) {
+                    year_text = year_str.trim().to_string();
                     year_val = year_str.parse::<i32>().ok();
                 }
             }
@@ -174,11 +192,11 @@ fn extract_date_from_year_element<B: BufRead>(
     if year_val.is_none() {
         let mut local_buf = Vec::new();
         let start_pos = reader.buffer_position() as usize;
-        if let Ok(year) =
-            extract_text_with_position(reader, &mut local_buf, b"year", content, start_pos)?
-                .parse::<i32>()
-        {
+        let text = extract_text_with_position(reader, &mut local_buf, b"year", content, start_pos)?;
+        if let Ok(year) = text.parse::<i32>() {
             year_val = Some(year);
+        } else if !text.is_empty() {
+            year_text = text;
         }
     } else {
         // Still need to consume the text content
@@ -187,17 +205,32 @@ fn extract_date_from_year_element<B: BufRead>(
         let _ = extract_text_with_position(reader, &mut local_buf, b"year", content, start_pos)?;
     }
 
+    if year_val.is_none() && !year_text.is_empty() {
+        warnings.push(ParseWarning::at_line(
+            attr_line,
+            CitationFormat::EndNoteXml,
+            WarningKind::UnparseableDate {
+                key: "year".to_string(),
+                value: year_text,
+            },
+        ));
+    }
+
     Ok((year_val, month_val, day_val))
 }
 
-/// Parse a single record element into a Citation
+/// Parse a single record element into a Citation and its warnings
 fn parse_record<B: BufRead>(
     reader: &mut Reader<B>,
     buf: &mut Vec<u8>,
     content: &str,
     start_pos: usize,
-) -> Result<Citation, ParseError> {
+) -> Result<(Citation, Vec<ParseWarning>), ParseError> {
     let mut citation = Citation::new();
+    let mut warnings = Vec::new();
+    // Role of `<author>` elements in the current contributor group; `None`
+    // inside `<translated-authors>`, which holds other forms of the names.
+    let mut role = Some(ContributorRole::Author);
 
     loop {
         match reader.read_event_into(buf) {
@@ -236,22 +269,23 @@ fn parse_record<B: BufRead>(
                     citation.title =
                         extract_text_with_position(reader, buf, b"title", content, pos)?;
                 }
+                b"authors" => role = Some(ContributorRole::Author),
+                b"secondary-authors" => role = Some(ContributorRole::Editor),
+                b"tertiary-authors" => role = Some(ContributorRole::SeriesEditor),
+                b"subsidiary-authors" => role = Some(ContributorRole::Translator),
+                b"translated-authors" => role = None,
                 b"author" => {
                     let pos = reader.buffer_position() as usize;
                     let author_str =
                         extract_text_with_position(reader, buf, b"author", content, pos)?;
-                    let (family, given) = crate::utils::parse_author_name(&author_str);
-                    let (given_opt, middle_opt) = if given.is_empty() {
-                        (None, None)
-                    } else {
-                        crate::utils::split_given_and_middle(&given)
-                    };
-                    citation.authors.push(Author {
-                        name: family,
-                        given_name: given_opt,
-                        middle_name: middle_opt,
-                        affiliations: Vec::new(),
-                    });
+                    match role {
+                        Some(role) => citation.authors.push(parse_endnote_name(&author_str, role)),
+                        None => citation
+                            .extra_fields
+                            .entry("%H".to_string())
+                            .or_default()
+                            .push(author_str),
+                    }
                 }
                 b"secondary-title" => {
                     let pos = reader.buffer_position() as usize;
@@ -322,6 +356,17 @@ fn parse_record<B: BufRead>(
                         pos,
                     )?;
                     citation.doi = crate::utils::format_doi(&doi);
+                    if citation.doi.is_none() && !doi.is_empty() {
+                        let line = buffer_position_to_line_number(content, pos);
+                        warnings.push(ParseWarning::at_line(
+                            line,
+                            CitationFormat::EndNoteXml,
+                            WarningKind::InvalidDoi {
+                                key: "electronic-resource-num".to_string(),
+                                value: doi,
+                            },
+                        ));
+                    }
                 }
                 b"url" => {
                     let pos = reader.buffer_position() as usize;
@@ -333,7 +378,7 @@ fn parse_record<B: BufRead>(
                 }
                 b"year" => {
                     let (year_val, month_val, day_val) =
-                        extract_date_from_year_element(reader, e, content)?;
+                        extract_date_from_year_element(reader, e, content, &mut warnings)?;
                     citation.date = crate::utils::parse_endnote_date(year_val, month_val, day_val);
                 }
                 b"dates" => {
@@ -344,7 +389,12 @@ fn parse_record<B: BufRead>(
                             Ok(Event::Start(ref inner_e)) if inner_e.name() == QName(b"year") => {
                                 // Parse year element within dates
                                 let (year_val, month_val, day_val) =
-                                    extract_date_from_year_element(reader, inner_e, content)?;
+                                    extract_date_from_year_element(
+                                        reader,
+                                        inner_e,
+                                        content,
+                                        &mut warnings,
+                                    )?;
                                 citation.date =
                                     crate::utils::parse_endnote_date(year_val, month_val, day_val);
                             }
@@ -405,7 +455,7 @@ fn parse_record<B: BufRead>(
                 b"isbn" => {
                     let pos = reader.buffer_position() as usize;
                     let issns = extract_text_with_position(reader, buf, b"isbn", content, pos)?;
-                    citation.issn.extend(crate::utils::split_issns(&issns));
+                    sort_serial_numbers(&issns, &mut citation.issn, &mut citation.identifiers);
                 }
                 _ => (),
             },
@@ -425,8 +475,20 @@ fn parse_record<B: BufRead>(
         buf.clear();
     }
 
+    let (authors, contributors) = partition_contributors(std::mem::take(&mut citation.authors));
+    citation.authors = authors;
+    citation.contributors = contributors;
+    if let Some(identifier) = citation
+        .accession_number
+        .as_deref()
+        .and_then(detect_identifier)
+    {
+        citation.identifiers.insert(0, identifier);
+    }
+
     // Validate that we have at least a title or author
-    if citation.title.is_empty() && citation.authors.is_empty() {
+    if citation.title.is_empty() && citation.authors.is_empty() && citation.contributors.is_empty()
+    {
         let end_pos = reader.buffer_position() as usize;
         let line_num = buffer_position_to_line_number(content, start_pos);
         return Err(ParseError::at_line(
@@ -440,5 +502,5 @@ fn parse_record<B: BufRead>(
         .with_span(SourceSpan::new(start_pos, end_pos)));
     }
 
-    Ok(citation)
+    Ok((citation, warnings))
 }
@@ -0,0 +1,263 @@
+//! EndNote XML writing implementation.
+//!
+//! Produces the `<xml><records><record>…` layout read by
+//! [`super::parse::parse_endnote_xml`].
+
+use crate::utils::{endnote_ref_type, format_endnote_name};
+use crate::{Citation, ContributorRole, Scheme};
+use quick_xml::escape::escape;
+
+/// Generic EndNote `ref-type` number, used for types without a known mapping.
+const GENERIC_REF_TYPE: u8 = 13;
+
+/// Title elements below `<titles>` that `extra_fields` can fill.
+const TITLE_FIELDS: [(&str, &str); 2] = [("%Q", "translated-title"), ("%S", "tertiary-title")];
+
+/// Record-level elements that `extra_fields` can fill, keyed by ENW or RIS tag.
+const RECORD_FIELDS: [(&str, &str); 9] = [
+    ("%C", "pub-location"),
+    ("%7", "edition"),
+    ("%6", "num-vols"),
+    ("%L", "call-num"),
+    ("%F", "label"),
+    ("%Z", "notes"),
+    ("N1", "notes"),
+    ("%(", "orig-pub"),
+    ("%[", "access-date"),
+];
+
+/// Serialize citations into an EndNote XML document.
+pub(crate) fn write_endnote_xml(citations: &[Citation]) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<xml>\n  <records>\n");
+    for (index, citation) in citations.iter().enumerate() {
+        write_record(&mut out, citation, index + 1);
+    }
+    out.push_str("  </records>\n</xml>\n");
+    out
+}
+
+fn write_record(out: &mut String, citation: &Citation, rec_number: usize) {
+    out.push_str("    <record>\n");
+    push_element(out, 3, "rec-number", &rec_number.to_string());
+
+    if let Some(citation_type) = citation.citation_type.first() {
+        let (name, number) =
+            endnote_ref_type(citation_type).unwrap_or((citation_type.as_str(), GENERIC_REF_TYPE));
+        out.push_str(&format!(
+            "      <ref-type name=\"{}\">{}</ref-type>\n",
+            escape(name),
+            number
+        ));
+    }
+
+    write_contributors(out, citation);
+
+    let mut titles = String::new();
+    push_element(&mut titles, 4, "title", &citation.title);
+    push_opt(
+        &mut titles,
+        4,
+        "secondary-title",
+        citation.journal.as_deref(),
+    );
+    push_opt(
+        &mut titles,
+        4,
+        "alt-title",
+        citation.journal_abbr.as_deref(),
+    );
+    for (key, element) in TITLE_FIELDS {
+        push_extra(&mut titles, 4, citation, key, element);
+    }
+    push_group(out, 3, "titles", &titles);
+
+    push_opt(out, 3, "pages", citation.pages.as_deref());
+    push_opt(out, 3, "volume", citation.volume.as_deref());
+    push_opt(out, 3, "number", citation.issue.as_deref());
+
+    let mut keywords = String::new();
+    for keyword in &citation.keywords {
+        push_element(&mut keywords, 4, "keyword", keyword);
+    }
+    push_group(out, 3, "keywords", &keywords);
+
+    if let Some(date) = &citation.date {
+        let mut attributes = String::new();
+        if let Some(month) = date.month {
+            attributes.push_str(&format!(" month=\"{}\"", month));
+        }
+        if let Some(day) = date.day {
+            attributes.push_str(&format!(" day=\"{}\"", day));
+        }
+        out.push_str(&format!(
+            "      <dates>\n        <year{}>{}</year>\n      </dates>\n",
+            attributes, date.year
+        ));
+    }
+
+    push_opt(out, 3, "publisher", citation.publisher.as_deref());
+    let isbn: Vec<&str> = citation
+        .issn
+        .iter()
+        .map(String::as_str)
+        .chain(citation.identifiers_of(Scheme::Isbn))
+        .collect();
+    if !isbn.is_empty() {
+        push_element(out, 3, "isbn", &isbn.join("; "));
+    }
+    push_opt(
+        out,
+        3,
+        "accession-num",
+        citation.accession_number.as_deref(),
+    );
+    push_opt(out, 3, "abstract", citation.abstract_text.as_deref());
+    for (key, element) in RECORD_FIELDS {
+        push_extra(out, 3, citation, key, element);
+    }
+    push_opt(out, 3, "electronic-resource-num", citation.doi.as_deref());
+
+    let mut urls = String::new();
+    for url in &citation.urls {
+        push_element(&mut urls, 5, "url", url);
+    }
+    if !urls.is_empty() {
+        out.push_str("      <urls>\n");
+        push_group(out, 4, "related-urls", &urls);
+        out.push_str("      </urls>\n");
+    }
+
+    push_opt(out, 3, "custom2", citation.pmc_id.as_deref());
+    push_opt(out, 3, "language", citation.language.as_deref());
+    out.push_str("    </record>\n");
+}
+
+/// Write `<contributors>`, placing each contributor in the group for their role.
+///
+/// Translated author names preserved under `%H` in `extra_fields` go to
+/// `<translated-authors>`. Investigators have no group and are not written.
+fn write_contributors(out: &mut String, citation: &Citation) {
+    let mut groups: [(&str, String); 5] = [
+        ("authors", String::new()),
+        ("secondary-authors", String::new()),
+        ("tertiary-authors", String::new()),
+        ("subsidiary-authors", String::new()),
+        ("translated-authors", String::new()),
+    ];
+    for author in citation.authors.iter().chain(&citation.contributors) {
+        let group = match author.role {
+            ContributorRole::Author | ContributorRole::CorporateAuthor => 0,
+            ContributorRole::Editor => 1,
+            ContributorRole::SeriesEditor => 2,
+            ContributorRole::Translator => 3,
+            ContributorRole::Investigator => continue,
+        };
+        push_element(
+            &mut groups[group].1,
+            5,
+            "author",
+            &format_endnote_name(author),
+        );
+    }
+    push_extra(&mut groups[4].1, 5, citation, "%H", "author");
+
+    let mut contributors = String::new();
+    for (element, inner) in &groups {
+        push_group(&mut contributors, 4, element, inner);
+    }
+    push_group(out, 3, "contributors", &contributors);
+}
+
+fn push_extra(out: &mut String, depth: usize, citation: &Citation, key: &str, element: &str) {
+    for value in citation.extra_fields.get(key).into_iter().flatten() {
+        push_element(out, depth, element, value);
+    }
+}
+
+/// Append `<element>value</element>` at the given indentation, skipping blank values.
+fn push_element(out: &mut String, depth: usize, element: &str, value: &str) {
+    let value = value.trim();
+    if value.is_empty() {
+        return;
+    }
+    out.push_str(&"  ".repeat(depth));
+    out.push_str(&format!("<{element}>{}</{element}>\n", escape(value)));
+}
+
+fn push_opt(out: &mut String, depth: usize, element: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        push_element(out, depth, element, value);
+    }
+}
+
+/// Wrap already-rendered children in `<element>`, skipping empty groups.
+fn push_group(out: &mut String, depth: usize, element: &str, children: &str) {
+    if children.is_empty() {
+        return;
+    }
+    let indent = "  ".repeat(depth);
+    out.push_str(&format!(
+        "{indent}<{element}>\n{children}{indent}</{element}>\n"
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Author;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_write_minimal_record() {
+        let citation = Citation {
+            citation_type: vec!["Journal Article".to_string()],
+            title: "Fish & Chips".to_string(),
+            authors: vec![Author {
+                name: "Smith".to_string(),
+                given_name: Some("John".to_string()),
+                middle_name: None,
+                affiliations: Vec::new(),
+                role: ContributorRole::Author,
+                orcid: None,
+                is_organization: false,
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            write_endnote_xml(&[citation]),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<xml>
+  <records>
+    <record>
+      <rec-number>1</rec-number>
+      <ref-type name="Journal Article">17</ref-type>
+      <contributors>
+        <authors>
+          <author>Smith, John</author>
+        </authors>
+      </contributors>
+      <titles>
+        <title>Fish &amp; Chips</title>
+      </titles>
+    </record>
+  </records>
+</xml>
+"#
+        );
+    }
+
+    #[test]
+    fn test_unknown_type_uses_generic_number() {
+        let citation = Citation {
+            citation_type: vec!["Clinical Trial".to_string()],
+            title: "Trial".to_string(),
+            ..Default::default()
+        };
+
+        assert!(
+            write_endnote_xml(&[citation])
+                .contains("<ref-type name=\"Clinical Trial\">13</ref-type>")
+        );
+    }
+}
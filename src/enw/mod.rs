@@ -22,11 +22,18 @@
 //! ```
 
 mod parse;
+mod write;
 
 use crate::error::ParseError;
-use crate::{Citation, CitationParser};
+use crate::stream::chunk::parse_chunks;
+use crate::stream::line::LineChunks;
+use crate::{
+    Citation, CitationFormat, CitationParser, CitationWriter, OnError, ParseOptions, ParseReport,
+    collect_report,
+};
 pub(crate) use parse::looks_like_enw;
-use parse::parse_enw;
+use parse::{enw_boundary, parse_enw, parse_enw_with_warnings};
+use write::write_enw;
 
 /// Parser for EndNote Tagged (`.enw`) citations.
 #[derive(Debug, Clone, Default)]
@@ -48,12 +55,105 @@ impl CitationParser for EnwParser {
 
         parse_enw(input)
     }
+
+    /// Reads one `%0` record at a time.
+    fn parse_reader<'r, R>(
+        &self,
+        reader: R,
+    ) -> Box<dyn Iterator<Item = Result<Citation, ParseError>> + 'r>
+    where
+        R: std::io::Read + 'r,
+    {
+        let chunks = LineChunks::new(
+            std::io::BufReader::new(reader),
+            CitationFormat::Enw,
+            enw_boundary,
+        );
+        Box::new(parse_chunks(chunks, |chunk| EnwParser.parse(chunk)))
+    }
+
+    /// Reports unparseable dates and `%R` values that are not DOIs as
+    /// warnings.
+    fn parse_with_options(
+        &self,
+        input: &str,
+        options: &ParseOptions,
+    ) -> Result<ParseReport, ParseError> {
+        if options.on_error == OnError::Fail {
+            let records = parse_enw_with_warnings(input)?;
+            return collect_report(records.into_iter().map(Ok), options);
+        }
+
+        let chunks = LineChunks::new(input.as_bytes(), CitationFormat::Enw, enw_boundary);
+        collect_report(parse_chunks(chunks, parse_enw_with_warnings), options)
+    }
+}
+
+/// Writer for EndNote Tagged (`.enw`) citations.
+///
+/// Uses the same tag table as [`EnwParser`]. Citation types are written as
+/// EndNote reference type names where one is known. `journal_abbr`, `pmid`,
+/// `pmc_id` and `mesh_terms` have no ENW tag and are not written; `%`-tagged
+/// `extra_fields` entries are written back under their tag.
+///
+/// # Example
+///
+/// ```
+/// use biblib::{CitationParser, CitationWriter, EnwParser, EnwWriter};
+///
+/// let input = "%0 Journal Article\n%T Example Title\n%A Smith, John\n%D 2024\n";
+/// let citations = EnwParser::new().parse(input).unwrap();
+///
+/// let output = EnwWriter::new().write(&citations);
+/// assert_eq!(EnwParser::new().parse(&output).unwrap(), citations);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EnwWriter;
+
+impl EnwWriter {
+    /// Creates a new EndNote Tagged writer instance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CitationWriter for EnwWriter {
+    fn write(&self, citations: &[Citation]) -> String {
+        write_enw(citations)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ValueError;
+    use crate::{ContributorRole, DateKind, Scheme, ValueError};
+
+    #[test]
+    fn test_parse_reader_matches_parse() {
+        let input = "%0 Journal Article\n%T First\n%A Smith, John\n\n%0 Book\n%T Second\n%X An abstract\nthat wraps\n";
+        let parser = EnwParser::new();
+
+        let streamed: Result<Vec<_>, _> = parser.parse_reader(input.as_bytes()).collect();
+        assert_eq!(streamed.unwrap(), parser.parse(input).unwrap());
+    }
+
+    #[test]
+    fn test_parse_reader_reports_positions_in_whole_input() {
+        let input =
+            "%0 Journal Article\n%T First\n\n%0 Journal Article\n%Tbad\n\n%0 Book\n%T Third\n";
+        let parser = EnwParser::new();
+        let expected = parser.parse(input).unwrap_err();
+
+        let streamed: Vec<_> = parser.parse_reader(input.as_bytes()).collect();
+        assert_eq!(streamed.len(), 3);
+        assert!(streamed[0].is_ok() && streamed[2].is_ok());
+
+        let err = streamed[1].as_ref().unwrap_err();
+        assert!(matches!(err.error, ValueError::Syntax(_)));
+        assert_eq!(err.line, expected.line);
+        assert_eq!(err.span, expected.span);
+    }
 
     #[test]
     fn test_parse_sample_enw_record() {
@@ -84,17 +184,17 @@ mod tests {
         assert_eq!(citation.pages.as_deref(), Some("324 S."));
         assert_eq!(citation.keywords, vec!["E-Learning"]);
         assert_eq!(citation.date.as_ref().map(|d| d.year), Some(2006));
-        assert_eq!(citation.issn, vec!["978-3-8309-1689-5"]);
-        assert_eq!(citation.authors.len(), 2);
-        assert_eq!(
+        assert!(citation.issn.is_empty());
+        assert_eq!(citation.identifier(Scheme::Isbn), Some("9783830916895"));
+        assert!(citation.authors.is_empty());
+        assert_eq!(citation.contributors.len(), 2);
+        assert!(
             citation
-                .extra_fields
-                .get("%E")
-                .cloned()
-                .unwrap_or_default()
-                .len(),
-            2
+                .contributors
+                .iter()
+                .all(|c| c.role == ContributorRole::Editor)
         );
+        assert!(!citation.extra_fields.contains_key("%E"));
         assert_eq!(
             citation.extra_fields.get("%C"),
             Some(&vec!["Münster".to_string()])
@@ -136,7 +236,7 @@ mod tests {
     }
 
     #[test]
-    fn test_contributor_roles_are_flattened_and_preserved() {
+    fn test_contributor_roles() {
         let input = r#"%0 Book
 %T Example
 %A Smith, John
@@ -148,26 +248,51 @@ mod tests {
 
         let citations = EnwParser::new().parse(input).unwrap();
         let citation = &citations[0];
-        assert_eq!(citation.authors.len(), 5);
-        assert!(citation.extra_fields.get("%A").is_none());
-        assert_eq!(
-            citation.extra_fields.get("%E"),
-            Some(&vec!["Doe, Jane".to_string()])
-        );
-        assert_eq!(
-            citation.extra_fields.get("%Y"),
-            Some(&vec!["Brown, Alex".to_string()])
-        );
+        assert_eq!(citation.authors.len(), 1);
+        assert_eq!(citation.authors[0].name, "Smith");
+        let roles: Vec<_> = citation
+            .contributors
+            .iter()
+            .map(|c| (c.name.as_str(), c.role))
+            .collect();
         assert_eq!(
-            citation.extra_fields.get("%?"),
-            Some(&vec!["Helper, Sam".to_string()])
+            roles,
+            vec![
+                ("Doe", ContributorRole::Editor),
+                ("Brown", ContributorRole::SeriesEditor),
+                ("Helper", ContributorRole::Translator),
+            ]
         );
+        for tag in ["%A", "%E", "%Y", "%?"] {
+            assert!(!citation.extra_fields.contains_key(tag));
+        }
+        // Translated author names are not separate people.
         assert_eq!(
             citation.extra_fields.get("%H"),
             Some(&vec!["Translator, Terry".to_string()])
         );
     }
 
+    #[test]
+    fn test_trailing_comma_marks_corporate_author() {
+        let input = "%0 Report\n%T Example\n%A World Health Organization,\n%A Smith, John\n%E Cochrane Collaboration,\n";
+
+        let citation = &EnwParser::new().parse(input).unwrap()[0];
+        let organization = &citation.authors[0];
+        assert_eq!(organization.name, "World Health Organization");
+        assert_eq!(organization.given_name, None);
+        assert_eq!(organization.role, ContributorRole::CorporateAuthor);
+        assert!(organization.is_organization);
+        assert!(!citation.authors[1].is_organization);
+        assert_eq!(citation.contributors[0].role, ContributorRole::Editor);
+        assert!(citation.contributors[0].is_organization);
+
+        let output = EnwWriter::new().write(std::slice::from_ref(citation));
+        assert!(output.contains("%A World Health Organization,\n%A Smith, John\n"));
+        assert!(output.contains("%E Cochrane Collaboration,\n"));
+        assert_eq!(EnwParser::new().parse(&output).unwrap()[0], *citation);
+    }
+
     #[test]
     fn test_container_priority_prefers_j_over_b_over_s() {
         let input = r#"%0 Journal Article
@@ -187,7 +312,7 @@ mod tests {
             citation.extra_fields.get("%S"),
             Some(&vec!["Tertiary Title".to_string()])
         );
-        assert!(citation.extra_fields.get("%J").is_none());
+        assert!(!citation.extra_fields.contains_key("%J"));
     }
 
     #[test]
@@ -228,6 +353,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_access_date() {
+        let input = r#"%0 Web Page
+%T Example
+%[ 2024-01-15
+"#;
+
+        let citation = EnwParser::new().parse(input).unwrap().remove(0);
+        let accessed = citation.date_of(DateKind::Accessed).unwrap();
+        assert_eq!(
+            (accessed.year, accessed.month, accessed.day),
+            (2024, Some(1), Some(15))
+        );
+        assert!(!citation.extra_fields.contains_key("%["));
+    }
+
     #[test]
     fn test_doi_extraction_from_percent_r_and_url_fallback() {
         let input = r#"%0 Journal Article
@@ -257,11 +398,40 @@ mod tests {
     fn test_percent_at_preserves_raw_identifier_when_not_issn_like() {
         let input = r#"%0 Book
 %T Example
-%@ 978-3-8309-1689-5
+%@ 978-3-8309-1689-4
+%@ Report 12
+"#;
+
+        let citation = EnwParser::new().parse(input).unwrap().remove(0);
+        assert_eq!(citation.issn, vec!["978-3-8309-1689-4", "Report 12"]);
+        assert!(citation.identifiers.is_empty());
+    }
+
+    #[test]
+    fn test_percent_at_sorts_issns_and_isbns() {
+        let input = r#"%0 Book Section
+%T Example
+%@ 0028-0836 (Print); 0-306-40615-2
 "#;
 
         let citation = EnwParser::new().parse(input).unwrap().remove(0);
-        assert_eq!(citation.issn, vec!["978-3-8309-1689-5"]);
+        assert_eq!(citation.issn, vec!["0028-0836 (Print)"]);
+        assert_eq!(citation.identifier(Scheme::Isbn), Some("9780306406157"));
+    }
+
+    #[test]
+    fn test_accession_number_fills_typed_identifier() {
+        let input = r#"%0 Journal Article
+%T Example
+%M 2-s2.0-85012345678
+"#;
+
+        let citation = EnwParser::new().parse(input).unwrap().remove(0);
+        assert_eq!(
+            citation.accession_number.as_deref(),
+            Some("2-s2.0-85012345678")
+        );
+        assert_eq!(citation.identifier(Scheme::Eid), Some("2-s2.0-85012345678"));
     }
 
     #[test]
@@ -320,4 +490,90 @@ Second line continues here.
         let span = err.span.expect("expected span");
         assert!(span.end > span.start);
     }
+
+    #[test]
+    fn test_write_round_trip() {
+        let input = r#"%0 Edited Book
+%@ 978-3-8309-1689-5
+%E Handke, Jürgen
+%A Smith, John A
+%E Franke, Peter
+%T The virtual linguistics campus
+%8 2006-05-09
+%D 2006
+%C Münster
+%I Waxmann
+%J Strategies and concepts for successful e-learning
+%B Series Title
+%P 324 S.
+%K E-Learning
+%X First paragraph
+continues here.
+%X Second paragraph.
+%R 10.1000/enw
+%U https://example.com/enw
+%[ 2024-01-15
+%F label-1
+
+%0 Journal Article
+%T Second
+%A Doe, Jane
+%D 2020
+"#;
+        let citations = EnwParser::new().parse(input).unwrap();
+        let output = EnwWriter::new().write(&citations);
+        let reparsed = EnwParser::new().parse(&output).unwrap();
+
+        assert_eq!(reparsed, citations);
+        assert!(output.contains("%A Smith, John A\n%E Handke, Jürgen\n%E Franke, Peter\n"));
+    }
+
+    #[test]
+    fn test_write_maps_reference_types() {
+        let citation = Citation {
+            citation_type: vec!["Book Chapter".to_string()],
+            title: "Chapter".to_string(),
+            ..Default::default()
+        };
+        let output = EnwWriter::new().write(&[citation]);
+        assert!(output.starts_with("%0 Book Section\n"));
+    }
+
+    #[test]
+    fn test_parse_with_options_reports_warnings() {
+        use crate::{OnError, ParseOptions, WarningKind};
+
+        let input = "%0 Journal Article\n%T First\n%D 2024\n\n%0 Journal Article\n%T Second\n%D someday\n%R not a doi\n%[ yesterday\n";
+        for on_error in [OnError::Fail, OnError::Collect] {
+            let options = ParseOptions::new().with_on_error(on_error);
+            let report = EnwParser::new()
+                .parse_with_options(input, &options)
+                .unwrap();
+
+            assert_eq!(report.citations.len(), 2);
+            let kinds: Vec<_> = report.warnings.iter().map(|w| &w.kind).collect();
+            assert_eq!(
+                kinds,
+                vec![
+                    &WarningKind::UnparseableDate {
+                        key: "%D".to_string(),
+                        value: "someday".to_string()
+                    },
+                    &WarningKind::UnparseableDate {
+                        key: "%[".to_string(),
+                        value: "yesterday".to_string()
+                    },
+                    &WarningKind::InvalidDoi {
+                        key: "%R".to_string(),
+                        value: "not a doi".to_string()
+                    },
+                ]
+            );
+            assert_eq!(report.warnings[0].line, Some(5));
+            assert_eq!(
+                report.citations[1].extra_fields.get("%D"),
+                Some(&vec!["someday".to_string()])
+            );
+        }
+    }
 }
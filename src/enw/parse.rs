@@ -1,9 +1,11 @@
-use crate::error::{ParseError, SourceSpan, ValueError};
-use crate::{Author, Citation, CitationFormat};
+use crate::error::{ParseError, ParseWarning, SourceSpan, ValueError, WarningKind};
+use crate::stream::line::Boundary;
+use crate::utils::{detect_identifier, partition_contributors, sort_serial_numbers};
+use crate::{Author, Citation, CitationFormat, ContributorRole, Identifier};
 use std::collections::HashMap;
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
-enum EnwTag {
+pub(super) enum EnwTag {
     ReferenceType,
     Author,
     SecondaryTitle,
@@ -42,7 +44,7 @@ enum EnwTag {
 }
 
 impl EnwTag {
-    fn from_code(code: char) -> Self {
+    pub(super) fn from_code(code: char) -> Self {
         match code {
             '0' => Self::ReferenceType,
             'A' => Self::Author,
@@ -82,7 +84,7 @@ impl EnwTag {
         }
     }
 
-    fn as_key(&self) -> String {
+    pub(super) fn as_key(&self) -> String {
         match self {
             Self::ReferenceType => "%0".to_string(),
             Self::Author => "%A".to_string(),
@@ -122,15 +124,18 @@ impl EnwTag {
         }
     }
 
-    fn is_contributor_tag(&self) -> bool {
-        matches!(
-            self,
-            Self::Author
-                | Self::Editor
-                | Self::TertiaryAuthor
-                | Self::SubsidiaryAuthor
-                | Self::TranslatedAuthor
-        )
+    /// The role of the contributors listed under this tag.
+    ///
+    /// `%H` holds translated forms of the author names rather than other
+    /// people, so it is kept as a plain field.
+    pub(super) fn contributor_role(&self) -> Option<ContributorRole> {
+        match self {
+            Self::Author => Some(ContributorRole::Author),
+            Self::Editor => Some(ContributorRole::Editor),
+            Self::TertiaryAuthor => Some(ContributorRole::SeriesEditor),
+            Self::SubsidiaryAuthor => Some(ContributorRole::Translator),
+            _ => None,
+        }
     }
 }
 
@@ -140,6 +145,7 @@ struct RawEnwRecord {
     authors: Vec<Author>,
     start_line: Option<usize>,
     record_span: Option<SourceSpan>,
+    warnings: Vec<ParseWarning>,
 }
 
 impl RawEnwRecord {
@@ -149,9 +155,27 @@ impl RawEnwRecord {
             authors: Vec::new(),
             start_line: None,
             record_span: None,
+            warnings: Vec::new(),
         }
     }
 
+    /// Add a warning located at the start of this record.
+    fn warn(&mut self, kind: WarningKind) {
+        let warning = ParseWarning::new(self.start_line, CitationFormat::Enw, kind);
+        self.warnings.push(match self.record_span.clone() {
+            Some(span) => warning.with_span(span),
+            None => warning,
+        });
+    }
+
+    /// The first non-empty value of `tag`, left in place.
+    fn first_non_empty(&self, tag: &EnwTag) -> Option<String> {
+        self.data
+            .get(tag)
+            .and_then(|values| values.iter().find(|value| !value.trim().is_empty()))
+            .cloned()
+    }
+
     fn add_data(&mut self, tag: EnwTag, value: String) {
         self.data.entry(tag).or_default().push(value);
     }
@@ -194,6 +218,16 @@ pub(crate) fn looks_like_enw(content: &str) -> bool {
 }
 
 pub(crate) fn parse_enw(content: &str) -> Result<Vec<Citation>, ParseError> {
+    Ok(parse_enw_with_warnings(content)?
+        .into_iter()
+        .map(|(citation, _)| citation)
+        .collect())
+}
+
+/// Parse ENW text, keeping the warnings for each citation.
+pub(crate) fn parse_enw_with_warnings(
+    content: &str,
+) -> Result<Vec<(Citation, Vec<ParseWarning>)>, ParseError> {
     let mut records = Vec::new();
     let mut current = RawEnwRecord::new();
     let mut line_number = 0usize;
@@ -226,11 +260,12 @@ pub(crate) fn parse_enw(content: &str) -> Result<Vec<Citation>, ParseError> {
                 current.extend_span(line_byte_end);
             }
 
-            current.add_data(tag.clone(), value.clone());
-            last_tag = Some(tag.clone());
-
-            if tag.is_contributor_tag() {
-                current.add_author(parse_author(&value));
+            if let Some(role) = tag.contributor_role() {
+                current.add_author(crate::utils::parse_endnote_name(&value, role));
+                last_tag = None;
+            } else {
+                current.add_data(tag.clone(), value);
+                last_tag = Some(tag);
             }
         } else if current.has_started() {
             current.extend_span(line_byte_end);
@@ -248,7 +283,10 @@ pub(crate) fn parse_enw(content: &str) -> Result<Vec<Citation>, ParseError> {
         records.push(current);
     }
 
-    records.into_iter().map(TryInto::try_into).collect()
+    records
+        .into_iter()
+        .map(into_citation_with_warnings)
+        .collect()
 }
 
 fn parse_enw_line(
@@ -302,108 +340,121 @@ fn parse_enw_line(
     Ok((EnwTag::from_code(tag_char), value))
 }
 
+/// Record boundaries for [`EnwParser::parse_reader`](crate::EnwParser): each
+/// `%0` line starts a record.
+pub(crate) fn enw_boundary(_previous: Option<&str>, line: &str) -> Boundary {
+    if is_enw_record_start(line) {
+        Boundary::Before
+    } else {
+        Boundary::Inside
+    }
+}
+
 fn is_enw_record_start(line: &str) -> bool {
     let trimmed = line.trim();
     trimmed.starts_with("%0 ") || trimmed == "%0"
 }
 
-fn parse_author(author_str: &str) -> Author {
-    let (family, given) = crate::utils::parse_author_name(author_str);
-    let (given_name, middle_name) = if given.is_empty() {
-        (None, None)
-    } else {
-        crate::utils::split_given_and_middle(&given)
-    };
-
-    Author {
-        name: family,
-        given_name,
-        middle_name,
-        affiliations: Vec::new(),
+/// Convert a record to a citation, returning the warnings for dates and DOIs
+/// that could not be read.
+fn into_citation_with_warnings(
+    mut raw: RawEnwRecord,
+) -> Result<(Citation, Vec<ParseWarning>), ParseError> {
+    let start_line = raw.start_line;
+    let record_span = raw.record_span.clone();
+
+    let mut citation_type = Vec::new();
+    for value in raw.remove_all(&EnwTag::ReferenceType) {
+        push_unique(&mut citation_type, value);
+    }
+    for value in raw.remove_all(&EnwTag::WorkType) {
+        push_unique(&mut citation_type, value);
     }
-}
-
-impl TryFrom<RawEnwRecord> for Citation {
-    type Error = ParseError;
-
-    fn try_from(mut raw: RawEnwRecord) -> Result<Self, Self::Error> {
-        let start_line = raw.start_line;
-        let record_span = raw.record_span.clone();
-
-        let mut citation_type = Vec::new();
-        for value in raw.remove_all(&EnwTag::ReferenceType) {
-            push_unique(&mut citation_type, value);
-        }
-        for value in raw.remove_all(&EnwTag::WorkType) {
-            push_unique(&mut citation_type, value);
-        }
 
-        let title = raw
-            .take_first_non_empty(&EnwTag::Title)
-            .or_else(|| raw.take_first_non_empty(&EnwTag::TranslatedTitle))
-            .unwrap_or_default();
-
-        let journal = extract_best_container(&mut raw);
-        let date = extract_date(&mut raw);
-        let volume = raw.take_first_non_empty(&EnwTag::Volume);
-        let issue = raw.take_first_non_empty(&EnwTag::Issue);
-        let pages = raw
-            .take_first_non_empty(&EnwTag::Pages)
-            .map(|pages| crate::utils::format_page_numbers(&pages));
-        let accession_number = raw.take_first_non_empty(&EnwTag::AccessionNumber);
-        let publisher = raw.take_first_non_empty(&EnwTag::Publisher);
-        let language = raw.take_first_non_empty(&EnwTag::Language);
-        let keywords = raw.remove_all(&EnwTag::Keywords);
-        let abstract_text = join_field_values(raw.remove_all(&EnwTag::Abstract));
-        let (doi, urls) = extract_doi_and_urls(&mut raw);
-        let issn = extract_isbn_issn(&mut raw);
-        raw.remove_all(&EnwTag::Author);
-
-        if title.is_empty() && raw.authors.is_empty() {
-            let err = ParseError::new(
-                start_line,
-                None,
-                CitationFormat::Enw,
-                ValueError::MissingValue {
-                    field: "title or author",
-                    key: "title/author",
-                },
-            );
-            return Err(if let Some(span) = record_span {
-                err.with_span(span)
-            } else {
-                err
-            });
-        }
+    let title = raw
+        .take_first_non_empty(&EnwTag::Title)
+        .or_else(|| raw.take_first_non_empty(&EnwTag::TranslatedTitle))
+        .unwrap_or_default();
 
-        Ok(Citation {
-            citation_type,
-            title,
-            authors: raw.authors,
-            journal,
-            journal_abbr: None,
+    let journal = extract_best_container(&mut raw);
+    let date = extract_date(&mut raw);
+    let dates = extract_access_date(&mut raw)
+        .map(|date| crate::TypedDate {
+            kind: crate::DateKind::Accessed,
             date,
-            volume,
-            issue,
-            pages,
-            issn,
-            doi,
-            accession_number,
-            pmid: None,
-            pmc_id: None,
-            abstract_text,
-            keywords,
-            urls,
-            language,
-            mesh_terms: Vec::new(),
-            publisher,
-            extra_fields: raw
-                .data
-                .drain()
-                .map(|(tag, values)| (tag.as_key(), values))
-                .collect(),
         })
+        .into_iter()
+        .collect();
+    let volume = raw.take_first_non_empty(&EnwTag::Volume);
+    let issue = raw.take_first_non_empty(&EnwTag::Issue);
+    let pages = raw
+        .take_first_non_empty(&EnwTag::Pages)
+        .map(|pages| crate::utils::format_page_numbers(&pages));
+    let accession_number = raw.take_first_non_empty(&EnwTag::AccessionNumber);
+    let publisher = raw.take_first_non_empty(&EnwTag::Publisher);
+    let language = raw.take_first_non_empty(&EnwTag::Language);
+    let keywords = raw.remove_all(&EnwTag::Keywords);
+    let abstract_text = join_field_values(raw.remove_all(&EnwTag::Abstract));
+    let (doi, urls) = extract_doi_and_urls(&mut raw);
+    let mut identifiers: Vec<Identifier> = accession_number
+        .as_deref()
+        .and_then(detect_identifier)
+        .into_iter()
+        .collect();
+    let mut issn = Vec::new();
+    for value in raw.remove_all(&EnwTag::IsbnIssn) {
+        sort_serial_numbers(&value, &mut issn, &mut identifiers);
+    }
+
+    if title.is_empty() && raw.authors.is_empty() {
+        let err = ParseError::new(
+            start_line,
+            None,
+            CitationFormat::Enw,
+            ValueError::MissingValue {
+                field: "title or author",
+                key: "title/author",
+            },
+        );
+        return Err(if let Some(span) = record_span {
+            err.with_span(span)
+        } else {
+            err
+        });
     }
+
+    let (authors, contributors) = partition_contributors(std::mem::take(&mut raw.authors));
+    let citation = Citation {
+        citation_type,
+        title,
+        authors,
+        contributors,
+        journal,
+        journal_abbr: None,
+        date,
+        dates,
+        volume,
+        issue,
+        pages,
+        issn,
+        doi,
+        identifiers,
+        accession_number,
+        pmid: None,
+        pmc_id: None,
+        abstract_text,
+        keywords,
+        urls,
+        language,
+        mesh_terms: Vec::new(),
+        publisher,
+        extra_fields: raw
+            .data
+            .drain()
+            .map(|(tag, values)| (tag.as_key(), values))
+            .collect(),
+    };
+    Ok((citation, raw.warnings))
 }
 
 fn push_unique(values: &mut Vec<String>, value: String) {
@@ -419,32 +470,48 @@ fn extract_best_container(raw: &mut RawEnwRecord) -> Option<String> {
         .or_else(|| raw.take_first_non_empty(&EnwTag::TertiaryTitle))
 }
 
+/// Takes the `%8` date, or failing that the `%D` year, when it parses;
+/// otherwise the values stay in `extra_fields` and a warning is added.
 fn extract_date(raw: &mut RawEnwRecord) -> Option<crate::Date> {
-    if let Some(date_text) = raw
-        .data
-        .get(&EnwTag::Date)
-        .and_then(|values| values.iter().find(|value| !value.trim().is_empty()))
-        .cloned()
-        && let Some(date) = crate::utils::parse_enw_date(&date_text)
-    {
+    let date_text = raw.first_non_empty(&EnwTag::Date);
+    if let Some(date) = date_text.as_deref().and_then(crate::utils::parse_enw_date) {
         let _ = raw.take_first_non_empty(&EnwTag::Date);
         return Some(date);
     }
 
-    if let Some(date_text) = raw
-        .data
-        .get(&EnwTag::Year)
-        .and_then(|values| values.iter().find(|value| !value.trim().is_empty()))
-        .cloned()
-        && let Some(date) = crate::utils::parse_year_only(&date_text)
-    {
+    let year_text = raw.first_non_empty(&EnwTag::Year);
+    if let Some(date) = year_text.as_deref().and_then(crate::utils::parse_year_only) {
         let _ = raw.take_first_non_empty(&EnwTag::Year);
         return Some(date);
     }
 
+    if let Some((tag, value)) = date_text
+        .map(|value| (EnwTag::Date, value))
+        .or_else(|| year_text.map(|value| (EnwTag::Year, value)))
+    {
+        raw.warn(WarningKind::UnparseableDate {
+            key: tag.as_key(),
+            value,
+        });
+    }
     None
 }
 
+/// Takes the `%[` access date when it parses; otherwise it stays in
+/// `extra_fields`.
+fn extract_access_date(raw: &mut RawEnwRecord) -> Option<crate::Date> {
+    let value = raw.first_non_empty(&EnwTag::AccessDate)?;
+    let Some(date) = crate::utils::parse_enw_date(&value) else {
+        raw.warn(WarningKind::UnparseableDate {
+            key: EnwTag::AccessDate.as_key(),
+            value,
+        });
+        return None;
+    };
+    let _ = raw.take_first_non_empty(&EnwTag::AccessDate);
+    Some(date)
+}
+
 fn extract_doi_and_urls(raw: &mut RawEnwRecord) -> (Option<String>, Vec<String>) {
     let mut doi = None;
 
@@ -459,6 +526,14 @@ fn extract_doi_and_urls(raw: &mut RawEnwRecord) -> (Option<String>, Vec<String>)
             leftovers.push(value);
         }
     }
+    if doi.is_none()
+        && let Some(value) = leftovers.iter().find(|value| !value.trim().is_empty())
+    {
+        raw.warn(WarningKind::InvalidDoi {
+            key: EnwTag::ElectronicResourceNumber.as_key(),
+            value: value.clone(),
+        });
+    }
     if !leftovers.is_empty() {
         raw.data.insert(EnwTag::ElectronicResourceNumber, leftovers);
     }
@@ -476,29 +551,6 @@ fn extract_doi_and_urls(raw: &mut RawEnwRecord) -> (Option<String>, Vec<String>)
     (doi, urls)
 }
 
-fn extract_isbn_issn(raw: &mut RawEnwRecord) -> Vec<String> {
-    let mut identifiers = Vec::new();
-    for value in raw.remove_all(&EnwTag::IsbnIssn) {
-        let trimmed = value.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-
-        if looks_like_isbn(trimmed) {
-            identifiers.push(trimmed.to_string());
-            continue;
-        }
-
-        let split = crate::utils::split_issns(trimmed);
-        if split.is_empty() {
-            identifiers.push(trimmed.to_string());
-        } else {
-            identifiers.extend(split);
-        }
-    }
-    identifiers
-}
-
 fn join_field_values(values: Vec<String>) -> Option<String> {
     let joined = values
         .into_iter()
@@ -509,19 +561,3 @@ fn join_field_values(values: Vec<String>) -> Option<String> {
 
     (!joined.is_empty()).then_some(joined)
 }
-
-fn looks_like_isbn(value: &str) -> bool {
-    let compact: String = value
-        .chars()
-        .filter(|c| !c.is_whitespace() && *c != '-')
-        .collect();
-
-    match compact.len() {
-        10 => compact
-            .chars()
-            .enumerate()
-            .all(|(idx, c)| c.is_ascii_digit() || (idx == 9 && matches!(c, 'X' | 'x'))),
-        13 => compact.chars().all(|c| c.is_ascii_digit()),
-        _ => false,
-    }
-}
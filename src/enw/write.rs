@@ -0,0 +1,204 @@
+//! EndNote Tagged (`.enw`) writing implementation.
+
+use super::parse::EnwTag;
+use crate::utils::{endnote_ref_type, format_endnote_name, format_ris_date};
+use crate::{Citation, ContributorRole, DateKind, Scheme};
+
+/// Serialize citations into EndNote Tagged text, one blank-line separated record each.
+pub(crate) fn write_enw(citations: &[Citation]) -> String {
+    let mut out = String::new();
+    for citation in citations {
+        write_record(&mut out, citation);
+        out.push('\n');
+    }
+    out
+}
+
+fn write_record(out: &mut String, citation: &Citation) {
+    let mut types = citation.citation_type.iter();
+    let ref_type = types
+        .next()
+        .map(|t| endnote_ref_type(t).map_or(t.as_str(), |(name, _)| name))
+        .unwrap_or("Generic");
+    push_line(out, &EnwTag::ReferenceType, ref_type);
+    for work_type in types {
+        push_line(out, &EnwTag::WorkType, work_type);
+    }
+
+    push_line(out, &EnwTag::Title, &citation.title);
+    write_contributors(out, citation);
+    push_opt(out, &EnwTag::Journal, citation.journal.as_deref());
+    if let Some(date) = &citation.date {
+        // `%D` only carries a year; fuller dates go to `%8`, which the parser
+        // prefers. `%8` is also used when `%D` is already kept in extra fields.
+        if date.month.is_some()
+            || date.day.is_some()
+            || date.season.is_some()
+            || citation.extra_fields.contains_key("%D")
+        {
+            push_line(out, &EnwTag::Date, &format_ris_date(date));
+        } else {
+            push_line(out, &EnwTag::Year, &date.year.to_string());
+        }
+    }
+    if let Some(date) = citation.date_of(DateKind::Accessed) {
+        push_line(out, &EnwTag::AccessDate, &format_ris_date(date));
+    }
+    push_opt(out, &EnwTag::Volume, citation.volume.as_deref());
+    push_opt(out, &EnwTag::Issue, citation.issue.as_deref());
+    push_opt(out, &EnwTag::Pages, citation.pages.as_deref());
+    push_opt(
+        out,
+        &EnwTag::AccessionNumber,
+        citation.accession_number.as_deref(),
+    );
+    push_opt(out, &EnwTag::Publisher, citation.publisher.as_deref());
+    push_opt(out, &EnwTag::Language, citation.language.as_deref());
+    for keyword in &citation.keywords {
+        push_line(out, &EnwTag::Keywords, keyword);
+    }
+    if let Some(abstract_text) = citation.abstract_text.as_deref() {
+        for paragraph in abstract_text.split("\n\n") {
+            push_line(out, &EnwTag::Abstract, paragraph);
+        }
+    }
+    push_opt(
+        out,
+        &EnwTag::ElectronicResourceNumber,
+        citation.doi.as_deref(),
+    );
+    for url in &citation.urls {
+        push_line(out, &EnwTag::Url, url);
+    }
+    for identifier in &citation.issn {
+        push_line(out, &EnwTag::IsbnIssn, identifier);
+    }
+    for isbn in citation.identifiers_of(Scheme::Isbn) {
+        push_line(out, &EnwTag::IsbnIssn, isbn);
+    }
+
+    let mut extra_keys: Vec<(&String, EnwTag)> = citation
+        .extra_fields
+        .keys()
+        .filter_map(|key| enw_tag_for_key(key).map(|tag| (key, tag)))
+        .filter(|(_, tag)| tag.contributor_role().is_none())
+        .collect();
+    extra_keys.sort_by(|a, b| a.0.cmp(b.0));
+    for (key, tag) in extra_keys {
+        for value in &citation.extra_fields[key] {
+            push_line(out, &tag, value);
+        }
+    }
+}
+
+/// Write authors as `%A`, then other contributors under the tag for their role.
+///
+/// Investigators have no EndNote Tagged tag and are not written.
+fn write_contributors(out: &mut String, citation: &Citation) {
+    for author in &citation.authors {
+        push_line(out, &EnwTag::Author, &format_endnote_name(author));
+    }
+    for contributor in &citation.contributors {
+        if let Some(tag) = contributor_tag(contributor.role) {
+            push_line(out, &tag, &format_endnote_name(contributor));
+        }
+    }
+}
+
+/// The tag a contributor with `role` is written under.
+fn contributor_tag(role: ContributorRole) -> Option<EnwTag> {
+    match role {
+        ContributorRole::Author | ContributorRole::CorporateAuthor => Some(EnwTag::Author),
+        ContributorRole::Editor => Some(EnwTag::Editor),
+        ContributorRole::SeriesEditor => Some(EnwTag::TertiaryAuthor),
+        ContributorRole::Translator => Some(EnwTag::SubsidiaryAuthor),
+        ContributorRole::Investigator => None,
+    }
+}
+
+/// Resolve an `extra_fields` key such as `%C` to its tag.
+fn enw_tag_for_key(key: &str) -> Option<EnwTag> {
+    let mut chars = key.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some('%'), Some(code), None) => match EnwTag::from_code(code) {
+            EnwTag::ReferenceType | EnwTag::Author => None,
+            tag => Some(tag),
+        },
+        _ => None,
+    }
+}
+
+/// Append a `%X value` line, skipping blank values.
+///
+/// Embedded line breaks become continuation lines, which the parser joins
+/// back with a newline. A continuation that would start with `%` is folded
+/// into the previous line instead so it is not read as a new tag.
+fn push_line(out: &mut String, tag: &EnwTag, value: &str) {
+    let value = value.trim();
+    if value.is_empty() {
+        return;
+    }
+    out.push_str(&tag.as_key());
+    out.push(' ');
+    let mut lines = value.lines().map(str::trim).filter(|line| !line.is_empty());
+    if let Some(first) = lines.next() {
+        out.push_str(first);
+    }
+    for line in lines {
+        out.push(if line.starts_with('%') { ' ' } else { '\n' });
+        out.push_str(line);
+    }
+    out.push('\n');
+}
+
+fn push_opt(out: &mut String, tag: &EnwTag, value: Option<&str>) {
+    if let Some(value) = value {
+        push_line(out, tag, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Author;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_write_record() {
+        let citation = Citation {
+            citation_type: vec!["JOUR".to_string(), "article".to_string()],
+            title: "Example".to_string(),
+            authors: vec![Author {
+                name: "Smith".to_string(),
+                given_name: Some("John".to_string()),
+                middle_name: None,
+                affiliations: Vec::new(),
+                role: ContributorRole::Author,
+                orcid: None,
+                is_organization: false,
+            }],
+            date: Some(crate::Date {
+                year: 2024,
+                month: None,
+                day: None,
+                season: None,
+                end: None,
+            }),
+            abstract_text: Some("Line one\nline two\n\nSecond".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            write_enw(&[citation]),
+            "%0 JOUR\n%9 article\n%T Example\n%A Smith, John\n%D 2024\n%X Line one\nline two\n%X Second\n\n"
+        );
+    }
+
+    #[test]
+    fn test_enw_tag_for_key() {
+        assert_eq!(enw_tag_for_key("%C"), Some(EnwTag::PlacePublished));
+        assert_eq!(enw_tag_for_key("%A"), None);
+        assert_eq!(enw_tag_for_key("note"), None);
+        assert_eq!(enw_tag_for_key("%"), None);
+    }
+}
@@ -155,6 +155,94 @@ pub enum ValueError {
         second_row: Option<usize>,
         second_col: Option<usize>,
     },
+
+    #[error("Failed to read input: {0}")]
+    Io(#[source] std::io::Error),
+
+    #[error("Input is not valid {encoding}")]
+    Encoding { encoding: &'static str },
+}
+
+/// Data that was dropped or could not be interpreted while a record was
+/// still parsed successfully.
+///
+/// Warnings are returned in [`crate::ParseReport::warnings`] by
+/// [`crate::CitationParser::parse_with_options`].
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("Warning in {format} format{}: {kind}",
+    match line {
+        Some(l) => format!(" at line {}", l),
+        None => String::new(),
+    }
+)]
+pub struct ParseWarning {
+    /// Line number the warning refers to (1-based, None if not available)
+    pub line: Option<usize>,
+    /// Byte-offset span into the source text, for rich diagnostic rendering.
+    pub span: Option<SourceSpan>,
+    /// The citation format being parsed
+    pub format: CitationFormat,
+    /// What was dropped or coerced
+    pub kind: WarningKind,
+}
+
+impl ParseWarning {
+    /// Create a new ParseWarning.
+    pub fn new(line: Option<usize>, format: CitationFormat, kind: WarningKind) -> Self {
+        Self {
+            line,
+            span: None,
+            format,
+            kind,
+        }
+    }
+
+    /// Create a ParseWarning with line information.
+    pub fn at_line(line: usize, format: CitationFormat, kind: WarningKind) -> Self {
+        Self::new(Some(line), format, kind)
+    }
+
+    /// Attach a byte-offset span to this warning, returning `self` (builder style).
+    pub fn with_span(mut self, span: SourceSpan) -> Self {
+        self.span = Some(span);
+        self
+    }
+}
+
+/// The kinds of data loss reported by [`ParseWarning`].
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum WarningKind {
+    /// A line that could not be read as a field and was dropped.
+    #[error("Ignored line: \"{line}\"")]
+    IgnoredLine { line: String },
+
+    /// A tag or column with no `Citation` field; its values are kept in
+    /// `extra_fields`.
+    #[error("Unknown tag {tag} kept in extra_fields")]
+    UnknownTag { tag: String },
+
+    /// A date value that could not be parsed, leaving `date` empty.
+    #[error("Unparseable date in {key}: \"{value}\"")]
+    UnparseableDate { key: String, value: String },
+
+    /// A DOI value that could not be normalized and was dropped.
+    #[error("Invalid DOI in {key}: \"{value}\"")]
+    InvalidDoi { key: String, value: String },
+
+    /// An ISSN or ISBN whose check digit is wrong. The value is kept as
+    /// written in `issn`.
+    #[error("Invalid check digit in {key}: \"{value}\"")]
+    InvalidCheckDigit { key: String, value: String },
+
+    /// A repeated value for a single-valued field that was dropped in favour
+    /// of another value.
+    #[error("Dropped duplicate value for {key}: \"{value}\"")]
+    DroppedValue { key: String, value: String },
+
+    /// Repeated values for a single-valued field that were joined with
+    /// `" AND "`.
+    #[error("Joined {count} values for {key}")]
+    JoinedValues { key: String, count: usize },
 }
 
 // Conversion implementations for external error types
@@ -277,10 +365,17 @@ mod tests {
     fn test_citation_format_display() {
         assert_eq!(format!("{}", CitationFormat::Ris), "RIS");
         assert_eq!(format!("{}", CitationFormat::PubMed), "PubMed");
+        assert_eq!(format!("{}", CitationFormat::PubMedXml), "PubMed XML");
         assert_eq!(format!("{}", CitationFormat::EndNoteXml), "EndNote XML");
         assert_eq!(format!("{}", CitationFormat::IctrpXml), "ICTRP XML");
         assert_eq!(format!("{}", CitationFormat::Enw), "EndNote Tagged");
         assert_eq!(format!("{}", CitationFormat::Bib), "BibTeX / BibLaTeX");
+        assert_eq!(format!("{}", CitationFormat::CslJson), "CSL-JSON");
+        assert_eq!(
+            format!("{}", CitationFormat::WebOfScience),
+            "Web of Science"
+        );
+        assert_eq!(format!("{}", CitationFormat::Ovid), "Ovid");
         assert_eq!(format!("{}", CitationFormat::Csv), "CSV");
         assert_eq!(format!("{}", CitationFormat::IctrpCsv), "ICTRP CSV");
     }
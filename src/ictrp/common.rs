@@ -1,5 +1,7 @@
-use crate::Date;
+use crate::utils::{detect_identifier, parse_month_name};
+use crate::{Date, DateKind, Identifier, TypedDate};
 
+#[cfg(feature = "xml")]
 pub(crate) const ICTRP_URL_FIELD_KEYS: &[&str] = &[
     "web address",
     "results url link",
@@ -9,6 +11,7 @@ pub(crate) const ICTRP_URL_FIELD_KEYS: &[&str] = &[
     "results_url_protocol",
 ];
 
+#[cfg(feature = "xml")]
 pub(crate) fn is_ictrp_url_field(key: &str) -> bool {
     ICTRP_URL_FIELD_KEYS.contains(&key)
 }
@@ -23,21 +26,26 @@ pub(crate) fn dedupe_urls(urls: Vec<String>) -> Vec<String> {
     unique
 }
 
-pub(crate) fn parse_ictrp_compact_date(value: &str) -> Option<Date> {
-    let trimmed = value.trim();
-    if trimmed.len() != 8 {
-        return None;
+/// Typed identifiers for a trial: its own registry number followed by any
+/// recognised secondary IDs. Secondary ID fields separate values with `;`.
+pub(crate) fn trial_identifiers<'a>(
+    trial_id: &str,
+    secondary_ids: impl IntoIterator<Item = &'a String>,
+) -> Vec<Identifier> {
+    let mut identifiers: Vec<Identifier> = Vec::new();
+    let secondary = secondary_ids
+        .into_iter()
+        .flat_map(|value| value.split(';'))
+        .map(str::trim);
+    for identifier in std::iter::once(trial_id)
+        .chain(secondary)
+        .filter_map(detect_identifier)
+    {
+        if !identifiers.contains(&identifier) {
+            identifiers.push(identifier);
+        }
     }
-
-    let year = trimmed[0..4].parse().ok()?;
-    let month = trimmed[4..6].parse().ok()?;
-    let day = trimmed[6..8].parse().ok()?;
-
-    Some(Date {
-        year,
-        month: Some(month),
-        day: Some(day),
-    })
+    identifiers
 }
 
 pub(crate) fn parse_ictrp_standard_date(value: &str) -> Option<Date> {
@@ -69,6 +77,8 @@ fn parse_ictrp_slash_date(value: &str) -> Option<Date> {
         year,
         month: Some(month),
         day: Some(day),
+        season: None,
+        end: None,
     })
 }
 
@@ -83,23 +93,59 @@ fn parse_ictrp_hyphen_date(value: &str) -> Option<Date> {
         year: parts[0].parse().ok()?,
         month: Some(parts[1].parse().ok()?),
         day: Some(parts[2].parse().ok()?),
+        season: None,
+        end: None,
+    })
+}
+
+/// Parses `Last_Refreshed_on` dates such as `1 December 2025`.
+pub(crate) fn parse_ictrp_refreshed_date(value: &str) -> Option<Date> {
+    let parts = value.split_whitespace().collect::<Vec<_>>();
+
+    if parts.len() != 3 {
+        return None;
+    }
+
+    Some(Date {
+        year: parts[2].parse().ok()?,
+        month: Some(parse_month_name(parts[1])?),
+        day: Some(parts[0].parse().ok().filter(|day| (1..=31).contains(day))?),
+        season: None,
+        end: None,
     })
 }
 
+/// Typed dates of a trial: its registration date and, when known, the date
+/// the registry record was last refreshed.
+pub(crate) fn trial_dates(registered: Option<&Date>, refreshed: Option<Date>) -> Vec<TypedDate> {
+    let registered = registered.cloned().map(|date| TypedDate {
+        kind: DateKind::Registered,
+        date,
+    });
+    let refreshed = refreshed.map(|date| TypedDate {
+        kind: DateKind::LastUpdated,
+        date,
+    });
+    registered.into_iter().chain(refreshed).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_ictrp_compact_date() {
+    fn test_parse_ictrp_refreshed_date() {
         assert_eq!(
-            parse_ictrp_compact_date("20260501"),
+            parse_ictrp_refreshed_date("1 December 2025"),
             Some(Date {
-                year: 2026,
-                month: Some(5),
+                year: 2025,
+                month: Some(12),
                 day: Some(1),
+                season: None,
+                end: None,
             })
         );
+        assert_eq!(parse_ictrp_refreshed_date("December 2025"), None);
     }
 
     #[test]
@@ -110,6 +156,8 @@ mod tests {
                 year: 2026,
                 month: Some(5),
                 day: Some(1),
+                season: None,
+                end: None,
             })
         );
     }
@@ -122,6 +170,8 @@ mod tests {
                 year: 2026,
                 month: Some(5),
                 day: Some(1),
+                season: None,
+                end: None,
             })
         );
     }
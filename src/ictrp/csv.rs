@@ -1,7 +1,11 @@
-use crate::csv::{CsvConfig, RawCsvData, csv_parse_with_format};
-use crate::error::{ParseError, SourceSpan, ValueError, fields};
-use crate::ictrp::{dedupe_urls, parse_ictrp_compact_date, parse_ictrp_standard_date};
-use crate::{Citation, CitationFormat, CitationParser};
+use crate::csv::{CsvConfig, CsvRecords, RawCsvData, csv_parse_with_format};
+use crate::error::{ParseError, ParseWarning, SourceSpan, ValueError, WarningKind, fields};
+use crate::ictrp::{
+    dedupe_urls, parse_ictrp_refreshed_date, parse_ictrp_standard_date, trial_dates,
+    trial_identifiers,
+};
+use crate::utils::parse_compact_date;
+use crate::{Citation, CitationFormat, CitationParser, ParseOptions, ParseReport, collect_report};
 use csv::ReaderBuilder;
 use std::collections::HashMap;
 
@@ -57,6 +61,44 @@ impl CitationParser for IctrpCsvParser {
             .map(RawCsvData::into_ictrp_citation)
             .collect()
     }
+
+    /// Reads one CSV row at a time.
+    fn parse_reader<'r, R>(
+        &self,
+        reader: R,
+    ) -> Box<dyn Iterator<Item = Result<Citation, ParseError>> + 'r>
+    where
+        R: std::io::Read + 'r,
+    {
+        match CsvRecords::new(reader, Self::config(), CitationFormat::IctrpCsv) {
+            Ok(records) => {
+                Box::new(records.map(|raw| raw.and_then(RawCsvData::into_ictrp_citation)))
+            }
+            Err(err) => Box::new(std::iter::once(Err(err))),
+        }
+    }
+
+    /// Reports registration and refresh dates that cannot be parsed as
+    /// warnings.
+    fn parse_with_options(
+        &self,
+        input: &str,
+        options: &ParseOptions,
+    ) -> Result<ParseReport, ParseError> {
+        if input.trim().is_empty() {
+            return Ok(ParseReport::default());
+        }
+
+        let records =
+            match CsvRecords::new(input.as_bytes(), Self::config(), CitationFormat::IctrpCsv) {
+                Ok(records) => records,
+                Err(err) => return collect_report(std::iter::once(Err(err)), options),
+            };
+        collect_report(
+            records.map(|raw| raw.and_then(RawCsvData::into_ictrp_citation_with_warnings)),
+            options,
+        )
+    }
 }
 
 pub(crate) fn looks_like_ictrp_csv(content: &str) -> bool {
@@ -88,7 +130,16 @@ pub(crate) fn looks_like_ictrp_csv(content: &str) -> bool {
 }
 
 impl RawCsvData {
-    pub(crate) fn into_ictrp_citation(mut self) -> Result<Citation, ParseError> {
+    pub(crate) fn into_ictrp_citation(self) -> Result<Citation, ParseError> {
+        self.into_ictrp_citation_with_warnings()
+            .map(|(citation, _)| citation)
+    }
+
+    /// Convert an ICTRP row to a citation, returning the warnings for dates
+    /// that could not be read.
+    pub(crate) fn into_ictrp_citation_with_warnings(
+        mut self,
+    ) -> Result<(Citation, Vec<ParseWarning>), ParseError> {
         let accession_number = self
             .fields
             .remove("accession_number")
@@ -129,15 +180,49 @@ impl RawCsvData {
                 .with_span(SourceSpan::new(self.byte_offset, self.byte_offset))
             })?;
 
-        let date = self
+        let compact = self
             .fields
             .remove("date_registration_compact")
-            .and_then(|value| parse_ictrp_compact_date(&value))
-            .or_else(|| {
-                self.fields
+            .filter(|value| !value.trim().is_empty());
+        let date = match compact.as_deref().and_then(parse_compact_date) {
+            Some(date) => Some(date),
+            None => {
+                let standard = self
+                    .fields
                     .remove("date_registration")
-                    .and_then(|value| parse_ictrp_standard_date(&value))
+                    .filter(|value| !value.trim().is_empty());
+                let date = standard.as_deref().and_then(parse_ictrp_standard_date);
+                if date.is_none()
+                    && let Some((key, value)) = standard
+                        .map(|value| ("Date registration", value))
+                        .or_else(|| compact.map(|value| ("Date registration3", value)))
+                {
+                    self.warn_ictrp(WarningKind::UnparseableDate {
+                        key: key.to_string(),
+                        value,
+                    });
+                }
+                date
+            }
+        };
+
+        let refreshed = self
+            .fields
+            .get("Last Refreshed on")
+            .and_then(|value| parse_ictrp_refreshed_date(value));
+        if refreshed.is_some() {
+            self.fields.remove("Last Refreshed on");
+        } else if let Some(value) = self
+            .get_field("Last Refreshed on")
+            .filter(|value| !value.trim().is_empty())
+            .cloned()
+        {
+            self.warn_ictrp(WarningKind::UnparseableDate {
+                key: "Last Refreshed on".to_string(),
+                value,
             });
+        }
+        let dates = trial_dates(date.as_ref(), refreshed);
 
         let publisher = self.fields.remove("publisher");
         let mut citation_type = vec!["Clinical Trial".to_string()];
@@ -156,18 +241,24 @@ impl RawCsvData {
             extra_fields.insert(key, vec![value]);
         }
 
-        Ok(Citation {
+        let citation = Citation {
             citation_type,
             title,
             authors: Vec::new(),
+            contributors: Vec::new(),
             journal: None,
             journal_abbr: None,
             date,
+            dates,
             volume: None,
             issue: None,
             pages: None,
             issn: Vec::new(),
             doi: None,
+            identifiers: trial_identifiers(
+                &accession_number,
+                extra_fields.get("Secondary ID").into_iter().flatten(),
+            ),
             accession_number: Some(accession_number),
             pmid: None,
             pmc_id: None,
@@ -178,7 +269,16 @@ impl RawCsvData {
             mesh_terms: Vec::new(),
             publisher,
             extra_fields,
-        })
+        };
+        Ok((citation, self.warnings))
+    }
+
+    /// Add a warning located at the start of this row.
+    fn warn_ictrp(&mut self, kind: WarningKind) {
+        self.warnings.push(
+            ParseWarning::at_line(self.line_number, CitationFormat::IctrpCsv, kind)
+                .with_span(SourceSpan::new(self.byte_offset, self.byte_offset)),
+        );
     }
 }
 
@@ -186,7 +286,7 @@ impl RawCsvData {
 #[allow(deprecated)]
 mod tests {
     use super::*;
-    use crate::Date;
+    use crate::{Date, Scheme};
 
     #[test]
     fn test_looks_like_ictrp_csv() {
@@ -207,6 +307,8 @@ mod tests {
 
         let citation = IctrpCsvParser::new().parse(input).unwrap().remove(0);
         assert_eq!(citation.accession_number.as_deref(), Some("NCT00000001"));
+        assert_eq!(citation.identifier(Scheme::Nct), Some("NCT00000001"));
+        assert_eq!(citation.identifiers.len(), 1);
         assert_eq!(citation.title, "Scientific title");
         assert_eq!(citation.publisher.as_deref(), Some("Sponsor"));
         assert_eq!(
@@ -218,7 +320,9 @@ mod tests {
             Some(Date {
                 year: 2026,
                 month: Some(5),
-                day: Some(1)
+                day: Some(1),
+                season: None,
+                end: None
             })
         );
         assert_eq!(
@@ -282,8 +386,39 @@ mod tests {
             Some(Date {
                 year: 2026,
                 month: Some(5),
-                day: Some(1)
+                day: Some(1),
+                season: None,
+                end: None
             })
         );
     }
+
+    #[test]
+    fn test_parse_with_options_reports_warnings() {
+        use crate::{OnError, WarningKind};
+
+        let input = concat!(
+            "TrialID,Public title,Scientific title,Date registration,Source Register\n",
+            "NCT00000006,Public,First,01/05/2026,ClinicalTrials.gov\n",
+            "NCT00000007,Public,Second,someday,ClinicalTrials.gov\n"
+        );
+        for on_error in [OnError::Fail, OnError::Collect] {
+            let options = ParseOptions::new().with_on_error(on_error);
+            let report = IctrpCsvParser::new()
+                .parse_with_options(input, &options)
+                .unwrap();
+
+            assert_eq!(report.citations.len(), 2);
+            assert_eq!(report.warnings.len(), 1);
+            assert_eq!(report.warnings[0].format, CitationFormat::IctrpCsv);
+            assert_eq!(report.warnings[0].line, Some(3));
+            assert_eq!(
+                report.warnings[0].kind,
+                WarningKind::UnparseableDate {
+                    key: "Date registration".to_string(),
+                    value: "someday".to_string()
+                }
+            );
+        }
+    }
 }
@@ -4,8 +4,11 @@ pub(crate) mod csv;
 #[cfg(feature = "xml")]
 pub(crate) mod xml;
 
+#[cfg(feature = "xml")]
+pub(crate) use common::is_ictrp_url_field;
 pub(crate) use common::{
-    dedupe_urls, is_ictrp_url_field, parse_ictrp_compact_date, parse_ictrp_standard_date,
+    dedupe_urls, parse_ictrp_refreshed_date, parse_ictrp_standard_date, trial_dates,
+    trial_identifiers,
 };
 #[cfg(feature = "csv")]
 pub(crate) use csv::looks_like_ictrp_csv;
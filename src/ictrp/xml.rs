@@ -4,11 +4,17 @@
 //! This parser keeps the normalized ICTRP citation mapping aligned with the
 //! existing ICTRP CSV parser while preserving raw XML fields in `extra_fields`.
 
-use crate::error::{ParseError, SourceSpan, ValueError, fields};
+use crate::error::{ParseError, ParseWarning, SourceSpan, ValueError, WarningKind, fields};
 use crate::ictrp::{
-    dedupe_urls, is_ictrp_url_field, parse_ictrp_compact_date, parse_ictrp_standard_date,
+    dedupe_urls, is_ictrp_url_field, parse_ictrp_refreshed_date, parse_ictrp_standard_date,
+    trial_dates, trial_identifiers,
+};
+use crate::stream::chunk::parse_chunks;
+use crate::stream::element::ElementChunks;
+use crate::utils::parse_compact_date;
+use crate::{
+    Citation, CitationFormat, CitationParser, OnError, ParseOptions, ParseReport, collect_report,
 };
-use crate::{Citation, CitationFormat, CitationParser};
 use quick_xml::Reader;
 use quick_xml::escape::unescape;
 use quick_xml::events::Event;
@@ -43,6 +49,45 @@ impl CitationParser for IctrpXmlParser {
 
         parse_ictrp_xml(input)
     }
+
+    /// Reads one `<Trial>` element at a time. Unlike [`IctrpXmlParser::parse`],
+    /// the input is not checked for the ICTRP root element first.
+    fn parse_reader<'r, R>(
+        &self,
+        reader: R,
+    ) -> Box<dyn Iterator<Item = Result<Citation, ParseError>> + 'r>
+    where
+        R: std::io::Read + 'r,
+    {
+        let chunks = ElementChunks::new(reader, CitationFormat::IctrpXml, "Trial");
+        Box::new(parse_chunks(chunks, parse_ictrp_xml))
+    }
+
+    /// Reports registration and refresh dates that cannot be parsed as
+    /// warnings. With [`OnError::Fail`] the input is checked like
+    /// [`IctrpXmlParser::parse`]; otherwise trials are read like
+    /// [`IctrpXmlParser::parse_reader`].
+    fn parse_with_options(
+        &self,
+        input: &str,
+        options: &ParseOptions,
+    ) -> Result<ParseReport, ParseError> {
+        if options.on_error == OnError::Fail {
+            if !input.trim().is_empty() && !looks_like_ictrp_xml(input) {
+                return Err(ParseError::without_position(
+                    CitationFormat::IctrpXml,
+                    ValueError::Syntax(
+                        "Input does not appear to be an ICTRP XML export".to_string(),
+                    ),
+                ));
+            }
+            let records = parse_ictrp_xml_with_warnings(input)?;
+            return collect_report(records.into_iter().map(Ok), options);
+        }
+
+        let chunks = ElementChunks::new(input.as_bytes(), CitationFormat::IctrpXml, "Trial");
+        collect_report(parse_chunks(chunks, parse_ictrp_xml_with_warnings), options)
+    }
 }
 
 pub(crate) fn looks_like_ictrp_xml(content: &str) -> bool {
@@ -54,6 +99,15 @@ pub(crate) fn looks_like_ictrp_xml(content: &str) -> bool {
 }
 
 fn parse_ictrp_xml(content: &str) -> Result<Vec<Citation>, ParseError> {
+    Ok(parse_ictrp_xml_with_warnings(content)?
+        .into_iter()
+        .map(|(citation, _)| citation)
+        .collect())
+}
+
+fn parse_ictrp_xml_with_warnings(
+    content: &str,
+) -> Result<Vec<(Citation, Vec<ParseWarning>)>, ParseError> {
     let mut reader = Reader::from_str(content);
     reader.config_mut().trim_text(false);
 
@@ -81,7 +135,7 @@ fn parse_trial<B: BufRead>(
     buf: &mut Vec<u8>,
     content: &str,
     start_pos: usize,
-) -> Result<Citation, ParseError> {
+) -> Result<(Citation, Vec<ParseWarning>), ParseError> {
     let mut fields = HashMap::<String, Vec<String>>::new();
     let mut urls = Vec::new();
 
@@ -201,7 +255,8 @@ fn build_trial_citation(
     content: &str,
     start_pos: usize,
     end_pos: usize,
-) -> Result<Citation, ParseError> {
+) -> Result<(Citation, Vec<ParseWarning>), ParseError> {
+    let mut warnings = Vec::new();
     let accession_number = take_first_value(&mut fields, &["TrialID"]).ok_or_else(|| {
         trial_error(
             content,
@@ -234,12 +289,12 @@ fn build_trial_citation(
     let fallback_date = first_value(&fields, &["Date_registration"]);
     let date = compact_date
         .as_deref()
-        .and_then(parse_ictrp_compact_date)
+        .and_then(parse_compact_date)
         .or_else(|| fallback_date.as_deref().and_then(parse_ictrp_standard_date));
 
     if compact_date
         .as_deref()
-        .and_then(parse_ictrp_compact_date)
+        .and_then(parse_compact_date)
         .is_some()
     {
         fields.remove("Date_registration3");
@@ -249,7 +304,39 @@ fn build_trial_citation(
         .is_some()
     {
         fields.remove("Date_registration");
+    } else if let Some((key, value)) = fallback_date
+        .map(|value| ("Date_registration", value))
+        .or_else(|| compact_date.map(|value| ("Date_registration3", value)))
+    {
+        warnings.push(trial_warning(
+            content,
+            start_pos,
+            end_pos,
+            WarningKind::UnparseableDate {
+                key: key.to_string(),
+                value,
+            },
+        ));
+    }
+
+    let refreshed_date = first_value(&fields, &["Last_Refreshed_on"]);
+    let refreshed = refreshed_date
+        .as_deref()
+        .and_then(parse_ictrp_refreshed_date);
+    if refreshed.is_some() {
+        fields.remove("Last_Refreshed_on");
+    } else if let Some(value) = refreshed_date {
+        warnings.push(trial_warning(
+            content,
+            start_pos,
+            end_pos,
+            WarningKind::UnparseableDate {
+                key: "Last_Refreshed_on".to_string(),
+                value,
+            },
+        ));
     }
+    let dates = trial_dates(date.as_ref(), refreshed);
 
     let publisher = take_first_value(&mut fields, &["Primary_sponsor"]);
     let mut citation_type = vec!["Clinical Trial".to_string()];
@@ -264,18 +351,24 @@ fn build_trial_citation(
     fields.remove("results_url_link");
     fields.remove("results_url_protocol");
 
-    Ok(Citation {
+    let citation = Citation {
         citation_type,
         title,
         authors: Vec::new(),
+        contributors: Vec::new(),
         journal: None,
         journal_abbr: None,
         date,
+        dates,
         volume: None,
         issue: None,
         pages: None,
         issn: Vec::new(),
         doi: None,
+        identifiers: trial_identifiers(
+            &accession_number,
+            fields.get("Secondary_ID").into_iter().flatten(),
+        ),
         accession_number: Some(accession_number),
         pmid: None,
         pmc_id: None,
@@ -286,7 +379,8 @@ fn build_trial_citation(
         mesh_terms: Vec::new(),
         publisher,
         extra_fields: fields,
-    })
+    };
+    Ok((citation, warnings))
 }
 
 fn first_value(fields: &HashMap<String, Vec<String>>, keys: &[&str]) -> Option<String> {
@@ -414,6 +508,20 @@ fn trial_error(content: &str, start_pos: usize, end_pos: usize, error: ValueErro
     .with_span(SourceSpan::new(start_pos, end_pos))
 }
 
+fn trial_warning(
+    content: &str,
+    start_pos: usize,
+    end_pos: usize,
+    kind: WarningKind,
+) -> ParseWarning {
+    ParseWarning::at_line(
+        buffer_position_to_line_number(content, start_pos),
+        CitationFormat::IctrpXml,
+        kind,
+    )
+    .with_span(SourceSpan::new(start_pos, end_pos))
+}
+
 fn xml_error(content: &str, start_pos: usize, end_pos: usize, detail: String) -> ParseError {
     ParseError::at_line(
         buffer_position_to_line_number(content, start_pos),
@@ -544,6 +652,7 @@ fn extract_text_with_position<B: BufRead>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{DateKind, Scheme};
 
     #[test]
     fn test_looks_like_ictrp_xml() {
@@ -568,15 +677,24 @@ mod tests {
     <Primary_sponsor>Sponsor</Primary_sponsor>
     <Date_registration3>20260501</Date_registration3>
     <Date_registration>01/05/2026</Date_registration>
+    <Last_Refreshed_on>12 June 2026</Last_Refreshed_on>
     <Study_type>Interventional</Study_type>
     <web_address>https://example.test/study</web_address>
     <results_url_link>https://example.test/results</results_url_link>
+    <Secondary_ID>EUCTR2004-000123-45-GB;ABC-123</Secondary_ID>
     <Source_Register>ClinicalTrials.gov</Source_Register>
   </Trial>
 </Trials_downloaded_from_ICTRP>"#;
 
         let citation = IctrpXmlParser::new().parse(input).unwrap().remove(0);
         assert_eq!(citation.accession_number.as_deref(), Some("NCT00000001"));
+        assert_eq!(citation.identifier(Scheme::Nct), Some("NCT00000001"));
+        assert_eq!(citation.identifier(Scheme::EudraCt), Some("2004-000123-45"));
+        assert_eq!(citation.identifiers.len(), 2);
+        assert_eq!(
+            citation.extra_fields.get("Secondary_ID").unwrap(),
+            &vec!["EUCTR2004-000123-45-GB;ABC-123".to_string()]
+        );
         assert_eq!(citation.title, "Scientific title");
         assert_eq!(citation.publisher.as_deref(), Some("Sponsor"));
         assert_eq!(
@@ -589,6 +707,16 @@ mod tests {
         assert!(!citation.extra_fields.contains_key("Primary_sponsor"));
         assert!(!citation.extra_fields.contains_key("Study_type"));
         assert!(!citation.extra_fields.contains_key("Date_registration3"));
+        assert!(!citation.extra_fields.contains_key("Last_Refreshed_on"));
+        assert_eq!(
+            citation.date_of(DateKind::Registered),
+            citation.date.as_ref()
+        );
+        let refreshed = citation.date_of(DateKind::LastUpdated).unwrap();
+        assert_eq!(
+            (refreshed.year, refreshed.month, refreshed.day),
+            (2026, Some(6), Some(12))
+        );
         assert!(!citation.extra_fields.contains_key("web_address"));
         assert!(!citation.extra_fields.contains_key("results_url_link"));
         assert_eq!(
@@ -649,11 +777,47 @@ mod tests {
                 year: 2026,
                 month: Some(4),
                 day: Some(20),
+                season: None,
+                end: None,
             })
         );
         assert!(!citation.extra_fields.contains_key("Date_registration"));
     }
 
+    #[test]
+    fn test_parse_with_options_reports_warnings() {
+        let input = r#"<?xml version='1.0' encoding='UTF-8' ?>
+<Trials_downloaded_from_ICTRP>
+  <Trial>
+    <TrialID>NCT00000003</TrialID>
+    <Scientific_title>First</Scientific_title>
+    <Date_registration>2026-04-20</Date_registration>
+  </Trial>
+  <Trial>
+    <TrialID>NCT00000004</TrialID>
+    <Scientific_title>Second</Scientific_title>
+    <Date_registration>someday</Date_registration>
+  </Trial>
+</Trials_downloaded_from_ICTRP>"#;
+        for on_error in [OnError::Fail, OnError::Collect] {
+            let options = ParseOptions::new().with_on_error(on_error);
+            let report = IctrpXmlParser::new()
+                .parse_with_options(input, &options)
+                .unwrap();
+
+            assert_eq!(report.citations.len(), 2);
+            assert_eq!(report.warnings.len(), 1);
+            assert_eq!(report.warnings[0].line, Some(8));
+            assert_eq!(
+                report.warnings[0].kind,
+                WarningKind::UnparseableDate {
+                    key: "Date_registration".to_string(),
+                    value: "someday".to_string()
+                }
+            );
+        }
+    }
+
     #[test]
     fn test_parse_ictrp_xml_ignores_empty_fields() {
         let input = r#"<?xml version='1.0' encoding='UTF-8' ?>
@@ -911,4 +1075,25 @@ mod tests {
             "{bullet_inclusion:?}"
         );
     }
+
+    #[test]
+    fn test_parse_reader_matches_parse() {
+        let input = r#"<?xml version='1.0' encoding='UTF-8' ?>
+<Trials_downloaded_from_ICTRP>
+  <Trial>
+    <TrialID>NCT00000001</TrialID>
+    <Scientific_title>First trial</Scientific_title>
+  </Trial>
+  <Trial>
+    <TrialID>NCT00000002</TrialID>
+    <Public_title>Second trial</Public_title>
+  </Trial>
+</Trials_downloaded_from_ICTRP>"#;
+        let parser = IctrpXmlParser::new();
+
+        let streamed: Result<Vec<_>, _> = parser.parse_reader(input.as_bytes()).collect();
+        let streamed = streamed.unwrap();
+        assert_eq!(streamed.len(), 2);
+        assert_eq!(streamed, parser.parse(input).unwrap());
+    }
 }
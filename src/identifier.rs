@@ -0,0 +1,359 @@
+//! Typed identifiers for citations.
+//!
+//! Besides a DOI, PMID and PMC ID, citations often carry ISBNs, arXiv IDs,
+//! Scopus EIDs, Embase and Web of Science accession numbers, and trial
+//! registry numbers. Parsers store these in [`Citation::identifiers`] as
+//! [`Identifier`] values, each tagged with its [`Scheme`] and normalized to
+//! one canonical spelling, so they can be compared across formats.
+//!
+//! [`Identifier::new`] validates and normalizes a value for a known scheme,
+//! and [`Identifier::detect`] recognises values that name their own scheme,
+//! such as `NCT01234567` or `arXiv:2101.00001`.
+//!
+//! # Example
+//!
+//! ```
+//! use biblib::identifier::{Identifier, Scheme};
+//!
+//! let isbn = Identifier::new(Scheme::Isbn, "ISBN 978-0-306-40615-7").unwrap();
+//! assert_eq!(isbn.value, "9780306406157");
+//!
+//! let trial = Identifier::detect("EUCTR2004-000123-45-GB").unwrap();
+//! assert_eq!(trial.scheme, Scheme::EudraCt);
+//! assert_eq!(trial.value, "2004-000123-45");
+//! ```
+//!
+//! [`Citation::identifiers`]: crate::Citation::identifiers
+
+use crate::regex::Regex;
+use crate::utils::format_doi;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::LazyLock;
+
+static ARXIV_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:\d{4}\.\d{4,5}|[a-z][a-z\-]*(?:\.[A-Z]{2})?/\d{7})(?:v\d+)?$").unwrap()
+});
+
+static EUDRACT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:EUCTR)?(\d{4}-\d{6}-\d{2})(?:-[A-Z]{2,3})?$").unwrap());
+
+static CHICTR_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^CHICTR((?:-[A-Z]{3}-)?\d{8,10})$").unwrap());
+
+/// The kind of an [`Identifier`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Scheme {
+    /// Digital Object Identifier, stored in [`Citation::doi`](crate::Citation::doi).
+    Doi,
+    /// PubMed ID, stored in [`Citation::pmid`](crate::Citation::pmid).
+    Pmid,
+    /// PubMed Central ID, stored in [`Citation::pmc_id`](crate::Citation::pmc_id).
+    Pmcid,
+    /// ISBN, as an ISBN-13 without hyphens. ISBN-10 values are converted.
+    Isbn,
+    /// arXiv identifier, without the `arXiv:` prefix.
+    Arxiv,
+    /// Scopus EID, such as `2-s2.0-85012345678`.
+    Eid,
+    /// Embase accession number.
+    Embase,
+    /// Web of Science accession number (`UT`), such as `WOS:000123456700001`.
+    Wos,
+    /// ClinicalTrials.gov registration number, such as `NCT01234567`.
+    Nct,
+    /// ISRCTN registry number, such as `ISRCTN12345678`.
+    Isrctn,
+    /// EudraCT number, such as `2004-000123-45`.
+    EudraCt,
+    /// Chinese Clinical Trial Registry number, such as `ChiCTR2000012345`.
+    ChiCtr,
+}
+
+impl Scheme {
+    /// Lowercase name of the scheme, as used in serialized identifiers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scheme::Doi => "doi",
+            Scheme::Pmid => "pmid",
+            Scheme::Pmcid => "pmcid",
+            Scheme::Isbn => "isbn",
+            Scheme::Arxiv => "arxiv",
+            Scheme::Eid => "eid",
+            Scheme::Embase => "embase",
+            Scheme::Wos => "wos",
+            Scheme::Nct => "nct",
+            Scheme::Isrctn => "isrctn",
+            Scheme::EudraCt => "eudract",
+            Scheme::ChiCtr => "chictr",
+        }
+    }
+
+    /// Whether the scheme has its own [`Citation`](crate::Citation) field
+    /// instead of an entry in `identifiers`.
+    pub fn has_dedicated_field(&self) -> bool {
+        matches!(self, Scheme::Doi | Scheme::Pmid | Scheme::Pmcid)
+    }
+
+    /// Validate `value` for this scheme and return its canonical spelling,
+    /// or `None` when it is not a valid identifier of this kind.
+    pub fn normalize(&self, value: &str) -> Option<String> {
+        let value = value.trim();
+        let upper = value.to_ascii_uppercase();
+        match self {
+            Scheme::Doi => format_doi(value).filter(|doi| doi.contains('/')),
+            Scheme::Pmid => {
+                let digits = strip_prefix_ignore_case(value, "PMID:").trim();
+                is_digits(digits).then(|| digits.to_string())
+            }
+            Scheme::Pmcid => {
+                let digits = upper.strip_prefix("PMC")?;
+                is_digits(digits).then(|| upper.clone())
+            }
+            Scheme::Isbn => normalize_isbn(value),
+            Scheme::Arxiv => {
+                let id = value
+                    .rfind("arxiv.org/abs/")
+                    .map_or(value, |pos| &value[pos + "arxiv.org/abs/".len()..]);
+                let id = strip_prefix_ignore_case(id, "arXiv:").trim();
+                ARXIV_REGEX.is_match(id).then(|| id.to_string())
+            }
+            Scheme::Eid => {
+                let digits = value.strip_prefix("2-s2.0-")?;
+                is_digits(digits).then(|| value.to_string())
+            }
+            Scheme::Embase => {
+                let digits = upper.strip_prefix('L').unwrap_or(&upper);
+                is_digits(digits).then(|| upper.clone())
+            }
+            Scheme::Wos => {
+                let id = upper.strip_prefix("WOS:").unwrap_or(&upper);
+                (id.len() == 15 && id.chars().all(|c| c.is_ascii_alphanumeric()))
+                    .then(|| format!("WOS:{}", id))
+            }
+            Scheme::Nct => registry_number(&upper, "NCT"),
+            Scheme::Isrctn => registry_number(&upper, "ISRCTN"),
+            Scheme::EudraCt => EUDRACT_REGEX
+                .captures(&upper)
+                .map(|captures| captures[1].to_string()),
+            Scheme::ChiCtr => CHICTR_REGEX
+                .captures(&upper)
+                .map(|captures| format!("ChiCTR{}", &captures[1])),
+        }
+    }
+}
+
+impl fmt::Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An identifier of a work, normalized for its [`Scheme`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Identifier {
+    /// The kind of identifier.
+    pub scheme: Scheme,
+    /// The normalized identifier, as returned by [`Scheme::normalize`].
+    pub value: String,
+}
+
+impl Identifier {
+    /// Validate and normalize `value` as an identifier of `scheme`.
+    ///
+    /// Returns `None` when `value` is not a valid identifier of that kind,
+    /// for example an ISBN with a wrong check digit.
+    pub fn new(scheme: Scheme, value: &str) -> Option<Self> {
+        Some(Self {
+            scheme,
+            value: scheme.normalize(value)?,
+        })
+    }
+
+    /// Recognise an identifier whose scheme can be told from the value
+    /// alone: DOIs, PMC IDs, `arXiv:` IDs, Scopus EIDs, `WOS:` accession
+    /// numbers and trial registry numbers.
+    ///
+    /// Bare numbers such as PMIDs, ISBNs and Embase accession numbers are
+    /// ambiguous and are not detected.
+    pub fn detect(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let upper = value.to_ascii_uppercase();
+        let scheme = if upper.starts_with("10.")
+            || upper.starts_with("DOI:")
+            || upper.contains("DOI.ORG/")
+        {
+            Scheme::Doi
+        } else if upper.starts_with("PMC") {
+            Scheme::Pmcid
+        } else if upper.starts_with("ARXIV:") || upper.contains("ARXIV.ORG/ABS/") {
+            Scheme::Arxiv
+        } else if value.starts_with("2-s2.0-") {
+            Scheme::Eid
+        } else if upper.starts_with("WOS:") {
+            Scheme::Wos
+        } else if upper.starts_with("NCT") {
+            Scheme::Nct
+        } else if upper.starts_with("ISRCTN") {
+            Scheme::Isrctn
+        } else if upper.starts_with("CHICTR") {
+            Scheme::ChiCtr
+        } else {
+            Scheme::EudraCt
+        };
+        Self::new(scheme, value)
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.scheme, self.value)
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(value: &'a str, prefix: &str) -> &'a str {
+    match value.get(..prefix.len()) {
+        Some(start) if start.eq_ignore_ascii_case(prefix) => &value[prefix.len()..],
+        _ => value,
+    }
+}
+
+fn is_digits(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_digit())
+}
+
+/// `prefix` followed by exactly eight digits, as used by ClinicalTrials.gov
+/// and ISRCTN.
+fn registry_number(upper: &str, prefix: &str) -> Option<String> {
+    let digits = upper.strip_prefix(prefix)?;
+    (digits.len() == 8 && is_digits(digits)).then(|| upper.to_string())
+}
+
+/// Strip an `ISBN` label, hyphens, spaces and a trailing qualifier such as
+/// `(pbk.)`, check the ISBN-10 or ISBN-13 check digit, and return the
+/// ISBN-13. ISBN-10 values are converted by prefixing `978`.
+fn normalize_isbn(value: &str) -> Option<String> {
+    let value = value.split('(').next().unwrap_or_default();
+    let value = strip_prefix_ignore_case(value.trim(), "ISBN");
+    let value = value
+        .strip_prefix("-10")
+        .or_else(|| value.strip_prefix("-13"))
+        .unwrap_or(value)
+        .trim_start_matches(':')
+        .trim();
+    let chars: Vec<char> = value
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let digits: Vec<u32> = chars.iter().map_while(|c| c.to_digit(10)).collect();
+
+    let isbn13 = match (chars.len(), digits.len()) {
+        (10, 9) | (10, 10) => {
+            let check = match chars[9] {
+                'X' => 10,
+                c => c.to_digit(10)?,
+            };
+            let total: u32 = digits[..9]
+                .iter()
+                .zip((2..=10).rev())
+                .map(|(digit, weight)| digit * weight)
+                .sum();
+            if !(total + check).is_multiple_of(11) {
+                return None;
+            }
+            let mut isbn13 = vec![9, 7, 8];
+            isbn13.extend_from_slice(&digits[..9]);
+            isbn13.push(isbn13_check_digit(&isbn13));
+            isbn13
+        }
+        (13, 13)
+            if matches!(digits[..3], [9, 7, 8] | [9, 7, 9])
+                && isbn13_check_digit(&digits[..12]) == digits[12] =>
+        {
+            digits
+        }
+        _ => return None,
+    };
+    Some(
+        isbn13
+            .into_iter()
+            .filter_map(|digit| char::from_digit(digit, 10))
+            .collect(),
+    )
+}
+
+/// The ISBN-13 check digit for the first twelve digits.
+fn isbn13_check_digit(digits: &[u32]) -> u32 {
+    let total: u32 = digits
+        .iter()
+        .enumerate()
+        .map(|(i, digit)| if i % 2 == 0 { *digit } else { digit * 3 })
+        .sum();
+    (10 - total % 10) % 10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Scheme::Doi, "https://doi.org/10.1000/ABC", Some("10.1000/abc"))]
+    #[case(Scheme::Doi, "not a doi", None)]
+    #[case(Scheme::Pmid, "PMID: 12345678", Some("12345678"))]
+    #[case(Scheme::Pmid, "12345a", None)]
+    #[case(Scheme::Pmcid, "pmc123456", Some("PMC123456"))]
+    #[case(Scheme::Isbn, "978-0-306-40615-7", Some("9780306406157"))]
+    #[case(Scheme::Isbn, "ISBN 0-306-40615-2 (pbk.)", Some("9780306406157"))]
+    #[case(Scheme::Isbn, "ISBN-13: 978-0-306-40615-7", Some("9780306406157"))]
+    #[case(Scheme::Isbn, "080442957x", Some("9780804429573"))]
+    #[case(Scheme::Isbn, "0-306-40615-1", None)]
+    #[case(Scheme::Isbn, "978-0-306-40615-8", None)]
+    #[case(Scheme::Isbn, "1234-5678", None)]
+    #[case(Scheme::Arxiv, "arXiv:2101.00001v2", Some("2101.00001v2"))]
+    #[case(
+        Scheme::Arxiv,
+        "https://arxiv.org/abs/hep-th/9901001",
+        Some("hep-th/9901001")
+    )]
+    #[case(Scheme::Arxiv, "2101", None)]
+    #[case(Scheme::Eid, "2-s2.0-85012345678", Some("2-s2.0-85012345678"))]
+    #[case(Scheme::Embase, "l2031234567", Some("L2031234567"))]
+    #[case(Scheme::Wos, "000123456700001", Some("WOS:000123456700001"))]
+    #[case(Scheme::Wos, "wos:A1997XB12300001", Some("WOS:A1997XB12300001"))]
+    #[case(Scheme::Nct, "nct01234567", Some("NCT01234567"))]
+    #[case(Scheme::Nct, "NCT0123", None)]
+    #[case(Scheme::Isrctn, "ISRCTN12345678", Some("ISRCTN12345678"))]
+    #[case(Scheme::EudraCt, "EUCTR2004-000123-45-GB", Some("2004-000123-45"))]
+    #[case(Scheme::ChiCtr, "ChiCTR-TRC-12002345", Some("ChiCTR-TRC-12002345"))]
+    #[case(Scheme::ChiCtr, "chictr2000012345", Some("ChiCTR2000012345"))]
+    fn test_normalize(#[case] scheme: Scheme, #[case] value: &str, #[case] expected: Option<&str>) {
+        assert_eq!(scheme.normalize(value).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case("NCT01234567", Some((Scheme::Nct, "NCT01234567")))]
+    #[case("ISRCTN12345678", Some((Scheme::Isrctn, "ISRCTN12345678")))]
+    #[case("2004-000123-45", Some((Scheme::EudraCt, "2004-000123-45")))]
+    #[case("ChiCTR2000012345", Some((Scheme::ChiCtr, "ChiCTR2000012345")))]
+    #[case("WOS:000123456700001", Some((Scheme::Wos, "WOS:000123456700001")))]
+    #[case("2-s2.0-85012345678", Some((Scheme::Eid, "2-s2.0-85012345678")))]
+    #[case("arXiv:2101.00001", Some((Scheme::Arxiv, "2101.00001")))]
+    #[case("doi:10.1000/xyz", Some((Scheme::Doi, "10.1000/xyz")))]
+    #[case("PMC123", Some((Scheme::Pmcid, "PMC123")))]
+    #[case("12345678", None)]
+    #[case("DRKS00012345", None)]
+    fn test_detect(#[case] value: &str, #[case] expected: Option<(Scheme, &str)>) {
+        let detected = Identifier::detect(value);
+        assert_eq!(
+            detected
+                .as_ref()
+                .map(|identifier| (identifier.scheme, identifier.value.as_str())),
+            expected
+        );
+    }
+}
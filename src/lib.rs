@@ -10,13 +10,23 @@
 //!
 //! # What You Get
 //!
-//! - Dedicated parsers for RIS, PubMed / MEDLINE, EndNote XML, ICTRP XML,
-//!   EndNote Tagged (`.enw`), BibTeX / BibLaTeX (`.bib`), generic CSV, and
-//!   ICTRP CSV exports
+//! - Dedicated parsers for RIS, PubMed / MEDLINE, PubMed XML, EndNote XML, ICTRP XML,
+//!   EndNote Tagged (`.enw`), BibTeX / BibLaTeX (`.bib`), CSL-JSON, Web of
+//!   Science, Ovid, generic CSV, and ICTRP CSV exports
 //! - A shared [`Citation`] output type with normalized identifiers such as DOI,
 //!   PMID, PMCID, and `accession_number`
+//! - Typed [`Identifier`]s for ISBNs, arXiv IDs, database accession numbers and
+//!   trial registry numbers, normalized for their [`Scheme`]
+//! - Editors, translators and other contributors kept apart from authors, with
+//!   their [`ContributorRole`], ORCID iD and organization flag
+//! - Publication dates with seasons and ranges, plus e-publication, access,
+//!   registration and update dates by [`DateKind`]
 //! - Preservation of source-specific leftovers through `extra_fields`
-//! - Optional duplicate detection via [`dedupe::Deduplicator`]
+//! - Byte input in UTF-8, UTF-16 or legacy encodings via [`detect_and_parse_bytes`]
+//! - Optional repair of mojibake, entities and inline markup via [`text`]
+//! - LaTeX accents, symbols and braces in `.bib` values decoded to plain Unicode
+//! - Optional duplicate detection with configurable matching rules and match
+//!   explanations via [`dedupe::Deduplicator`]
 //! - Optional human-friendly parse diagnostics with the `diagnostics` feature
 //!
 //! # Quick Start
@@ -41,27 +51,32 @@
 //!
 //! ```rust
 //! use biblib::{
-//!     BibParser, CitationParser, EndNoteXmlParser, EnwParser, IctrpXmlParser, PubMedParser,
-//!     RisParser,
+//!     BibParser, CitationParser, CslJsonParser, EndNoteXmlParser, EnwParser, IctrpXmlParser,
+//!     OvidParser, PubMedParser, PubMedXmlParser, RisParser, WosParser,
 //! };
 //! use biblib::csv::CsvParser;
 //!
 //! let _ris = RisParser::new();
 //! let _pubmed = PubMedParser::new();
+//! let _pubmed_xml = PubMedXmlParser::new();
 //! let _endnote = EndNoteXmlParser::new();
 //! let _ictrp_xml = IctrpXmlParser::new();
 //! let _enw = EnwParser::new();
 //! let _bib = BibParser::new();
+//! let _csl = CslJsonParser::new();
+//! let _wos = WosParser::new();
+//! let _ovid = OvidParser::new();
 //! let _csv = CsvParser::new();
 //! ```
 //!
 //! # Auto-Detection
 //!
-//! [`detect_and_parse`] currently auto-detects RIS, PubMed, ICTRP XML,
-//! EndNote XML, EndNote Tagged, BibTeX / BibLaTeX, and ICTRP CSV. ICTRP XML
-//! is the preferred ICTRP ingestion path; ICTRP CSV remains for backward
-//! compatibility. Generic CSV remains explicit because header mapping is
-//! application-specific.
+//! [`detect_and_parse`] currently auto-detects RIS, PubMed, PubMed XML,
+//! ICTRP XML, EndNote XML, EndNote Tagged, BibTeX / BibLaTeX, CSL-JSON, Web of
+//! Science, Ovid, and ICTRP CSV.
+//! ICTRP XML is the preferred ICTRP ingestion path; ICTRP CSV remains for
+//! backward compatibility. Generic CSV remains explicit because header mapping
+//! is application-specific.
 //!
 //! ```rust
 //! use biblib::detect_and_parse;
@@ -90,6 +105,9 @@
 //! - `csv`
 //! - `enw`
 //! - `bib`
+//! - `csl`
+//! - `wos`
+//! - `ovid`
 //! - `dedupe`
 //! - `diagnostics`
 //!
@@ -107,14 +125,14 @@
 //!     Citation {
 //!         title: "Example Title".to_string(),
 //!         doi: Some("10.1000/example".to_string()),
-//!         date: Some(Date { year: 2023, month: None, day: None }),
+//!         date: Some(Date { year: 2023, month: None, day: None, season: None, end: None }),
 //!         journal: Some("Example Journal".to_string()),
 //!         ..Default::default()
 //!     },
 //!     Citation {
 //!         title: "Example Title".to_string(),
 //!         doi: Some("10.1000/example".to_string()),
-//!         date: Some(Date { year: 2023, month: None, day: None }),
+//!         date: Some(Date { year: 2023, month: None, day: None, season: None, end: None }),
 //!         journal: Some("Example Journal".to_string()),
 //!         ..Default::default()
 //!     },
@@ -124,6 +142,7 @@
 //!     group_by_year: true,
 //!     run_in_parallel: true,
 //!     source_preferences: vec!["PubMed".to_string()],
+//!     ..Default::default()
 //! };
 //!
 //! let groups = Deduplicator::new()
@@ -162,44 +181,67 @@ extern crate csv as csv_crate;
 
 #[cfg(feature = "bib")]
 pub mod bib;
+#[cfg(feature = "csl")]
+pub mod csl;
 #[cfg(feature = "csv")]
 pub mod csv;
 #[cfg(feature = "dedupe")]
 pub mod dedupe;
 #[cfg(feature = "diagnostics")]
 pub mod diagnostics;
+pub mod encoding;
 #[cfg(feature = "xml")]
 pub mod endnote_xml;
 #[cfg(feature = "enw")]
 pub mod enw;
 pub mod error;
+pub mod identifier;
+#[cfg(feature = "ovid")]
+pub mod ovid;
 #[cfg(feature = "pubmed")]
 pub mod pubmed;
+#[cfg(feature = "xml")]
+pub mod pubmed_xml;
 #[cfg(feature = "ris")]
 pub mod ris;
+pub mod text;
+#[cfg(feature = "wos")]
+pub mod wos;
 
 // Reexports
 #[cfg(feature = "bib")]
-pub use bib::BibParser;
+pub use bib::{BibDialect, BibParser, BibWriter};
+#[cfg(feature = "csl")]
+pub use csl::{CslJsonParser, CslJsonWriter};
 #[cfg(feature = "csv")]
 #[allow(deprecated)]
-pub use csv::{CsvParser, IctrpCsvParser};
+pub use csv::{CsvParser, CsvWriter, IctrpCsvParser};
 #[cfg(feature = "diagnostics")]
 pub use diagnostics::parse_with_diagnostics;
+pub use encoding::TextEncoding;
 #[cfg(feature = "xml")]
-pub use endnote_xml::EndNoteXmlParser;
+pub use endnote_xml::{EndNoteXmlParser, EndNoteXmlWriter};
 #[cfg(feature = "enw")]
-pub use enw::EnwParser;
-pub use error::{CitationError, ParseError, SourceSpan, ValueError};
+pub use enw::{EnwParser, EnwWriter};
+pub use error::{CitationError, ParseError, ParseWarning, SourceSpan, ValueError, WarningKind};
 #[cfg(feature = "xml")]
 pub use ictrp::xml::IctrpXmlParser;
+pub use identifier::{Identifier, Scheme};
+#[cfg(feature = "ovid")]
+pub use ovid::OvidParser;
 #[cfg(feature = "pubmed")]
-pub use pubmed::PubMedParser;
+pub use pubmed::{PubMedParser, PubMedWriter};
+#[cfg(feature = "xml")]
+pub use pubmed_xml::PubMedXmlParser;
 #[cfg(feature = "ris")]
-pub use ris::RisParser;
+pub use ris::{RisParser, RisWriter};
+#[cfg(feature = "wos")]
+pub use wos::WosParser;
 
+#[cfg(any(feature = "csv", feature = "xml"))]
 mod ictrp;
 mod regex;
+mod stream;
 mod utils;
 
 #[cfg(feature = "xml")]
@@ -213,10 +255,14 @@ pub mod ictrp_xml {
 pub enum CitationFormat {
     Ris,
     PubMed,
+    PubMedXml,
     EndNoteXml,
     IctrpXml,
     Enw,
     Bib,
+    CslJson,
+    WebOfScience,
+    Ovid,
     Csv,
     IctrpCsv,
     Unknown,
@@ -228,10 +274,14 @@ impl CitationFormat {
         match self {
             CitationFormat::Ris => "RIS",
             CitationFormat::PubMed => "PubMed",
+            CitationFormat::PubMedXml => "PubMed XML",
             CitationFormat::EndNoteXml => "EndNote XML",
             CitationFormat::IctrpXml => "ICTRP XML",
             CitationFormat::Enw => "EndNote Tagged",
             CitationFormat::Bib => "BibTeX / BibLaTeX",
+            CitationFormat::CslJson => "CSL-JSON",
+            CitationFormat::WebOfScience => "Web of Science",
+            CitationFormat::Ovid => "Ovid",
             CitationFormat::Csv => "CSV",
             CitationFormat::IctrpCsv => "ICTRP CSV",
             CitationFormat::Unknown => "Unknown",
@@ -246,6 +296,10 @@ impl std::fmt::Display for CitationFormat {
 }
 
 /// Represents a publication date with required year and optional month/day components.
+///
+/// Journals published quarterly give a [`Season`] instead of a month, as in
+/// PubMed's `2023 Winter`. A date can also be the start of a range such as
+/// `2022 Dec-2023 Jan`, with the last date of the range in `end`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Date {
     /// Publication year (required)
@@ -254,9 +308,107 @@ pub struct Date {
     pub month: Option<u8>,
     /// Publication day (1-31)
     pub day: Option<u8>,
+    /// Season, for dates without a month such as `2023 Winter`
+    #[serde(default)]
+    pub season: Option<Season>,
+    /// End of a date range, such as `2023 Jan` in `2022 Dec-2023 Jan`
+    #[serde(default)]
+    pub end: Option<Box<Date>>,
+}
+
+/// The season of a quarterly publication date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Season {
+    /// Spring.
+    Spring,
+    /// Summer.
+    Summer,
+    /// Autumn, also written `Fall`.
+    Autumn,
+    /// Winter.
+    Winter,
+}
+
+impl Season {
+    /// Parse a season name, accepting `Fall` for autumn.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "spring" => Some(Season::Spring),
+            "summer" => Some(Season::Summer),
+            "autumn" | "fall" => Some(Season::Autumn),
+            "winter" => Some(Season::Winter),
+            _ => None,
+        }
+    }
+
+    /// The season's name, such as `"Winter"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+            Season::Winter => "Winter",
+        }
+    }
+}
+
+/// What a date in [`Citation::dates`] records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum DateKind {
+    /// Publication date, stored in [`Citation::date`].
+    Published,
+    /// Electronic publication ahead of print, such as PubMed's `DEP`.
+    Epub,
+    /// Date a web resource was accessed.
+    Accessed,
+    /// Registration date of a clinical trial.
+    Registered,
+    /// Date the record was last revised.
+    LastUpdated,
 }
 
-/// Represents an author of a citation.
+/// A date other than the publication date, tagged with its [`DateKind`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypedDate {
+    /// What the date records.
+    pub kind: DateKind,
+    /// The date.
+    pub date: Date,
+}
+
+/// The part a contributor played in creating a work.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ContributorRole {
+    /// An author of the work.
+    #[default]
+    Author,
+    /// A corporate or group author, such as a consortium or trial group.
+    CorporateAuthor,
+    /// An editor of the work, or of the book or proceedings containing it.
+    Editor,
+    /// An editor of the series the work belongs to.
+    SeriesEditor,
+    /// A translator of the work.
+    Translator,
+    /// An investigator or collaborator credited without authorship, such as
+    /// PubMed's `FIR`/`IR`.
+    Investigator,
+}
+
+impl ContributorRole {
+    /// Whether contributors with this role belong in [`Citation::authors`]
+    /// rather than [`Citation::contributors`].
+    pub fn is_author(&self) -> bool {
+        matches!(
+            self,
+            ContributorRole::Author | ContributorRole::CorporateAuthor
+        )
+    }
+}
+
+/// Represents an author or other contributor of a citation.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Author {
     /// The primary name of the person. This can be the family name or full name for mononyms.
@@ -270,23 +422,143 @@ pub struct Author {
 
     /// List of affiliation strings associated with the author.
     pub affiliations: Vec<String>,
+
+    /// The part this person or organization played in creating the work.
+    #[serde(default)]
+    pub role: ContributorRole,
+
+    /// ORCID iD in its hyphenated form (`0000-0002-1825-0097`), present only
+    /// when its check digit is valid.
+    #[serde(default)]
+    pub orcid: Option<String>,
+
+    /// Whether this is an organization, such as a study group or agency,
+    /// rather than a person. Organizations keep their whole name in `name`.
+    ///
+    /// Always set for a [`ContributorRole::CorporateAuthor`]. Organizations in
+    /// other roles, such as a consortium credited as editor, keep that role.
+    /// [`Author::organization`] builds an organization with both set
+    /// consistently.
+    #[serde(default)]
+    pub is_organization: bool,
+}
+
+impl Author {
+    /// An organization credited with `role`, whose whole name is `name`. An
+    /// organization in the [`ContributorRole::Author`] role becomes a
+    /// [`ContributorRole::CorporateAuthor`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{Author, ContributorRole};
+    ///
+    /// let group = Author::organization("COVID-19 Study Group", ContributorRole::Author);
+    /// assert_eq!(group.role, ContributorRole::CorporateAuthor);
+    /// assert!(group.is_organization);
+    ///
+    /// let editor = Author::organization("Cochrane Collaboration", ContributorRole::Editor);
+    /// assert_eq!(editor.role, ContributorRole::Editor);
+    /// assert!(editor.is_organization);
+    /// ```
+    pub fn organization(name: impl Into<String>, role: ContributorRole) -> Self {
+        Author {
+            name: name.into(),
+            given_name: None,
+            middle_name: None,
+            affiliations: Vec::new(),
+            role: match role {
+                ContributorRole::Author => ContributorRole::CorporateAuthor,
+                role => role,
+            },
+            orcid: None,
+            is_organization: true,
+        }
+    }
+
+    /// Whether `self` and `other` name the same contributor.
+    ///
+    /// When both have an ORCID iD, the iDs decide. Otherwise organizations
+    /// match on their name, and people on their family name and given name,
+    /// ignoring case. An initial matches any given name starting with it, and
+    /// a missing given name matches any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{Author, ContributorRole};
+    ///
+    /// let full = Author {
+    ///     name: "Watson".to_string(),
+    ///     given_name: Some("James".to_string()),
+    ///     middle_name: Some("Dewey".to_string()),
+    ///     affiliations: Vec::new(),
+    ///     role: ContributorRole::Author,
+    ///     orcid: None,
+    ///     is_organization: false,
+    /// };
+    /// let short = Author {
+    ///     given_name: Some("J".to_string()),
+    ///     middle_name: None,
+    ///     ..full.clone()
+    /// };
+    /// assert!(full.is_same_person(&short));
+    /// ```
+    pub fn is_same_person(&self, other: &Author) -> bool {
+        if let (Some(orcid), Some(other_orcid)) = (&self.orcid, &other.orcid) {
+            return orcid == other_orcid;
+        }
+        if self.is_organization != other.is_organization
+            || !self.name.trim().eq_ignore_ascii_case(other.name.trim())
+        {
+            return false;
+        }
+        if self.is_organization {
+            return true;
+        }
+        let given = |author: &Author| {
+            author
+                .given_name
+                .as_deref()
+                .map(|given| given.trim().trim_end_matches('.').to_lowercase())
+                .filter(|given| !given.is_empty())
+        };
+        match (given(self), given(other)) {
+            (Some(given), Some(other_given)) => {
+                if given.chars().count() == 1 || other_given.chars().count() == 1 {
+                    given.chars().next() == other_given.chars().next()
+                } else {
+                    given == other_given
+                }
+            }
+            _ => true,
+        }
+    }
 }
 
 /// Represents a single citation with its metadata.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Citation {
     /// Type of the citation
     pub citation_type: Vec<String>,
     /// Title of the work
     pub title: String,
-    /// List of authors
+    /// List of authors, including corporate and group authors
     pub authors: Vec<Author>,
+    /// Editors, translators, investigators and other contributors who are not
+    /// authors, in source order
+    #[serde(default)]
+    pub contributors: Vec<Author>,
     /// Journal name
     pub journal: Option<String>,
     /// Journal abbreviation
     pub journal_abbr: Option<String>,
     /// Publication date with year, month, and day
     pub date: Option<Date>,
+    /// Electronic publication, access, registration and revision dates, at
+    /// most one of each [`DateKind`]
+    #[serde(default)]
+    pub dates: Vec<TypedDate>,
     /// Volume number
     pub volume: Option<String>,
     /// Issue number
@@ -303,6 +575,11 @@ pub struct Citation {
     pub pmid: Option<String>,
     /// PMC ID
     pub pmc_id: Option<String>,
+    /// ISBNs, arXiv IDs, database accession numbers, trial registry numbers
+    /// and other identifiers without a dedicated field, normalized for their
+    /// [`Scheme`]
+    #[serde(default)]
+    pub identifiers: Vec<Identifier>,
     /// Abstract text
     pub abstract_text: Option<String>,
     /// Keywords
@@ -324,6 +601,108 @@ impl Citation {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// The date of `kind`, reading [`date`](Self::date) for
+    /// [`DateKind::Published`] and [`dates`](Self::dates) for the others.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{Citation, Date, DateKind};
+    ///
+    /// let mut citation = Citation::new();
+    /// citation.set_date(
+    ///     DateKind::Accessed,
+    ///     Date { year: 2024, month: Some(3), day: Some(1), season: None, end: None },
+    /// );
+    ///
+    /// assert_eq!(citation.date_of(DateKind::Accessed).map(|d| d.year), Some(2024));
+    /// assert_eq!(citation.date_of(DateKind::Epub), None);
+    /// ```
+    pub fn date_of(&self, kind: DateKind) -> Option<&Date> {
+        match kind {
+            DateKind::Published => self.date.as_ref(),
+            _ => self
+                .dates
+                .iter()
+                .find(|typed| typed.kind == kind)
+                .map(|typed| &typed.date),
+        }
+    }
+
+    /// Set the date of `kind`, replacing any date of that kind.
+    pub fn set_date(&mut self, kind: DateKind, date: Date) {
+        if kind == DateKind::Published {
+            self.date = Some(date);
+            return;
+        }
+        match self.dates.iter_mut().find(|typed| typed.kind == kind) {
+            Some(typed) => typed.date = date,
+            None => self.dates.push(TypedDate { kind, date }),
+        }
+    }
+
+    /// The first identifier of `scheme`, reading [`doi`](Self::doi),
+    /// [`pmid`](Self::pmid) and [`pmc_id`](Self::pmc_id) for their schemes
+    /// and [`identifiers`](Self::identifiers) for the others.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{Citation, Scheme};
+    ///
+    /// let mut citation = Citation::new();
+    /// citation.add_identifier(Scheme::Isbn, "978-0-306-40615-7");
+    /// citation.add_identifier(Scheme::Doi, "https://doi.org/10.1000/xyz");
+    ///
+    /// assert_eq!(citation.identifier(Scheme::Isbn), Some("9780306406157"));
+    /// assert_eq!(citation.identifier(Scheme::Doi), Some("10.1000/xyz"));
+    /// assert_eq!(citation.identifier(Scheme::Nct), None);
+    /// ```
+    pub fn identifier(&self, scheme: Scheme) -> Option<&str> {
+        self.identifiers_of(scheme).next()
+    }
+
+    /// All identifiers of `scheme`, in the order they were added.
+    pub fn identifiers_of(&self, scheme: Scheme) -> impl Iterator<Item = &str> {
+        let dedicated = match scheme {
+            Scheme::Doi => self.doi.as_deref(),
+            Scheme::Pmid => self.pmid.as_deref(),
+            Scheme::Pmcid => self.pmc_id.as_deref(),
+            _ => None,
+        };
+        dedicated.into_iter().chain(
+            self.identifiers
+                .iter()
+                .filter(move |identifier| identifier.scheme == scheme)
+                .map(|identifier| identifier.value.as_str()),
+        )
+    }
+
+    /// Validate and normalize `value` as an identifier of `scheme` and add
+    /// it, unless the citation already has it. DOIs, PMIDs and PMC IDs fill
+    /// their dedicated field when it is empty.
+    ///
+    /// Returns `false` when `value` is not a valid identifier of `scheme`.
+    pub fn add_identifier(&mut self, scheme: Scheme, value: &str) -> bool {
+        let Some(identifier) = Identifier::new(scheme, value) else {
+            return false;
+        };
+        let dedicated = match scheme {
+            Scheme::Doi => Some(&mut self.doi),
+            Scheme::Pmid => Some(&mut self.pmid),
+            Scheme::Pmcid => Some(&mut self.pmc_id),
+            _ => None,
+        };
+        match dedicated {
+            Some(field) => {
+                field.get_or_insert(identifier.value);
+            }
+            None if !self.identifiers.contains(&identifier) => self.identifiers.push(identifier),
+            None => {}
+        }
+        true
+    }
 }
 
 /// Represents a group of duplicate citations with one unique citation
@@ -335,6 +714,98 @@ pub struct DuplicateGroup {
     pub duplicates: Vec<Citation>,
 }
 
+/// What [`CitationParser::parse_with_options`] does with a record that fails
+/// to parse.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnError {
+    /// Return the first error, as [`CitationParser::parse`] does.
+    #[default]
+    Fail,
+    /// Leave the record out and discard its error.
+    Skip,
+    /// Leave the record out and add its error to [`ParseReport::errors`].
+    Collect,
+}
+
+/// Options for [`CitationParser::parse_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// How records that fail to parse are handled.
+    pub on_error: OnError,
+    /// Clean titles, abstracts, journals and author names with
+    /// [`text::clean_citation`], repairing mojibake, entities, `<U+XXXX>`
+    /// escapes and inline markup. Off by default.
+    pub clean_text: bool,
+}
+
+impl ParseOptions {
+    /// Creates options that fail on the first error.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how records that fail to parse are handled.
+    #[must_use]
+    pub fn with_on_error(mut self, on_error: OnError) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
+    /// Sets whether text fields of parsed citations are cleaned.
+    #[must_use]
+    pub fn with_clean_text(mut self, clean_text: bool) -> Self {
+        self.clean_text = clean_text;
+        self
+    }
+}
+
+/// The result of [`CitationParser::parse_with_options`].
+#[derive(Debug, Default)]
+pub struct ParseReport {
+    /// Citations parsed successfully, in input order.
+    pub citations: Vec<Citation>,
+    /// Errors for the records that were left out, in input order. Only
+    /// filled in with [`OnError::Collect`].
+    pub errors: Vec<crate::error::ParseError>,
+    /// Data dropped or coerced in the returned citations, in input order.
+    pub warnings: Vec<crate::error::ParseWarning>,
+}
+
+/// Gather per-record results into a report, stopping at the first error with
+/// [`OnError::Fail`].
+fn collect_report<I>(
+    records: I,
+    options: &ParseOptions,
+) -> std::result::Result<ParseReport, crate::error::ParseError>
+where
+    I: IntoIterator<
+        Item = std::result::Result<
+            (Citation, Vec<crate::error::ParseWarning>),
+            crate::error::ParseError,
+        >,
+    >,
+{
+    let mut report = ParseReport::default();
+    for record in records {
+        match record {
+            Ok((mut citation, warnings)) => {
+                if options.clean_text {
+                    text::clean_citation(&mut citation);
+                }
+                report.citations.push(citation);
+                report.warnings.extend(warnings);
+            }
+            Err(err) => match options.on_error {
+                OnError::Fail => return Err(err),
+                OnError::Skip => {}
+                OnError::Collect => report.errors.push(err),
+            },
+        }
+    }
+    Ok(report)
+}
+
 /// Trait for implementing citation parsers.
 pub trait CitationParser {
     /// Parse a string containing one or more citations.
@@ -351,6 +822,165 @@ pub trait CitationParser {
     ///
     /// Returns `ParseError` if the input is malformed
     fn parse(&self, input: &str) -> std::result::Result<Vec<Citation>, crate::error::ParseError>;
+
+    /// Parse bytes in any supported encoding, returning the citations and the
+    /// detected encoding.
+    ///
+    /// The input is decoded with [`encoding::decode`], which recognises byte
+    /// order marks, UTF-16 without one, XML encoding declarations and
+    /// Windows-1252, and then passed to [`CitationParser::parse`]. Line
+    /// numbers and spans in errors refer to the decoded text.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` with [`ValueError::Encoding`] if the input cannot
+    /// be decoded, or the error from `parse`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, RisParser, TextEncoding};
+    ///
+    /// let input: Vec<u8> = "\u{feff}TY  - JOUR\nTI  - Étude\nER  -\n"
+    ///     .encode_utf16()
+    ///     .flat_map(u16::to_le_bytes)
+    ///     .collect();
+    /// let (citations, encoding) = RisParser::new().parse_bytes(&input).unwrap();
+    ///
+    /// assert_eq!(encoding, TextEncoding::Utf16Le);
+    /// assert_eq!(citations[0].title, "Étude");
+    /// ```
+    fn parse_bytes(
+        &self,
+        input: &[u8],
+    ) -> std::result::Result<(Vec<Citation>, TextEncoding), crate::error::ParseError> {
+        let (text, encoding) = encoding::decode(input)?;
+        self.parse(&text).map(|citations| (citations, encoding))
+    }
+
+    /// Parse citations from a reader, one record at a time.
+    ///
+    /// RIS, PubMed, EndNote Tagged, EndNote XML, ICTRP XML, Web of Science
+    /// plain-text, Ovid and CSV parsers read one record at a time, so memory
+    /// use is bounded by the largest record rather than the whole input.
+    /// PubMed XML, `.bib`, CSL-JSON and tab-delimited Web of Science parsers
+    /// read the whole input first but still yield one result per record.
+    /// Other implementations read the whole input and then call
+    /// [`CitationParser::parse`].
+    ///
+    /// Line numbers and spans in errors are relative to the start of the
+    /// input, as they are for `parse`. A record that fails to parse yields an
+    /// `Err` and iteration continues with the next record; read errors and
+    /// invalid UTF-8 end the iteration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, RisParser};
+    ///
+    /// let input = "TY  - JOUR\nTI  - First\nER  -\n\nTY  - JOUR\nTI  - Second\nER  -\n";
+    /// let titles: Vec<String> = RisParser::new()
+    ///     .parse_reader(input.as_bytes())
+    ///     .map(|citation| citation.unwrap().title)
+    ///     .collect();
+    ///
+    /// assert_eq!(titles, vec!["First", "Second"]);
+    /// ```
+    fn parse_reader<'r, R>(
+        &self,
+        reader: R,
+    ) -> Box<dyn Iterator<Item = std::result::Result<Citation, crate::error::ParseError>> + 'r>
+    where
+        R: std::io::Read + 'r,
+        Self: Sized,
+    {
+        let result =
+            stream::read_all(reader, CitationFormat::Unknown).and_then(|input| self.parse(&input));
+
+        match result {
+            Ok(citations) => Box::new(citations.into_iter().map(Ok)),
+            Err(err) => Box::new(std::iter::once(Err(err))),
+        }
+    }
+
+    /// Parse a string, optionally leaving out records that fail to parse
+    /// instead of failing the whole input.
+    ///
+    /// With [`OnError::Fail`] this behaves like [`CitationParser::parse`].
+    /// With [`OnError::Skip`] or [`OnError::Collect`], each record that fails
+    /// is left out and the remaining records are still returned; with
+    /// `Collect` its error, with the line number and span of the record in
+    /// `input`, is added to [`ParseReport::errors`]. Records are split as
+    /// [`CitationParser::parse_reader`] splits them, so an error that cannot
+    /// be attributed to one record, such as invalid JSON in a CSL-JSON
+    /// document, still leaves out everything after it.
+    ///
+    /// With [`ParseOptions::clean_text`], the titles, abstracts, journals and
+    /// author names of the returned citations are cleaned with
+    /// [`text::clean_citation`].
+    ///
+    /// Parsers also report [`ParseReport::warnings`] for data that was
+    /// dropped or coerced in the citations they return. Every parser reports
+    /// unparseable dates and invalid DOIs; RIS, PubMed and CSV parsers also
+    /// report ignored lines, unknown tags and other dropped values.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first `ParseError` only with [`OnError::Fail`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, OnError, ParseOptions, RisParser, ValueError};
+    ///
+    /// let input = "TY  - JOUR\nTI  - First\nER  -\nTY  - JOUR\nAU  - Smith, J\nER  -\nTY  - JOUR\nTI  - Third\nER  -\n";
+    /// let options = ParseOptions::new().with_on_error(OnError::Collect);
+    /// let report = RisParser::new().parse_with_options(input, &options).unwrap();
+    ///
+    /// assert_eq!(report.citations.len(), 2);
+    /// assert_eq!(report.errors[0].line, Some(4));
+    /// assert!(matches!(report.errors[0].error, ValueError::MissingValue { key: "TI", .. }));
+    /// ```
+    fn parse_with_options(
+        &self,
+        input: &str,
+        options: &ParseOptions,
+    ) -> std::result::Result<ParseReport, crate::error::ParseError>
+    where
+        Self: Sized,
+    {
+        if options.on_error == OnError::Fail {
+            let citations = self.parse(input)?;
+            return collect_report(
+                citations
+                    .into_iter()
+                    .map(|citation| Ok((citation, Vec::new()))),
+                options,
+            );
+        }
+
+        let records = self
+            .parse_reader(input.as_bytes())
+            .map(|record| record.map(|citation| (citation, Vec::new())));
+        collect_report(records, options)
+    }
+}
+
+/// Trait for implementing citation writers.
+///
+/// Writers are the inverse of [`CitationParser`]: they serialize normalized
+/// [`Citation`] values back into a concrete export format.
+pub trait CitationWriter {
+    /// Serialize citations into a string in the writer's format.
+    ///
+    /// # Arguments
+    ///
+    /// * `citations` - The citations to serialize
+    ///
+    /// # Returns
+    ///
+    /// The serialized citations. An empty slice produces an empty string.
+    fn write(&self, citations: &[Citation]) -> String;
 }
 
 /// Format detection and automatic parsing of citation files
@@ -396,6 +1026,15 @@ pub fn detect_and_parse(
             .map_err(CitationError::Parse);
     }
 
+    #[cfg(feature = "xml")]
+    if pubmed_xml::looks_like_pubmed_xml(content) {
+        let parser = PubMedXmlParser::new();
+        return parser
+            .parse(content)
+            .map(|citations| (citations, CitationFormat::PubMedXml))
+            .map_err(CitationError::Parse);
+    }
+
     if trimmed.starts_with("<?xml") || trimmed.starts_with("<xml>") {
         // EndNote XML format
         #[cfg(feature = "xml")]
@@ -408,6 +1047,24 @@ pub fn detect_and_parse(
         return Err(CitationError::UnknownFormat);
     }
 
+    #[cfg(feature = "csl")]
+    if csl::looks_like_csl_json(content) {
+        let parser = CslJsonParser::new();
+        return parser
+            .parse(content)
+            .map(|citations| (citations, CitationFormat::CslJson))
+            .map_err(CitationError::Parse);
+    }
+
+    #[cfg(feature = "ovid")]
+    if ovid::looks_like_ovid(content) {
+        let parser = OvidParser::new();
+        return parser
+            .parse(content)
+            .map(|citations| (citations, CitationFormat::Ovid))
+            .map_err(CitationError::Parse);
+    }
+
     // Check for RIS format (starts with TY or has TY  - pattern)
     if trimmed.starts_with("TY  -") || trimmed.contains("\nTY  -") {
         #[cfg(feature = "ris")]
@@ -436,6 +1093,15 @@ pub fn detect_and_parse(
         return Err(CitationError::UnknownFormat);
     }
 
+    #[cfg(feature = "wos")]
+    if wos::looks_like_wos(content) {
+        let parser = WosParser::new();
+        return parser
+            .parse(content)
+            .map(|citations| (citations, CitationFormat::WebOfScience))
+            .map_err(CitationError::Parse);
+    }
+
     // Check for EndNote Tagged / ENW format (records start with %0)
     #[cfg(feature = "enw")]
     if enw::looks_like_enw(content) {
@@ -468,6 +1134,40 @@ pub fn detect_and_parse(
     Err(CitationError::UnknownFormat)
 }
 
+/// Format detection and automatic parsing of citation files in any supported
+/// encoding.
+///
+/// The content is decoded with [`encoding::decode`] and then parsed with
+/// [`detect_and_parse`].
+///
+/// # Arguments
+///
+/// * `content` - The raw bytes of the file to parse
+///
+/// # Returns
+///
+/// A Result containing a vector of parsed Citations, the detected format and
+/// the detected encoding, or a CitationError if decoding or parsing fails
+///
+/// # Examples
+///
+/// ```
+/// use biblib::{TextEncoding, detect_and_parse_bytes};
+///
+/// let content = b"\xEF\xBB\xBFTY  - JOUR\nTI  - Example Title\nER  -";
+///
+/// let (citations, format, encoding) = detect_and_parse_bytes(content).unwrap();
+/// assert_eq!(format.as_str(), "RIS");
+/// assert_eq!(encoding, TextEncoding::Utf8 { bom: true });
+/// assert_eq!(citations[0].title, "Example Title");
+/// ```
+pub fn detect_and_parse_bytes(
+    content: &[u8],
+) -> std::result::Result<(Vec<Citation>, CitationFormat, TextEncoding), CitationError> {
+    let (text, encoding) = encoding::decode(content)?;
+    detect_and_parse(&text).map(|(citations, format)| (citations, format, encoding))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -478,16 +1178,71 @@ mod tests {
             given_name: Some("John".to_string()),
             middle_name: None,
             affiliations: Vec::new(),
+            role: ContributorRole::Author,
+            orcid: None,
+            is_organization: false,
         };
         let author2 = Author {
             name: "Smith".to_string(),
             given_name: Some("John".to_string()),
             middle_name: None,
             affiliations: Vec::new(),
+            role: ContributorRole::Author,
+            orcid: None,
+            is_organization: false,
         };
         assert_eq!(author1, author2);
     }
 
+    #[test]
+    fn test_author_is_same_person() {
+        let person = |name: &str, given: Option<&str>, orcid: Option<&str>| Author {
+            name: name.to_string(),
+            given_name: given.map(String::from),
+            middle_name: None,
+            affiliations: Vec::new(),
+            role: ContributorRole::Author,
+            orcid: orcid.map(String::from),
+            is_organization: false,
+        };
+        let orcid = Some("0000-0002-1825-0097");
+
+        assert!(person("Smith", Some("John"), None).is_same_person(&person(
+            "smith",
+            Some("J"),
+            None
+        )));
+        assert!(person("Smith", None, None).is_same_person(&person("Smith", Some("Jane"), None)));
+        assert!(!person("Smith", Some("John"), None).is_same_person(&person(
+            "Smith",
+            Some("Jane"),
+            None
+        )));
+        // Matching ORCID iDs win over differently written names.
+        assert!(person("Smith", Some("John"), orcid).is_same_person(&person(
+            "Smith-Jones",
+            Some("J"),
+            orcid
+        )));
+        assert!(
+            !person("Smith", Some("John"), orcid).is_same_person(&person(
+                "Smith",
+                Some("John"),
+                Some("0000-0001-5109-3700")
+            ))
+        );
+        // Only one side has an iD: fall back to the name.
+        assert!(person("Smith", Some("John"), orcid).is_same_person(&person(
+            "Smith",
+            Some("J"),
+            None
+        )));
+
+        let group = Author::organization("Test Study Group", ContributorRole::Author);
+        assert!(group.is_same_person(&group.clone()));
+        assert!(!group.is_same_person(&person("Test Study Group", None, None)));
+    }
+
     #[test]
     fn test_detect_and_parse_ris() {
         let content = r#"TY  - JOUR
@@ -523,6 +1278,54 @@ FAU - Smith, John"#;
         assert_eq!(citations[0].title, "Test Title");
     }
 
+    #[cfg(feature = "xml")]
+    #[test]
+    fn test_detect_and_parse_bytes_utf16_endnote() {
+        let content = "\u{feff}<?xml version=\"1.0\" encoding=\"UTF-16\"?>
+<xml><records><record>
+<titles><title>Étude</title></titles>
+</record></records></xml>";
+        let bytes: Vec<u8> = content.encode_utf16().flat_map(u16::to_le_bytes).collect();
+
+        let (citations, format, encoding) = detect_and_parse_bytes(&bytes).unwrap();
+        assert_eq!(format, CitationFormat::EndNoteXml);
+        assert_eq!(encoding, TextEncoding::Utf16Le);
+        assert_eq!(citations[0].title, "Étude");
+    }
+
+    #[test]
+    fn test_detect_and_parse_bytes_undecodable() {
+        let bytes = b"\xEF\xBB\xBFTY  - JOUR\nTI  - \xC9tude\nER  -";
+        let err = detect_and_parse_bytes(bytes).unwrap_err();
+        assert!(matches!(
+            err,
+            CitationError::Parse(ParseError {
+                error: ValueError::Encoding { encoding: "UTF-8" },
+                ..
+            })
+        ));
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    fn test_detect_and_parse_pubmed_xml() {
+        let content = r#"<?xml version="1.0" ?>
+<!DOCTYPE PubmedArticleSet>
+<PubmedArticleSet>
+  <PubmedArticle>
+    <MedlineCitation>
+      <PMID Version="1">12345678</PMID>
+      <Article><ArticleTitle>Test Title</ArticleTitle></Article>
+    </MedlineCitation>
+  </PubmedArticle>
+</PubmedArticleSet>"#;
+
+        let (citations, format) = detect_and_parse(content).unwrap();
+        assert_eq!(format, CitationFormat::PubMedXml);
+        assert_eq!(citations[0].title, "Test Title");
+        assert_eq!(citations[0].pmid.as_deref(), Some("12345678"));
+    }
+
     #[cfg(feature = "xml")]
     #[test]
     fn test_detect_and_parse_ictrp_xml() {
@@ -567,6 +1370,36 @@ FAU - Smith, John"#;
         assert_eq!(citations[0].citation_type, vec!["article"]);
     }
 
+    #[cfg(feature = "csl")]
+    #[test]
+    fn test_detect_and_parse_csl_json() {
+        let content = r#"[{"id": "a", "type": "article-journal", "title": "Test Title"}]"#;
+
+        let (citations, format) = detect_and_parse(content).unwrap();
+        assert_eq!(format, CitationFormat::CslJson);
+        assert_eq!(citations[0].title, "Test Title");
+    }
+
+    #[cfg(feature = "wos")]
+    #[test]
+    fn test_detect_and_parse_web_of_science() {
+        let content = "FN Clarivate Analytics Web of Science\nVR 1.0\nPT J\nTI Test Title\nUT WOS:000000000000001\nER\n\nEF\n";
+
+        let (citations, format) = detect_and_parse(content).unwrap();
+        assert_eq!(format, CitationFormat::WebOfScience);
+        assert_eq!(citations[0].title, "Test Title");
+    }
+
+    #[cfg(feature = "ovid")]
+    #[test]
+    fn test_detect_and_parse_ovid() {
+        let content = "Database: Embase <1974 to 2024>\n\n<1>\nVN  - Ovid Technologies\nDB  - Embase\nAN  - 2031234567\nTI  - Test Title\n";
+
+        let (citations, format) = detect_and_parse(content).unwrap();
+        assert_eq!(format, CitationFormat::Ovid);
+        assert_eq!(citations[0].title, "Test Title");
+    }
+
     #[test]
     fn test_detect_and_parse_empty() {
         let result = detect_and_parse("");
@@ -599,4 +1432,70 @@ FAU - Smith, John"#;
             Some("NCT00000001")
         );
     }
+
+    #[cfg(feature = "ris")]
+    #[test]
+    fn test_parse_with_options_collects_record_errors() {
+        let input = "TY  - JOUR\nTI  - First\nER  -\nTY  - JOUR\nAU  - Smith, J\nER  -\nTY  - JOUR\nTI  - Third\nER  -\n";
+        let parser = RisParser::new();
+        let expected = parser.parse(input).unwrap_err();
+
+        let options = ParseOptions::new().with_on_error(OnError::Collect);
+        let report = parser.parse_with_options(input, &options).unwrap();
+        let titles: Vec<_> = report.citations.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["First", "Third"]);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].line, expected.line);
+        assert_eq!(report.errors[0].span, expected.span);
+
+        let options = ParseOptions::new().with_on_error(OnError::Skip);
+        let report = parser.parse_with_options(input, &options).unwrap();
+        assert_eq!(report.citations.len(), 2);
+        assert!(report.errors.is_empty());
+
+        let err = parser
+            .parse_with_options(input, &ParseOptions::new())
+            .unwrap_err();
+        assert_eq!(err.line, expected.line);
+    }
+
+    #[cfg(feature = "pubmed")]
+    #[test]
+    fn test_parse_with_options_continues_after_missing_title() {
+        let input = "PMID- 1\nTI  - First\n\nPMID- 2\nAB  - No title\n\nPMID- 3\nTI  - Third\n";
+        let options = ParseOptions::new().with_on_error(OnError::Collect);
+        let report = PubMedParser::new()
+            .parse_with_options(input, &options)
+            .unwrap();
+
+        assert_eq!(report.citations.len(), 2);
+        assert_eq!(report.citations[1].pmid.as_deref(), Some("3"));
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].line, Some(4));
+    }
+
+    #[cfg(feature = "enw")]
+    #[test]
+    fn test_parse_with_options_cleans_text() {
+        let input = "%0 Journal Article\n%T Ã‰tude of H<sub>2</sub>O &amp;#x2013; a review\n%A M<U+00FC>ller, J\n%J J Chem &amp; Phys\n%K &amp;\n";
+        let parser = EnwParser::new();
+
+        let report = parser
+            .parse_with_options(input, &ParseOptions::new())
+            .unwrap();
+        assert_eq!(
+            report.citations[0].title,
+            "Ã‰tude of H<sub>2</sub>O &amp;#x2013; a review"
+        );
+
+        let options = ParseOptions::new().with_clean_text(true);
+        let citation = &parser
+            .parse_with_options(input, &options)
+            .unwrap()
+            .citations[0];
+        assert_eq!(citation.title, "Étude of H2O – a review");
+        assert_eq!(citation.authors[0].name, "Müller");
+        assert_eq!(citation.journal.as_deref(), Some("J Chem & Phys"));
+        assert_eq!(citation.keywords, vec!["&amp;"]);
+    }
 }
@@ -0,0 +1,329 @@
+//! Ovid parser implementation.
+//!
+//! Parses the Medlars-style text export produced by Ovid for Embase, Ovid
+//! MEDLINE and the other databases it hosts. Each record is numbered with a
+//! `<N>` line and fields are written as `TAG - value`.
+//!
+//! # Example
+//!
+//! ```
+//! use biblib::{CitationParser, OvidParser};
+//!
+//! let input = r#"<1>
+//! VN  - Ovid Technologies
+//! DB  - Embase
+//! AN  - 2012345678
+//! TI  - Example title that wraps
+//!       onto a second line
+//! AU  - Smith J.
+//! MH  - *heart disease
+//! MH  - human
+//! "#;
+//!
+//! let citations = OvidParser::new().parse(input).unwrap();
+//! assert_eq!(citations[0].title, "Example title that wraps onto a second line");
+//! assert_eq!(citations[0].accession_number.as_deref(), Some("2012345678"));
+//! assert_eq!(citations[0].mesh_terms, vec!["*heart disease", "human"]);
+//! assert_eq!(citations[0].extra_fields["DB"], vec!["Embase"]);
+//! ```
+//!
+//! The `DB` value can be used as the source name when deduplicating results
+//! from several databases:
+//!
+//! ```
+//! # #[cfg(feature = "dedupe")]
+//! # {
+//! use biblib::dedupe::{Deduplicator, DeduplicatorConfig};
+//! use biblib::{CitationParser, OvidParser};
+//!
+//! let record = "TI  - Title\nJN  - Journal\nYR  - 2020\nDO  - 10.1000/x\n";
+//! let input = format!("<1>\nDB  - Embase\nAN  - 1\n{record}\n<2>\nDB  - Ovid MEDLINE(R)\nUI  - 2\n{record}");
+//! let citations = OvidParser::new().parse(&input).unwrap();
+//! let sources: Vec<&str> = citations
+//!     .iter()
+//!     .map(|citation| citation.extra_fields["DB"][0].as_str())
+//!     .collect();
+//!
+//! let deduplicator = Deduplicator::new().with_config(DeduplicatorConfig {
+//!     source_preferences: vec!["Ovid MEDLINE(R)".to_string()],
+//!     ..Default::default()
+//! });
+//! let groups = deduplicator
+//!     .find_duplicates_with_sources(&citations, &sources)
+//!     .unwrap();
+//! assert_eq!(groups[0].unique.accession_number.as_deref(), Some("2"));
+//! # }
+//! ```
+
+mod parse;
+
+use crate::error::ParseError;
+use crate::stream::chunk::parse_chunks;
+use crate::stream::line::LineChunks;
+use crate::{
+    Citation, CitationFormat, CitationParser, OnError, ParseOptions, ParseReport, collect_report,
+};
+pub(crate) use parse::looks_like_ovid;
+use parse::{ovid_boundary, parse_ovid, parse_ovid_with_warnings};
+use std::io::BufReader;
+
+/// Parser for Ovid text exports (Embase, Ovid MEDLINE and other Ovid
+/// databases).
+///
+/// Tags are mapped as follows:
+///
+/// - `TI` → `title`, with wrapped lines joined by a space
+/// - `FA` (or `AU` when there are no full names) → `authors`
+/// - `UI` (or `AN`) → `accession_number`; `UI` is also the `pmid` for Ovid
+///   MEDLINE records, and `PM` → `pmid` otherwise. Embase accession numbers
+///   are also added to `identifiers`
+/// - `MH`, `SH`, `MJ`, `MI` → `mesh_terms`, with `MJ` headings marked as
+///   major topics (`*`)
+/// - `JN` → `journal`, `JA` → `journal_abbr`
+/// - `DP` (or `YR`) → `date`
+/// - `VO`/`VI`, `IP`, `PG` → `volume`, `issue`, `pages`
+/// - `IS` → `issn`, `DO` → `doi`, `PMC` → `pmc_id`
+/// - `PT`, `AB`, `KW`, `UR`, `LG`, `PU` → `citation_type`, `abstract_text`,
+///   `keywords`, `urls`, `language`, `publisher`
+///
+/// All other tags are kept in `extra_fields` under their tag, including `DB`,
+/// the name of the database the record was exported from.
+#[derive(Debug, Clone, Default)]
+pub struct OvidParser;
+
+impl OvidParser {
+    /// Creates a new Ovid parser instance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CitationParser for OvidParser {
+    fn parse(&self, input: &str) -> Result<Vec<Citation>, ParseError> {
+        if input.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        parse_ovid(input)
+    }
+
+    /// Reads one `<N>` record at a time.
+    fn parse_reader<'r, R>(
+        &self,
+        reader: R,
+    ) -> Box<dyn Iterator<Item = Result<Citation, ParseError>> + 'r>
+    where
+        R: std::io::Read + 'r,
+    {
+        let chunks = LineChunks::new(BufReader::new(reader), CitationFormat::Ovid, ovid_boundary);
+        Box::new(parse_chunks(chunks, parse_ovid))
+    }
+
+    /// Reports unparseable `DP`/`YR` dates and invalid `DO` DOIs as warnings.
+    fn parse_with_options(
+        &self,
+        input: &str,
+        options: &ParseOptions,
+    ) -> Result<ParseReport, ParseError> {
+        if options.on_error == OnError::Fail {
+            let records = parse_ovid_with_warnings(input)?;
+            return collect_report(records.into_iter().map(Ok), options);
+        }
+
+        let chunks = LineChunks::new(input.as_bytes(), CitationFormat::Ovid, ovid_boundary);
+        collect_report(parse_chunks(chunks, parse_ovid_with_warnings), options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Scheme, ValueError};
+    use pretty_assertions::assert_eq;
+
+    const EXPORT: &str = r#"Database: Embase <1974 to 2024 June 10>, Ovid MEDLINE(R) ALL <1946 to June 10, 2024>
+Search Strategy:
+--------------------------------------------------------------------------------
+1     heart failure.mp. (12345)
+
+<1>
+VN  - Ovid Technologies
+DB  - Embase
+AN  - 2031234567
+TI  - Heart failure outcomes in a
+      multicentre cohort
+AU  - Smith J.
+AU  - Doe J.A.
+IN  - (Smith) Department of Cardiology, Example University, Springfield, United States
+SO  - Journal of Examples. 12(3) (pp 100-110), 2020.
+AB  - Background text.
+AB  - Conclusion text.
+IS  - 1234-5678 (electronic)
+IS  - 8765-4321
+JN  - Journal of Examples
+VO  - 12
+IP  - 3
+PG  - 100-110
+YR  - 2020
+MJ  - heart failure
+MI  - human
+MI  - cohort analysis
+KW  - outcomes
+KW  - cohort
+PT  - Journal: Article
+LG  - English
+PM  - 32000001
+DO  - https://dx.doi.org/10.1000/EXAMPLE
+UR  - https://example.org/article
+
+<2>
+VN  - Ovid Technologies
+DB  - Ovid MEDLINE(R) ALL
+UI  - 32000001
+TI  - Heart failure outcomes in a multicentre cohort
+DP  - 2020 Mar
+FA  - Smith, John
+FA  - Doe, Jane A
+AU  - Smith J
+AU  - Doe JA
+MH  - *Heart Failure
+MH  - Humans
+JN  - Journal of Examples
+JA  - J Ex
+VI  - 12
+IP  - 3
+PG  - 100-10
+"#;
+
+    #[test]
+    fn test_parse_embase_and_medline_records() {
+        let citations = OvidParser::new().parse(EXPORT).unwrap();
+        assert_eq!(citations.len(), 2);
+
+        let embase = &citations[0];
+        assert_eq!(
+            embase.title,
+            "Heart failure outcomes in a multicentre cohort"
+        );
+        assert_eq!(embase.accession_number.as_deref(), Some("2031234567"));
+        assert_eq!(embase.identifier(Scheme::Embase), Some("2031234567"));
+        assert_eq!(embase.pmid.as_deref(), Some("32000001"));
+        assert_eq!(embase.citation_type, vec!["Journal: Article"]);
+        assert_eq!(embase.authors[1].name, "Doe");
+        assert_eq!(embase.authors[1].given_name.as_deref(), Some("J.A"));
+        assert_eq!(embase.journal.as_deref(), Some("Journal of Examples"));
+        assert_eq!(embase.volume.as_deref(), Some("12"));
+        assert_eq!(embase.issue.as_deref(), Some("3"));
+        assert_eq!(embase.pages.as_deref(), Some("100-110"));
+        assert_eq!(embase.issn, vec!["1234-5678 (electronic)", "8765-4321"]);
+        assert_eq!(embase.doi.as_deref(), Some("10.1000/example"));
+        assert_eq!(embase.date.as_ref().map(|date| date.year), Some(2020));
+        assert_eq!(
+            embase.abstract_text.as_deref(),
+            Some("Background text.\n\nConclusion text.")
+        );
+        assert_eq!(
+            embase.mesh_terms,
+            vec!["*heart failure", "human", "cohort analysis"]
+        );
+        assert_eq!(embase.keywords, vec!["outcomes", "cohort"]);
+        assert_eq!(embase.urls, vec!["https://example.org/article"]);
+        assert_eq!(embase.extra_fields["DB"], vec!["Embase"]);
+        assert_eq!(embase.extra_fields["VN"], vec!["Ovid Technologies"]);
+        assert!(embase.extra_fields.contains_key("IN"));
+        assert!(embase.extra_fields.contains_key("SO"));
+        assert!(!embase.extra_fields.contains_key("YR"));
+
+        let medline = &citations[1];
+        assert_eq!(medline.accession_number.as_deref(), Some("32000001"));
+        assert_eq!(medline.pmid.as_deref(), Some("32000001"));
+        assert!(medline.identifiers.is_empty());
+        assert_eq!(medline.authors[1].name, "Doe");
+        assert_eq!(medline.authors[1].given_name.as_deref(), Some("Jane"));
+        assert_eq!(medline.authors[1].middle_name.as_deref(), Some("A"));
+        assert_eq!(medline.mesh_terms, vec!["*Heart Failure", "Humans"]);
+        assert_eq!(medline.journal_abbr.as_deref(), Some("J Ex"));
+        assert_eq!(medline.pages.as_deref(), Some("100-110"));
+        let date = medline.date.as_ref().unwrap();
+        assert_eq!((date.year, date.month), (2020, Some(3)));
+        assert_eq!(medline.extra_fields["DB"], vec!["Ovid MEDLINE(R) ALL"]);
+        assert!(!medline.extra_fields.contains_key("AU"));
+    }
+
+    #[test]
+    fn test_missing_title_reports_line_and_span() {
+        let input = "<1>\nDB  - Embase\nAN  - 1\n\n<2>\nDB  - Embase\nAN  - 2\nTI  - Title\n";
+        let err = OvidParser::new().parse(input).unwrap_err();
+
+        assert_eq!(err.line, Some(1));
+        assert_eq!(err.format, CitationFormat::Ovid);
+        assert!(matches!(
+            err.error,
+            ValueError::MissingValue { key: "TI", .. }
+        ));
+        let span = err.span.expect("expected span");
+        assert_eq!(&input[span.start..span.end], "<1>\nDB  - Embase\nAN  - 1");
+    }
+
+    #[test]
+    fn test_malformed_line_is_error() {
+        let input = "<1>\nTI  - Title\nnot a tag\n";
+        let err = OvidParser::new().parse(input).unwrap_err();
+
+        assert_eq!(err.line, Some(3));
+        assert!(matches!(err.error, ValueError::Syntax(_)));
+    }
+
+    #[test]
+    fn test_parse_reader_matches_parse() {
+        let parser = OvidParser::new();
+        let streamed: Result<Vec<_>, _> = parser.parse_reader(EXPORT.as_bytes()).collect();
+        assert_eq!(streamed.unwrap(), parser.parse(EXPORT).unwrap());
+    }
+
+    #[test]
+    fn test_parse_reader_continues_after_bad_record() {
+        let input = "<1>\nDB  - Embase\nAN  - 1\n\n<2>\nTI  - Title\nnot a tag\n\n<3>\nAN  - 3\nTI  - Third\n";
+        let streamed: Vec<_> = OvidParser::new().parse_reader(input.as_bytes()).collect();
+
+        assert_eq!(streamed.len(), 3);
+        let err = streamed[0].as_ref().unwrap_err();
+        assert_eq!(err.line, Some(1));
+        let span = err.span.clone().expect("expected span");
+        assert_eq!(&input[span.start..span.end], "<1>\nDB  - Embase\nAN  - 1");
+        assert_eq!(streamed[1].as_ref().unwrap_err().line, Some(7));
+        assert_eq!(streamed[2].as_ref().unwrap().title, "Third");
+    }
+
+    #[test]
+    fn test_parse_with_options_reports_warnings() {
+        use crate::{OnError, ParseOptions, WarningKind};
+
+        let input =
+            "<1>\nTI  - First\nDP  - 2020\n\n<2>\nTI  - Second\nDP  - someday\nDO  - not a doi\n";
+        for on_error in [OnError::Fail, OnError::Collect] {
+            let options = ParseOptions::new().with_on_error(on_error);
+            let report = OvidParser::new()
+                .parse_with_options(input, &options)
+                .unwrap();
+
+            assert_eq!(report.citations.len(), 2);
+            let kinds: Vec<_> = report.warnings.iter().map(|w| &w.kind).collect();
+            assert_eq!(
+                kinds,
+                vec![
+                    &WarningKind::UnparseableDate {
+                        key: "DP".to_string(),
+                        value: "someday".to_string()
+                    },
+                    &WarningKind::InvalidDoi {
+                        key: "DO".to_string(),
+                        value: "not a doi".to_string()
+                    },
+                ]
+            );
+            assert_eq!(report.warnings[0].line, Some(5));
+        }
+    }
+}
@@ -0,0 +1,421 @@
+//! Ovid parsing implementation.
+//!
+//! Ovid's Medlars-style text export numbers each record with a `<N>` line and
+//! writes one `TAG - value` line per field, with tags padded to four columns
+//! (`DB  - Embase`, `PMC - PMC1234567`). Long values wrap onto lines indented
+//! with spaces. Text before the first `<N>` line, such as the database banner
+//! and search strategy, is ignored.
+
+use crate::error::{ParseError, ParseWarning, SourceSpan, ValueError, WarningKind, fields};
+use crate::stream::line::Boundary;
+use crate::utils::{
+    detect_identifier, format_doi, format_page_numbers, parse_author_name, parse_pubmed_date,
+    parse_year_only, sort_serial_numbers, split_given_and_middle,
+};
+use crate::{Author, Citation, CitationFormat, ContributorRole, Identifier, Scheme};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+struct RawOvidRecord {
+    data: HashMap<String, Vec<String>>,
+    start_line: usize,
+    record_span: SourceSpan,
+    warnings: Vec<ParseWarning>,
+}
+
+impl RawOvidRecord {
+    fn new(start_line: usize, record_span: SourceSpan) -> Self {
+        Self {
+            data: HashMap::new(),
+            start_line,
+            record_span,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Add a warning located at the start of this record.
+    fn warn(&mut self, kind: WarningKind) {
+        self.warnings.push(
+            ParseWarning::at_line(self.start_line, CitationFormat::Ovid, kind)
+                .with_span(self.record_span.clone()),
+        );
+    }
+
+    fn add_data(&mut self, tag: &str, value: &str) {
+        self.data
+            .entry(tag.to_string())
+            .or_default()
+            .push(value.trim().to_string());
+    }
+
+    /// Append a wrapped line to the last value of `tag`.
+    fn continue_data(&mut self, tag: &str, value: &str) {
+        if let Some(last) = self.data.get_mut(tag).and_then(|values| values.last_mut()) {
+            if !last.is_empty() {
+                last.push(' ');
+            }
+            last.push_str(value.trim());
+        }
+    }
+
+    fn remove_all(&mut self, tag: &str) -> Vec<String> {
+        self.data
+            .remove(tag)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|value| !value.is_empty())
+            .collect()
+    }
+
+    fn take_first(&mut self, tag: &str) -> Option<String> {
+        let mut values = self.remove_all(tag);
+        if values.is_empty() {
+            return None;
+        }
+        let value = values.remove(0);
+        if !values.is_empty() {
+            self.data.insert(tag.to_string(), values);
+        }
+        Some(value)
+    }
+
+    fn first(&self, tag: &str) -> Option<&str> {
+        self.data
+            .get(tag)
+            .and_then(|values| values.iter().find(|value| !value.is_empty()))
+            .map(String::as_str)
+    }
+}
+
+/// Quick check used by format auto-detection: the first `<N>` record marker
+/// must be followed by an Ovid tag line.
+pub(crate) fn looks_like_ovid(content: &str) -> bool {
+    let mut lines = content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty());
+
+    lines.by_ref().find(|line| is_record_marker(line)).is_some()
+        && lines.next().is_some_and(|line| split_tag(line).is_some())
+}
+
+/// Parse an Ovid text export into citations.
+pub(crate) fn parse_ovid(content: &str) -> Result<Vec<Citation>, ParseError> {
+    Ok(parse_ovid_with_warnings(content)?
+        .into_iter()
+        .map(|(citation, _)| citation)
+        .collect())
+}
+
+/// Parse an Ovid text export into citations, keeping the warnings for each
+/// citation.
+pub(crate) fn parse_ovid_with_warnings(
+    content: &str,
+) -> Result<Vec<(Citation, Vec<ParseWarning>)>, ParseError> {
+    parse_records(content)?
+        .into_iter()
+        .map(into_citation_with_warnings)
+        .collect()
+}
+
+fn parse_records(content: &str) -> Result<Vec<RawOvidRecord>, ParseError> {
+    let text_ptr = content.as_ptr() as usize;
+    let mut records = Vec::new();
+    let mut current: Option<RawOvidRecord> = None;
+    let mut last_tag: Option<String> = None;
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let line_start = raw_line.as_ptr() as usize - text_ptr;
+        let line_end = line_start + raw_line.len();
+        let line = raw_line.trim_start_matches('\u{feff}').trim_end();
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if is_record_marker(line) {
+            records.extend(current.take());
+            current = Some(RawOvidRecord::new(
+                line_number,
+                SourceSpan::new(line_start, line_end),
+            ));
+            last_tag = None;
+            continue;
+        }
+
+        // Banner and search strategy lines before the first record.
+        let Some(record) = current.as_mut() else {
+            continue;
+        };
+        record.record_span.end = line_end;
+
+        if line.starts_with(char::is_whitespace) {
+            if let Some(tag) = &last_tag {
+                record.continue_data(tag, line);
+            }
+            continue;
+        }
+
+        let Some((tag, value)) = split_tag(line) else {
+            return Err(ParseError::at_line(
+                line_number,
+                CitationFormat::Ovid,
+                ValueError::Syntax(format!("Malformed Ovid line: '{}'", line)),
+            )
+            .with_span(SourceSpan::new(line_start, line_end)));
+        };
+        record.add_data(tag, value);
+        last_tag = Some(tag.to_string());
+    }
+
+    records.extend(current);
+    Ok(records)
+}
+
+/// Record boundaries for [`OvidParser::parse_reader`](crate::OvidParser):
+/// each `<N>` line starts a record.
+pub(crate) fn ovid_boundary(_previous: Option<&str>, line: &str) -> Boundary {
+    if is_record_marker(line.trim_start_matches('\u{feff}')) {
+        Boundary::Before
+    } else {
+        Boundary::Inside
+    }
+}
+
+/// Whether `line` is a record number such as `<1>` or `<25>`.
+fn is_record_marker(line: &str) -> bool {
+    line.trim()
+        .strip_prefix('<')
+        .and_then(|rest| rest.strip_suffix('>'))
+        .is_some_and(|number| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Split `TAG - value`, where the tag is up to four uppercase letters or
+/// digits padded with spaces to four columns.
+fn split_tag(line: &str) -> Option<(&str, &str)> {
+    let label = line.get(..4)?;
+    let rest = line[4..].strip_prefix('-')?;
+    let tag = label.trim_end();
+    let valid_tag = tag.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+        && tag
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+    (valid_tag && (rest.is_empty() || rest.starts_with(' '))).then(|| (tag, rest.trim()))
+}
+
+/// Convert a record to a citation, returning the warnings for dates and DOIs
+/// that could not be read.
+fn into_citation_with_warnings(
+    mut raw: RawOvidRecord,
+) -> Result<(Citation, Vec<ParseWarning>), ParseError> {
+    let title = raw.take_first("TI").ok_or_else(|| {
+        ParseError::at_line(
+            raw.start_line,
+            CitationFormat::Ovid,
+            ValueError::MissingValue {
+                field: fields::TITLE,
+                key: "TI",
+            },
+        )
+        .with_span(raw.record_span.clone())
+    })?;
+
+    // Ovid MEDLINE's unique identifier is the PMID; other databases carry
+    // the PMID of their MEDLINE counterpart in `PM`.
+    let database = raw
+        .first("DB")
+        .map(|db| db.to_ascii_uppercase())
+        .unwrap_or_default();
+    let is_medline = database.contains("MEDLINE");
+    let accession_number = raw.take_first("UI").or_else(|| raw.take_first("AN"));
+    let pmid = raw.take_first("PM").or_else(|| {
+        accession_number
+            .clone()
+            .filter(|id| is_medline && id.bytes().all(|b| b.is_ascii_digit()))
+    });
+    // Embase accession numbers are bare numbers, so they are only typed
+    // when the record says it came from Embase.
+    let mut identifiers: Vec<Identifier> = accession_number
+        .as_deref()
+        .and_then(|id| {
+            if database.contains("EMBASE") {
+                Identifier::new(Scheme::Embase, id)
+            } else {
+                detect_identifier(id)
+            }
+        })
+        .into_iter()
+        .collect();
+
+    let authors = extract_authors(&mut raw);
+    let date = match raw.first("DP").and_then(parse_pubmed_date) {
+        Some(date) => {
+            raw.remove_all("DP");
+            Some(date)
+        }
+        None => raw.first("YR").and_then(parse_year_only),
+    };
+    if date.is_some() {
+        raw.remove_all("YR");
+    } else if let Some((key, value)) = ["DP", "YR"]
+        .into_iter()
+        .find_map(|key| Some((key, raw.first(key)?.to_string())))
+    {
+        raw.warn(WarningKind::UnparseableDate {
+            key: key.to_string(),
+            value,
+        });
+    }
+
+    let mut issn = Vec::new();
+    for value in raw.remove_all("IS") {
+        sort_serial_numbers(&value, &mut issn, &mut identifiers);
+    }
+    let doi = raw.take_first("DO").and_then(|value| {
+        let doi = format_doi(&value);
+        if doi.is_none() {
+            raw.warn(WarningKind::InvalidDoi {
+                key: "DO".to_string(),
+                value,
+            });
+        }
+        doi
+    });
+    let abstract_text = Some(raw.remove_all("AB").join("\n\n")).filter(|text| !text.is_empty());
+
+    let citation = Citation {
+        citation_type: raw.remove_all("PT"),
+        title,
+        authors,
+        contributors: Vec::new(),
+        journal: raw.take_first("JN"),
+        journal_abbr: raw.take_first("JA"),
+        date,
+        dates: Vec::new(),
+        volume: raw.take_first("VO").or_else(|| raw.take_first("VI")),
+        issue: raw.take_first("IP"),
+        pages: raw
+            .take_first("PG")
+            .map(|pages| format_page_numbers(&pages)),
+        issn,
+        doi,
+        accession_number,
+        pmid,
+        pmc_id: raw.take_first("PMC"),
+        identifiers,
+        abstract_text,
+        keywords: raw.remove_all("KW"),
+        urls: raw.remove_all("UR"),
+        language: raw.take_first("LG"),
+        mesh_terms: extract_headings(&mut raw),
+        publisher: raw.take_first("PU"),
+        extra_fields: raw
+            .data
+            .into_iter()
+            .filter(|(_, values)| values.iter().any(|value| !value.is_empty()))
+            .collect(),
+    };
+    Ok((citation, raw.warnings))
+}
+
+/// Authors from `FA` (full names), falling back to `AU`.
+fn extract_authors(raw: &mut RawOvidRecord) -> Vec<Author> {
+    let full_names = raw.remove_all("FA");
+    let short_names = raw.remove_all("AU");
+    let names = if full_names.is_empty() {
+        short_names
+    } else {
+        full_names
+    };
+
+    names
+        .iter()
+        .map(|name| {
+            let (family, given) = parse_author_name(name);
+            let (given_name, middle_name) = if given.is_empty() {
+                (None, None)
+            } else {
+                split_given_and_middle(given.trim_end_matches('.'))
+            };
+            Author {
+                name: family,
+                given_name,
+                middle_name,
+                affiliations: Vec::new(),
+                role: ContributorRole::Author,
+                orcid: None,
+                is_organization: false,
+            }
+        })
+        .collect()
+}
+
+/// MeSH (`MH`) and Emtree (`SH`, `MJ`, `MI`) headings. Major headings from
+/// `MJ` are marked with a leading `*`, as `MH` already does.
+fn extract_headings(raw: &mut RawOvidRecord) -> Vec<String> {
+    let mut headings = raw.remove_all("MH");
+    headings.extend(raw.remove_all("SH"));
+    headings.extend(
+        raw.remove_all("MJ")
+            .into_iter()
+            .map(|heading| format!("*{}", heading.trim_start_matches('*'))),
+    );
+    headings.extend(raw.remove_all("MI"));
+
+    let mut seen = std::collections::HashSet::new();
+    headings.retain(|heading| seen.insert(heading.clone()));
+    headings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("DB  - Embase", Some(("DB", "Embase")))]
+    #[case("PMC - PMC1234567", Some(("PMC", "PMC1234567")))]
+    #[case("AB  -", Some(("AB", "")))]
+    #[case("TY  - JOUR", Some(("TY", "JOUR")))]
+    #[case("TI - Title", None)]
+    #[case("db  - Embase", None)]
+    #[case("Database: Embase", None)]
+    fn test_split_tag(#[case] line: &str, #[case] expected: Option<(&str, &str)>) {
+        assert_eq!(split_tag(line), expected);
+    }
+
+    #[rstest]
+    #[case("<1>", true)]
+    #[case("<125>", true)]
+    #[case("<>", false)]
+    #[case("<a>", false)]
+    #[case("<1> extra", false)]
+    fn test_is_record_marker(#[case] line: &str, #[case] expected: bool) {
+        assert_eq!(is_record_marker(line), expected);
+    }
+
+    #[test]
+    fn test_looks_like_ovid() {
+        assert!(looks_like_ovid(
+            "<1>\nVN  - Ovid Technologies\nDB  - Embase\n"
+        ));
+        assert!(looks_like_ovid(
+            "Database: Embase <1974 to 2024>\nSearch Strategy:\n\n<1>\nDB  - Embase\n"
+        ));
+        assert!(!looks_like_ovid("TY  - JOUR\nTI  - Title\nER  -\n"));
+        assert!(!looks_like_ovid("<1>\nAccession Number\n  123\n"));
+    }
+
+    #[test]
+    fn test_extract_headings_marks_major_topics() {
+        let mut raw = RawOvidRecord::new(1, SourceSpan::new(0, 0));
+        raw.add_data("MJ", "heart disease");
+        raw.add_data("MI", "human");
+        raw.add_data("SH", "*heart disease");
+
+        assert_eq!(extract_headings(&mut raw), vec!["*heart disease", "human"]);
+    }
+}
@@ -1,6 +1,7 @@
 //! (Pedantically correct) order-dependent parsing logic of author information
 //! from PubMed formatted `.nbib` files.
 
+use crate::ContributorRole;
 use crate::pubmed::tags::PubmedTag;
 use compact_str::CompactString;
 use std::borrow::Cow;
@@ -25,6 +26,11 @@ impl AuthorName {
         AuthorName { name, full: true }
     }
 
+    /// Get the name as written.
+    pub(crate) fn as_str(&self) -> &str {
+        &self.name
+    }
+
     /// Get the author's last (family) name.
     pub fn last_name(&self) -> &str {
         let parts = if self.full {
@@ -58,7 +64,7 @@ impl AuthorName {
         }
     }
 
-    #[allow(dead_code)]
+    /// Get the name as an `AU` value, e.g. "Watson JD".
     pub fn as_au(&self) -> Cow<'_, str> {
         if self.full {
             let initials = self.first_initials();
@@ -118,8 +124,22 @@ pub(crate) enum ConsecutiveTag {
     Author,
     /// FAU - Full author name
     FullAuthorName,
+    /// CN - Corporate author
+    CorporateAuthor,
+    /// ED - Editor
+    Editor,
+    /// FED - Full editor name
+    FullEditorName,
+    /// IR - Investigator
+    Investigator,
+    /// FIR - Full investigator name
+    FullInvestigatorName,
     /// AD - Affiliation
     Affiliation,
+    /// IRAD - Investigator affiliation
+    InvestigatorAffiliation,
+    /// AUID - Author identifier
+    AuthorIdentifier,
 }
 
 impl ConsecutiveTag {
@@ -128,58 +148,107 @@ impl ConsecutiveTag {
             PubmedTag::Author => Some(ConsecutiveTag::Author),
             PubmedTag::Affiliation => Some(ConsecutiveTag::Affiliation),
             PubmedTag::FullAuthorName => Some(ConsecutiveTag::FullAuthorName),
+            PubmedTag::CorporateAuthor => Some(ConsecutiveTag::CorporateAuthor),
+            PubmedTag::Editor => Some(ConsecutiveTag::Editor),
+            PubmedTag::FullEditorName => Some(ConsecutiveTag::FullEditorName),
+            PubmedTag::Investigator => Some(ConsecutiveTag::Investigator),
+            PubmedTag::FullInvestigatorName => Some(ConsecutiveTag::FullInvestigatorName),
+            PubmedTag::InvestigatorAffiliation => Some(ConsecutiveTag::InvestigatorAffiliation),
+            PubmedTag::AuthorIdentifier => Some(ConsecutiveTag::AuthorIdentifier),
             _ => None,
         }
     }
 }
 
-/// Details about an author from a PubMed formatted citation.
+/// Details about an author or other contributor from a PubMed formatted citation.
 #[derive(Debug, PartialEq)]
 pub(crate) struct PubmedAuthor {
     pub(crate) name: AuthorName,
     pub(crate) affiliations: Vec<String>,
+    pub(crate) role: ContributorRole,
+    pub(crate) orcid: Option<String>,
 }
 
 impl PubmedAuthor {
-    fn new(name: AuthorName) -> Self {
+    fn new(name: AuthorName, role: ContributorRole) -> Self {
         Self {
             name,
             affiliations: Vec::with_capacity(1),
+            role,
+            orcid: None,
         }
     }
+}
 
-    fn from_au(au: String) -> Self {
-        Self::new(AuthorName::au(au))
-    }
-
-    fn from_fau(au: String) -> Self {
-        Self::new(AuthorName::fau(au))
+/// Get the ORCID iD from an `AUID` value such as `ORCID: 0000-0002-1825-0097`,
+/// if it is valid.
+pub(crate) fn auid_orcid(auid: &str) -> Option<String> {
+    let (source, id) = auid.split_once(':')?;
+    if !source.trim().eq_ignore_ascii_case("ORCID") {
+        return None;
     }
+    crate::utils::format_orcid(id)
 }
 
-/// Resolve authors from an ordered list of author-related entries.
+/// Resolve authors and other contributors from an ordered list of
+/// contributor-related entries.
+///
+/// `FAU`, `FED` and `FIR` start a new contributor, and an `AU`, `ED` or `IR`
+/// that abbreviates the preceding full name of the same role is merged into
+/// it. `CN` adds a corporate author. An `AUID` holding a valid ORCID iD sets
+/// the ORCID of the most recent contributor that does not have one yet, or,
+/// when an earlier contributor of the same role already has that ORCID iD,
+/// merges the most recent contributor into them as the same person.
 ///
 /// Any leading affiliation entries are unassociated with an author,
-/// and they are returned in a separate [Vec].
+/// and they are returned in a separate [Vec], followed by the `AUID` values
+/// that were not used as an ORCID.
 pub(crate) fn resolve_authors(
     data: Vec<(ConsecutiveTag, String)>,
-) -> (Vec<PubmedAuthor>, Vec<String>) {
+) -> (Vec<PubmedAuthor>, Vec<String>, Vec<String>) {
     let mut authors: Vec<PubmedAuthor> = Vec::with_capacity(data.len() / 2 + 1);
     let mut unused_affiliations = Vec::new();
+    let mut unused_identifiers = Vec::new();
     for (tag, value) in data {
         match tag {
-            ConsecutiveTag::Author => {
-                // Add new author if AU is not the same as the previous FAU.
-                let prev = authors.last().map(|a| &a.name);
+            ConsecutiveTag::Author | ConsecutiveTag::Editor | ConsecutiveTag::Investigator => {
+                let role = match tag {
+                    ConsecutiveTag::Editor => ContributorRole::Editor,
+                    ConsecutiveTag::Investigator => ContributorRole::Investigator,
+                    _ => ContributorRole::Author,
+                };
+                // Add new contributor if AU is not the same as the previous FAU.
+                let prev = authors.last().filter(|a| a.role == role).map(|a| &a.name);
                 if !prev.is_some_and(|n| n.full && n.au_equals(&value)) {
-                    authors.push(PubmedAuthor::from_au(value));
+                    authors.push(PubmedAuthor::new(AuthorName::au(value), role));
                 }
             }
             ConsecutiveTag::FullAuthorName => {
                 // FAU always indicates start of new author description
-                authors.push(PubmedAuthor::from_fau(value));
+                authors.push(PubmedAuthor::new(
+                    AuthorName::fau(value),
+                    ContributorRole::Author,
+                ));
+            }
+            ConsecutiveTag::FullEditorName => {
+                authors.push(PubmedAuthor::new(
+                    AuthorName::fau(value),
+                    ContributorRole::Editor,
+                ));
+            }
+            ConsecutiveTag::FullInvestigatorName => {
+                authors.push(PubmedAuthor::new(
+                    AuthorName::fau(value),
+                    ContributorRole::Investigator,
+                ));
+            }
+            ConsecutiveTag::CorporateAuthor => {
+                authors.push(PubmedAuthor::new(
+                    AuthorName::au(value),
+                    ContributorRole::CorporateAuthor,
+                ));
             }
-            ConsecutiveTag::Affiliation => {
+            ConsecutiveTag::Affiliation | ConsecutiveTag::InvestigatorAffiliation => {
                 // add affiliation to most recently parsed author
                 if let Some(author) = authors.last_mut() {
                     author.affiliations.push(value);
@@ -187,9 +256,30 @@ pub(crate) fn resolve_authors(
                     unused_affiliations.push(value);
                 }
             }
+            ConsecutiveTag::AuthorIdentifier => match (authors.pop(), auid_orcid(&value)) {
+                (Some(mut author), Some(orcid)) if author.orcid.is_none() => {
+                    let same = authors.iter_mut().find(|a| {
+                        a.role == author.role && a.orcid.as_deref() == Some(orcid.as_str())
+                    });
+                    if let Some(same) = same {
+                        for affiliation in author.affiliations {
+                            if !same.affiliations.contains(&affiliation) {
+                                same.affiliations.push(affiliation);
+                            }
+                        }
+                    } else {
+                        author.orcid = Some(orcid);
+                        authors.push(author);
+                    }
+                }
+                (author, _) => {
+                    authors.extend(author);
+                    unused_identifiers.push(value);
+                }
+            },
         }
     }
-    (authors, unused_affiliations)
+    (authors, unused_affiliations, unused_identifiers)
 }
 
 #[cfg(test)]
@@ -257,10 +347,10 @@ mod tests {
     #[case(&["Watson JD", "Watson JD"])]
     fn test_resolve_author_consecutive_au(#[case] names: &[&str]) {
         let data = names
-            .into_iter()
+            .iter()
             .map(|s| (ConsecutiveTag::Author, s.to_string()))
             .collect();
-        let (authors, _) = resolve_authors(data);
+        let (authors, _, _) = resolve_authors(data);
         let actual: Vec<_> = authors.iter().map(|a| a.name.as_au()).collect::<Vec<_>>();
         assert_eq!(&actual, names);
     }
@@ -285,7 +375,7 @@ mod tests {
             (ConsecutiveTag::Affiliation, "Department of Radiology, Massachusetts General Hospital and Harvard Medical School, Boston, Massachusetts, USA.".to_string()),
             (ConsecutiveTag::Affiliation, "Computer Science and Artificial Intelligence Laboratory, Massachusetts Institute of Technology, Cambridge, Massachusetts, USA.".to_string()),
         ];
-        let (actual, leading_affiliations) = resolve_authors(data);
+        let (actual, leading_affiliations, _) = resolve_authors(data);
         assert!(leading_affiliations.is_empty());
         let expected = vec![
             PubmedAuthor {
@@ -294,6 +384,8 @@ mod tests {
                     "Program in Neuroscience and Mental Health, The Hospital for Sick Children, Toronto, Canada.".to_string(),
                     "Department of Medical Biophysics, University of Toronto, Toronto, Canada.".to_string()
                 ],
+                role: ContributorRole::Author,
+                orcid: None,
             },
             PubmedAuthor {
                 name: AuthorName::fau("van der Kouwe, André J W".to_string()),
@@ -301,6 +393,8 @@ mod tests {
                     "Athinoula A. Martinos Center for Biomedical Research, Department of Radiology, Massachusetts General Hospital and Harvard Medical School, Charlestown, Massachusetts, USA.".to_string(),
                     "Department of Radiology, Massachusetts General Hospital and Harvard Medical School, Boston, Massachusetts, USA.".to_string()
                 ],
+                role: ContributorRole::Author,
+                orcid: None,
             },
             PubmedAuthor {
                 name: AuthorName::fau("Fischl, Bruce".to_string()),
@@ -309,6 +403,8 @@ mod tests {
                     "Department of Radiology, Massachusetts General Hospital and Harvard Medical School, Boston, Massachusetts, USA.".to_string(),
                     "Computer Science and Artificial Intelligence Laboratory, Massachusetts Institute of Technology, Cambridge, Massachusetts, USA.".to_string()
                 ],
+                role: ContributorRole::Author,
+                orcid: None,
             },
         ];
         assert_eq!(actual, expected);
@@ -331,15 +427,168 @@ mod tests {
         (ConsecutiveTag::Author, "Einstein A"),
     ])]
     fn test_resolve_author_deduplication(#[case] names: &[(ConsecutiveTag, &str)]) {
-        let data = names
-            .into_iter()
-            .map(|(t, n)| (*t, n.to_string()))
-            .collect();
-        let (authors, _) = resolve_authors(data);
+        let data = names.iter().map(|(t, n)| (*t, n.to_string())).collect();
+        let (authors, _, _) = resolve_authors(data);
         let actual: Vec<_> = authors.iter().map(|a| a.name.as_au()).collect::<Vec<_>>();
         assert_eq!(&actual, &["Bose SN", "Einstein A"]);
     }
 
+    #[rstest]
+    fn test_resolve_contributor_roles() {
+        let data = vec![
+            (
+                ConsecutiveTag::FullAuthorName,
+                "Bose, Satyendra N".to_string(),
+            ),
+            (ConsecutiveTag::Author, "Bose SN".to_string()),
+            (
+                ConsecutiveTag::CorporateAuthor,
+                "COVID-19 Study Group".to_string(),
+            ),
+            (ConsecutiveTag::Affiliation, "Geneva".to_string()),
+            (
+                ConsecutiveTag::FullEditorName,
+                "Einstein, Albert".to_string(),
+            ),
+            (ConsecutiveTag::Editor, "Einstein A".to_string()),
+            (
+                ConsecutiveTag::FullInvestigatorName,
+                "Curie, Marie".to_string(),
+            ),
+            (ConsecutiveTag::Investigator, "Curie M".to_string()),
+            (ConsecutiveTag::InvestigatorAffiliation, "Paris".to_string()),
+            // An `IR` does not merge into a preceding `FED`.
+            (ConsecutiveTag::Investigator, "Einstein A".to_string()),
+        ];
+        let (authors, _, _) = resolve_authors(data);
+        let actual: Vec<_> = authors
+            .iter()
+            .map(|a| (a.name.as_str(), a.role, a.affiliations.clone()))
+            .collect();
+        assert_eq!(
+            actual,
+            vec![
+                ("Bose, Satyendra N", ContributorRole::Author, vec![]),
+                (
+                    "COVID-19 Study Group",
+                    ContributorRole::CorporateAuthor,
+                    vec!["Geneva".to_string()]
+                ),
+                ("Einstein, Albert", ContributorRole::Editor, vec![]),
+                (
+                    "Curie, Marie",
+                    ContributorRole::Investigator,
+                    vec!["Paris".to_string()]
+                ),
+                ("Einstein A", ContributorRole::Investigator, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_author_identifiers() {
+        let data = vec![
+            (
+                ConsecutiveTag::AuthorIdentifier,
+                "ORCID: 0000-0002-1825-0097".to_string(),
+            ),
+            (
+                ConsecutiveTag::FullAuthorName,
+                "Carberry, Josiah".to_string(),
+            ),
+            (ConsecutiveTag::Author, "Carberry J".to_string()),
+            (
+                ConsecutiveTag::AuthorIdentifier,
+                "ORCID: https://orcid.org/0000-0002-1825-0097".to_string(),
+            ),
+            (
+                ConsecutiveTag::AuthorIdentifier,
+                "ORCID: 0000-0001-5109-3700".to_string(),
+            ),
+            (ConsecutiveTag::FullAuthorName, "Smith, John".to_string()),
+            (
+                ConsecutiveTag::AuthorIdentifier,
+                "ORCID: 0000-0002-1825-0098".to_string(),
+            ),
+            (
+                ConsecutiveTag::AuthorIdentifier,
+                "ISNI: 0000000121032683".to_string(),
+            ),
+        ];
+        let (authors, _, unused) = resolve_authors(data);
+        let orcids: Vec<_> = authors.iter().map(|a| a.orcid.as_deref()).collect();
+        assert_eq!(orcids, vec![Some("0000-0002-1825-0097"), None]);
+        assert_eq!(
+            unused,
+            vec![
+                // No contributor yet.
+                "ORCID: 0000-0002-1825-0097",
+                // The contributor already has an ORCID.
+                "ORCID: 0000-0001-5109-3700",
+                // Bad check digit.
+                "ORCID: 0000-0002-1825-0098",
+                "ISNI: 0000000121032683",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_author_same_orcid_merges() {
+        let data = vec![
+            (
+                ConsecutiveTag::FullAuthorName,
+                "Carberry, Josiah".to_string(),
+            ),
+            (ConsecutiveTag::Affiliation, "Brown University".to_string()),
+            (
+                ConsecutiveTag::AuthorIdentifier,
+                "ORCID: 0000-0002-1825-0097".to_string(),
+            ),
+            (ConsecutiveTag::FullAuthorName, "Smith, John".to_string()),
+            // Listed again under another spelling of the name.
+            (ConsecutiveTag::FullAuthorName, "Carberry, J S".to_string()),
+            (
+                ConsecutiveTag::Affiliation,
+                "Wesleyan University".to_string(),
+            ),
+            (
+                ConsecutiveTag::AuthorIdentifier,
+                "ORCID: 0000-0002-1825-0097".to_string(),
+            ),
+            // The same ORCID iD in another role is a separate credit.
+            (
+                ConsecutiveTag::FullInvestigatorName,
+                "Carberry, Josiah".to_string(),
+            ),
+            (
+                ConsecutiveTag::AuthorIdentifier,
+                "ORCID: 0000-0002-1825-0097".to_string(),
+            ),
+        ];
+        let (authors, _, unused) = resolve_authors(data);
+        let actual: Vec<_> = authors
+            .iter()
+            .map(|a| (a.name.as_str(), a.role, a.affiliations.clone()))
+            .collect();
+        assert_eq!(
+            actual,
+            vec![
+                (
+                    "Carberry, Josiah",
+                    ContributorRole::Author,
+                    vec![
+                        "Brown University".to_string(),
+                        "Wesleyan University".to_string()
+                    ]
+                ),
+                ("Smith, John", ContributorRole::Author, vec![]),
+                ("Carberry, Josiah", ContributorRole::Investigator, vec![]),
+            ]
+        );
+        assert_eq!(authors[2].orcid.as_deref(), Some("0000-0002-1825-0097"));
+        assert!(unused.is_empty());
+    }
+
     #[rstest]
     fn test_resolve_author_leading_affiliations() {
         let data = vec![
@@ -365,7 +614,7 @@ mod tests {
                 "University of Bern".to_string(),
             ),
         ];
-        let (authors, leading_affiliations) = resolve_authors(data);
+        let (authors, leading_affiliations, _) = resolve_authors(data);
         let expected = [
             "Lab of Unknown Stuff".to_string(),
             "Mysterious Basement".to_string(),
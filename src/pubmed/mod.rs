@@ -18,6 +18,18 @@
 //! let citations = parser.parse(input).unwrap();
 //! assert_eq!(citations[0].title, "Example Title");
 //! ```
+//!
+//! Citations can be written back to MEDLINE format with [`PubMedWriter`]:
+//!
+//! ```
+//! use biblib::{CitationParser, CitationWriter, PubMedParser, PubMedWriter};
+//!
+//! let input = "PMID- 12345678\nTI  - Example Title\nFAU - Smith, John\n";
+//! let citations = PubMedParser::new().parse(input).unwrap();
+//!
+//! let output = PubMedWriter::new().write(&citations);
+//! assert_eq!(PubMedParser::new().parse(&output).unwrap(), citations);
+//! ```
 
 mod author;
 mod parse;
@@ -25,10 +37,17 @@ mod split;
 mod structure;
 mod tags;
 mod whole_lines;
-
-use crate::error::ParseError;
-use crate::pubmed::parse::pubmed_parse;
-use crate::{Citation, CitationParser};
+mod write;
+
+use crate::error::{ParseError, ParseWarning};
+use crate::pubmed::parse::{pubmed_boundary, pubmed_parse};
+use crate::pubmed::write::write_pubmed;
+use crate::stream::chunk::parse_chunks;
+use crate::stream::line::LineChunks;
+use crate::{
+    Citation, CitationFormat, CitationParser, CitationWriter, OnError, ParseOptions, ParseReport,
+    collect_report,
+};
 use itertools::Itertools;
 
 /// Parser for PubMed format citations.
@@ -78,13 +97,116 @@ impl CitationParser for PubMedParser {
             .map(|x| x.try_into())
             .try_collect()
     }
+
+    /// Reads one blank-line separated record at a time.
+    fn parse_reader<'r, R>(
+        &self,
+        reader: R,
+    ) -> Box<dyn Iterator<Item = Result<Citation, ParseError>> + 'r>
+    where
+        R: std::io::Read + 'r,
+    {
+        let chunks = LineChunks::new(
+            std::io::BufReader::new(reader),
+            CitationFormat::PubMed,
+            pubmed_boundary,
+        );
+        Box::new(parse_chunks(chunks, |chunk| PubMedParser {}.parse(chunk)))
+    }
+
+    /// Reports ignored lines, dropped `DP` values and values joined with
+    /// `" AND "` as warnings, located at the start of their record.
+    fn parse_with_options(
+        &self,
+        input: &str,
+        options: &ParseOptions,
+    ) -> Result<ParseReport, ParseError> {
+        if options.on_error == OnError::Fail {
+            let records = parse_with_warnings(input)?;
+            return collect_report(records.into_iter().map(Ok), options);
+        }
+
+        let chunks = LineChunks::new(input.as_bytes(), CitationFormat::PubMed, pubmed_boundary);
+        collect_report(parse_chunks(chunks, parse_with_warnings), options)
+    }
+}
+
+/// Parse PubMed text, keeping the warnings for each citation.
+fn parse_with_warnings(input: &str) -> Result<Vec<(Citation, Vec<ParseWarning>)>, ParseError> {
+    if input.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    pubmed_parse(input)
+        .into_iter()
+        .map(|raw| raw.into_citation_with_warnings())
+        .try_collect()
+}
+
+/// Writer for PubMed (MEDLINE `.nbib`) format citations.
+///
+/// Each citation becomes one record of `TAG - value` lines, with long values
+/// wrapped onto six-column continuation lines. Authors are written as `FAU`/`AU`
+/// pairs followed by an `AUID` for their ORCID iD and their `AD` affiliations,
+/// and the DOI as an `LID` with a
+/// `[doi]` suffix. ISBNs are written as `ISBN` and trial registry numbers as
+/// `SI` lines such as `ClinicalTrials.gov/NCT01234567`. `extra_fields` entries are written when their key is a
+/// PubMed tag without a dedicated `Citation` field (for example `AID` or `GR`).
+/// Keywords and URLs have no MEDLINE tag and are not written.
+#[derive(Debug, Clone, Default)]
+pub struct PubMedWriter {}
+
+impl PubMedWriter {
+    /// Creates a new PubMed writer instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::PubMedWriter;
+    /// let writer = PubMedWriter::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CitationWriter for PubMedWriter {
+    fn write(&self, citations: &[Citation]) -> String {
+        write_pubmed(citations)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{ContributorRole, DateKind, Scheme};
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn test_parse_reader_matches_parse() {
+        let input = "PMID- 1\nTI  - First title that\n      wraps\nFAU - Smith, John\n\n\nPMID- 2\nTI  - Second\nAU  - Doe J\n";
+        let parser = PubMedParser::new();
+
+        let streamed: Result<Vec<_>, _> = parser.parse_reader(input.as_bytes()).collect();
+        assert_eq!(streamed.unwrap(), parser.parse(input).unwrap());
+    }
+
+    #[test]
+    fn test_parse_reader_reports_positions_in_whole_input() {
+        let input = "PMID- 1\nTI  - First\n\nPMID- 2\nFAU - Smith, John\n\nPMID- 3\nTI  - Third\n";
+        let parser = PubMedParser::new();
+        let expected = parser.parse(input).unwrap_err();
+
+        let streamed: Vec<_> = parser.parse_reader(input.as_bytes()).collect();
+        assert_eq!(streamed.len(), 3);
+        assert!(streamed[0].is_ok() && streamed[2].is_ok());
+
+        let err = streamed[1].as_ref().unwrap_err();
+        assert_eq!(err.line, expected.line);
+        assert_eq!(err.span, expected.span);
+    }
+
     #[test]
     fn test_parse_simple_citation() {
         let input = r#"PMID- 12345678
@@ -408,4 +530,203 @@ AID- 10.1016/j.example.2023.01.001 [doi]
         let err = PubMedParser::new().parse(input).unwrap_err();
         assert_eq!(err.line, Some(7), "third citation starts on line 7");
     }
+
+    #[test]
+    fn test_write_round_trip() {
+        let input = r#"PMID- 27206507
+OWN - NLM
+STAT- MEDLINE
+LR  - 20231115
+IS  - 1398-9995 (Electronic)
+IS  - 0105-4538 (Linking)
+VI  - 71
+IP  - 10
+DP  - 2016 Oct
+DEP - 20160629
+TI  - Fantastic yeasts and where to find them: the hidden diversity of dimorphic fungal
+      pathogens.
+PG  - 1425-1434
+LID - 10.1111/all.12945 [doi]
+AB  - BACKGROUND: A long abstract that needs to be wrapped over more than one line so
+      that the continuation logic is exercised by the round trip.
+FAU - van der Valk, J P M
+AU  - van der Valk JPM
+AD  - Department of Internal Medicine, Erasmus MC, Rotterdam, The Netherlands.
+FAU - Crick, Francis Harry Compton
+AU  - Crick FHC
+AUID- ORCID: 0000-0002-1825-0097
+AD  - Cambridge
+AD  - La Jolla
+CN  - COVID-19 Study Group
+FED - Watson, James D
+ED  - Watson JD
+LA  - eng
+GR  - R01 AI000001/AI/NIAID NIH HHS/United States
+PT  - Journal Article
+PT  - Research Support, Non-U.S. Gov't
+TA  - Allergy
+JT  - Allergy
+MH  - Adolescent
+MH  - Cross-Sectional Studies
+PMC - PMC1234567
+SI  - ClinicalTrials.gov/NCT01234567
+SI  - GENBANK/AB123456
+AID - 10.1111/all.12945 [doi]
+FIR - Curie, Marie
+IR  - Curie M
+IRAD- Paris
+
+PMID- 2
+TI  - Second record
+"#;
+        let citations = PubMedParser::new().parse(input).unwrap();
+        let output = PubMedWriter::new().write(&citations);
+        let reparsed = PubMedParser::new().parse(&output).unwrap();
+
+        assert_eq!(reparsed, citations);
+        assert!(output.starts_with("PMID- 27206507\n"));
+        assert!(output.contains(
+            "FAU - Crick, Francis Harry Compton\nAU  - Crick FHC\nAUID- ORCID: 0000-0002-1825-0097\nAD  - Cambridge\nAD  - La Jolla\n"
+        ));
+        assert!(output.contains("LID - 10.1111/all.12945 [doi]\n"));
+        assert!(output.contains("STAT- MEDLINE\n"));
+        assert_eq!(citations[0].identifier(Scheme::Nct), Some("NCT01234567"));
+        assert_eq!(
+            citations[0].extra_fields["SI"],
+            vec!["GENBANK/AB123456".to_string()]
+        );
+        assert!(output.contains("SI  - ClinicalTrials.gov/NCT01234567\n"));
+    }
+
+    #[test]
+    fn test_parse_typed_dates() {
+        let input = "PMID- 1\nDP  - 2022 Dec-2023 Jan\nTI  - Example.\nLR  - 20231115\nPHST- 2022/10/01 00:00 [received]\nPHST- 2022/11/20 00:00 [epublish]\n";
+
+        let citation = PubMedParser::new().parse(input).unwrap().remove(0);
+        let date = citation.date.as_ref().unwrap();
+        assert_eq!((date.year, date.month), (2022, Some(12)));
+        let end = date.end.as_deref().unwrap();
+        assert_eq!((end.year, end.month), (2023, Some(1)));
+
+        let epub = citation.date_of(DateKind::Epub).unwrap();
+        assert_eq!(
+            (epub.year, epub.month, epub.day),
+            (2022, Some(11), Some(20))
+        );
+        let revised = citation.date_of(DateKind::LastUpdated).unwrap();
+        assert_eq!(
+            (revised.year, revised.month, revised.day),
+            (2023, Some(11), Some(15))
+        );
+        assert_eq!(
+            citation.extra_fields["PHST"],
+            vec!["2022/10/01 00:00 [received]".to_string()]
+        );
+        assert!(!citation.extra_fields.contains_key("LR"));
+
+        let output = PubMedWriter::new().write(std::slice::from_ref(&citation));
+        assert!(output.contains("DP  - 2022 Dec-2023 Jan\nDEP - 20221120\nLR  - 20231115\n"));
+    }
+
+    #[test]
+    fn test_parse_book_isbn() {
+        let input = "PMID- 1\nBTI - A book.\nISBN- 978-0-306-40615-7\nISBN- 12345\n";
+
+        let citation = PubMedParser::new().parse(input).unwrap().remove(0);
+        assert_eq!(citation.identifier(Scheme::Isbn), Some("9780306406157"));
+        assert_eq!(citation.extra_fields["ISBN"], vec!["12345".to_string()]);
+
+        let output = PubMedWriter::new().write(std::slice::from_ref(&citation));
+        assert!(output.contains("ISBN- 9780306406157\n"));
+    }
+
+    #[test]
+    fn test_parse_contributor_roles() {
+        let input = r#"PMID- 1
+TI  - Trial of something.
+FAU - Smith, John
+AU  - Smith J
+AUID- ORCID: 0000-0002-1825-0097
+CN  - RECOVERY Collaborative Group
+AUID- ISNI: 0000000121032683
+FED - Doe, Jane
+ED  - Doe J
+FIR - Curie, Marie
+IR  - Curie M
+IRAD- Paris, France.
+"#;
+        let citation = PubMedParser::new().parse(input).unwrap().remove(0);
+
+        assert_eq!(citation.authors.len(), 2);
+        assert_eq!(citation.authors[0].role, ContributorRole::Author);
+        assert_eq!(citation.authors[1].name, "RECOVERY Collaborative Group");
+        assert_eq!(citation.authors[1].given_name, None);
+        assert_eq!(citation.authors[1].role, ContributorRole::CorporateAuthor);
+        assert!(citation.authors[1].is_organization);
+        assert_eq!(
+            citation.authors[0].orcid.as_deref(),
+            Some("0000-0002-1825-0097")
+        );
+        assert_eq!(citation.authors[1].orcid, None);
+        assert_eq!(
+            citation.extra_fields["AUID"],
+            vec!["ISNI: 0000000121032683"]
+        );
+
+        assert_eq!(citation.contributors.len(), 2);
+        assert_eq!(citation.contributors[0].name, "Doe");
+        assert_eq!(citation.contributors[0].role, ContributorRole::Editor);
+        assert_eq!(citation.contributors[1].name, "Curie");
+        assert_eq!(citation.contributors[1].role, ContributorRole::Investigator);
+        assert_eq!(
+            citation.contributors[1].affiliations,
+            vec!["Paris, France."]
+        );
+
+        for tag in ["CN", "FED", "ED", "FIR", "IR", "IRAD"] {
+            assert!(!citation.extra_fields.contains_key(tag));
+        }
+    }
+
+    #[test]
+    fn test_write_empty() {
+        assert_eq!(PubMedWriter::new().write(&[]), "");
+    }
+
+    #[test]
+    fn test_parse_with_options_reports_warnings() {
+        use crate::{OnError, ParseOptions, WarningKind};
+
+        let input = "PMID- 1\nTI  - First\n\nPMID- 2\nTI  - Second\nVI  - 1\nVI  - 2\nDP  - 2020\nDP  - 2021\nnot a tag\n";
+        let options = ParseOptions::new().with_on_error(OnError::Collect);
+        let report = PubMedParser::new()
+            .parse_with_options(input, &options)
+            .unwrap();
+
+        assert_eq!(report.citations[1].volume.as_deref(), Some("1 AND 2"));
+        assert_eq!(report.warnings.len(), 3);
+        assert!(report.warnings.iter().all(|w| w.line == Some(4)));
+        assert!(
+            report.warnings.contains(
+                &crate::ParseWarning::at_line(
+                    4,
+                    CitationFormat::PubMed,
+                    WarningKind::JoinedValues {
+                        key: "VI".to_string(),
+                        count: 2
+                    }
+                )
+                .with_span(crate::SourceSpan::new(21, input.len()))
+            )
+        );
+        assert!(report.warnings.iter().any(|w| w.kind
+            == WarningKind::DroppedValue {
+                key: "DP".to_string(),
+                value: "2021".to_string()
+            }));
+        assert!(report.warnings.iter().any(|w| w.kind
+            == WarningKind::IgnoredLine {
+                line: "not a tag".to_string()
+            }));
+    }
 }
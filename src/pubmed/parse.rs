@@ -4,6 +4,7 @@ use crate::pubmed::split::BlankLineSplit;
 use crate::pubmed::structure::RawPubmedData;
 use crate::pubmed::tags::PubmedTag;
 use crate::pubmed::whole_lines::WholeLinesIter;
+use crate::stream::line::Boundary;
 use crate::utils::newline_delimiter_of;
 use either::{Either, Left, Right};
 use itertools::Itertools;
@@ -24,19 +25,35 @@ pub fn pubmed_parse<S: AsRef<str>>(nbib_text: S) -> Vec<RawPubmedData> {
         .collect() // TODO do not collect, return an Iterator instead
 }
 
+/// Record boundaries for [`PubMedParser::parse_reader`](crate::PubMedParser):
+/// the first non-blank line after a blank line starts a record, matching
+/// [`BlankLineSplit`].
+pub(crate) fn pubmed_boundary(previous: Option<&str>, line: &str) -> Boundary {
+    if previous.is_some_and(str::is_empty) && !line.is_empty() {
+        Boundary::Before
+    } else {
+        Boundary::Inside
+    }
+}
+
 fn pubmed_parse_one(
     text: &str,
     line_break: &str,
     start_line: usize,
     start_byte: usize,
 ) -> RawPubmedData {
-    let (_ignored_lines, pairs): (Vec<_>, Vec<_>) =
+    let (ignored_lines, pairs): (Vec<_>, Vec<_>) =
         WholeLinesIter::new(text.split(line_break)).partition_map(parse_complete_entry);
-    let (data, others) = separate_stateless_entries(pairs);
-    let (authors, _leading_affiliations) = resolve_authors(others);
+    let (mut data, others) = separate_stateless_entries(pairs);
+    let (authors, _leading_affiliations, identifiers) = resolve_authors(others);
+    // Identifiers that are not an author's ORCID are kept as they were.
+    if !identifiers.is_empty() {
+        data.insert(PubmedTag::AuthorIdentifier, identifiers);
+    }
     RawPubmedData {
         data,
         authors,
+        ignored_lines,
         start_line,
         record_span: SourceSpan::new(start_byte, start_byte + text.len()),
     }
@@ -101,7 +118,7 @@ mod tests {
         assert_eq!(
             actual
                 .as_ref()
-                .map_either(|s| s.as_str(), |(t, s)| (t.clone(), s.as_str())),
+                .map_either(|s| s.as_str(), |(t, s)| (*t, s.as_str())),
             expected
         )
     }
@@ -1,16 +1,21 @@
-use crate::error::{ParseError, SourceSpan, ValueError, fields};
+use crate::error::{ParseError, ParseWarning, SourceSpan, ValueError, WarningKind, fields};
 use crate::pubmed::author::PubmedAuthor;
 use crate::pubmed::tags::PubmedTag;
-use crate::utils::parse_pubmed_date;
-use crate::{CitationFormat, Date};
+use crate::utils::{
+    detect_identifier, parse_compact_date, parse_pubmed_date, parse_ris_date,
+    partition_contributors,
+};
+use crate::{CitationFormat, ContributorRole, Date, DateKind, Identifier, Scheme, TypedDate};
 use std::collections::HashMap;
 
 /// Structured raw data from a PubMed formatted .nbib file.
 pub(crate) struct RawPubmedData {
     /// Key-value pair data from the .nbib file data.
     pub(crate) data: HashMap<PubmedTag, Vec<String>>,
-    /// Authors of the cited work.
+    /// Authors and other contributors of the cited work, in source order.
     pub(crate) authors: Vec<PubmedAuthor>,
+    /// Lines that are not a known `TAG - value` pair.
+    pub(crate) ignored_lines: Vec<String>,
     /// Starting line number of this citation in the source text (1-based).
     pub(crate) start_line: usize,
     /// Byte-offset span of the entire citation chunk in the source text.
@@ -19,99 +24,236 @@ pub(crate) struct RawPubmedData {
 
 impl TryFrom<RawPubmedData> for crate::Citation {
     type Error = ParseError;
-    fn try_from(
-        RawPubmedData {
-            mut data,
+    fn try_from(raw: RawPubmedData) -> Result<Self, Self::Error> {
+        raw.into_citation_with_warnings()
+            .map(|(citation, _)| citation)
+    }
+}
+
+impl RawPubmedData {
+    /// Convert to a citation, returning the warnings for data that was
+    /// dropped or coerced along the way. Warnings point at the whole record.
+    pub(crate) fn into_citation_with_warnings(
+        self,
+    ) -> Result<(crate::Citation, Vec<ParseWarning>), ParseError> {
+        let RawPubmedData {
+            data,
             authors,
+            ignored_lines,
+            start_line,
+            record_span,
+        } = self;
+        let mut record = Fields {
+            data,
+            warnings: Vec::new(),
             start_line,
             record_span,
-        }: RawPubmedData,
-    ) -> Result<Self, Self::Error> {
+        };
+        for line in ignored_lines {
+            if !line.trim().is_empty() {
+                record.warn(WarningKind::IgnoredLine { line });
+            }
+        }
+
         // unresolved question: what should we do if multiple values are found for
         // a field where one value is expected?
         // https://github.com/AliAzlanDev/biblib/pull/7#issuecomment-2984871452
         // current solution: join multiple values on hard-coded string " AND "
-        // alternative solutions:
-        let date = data
-            .remove(&PubmedTag::PublicationDate)
-            // multiple values ignored
-            .and_then(|v| v.into_iter().next())
-            .map(|v| parse_pubmed_date_err(v, start_line, &record_span))
+        // and report a `JoinedValues` warning
+        let date = record
+            .take_first(PubmedTag::PublicationDate)
+            .map(|v| parse_pubmed_date_err(v, start_line, &record.record_span))
             .transpose()?;
 
-        Ok(Self {
-            citation_type: data
-                .remove(&PubmedTag::PublicationType)
-                .unwrap_or_else(Vec::new),
-            title: data
-                .remove(&PubmedTag::Title)
-                .or_else(|| data.remove(&PubmedTag::BookTitle))
-                .and_then(join_if_some)
-                .ok_or_else(|| {
-                    ParseError::at_line(
-                        start_line,
-                        CitationFormat::PubMed,
-                        ValueError::MissingValue {
-                            field: fields::TITLE,
-                            key: "TI",
-                        },
-                    )
-                    .with_span(record_span.clone())
-                })?,
-            authors: authors.into_iter().map(|a| a.into()).collect(),
-            journal: data
-                .remove(&PubmedTag::FullJournalTitle)
-                .and_then(join_if_some),
-            journal_abbr: data
-                .remove(&PubmedTag::JournalTitleAbbreviation)
-                .and_then(join_if_some),
+        let title = match record.take_joined(PubmedTag::Title) {
+            Some(title) => Some(title),
+            None => record.take_joined(PubmedTag::BookTitle),
+        }
+        .ok_or_else(|| {
+            ParseError::at_line(
+                start_line,
+                CitationFormat::PubMed,
+                ValueError::MissingValue {
+                    field: fields::TITLE,
+                    key: "TI",
+                },
+            )
+            .with_span(record.record_span.clone())
+        })?;
+
+        let (authors, contributors) =
+            partition_contributors(authors.into_iter().map(Into::into).collect());
+        let identifiers = record.take_identifiers();
+        let dates = record.take_dates();
+        let citation = crate::Citation {
+            citation_type: record.take_all(PubmedTag::PublicationType),
+            title,
+            authors,
+            contributors,
+            journal: record.take_joined(PubmedTag::FullJournalTitle),
+            journal_abbr: record.take_joined(PubmedTag::JournalTitleAbbreviation),
             date,
-            volume: data.remove(&PubmedTag::Volume).and_then(join_if_some),
-            issue: data.remove(&PubmedTag::Issue).and_then(join_if_some),
-            pages: data.remove(&PubmedTag::Pagination).and_then(join_if_some),
-            issn: data.remove(&PubmedTag::Issn).unwrap_or_else(Vec::new),
-            doi: data
-                .remove(&PubmedTag::LocationId)
-                .unwrap_or_else(Vec::new)
+            dates,
+            volume: record.take_joined(PubmedTag::Volume),
+            issue: record.take_joined(PubmedTag::Issue),
+            pages: record.take_joined(PubmedTag::Pagination),
+            issn: record.take_all(PubmedTag::Issn),
+            doi: record
+                .take_all(PubmedTag::LocationId)
                 .into_iter()
                 .filter_map(parse_doi_from_lid)
                 .next()
                 // Fallback to AID field if DOI not found in LID
                 .or_else(|| {
-                    data.remove(&PubmedTag::ArticleIdentifier)
-                        .unwrap_or_else(Vec::new)
+                    record
+                        .take_all(PubmedTag::ArticleIdentifier)
                         .into_iter()
                         .filter_map(parse_doi_from_lid)
                         .next()
                 }),
             accession_number: None,
-            pmid: data
-                .remove(&PubmedTag::PubmedUniqueIdentifier)
-                .and_then(join_if_some),
-            pmc_id: data
-                .remove(&PubmedTag::PubmedCentralIdentifier)
-                .and_then(join_if_some),
-            abstract_text: data.remove(&PubmedTag::Abstract).and_then(join_if_some),
+            pmid: record.take_joined(PubmedTag::PubmedUniqueIdentifier),
+            pmc_id: record.take_joined(PubmedTag::PubmedCentralIdentifier),
+            identifiers,
+            abstract_text: record.take_joined(PubmedTag::Abstract),
             keywords: Vec::new(),
             urls: Vec::new(),
-            language: data.remove(&PubmedTag::Language).and_then(join_if_some),
-            mesh_terms: data.remove(&PubmedTag::MeshTerms).unwrap_or_else(Vec::new),
-            publisher: data.remove(&PubmedTag::Publisher).and_then(join_if_some),
-            extra_fields: data
+            language: record.take_joined(PubmedTag::Language),
+            mesh_terms: record.take_all(PubmedTag::MeshTerms),
+            publisher: record.take_joined(PubmedTag::Publisher),
+            extra_fields: record
+                .data
                 .into_iter()
                 .map(|(k, v)| (k.as_tag().to_string(), v))
                 .collect(),
-        })
+        };
+        Ok((citation, record.warnings))
     }
 }
 
-// FIXME when `CitationError::MultipleValues` is implemented.
-// https://github.com/AliAzlanDev/biblib/pull/7#issuecomment-2989915130
-fn join_if_some(v: Vec<String>) -> Option<String> {
-    if v.is_empty() {
-        None
-    } else {
-        Some(v.join(" AND "))
+/// Tag values of one record, with the warnings raised while taking them out.
+struct Fields {
+    data: HashMap<PubmedTag, Vec<String>>,
+    warnings: Vec<ParseWarning>,
+    start_line: usize,
+    record_span: SourceSpan,
+}
+
+impl Fields {
+    fn warn(&mut self, kind: WarningKind) {
+        self.warnings.push(
+            ParseWarning::at_line(self.start_line, CitationFormat::PubMed, kind)
+                .with_span(self.record_span.clone()),
+        );
+    }
+
+    /// Remove all values for a tag.
+    fn take_all(&mut self, tag: PubmedTag) -> Vec<String> {
+        self.data.remove(&tag).unwrap_or_default()
+    }
+
+    /// Remove the first value for a tag, warning about any others.
+    fn take_first(&mut self, tag: PubmedTag) -> Option<String> {
+        let mut values = self.take_all(tag).into_iter();
+        let first = values.next()?;
+        for value in values {
+            self.warn(WarningKind::DroppedValue {
+                key: tag.as_tag().to_string(),
+                value,
+            });
+        }
+        Some(first)
+    }
+
+    /// Remove typed identifiers from `ISBN` and from `SI` secondary source
+    /// IDs such as `ClinicalTrials.gov/NCT01234567`. Values that are not
+    /// recognised are left in place.
+    fn take_identifiers(&mut self) -> Vec<Identifier> {
+        let mut identifiers = self.take_recognised(PubmedTag::Isbn, |value| {
+            Identifier::new(Scheme::Isbn, value)
+        });
+        identifiers.extend(self.take_recognised(PubmedTag::SecondarySourceId, |value| {
+            detect_identifier(value.split_once('/')?.1)
+        }));
+        identifiers
+    }
+
+    /// Remove the electronic publication date, from `DEP` or else the
+    /// `[epublish]` entry of `PHST`, and the `LR` last revision date.
+    /// Values that do not parse are left in place.
+    fn take_dates(&mut self) -> Vec<TypedDate> {
+        let epub = self
+            .take_date(PubmedTag::DateOfElectronicPublication, parse_compact_date)
+            .or_else(|| {
+                self.take_date(PubmedTag::PublicationHistoryStatusDate, |value| {
+                    let date = value
+                        .strip_suffix("[epublish]")?
+                        .split_whitespace()
+                        .next()?;
+                    parse_ris_date(date)
+                })
+            });
+        let last_updated = self.take_date(PubmedTag::ModificationDate, parse_compact_date);
+        [
+            (DateKind::Epub, epub),
+            (DateKind::LastUpdated, last_updated),
+        ]
+        .into_iter()
+        .filter_map(|(kind, date)| Some(TypedDate { kind, date: date? }))
+        .collect()
+    }
+
+    /// Remove the first value of a tag that `parse` recognises as a date.
+    fn take_date(&mut self, tag: PubmedTag, parse: impl Fn(&str) -> Option<Date>) -> Option<Date> {
+        let values = self.data.get_mut(&tag)?;
+        let (index, date) = values
+            .iter()
+            .enumerate()
+            .find_map(|(index, value)| Some((index, parse(value.trim())?)))?;
+        values.remove(index);
+        if values.is_empty() {
+            self.data.remove(&tag);
+        }
+        Some(date)
+    }
+
+    /// Remove the values of a tag that `parse` recognises.
+    fn take_recognised(
+        &mut self,
+        tag: PubmedTag,
+        parse: impl Fn(&str) -> Option<Identifier>,
+    ) -> Vec<Identifier> {
+        let mut identifiers = Vec::new();
+        let mut unused = Vec::new();
+        for value in self.take_all(tag) {
+            match parse(&value) {
+                Some(identifier) => identifiers.push(identifier),
+                None => unused.push(value),
+            }
+        }
+        if !unused.is_empty() {
+            self.data.insert(tag, unused);
+        }
+        identifiers
+    }
+
+    // FIXME when `CitationError::MultipleValues` is implemented.
+    // https://github.com/AliAzlanDev/biblib/pull/7#issuecomment-2989915130
+    /// Remove all values for a tag joined with `" AND "`, warning if there
+    /// is more than one.
+    fn take_joined(&mut self, tag: PubmedTag) -> Option<String> {
+        let values = self.take_all(tag);
+        match values.len() {
+            0 => None,
+            1 => values.into_iter().next(),
+            count => {
+                self.warn(WarningKind::JoinedValues {
+                    key: tag.as_tag().to_string(),
+                    count,
+                });
+                Some(values.join(" AND "))
+            }
+        }
     }
 }
 
@@ -142,7 +284,21 @@ fn parse_doi_from_lid(s: String) -> Option<String> {
 }
 
 impl From<PubmedAuthor> for crate::Author {
-    fn from(PubmedAuthor { name, affiliations }: PubmedAuthor) -> Self {
+    fn from(
+        PubmedAuthor {
+            name,
+            affiliations,
+            role,
+            orcid,
+        }: PubmedAuthor,
+    ) -> Self {
+        if role == ContributorRole::CorporateAuthor {
+            return Self {
+                affiliations,
+                orcid,
+                ..Self::organization(name.as_str(), role)
+            };
+        }
         let (given_name_opt, middle_name_opt) = name
             .given_name()
             .map(crate::utils::split_given_and_middle)
@@ -152,6 +308,9 @@ impl From<PubmedAuthor> for crate::Author {
             given_name: given_name_opt,
             middle_name: middle_name_opt,
             affiliations,
+            role,
+            orcid,
+            is_organization: false,
         }
     }
 }
@@ -0,0 +1,366 @@
+//! PubMed (MEDLINE `.nbib`) writing implementation.
+//!
+//! Records are written as `TAG - value` lines with the tag padded to four
+//! columns, and long values wrapped onto continuation lines indented by six
+//! spaces, the layout read by [`super::parse::pubmed_parse`].
+
+use crate::pubmed::author::AuthorName;
+use crate::pubmed::tags::PubmedTag;
+use crate::utils::{format_author_name, format_compact_date, format_pubmed_date};
+use crate::{Author, Citation, ContributorRole, DateKind, Scheme};
+
+/// Maximum line length before a value is wrapped onto a continuation line.
+const LINE_WIDTH: usize = 88;
+
+/// Indentation of continuation lines, aligned with the start of the value.
+const CONTINUATION_INDENT: &str = "      ";
+
+/// Serialize citations into MEDLINE formatted text, one blank-line separated record each.
+pub(crate) fn write_pubmed(citations: &[Citation]) -> String {
+    let mut out = String::new();
+    for citation in citations {
+        write_record(&mut out, citation);
+        out.push('\n');
+    }
+    out
+}
+
+fn write_record(out: &mut String, citation: &Citation) {
+    push_opt(
+        out,
+        PubmedTag::PubmedUniqueIdentifier,
+        citation.pmid.as_deref(),
+    );
+    for issn in &citation.issn {
+        push_line(out, PubmedTag::Issn, issn);
+    }
+    for isbn in citation.identifiers_of(Scheme::Isbn) {
+        push_line(out, PubmedTag::Isbn, isbn);
+    }
+    push_opt(out, PubmedTag::Volume, citation.volume.as_deref());
+    push_opt(out, PubmedTag::Issue, citation.issue.as_deref());
+    if let Some(date) = &citation.date {
+        push_line(out, PubmedTag::PublicationDate, &format_pubmed_date(date));
+    }
+    // Unparsed `DEP` and `LR` values are passed through from extra fields.
+    for (kind, tag) in [
+        (DateKind::Epub, PubmedTag::DateOfElectronicPublication),
+        (DateKind::LastUpdated, PubmedTag::ModificationDate),
+    ] {
+        if let Some(date) = citation.date_of(kind)
+            && !citation.extra_fields.contains_key(tag.as_tag())
+        {
+            push_opt(out, tag, format_compact_date(date).as_deref());
+        }
+    }
+    push_line(out, PubmedTag::Title, &citation.title);
+    push_opt(out, PubmedTag::Pagination, citation.pages.as_deref());
+    if let Some(doi) = citation.doi.as_deref() {
+        push_line(out, PubmedTag::LocationId, &format!("{} [doi]", doi.trim()));
+    }
+    push_opt(out, PubmedTag::Abstract, citation.abstract_text.as_deref());
+
+    for author in &citation.authors {
+        if author.role == ContributorRole::CorporateAuthor {
+            push_line(out, PubmedTag::CorporateAuthor, &author.name);
+            push_orcid(out, author);
+            for affiliation in &author.affiliations {
+                push_line(out, PubmedTag::Affiliation, affiliation);
+            }
+        } else {
+            push_person(
+                out,
+                author,
+                [
+                    PubmedTag::FullAuthorName,
+                    PubmedTag::Author,
+                    PubmedTag::Affiliation,
+                ],
+            );
+        }
+    }
+    for editor in contributors_with_role(citation, ContributorRole::Editor) {
+        push_person(
+            out,
+            editor,
+            [
+                PubmedTag::FullEditorName,
+                PubmedTag::Editor,
+                PubmedTag::Affiliation,
+            ],
+        );
+    }
+
+    push_opt(out, PubmedTag::Language, citation.language.as_deref());
+    for citation_type in &citation.citation_type {
+        push_line(out, PubmedTag::PublicationType, citation_type);
+    }
+    push_opt(
+        out,
+        PubmedTag::JournalTitleAbbreviation,
+        citation.journal_abbr.as_deref(),
+    );
+    push_opt(
+        out,
+        PubmedTag::FullJournalTitle,
+        citation.journal.as_deref(),
+    );
+    push_opt(out, PubmedTag::Publisher, citation.publisher.as_deref());
+    for term in &citation.mesh_terms {
+        push_line(out, PubmedTag::MeshTerms, term);
+    }
+    push_opt(
+        out,
+        PubmedTag::PubmedCentralIdentifier,
+        citation.pmc_id.as_deref(),
+    );
+    for identifier in &citation.identifiers {
+        if let Some(databank) = registry_databank(identifier.scheme) {
+            push_line(
+                out,
+                PubmedTag::SecondarySourceId,
+                &format!("{}/{}", databank, identifier.value),
+            );
+        }
+    }
+    for investigator in contributors_with_role(citation, ContributorRole::Investigator) {
+        push_person(
+            out,
+            investigator,
+            [
+                PubmedTag::FullInvestigatorName,
+                PubmedTag::Investigator,
+                PubmedTag::InvestigatorAffiliation,
+            ],
+        );
+    }
+
+    // Sort keys so output is stable across runs despite HashMap ordering.
+    let mut extra_keys: Vec<(&String, PubmedTag)> = citation
+        .extra_fields
+        .keys()
+        .filter_map(|key| passthrough_tag(key).map(|tag| (key, tag)))
+        .collect();
+    extra_keys.sort_by(|a, b| a.0.cmp(b.0));
+    for (key, tag) in extra_keys {
+        for value in &citation.extra_fields[key] {
+            push_line(out, tag, value);
+        }
+    }
+}
+
+/// Append a contributor as full name, abbreviated name and affiliation lines,
+/// using the given `[full name, name, affiliation]` tags.
+fn push_person(
+    out: &mut String,
+    person: &Author,
+    [full_tag, tag, affiliation_tag]: [PubmedTag; 3],
+) {
+    let full = format_author_name(person);
+    if full.is_empty() {
+        return;
+    }
+    push_line(out, full_tag, &full);
+    // The parser merges an `AU` into the preceding `FAU` only when they
+    // describe the same name; otherwise it would become a second author.
+    let name = AuthorName::fau(full);
+    let abbreviated = name.as_au();
+    if name.au_equals(&abbreviated) {
+        push_line(out, tag, &abbreviated);
+    }
+    push_orcid(out, person);
+    for affiliation in &person.affiliations {
+        push_line(out, affiliation_tag, affiliation);
+    }
+}
+
+/// Append an `AUID` line for a contributor's ORCID iD.
+fn push_orcid(out: &mut String, person: &Author) {
+    if let Some(orcid) = person.orcid.as_deref() {
+        push_line(
+            out,
+            PubmedTag::AuthorIdentifier,
+            &format!("ORCID: {}", orcid),
+        );
+    }
+}
+
+/// Contributors with a role, in order. PubMed has tags for editors and
+/// investigators only.
+fn contributors_with_role(
+    citation: &Citation,
+    role: ContributorRole,
+) -> impl Iterator<Item = &Author> {
+    citation
+        .contributors
+        .iter()
+        .filter(move |contributor| contributor.role == role)
+}
+
+/// The `SI` databank name for a trial registry, such as `ClinicalTrials.gov`.
+fn registry_databank(scheme: Scheme) -> Option<&'static str> {
+    match scheme {
+        Scheme::Nct => Some("ClinicalTrials.gov"),
+        Scheme::Isrctn => Some("ISRCTN"),
+        Scheme::EudraCt => Some("EudraCT"),
+        Scheme::ChiCtr => Some("ChiCTR"),
+        _ => None,
+    }
+}
+
+/// Resolve an `extra_fields` key that should be written back as a PubMed tag.
+///
+/// Tags backed by a `Citation` field are emitted from that field instead, and
+/// contributor tags are skipped because their meaning depends on their position.
+fn passthrough_tag(key: &str) -> Option<PubmedTag> {
+    PubmedTag::from_tag(key).filter(|tag| {
+        !matches!(
+            tag,
+            PubmedTag::Abstract
+                | PubmedTag::Affiliation
+                | PubmedTag::Author
+                | PubmedTag::CorporateAuthor
+                | PubmedTag::Editor
+                | PubmedTag::FullAuthorName
+                | PubmedTag::FullEditorName
+                | PubmedTag::FullInvestigatorName
+                | PubmedTag::Investigator
+                | PubmedTag::InvestigatorAffiliation
+                | PubmedTag::FullJournalTitle
+                | PubmedTag::Issn
+                | PubmedTag::Issue
+                | PubmedTag::JournalTitleAbbreviation
+                | PubmedTag::Language
+                | PubmedTag::LocationId
+                | PubmedTag::MeshTerms
+                | PubmedTag::Pagination
+                | PubmedTag::PublicationDate
+                | PubmedTag::PublicationType
+                | PubmedTag::Publisher
+                | PubmedTag::PubmedCentralIdentifier
+                | PubmedTag::PubmedUniqueIdentifier
+                | PubmedTag::Title
+                | PubmedTag::Volume
+        )
+    })
+}
+
+/// Append a `TAG - value` line, skipping blank values.
+///
+/// Values longer than [`LINE_WIDTH`] are wrapped on whitespace. A line never
+/// ends in a word ending with `-`, because the parser joins such a line to the
+/// next without a space.
+fn push_line(out: &mut String, tag: PubmedTag, value: &str) {
+    let mut words = value.split_whitespace();
+    let Some(first) = words.next() else {
+        return;
+    };
+    let mut line = format!("{:<4}- {}", tag.as_tag(), first);
+    for word in words {
+        if line.len() + 1 + word.len() > LINE_WIDTH && !line.ends_with('-') {
+            out.push_str(&line);
+            out.push('\n');
+            line = format!("{CONTINUATION_INDENT}{word}");
+        } else {
+            line.push(' ');
+            line.push_str(word);
+        }
+    }
+    out.push_str(&line);
+    out.push('\n');
+}
+
+fn push_opt(out: &mut String, tag: PubmedTag, value: Option<&str>) {
+    if let Some(value) = value {
+        push_line(out, tag, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Author, ContributorRole};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("TI", Some(PubmedTag::Title), false)]
+    #[case("GR", Some(PubmedTag::GrantsAndFunding), true)]
+    #[case("AID", Some(PubmedTag::ArticleIdentifier), true)]
+    #[case("AU", Some(PubmedTag::Author), false)]
+    #[case("N1", None, false)]
+    fn test_passthrough_tag(
+        #[case] key: &str,
+        #[case] tag: Option<PubmedTag>,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(PubmedTag::from_tag(key), tag);
+        assert_eq!(passthrough_tag(key).is_some(), expected);
+    }
+
+    #[test]
+    fn test_write_minimal_record() {
+        let citation = Citation {
+            citation_type: vec!["Journal Article".to_string()],
+            title: "Example".to_string(),
+            authors: vec![
+                Author {
+                    name: "Watson".to_string(),
+                    given_name: Some("James".to_string()),
+                    middle_name: Some("D".to_string()),
+                    affiliations: vec!["Cambridge".to_string()],
+                    role: ContributorRole::Author,
+                    orcid: None,
+                    is_organization: false,
+                },
+                Author {
+                    name: "World Health Organization".to_string(),
+                    given_name: None,
+                    middle_name: None,
+                    affiliations: Vec::new(),
+                    role: ContributorRole::Author,
+                    orcid: None,
+                    is_organization: false,
+                },
+            ],
+            doi: Some("10.1000/xyz".to_string()),
+            pmid: Some("123".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            write_pubmed(&[citation]),
+            "PMID- 123\n\
+             TI  - Example\n\
+             LID - 10.1000/xyz [doi]\n\
+             FAU - Watson, James D\n\
+             AU  - Watson JD\n\
+             AD  - Cambridge\n\
+             FAU - World Health Organization\n\
+             PT  - Journal Article\n\n"
+        );
+    }
+
+    #[test]
+    fn test_push_line_wraps_long_values() {
+        let mut out = String::new();
+        push_line(&mut out, PubmedTag::Title, &"word ".repeat(20));
+        assert_eq!(
+            out,
+            "TI  - word word word word word word word word word word word word word word word word\n      \
+             word word word word\n"
+        );
+    }
+
+    #[test]
+    fn test_push_line_keeps_hyphenated_words_together() {
+        let mut out = String::new();
+        let value = format!("{}self- assembled end", "word ".repeat(15));
+        push_line(&mut out, PubmedTag::Abstract, &value);
+        assert_eq!(
+            out,
+            "AB  - word word word word word word word word word word word word word word word self- assembled\n      \
+             end\n"
+        );
+    }
+}
@@ -0,0 +1,454 @@
+//! PubMed XML parser implementation.
+//!
+//! Parses the `<PubmedArticleSet>` documents returned by NCBI E-utilities
+//! `efetch` (`db=pubmed&retmode=xml`), including `<PubmedBookArticle>`
+//! records. Fields are mapped the same way as the MEDLINE text format read by
+//! [`crate::PubMedParser`].
+//!
+//! # Example
+//!
+//! ```
+//! use biblib::{CitationParser, PubMedXmlParser};
+//!
+//! let input = r#"<?xml version="1.0" ?>
+//! <PubmedArticleSet>
+//!   <PubmedArticle>
+//!     <MedlineCitation>
+//!       <PMID Version="1">12345678</PMID>
+//!       <Article>
+//!         <Journal>
+//!           <JournalIssue><PubDate><Year>2023</Year><Month>Jan</Month></PubDate></JournalIssue>
+//!           <Title>Test Journal</Title>
+//!           <ISOAbbreviation>Test J</ISOAbbreviation>
+//!         </Journal>
+//!         <ArticleTitle>Example Title</ArticleTitle>
+//!         <AuthorList>
+//!           <Author><LastName>Smith</LastName><ForeName>John</ForeName></Author>
+//!         </AuthorList>
+//!       </Article>
+//!     </MedlineCitation>
+//!     <PubmedData>
+//!       <ArticleIdList>
+//!         <ArticleId IdType="doi">10.1000/example</ArticleId>
+//!       </ArticleIdList>
+//!     </PubmedData>
+//!   </PubmedArticle>
+//! </PubmedArticleSet>"#;
+//!
+//! let citations = PubMedXmlParser::new().parse(input).unwrap();
+//! assert_eq!(citations[0].pmid.as_deref(), Some("12345678"));
+//! assert_eq!(citations[0].journal_abbr.as_deref(), Some("Test J"));
+//! assert_eq!(citations[0].doi.as_deref(), Some("10.1000/example"));
+//! ```
+
+mod parse;
+mod tree;
+
+use crate::error::ParseError;
+use crate::stream::parse_whole;
+use crate::{Citation, CitationFormat, CitationParser, ParseOptions, ParseReport, collect_report};
+pub(crate) use parse::looks_like_pubmed_xml;
+use parse::{parse_pubmed_xml, pubmed_xml_records, pubmed_xml_records_with_warnings};
+
+/// Parser for PubMed XML (`PubmedArticleSet`) citations.
+///
+/// Fields are mapped as follows:
+///
+/// - `PMID`, and `pmc` / `doi` entries of `ArticleIdList` → `pmid`, `pmc_id`,
+///   `doi` (falling back to a DOI `ELocationID`)
+/// - `ArticleTitle` (or `BookTitle`) → `title`
+/// - `AuthorList` → `authors`, with `AffiliationInfo` as affiliations, an
+///   ORCID `Identifier` as `orcid` and `CollectiveName` as corporate authors;
+///   editor lists and `InvestigatorList` → `contributors`
+/// - `Journal/Title` and `ISOAbbreviation` → `journal` and `journal_abbr`
+/// - `PubDate` → `date`
+/// - `AbstractText` sections → `abstract_text`, labelled as in MEDLINE output
+/// - `MeshHeading` → `mesh_terms`, as MEDLINE `MH` values such as
+///   `*Neoplasms/therapy`
+/// - `PublicationType` → `citation_type`
+/// - Book `Isbn` and trial registry numbers in `DataBankList` → `identifiers`
+///
+/// Keywords, other article IDs, author identifiers other than ORCIDs and
+/// other data bank numbers are kept in `extra_fields` under the MEDLINE tags
+/// `OT`, `AID`, `AUID` and `SI`, as [`crate::PubMedParser`] does.
+#[derive(Debug, Clone, Default)]
+pub struct PubMedXmlParser;
+
+impl PubMedXmlParser {
+    /// Creates a new PubMed XML parser instance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CitationParser for PubMedXmlParser {
+    fn parse(&self, input: &str) -> Result<Vec<Citation>, ParseError> {
+        if input.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        parse_pubmed_xml(input)
+    }
+
+    /// Reads the whole input, then yields one result per article.
+    fn parse_reader<'r, R>(
+        &self,
+        reader: R,
+    ) -> Box<dyn Iterator<Item = Result<Citation, ParseError>> + 'r>
+    where
+        R: std::io::Read + 'r,
+    {
+        parse_whole(reader, CitationFormat::PubMedXml, pubmed_xml_records)
+    }
+
+    /// Reports unparseable `ArticleDate`/`DateRevised` dates and DOIs that
+    /// are not valid as warnings. Invalid DOIs are dropped.
+    fn parse_with_options(
+        &self,
+        input: &str,
+        options: &ParseOptions,
+    ) -> Result<ParseReport, ParseError> {
+        collect_report(pubmed_xml_records_with_warnings(input), options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContributorRole, DateKind, Scheme, ValueError};
+    use pretty_assertions::assert_eq;
+
+    const ARTICLE: &str = r#"<?xml version="1.0" ?>
+<!DOCTYPE PubmedArticleSet PUBLIC "-//NLM//DTD PubMedArticle, 1st January 2024//EN" "https://dtd.nlm.nih.gov/ncbi/pubmed/out/pubmed_240101.dtd">
+<PubmedArticleSet>
+  <PubmedArticle>
+    <MedlineCitation Status="MEDLINE" Owner="NLM">
+      <PMID Version="1">12345678</PMID>
+      <DateRevised><Year>2023</Year><Month>03</Month><Day>15</Day></DateRevised>
+      <Article PubModel="Print-Electronic">
+        <Journal>
+          <ISSN IssnType="Electronic">1234-5678</ISSN>
+          <JournalIssue CitedMedium="Internet">
+            <Volume>10</Volume>
+            <Issue>2</Issue>
+            <PubDate><Year>2023</Year><Month>Jan</Month><Day>23</Day></PubDate>
+          </JournalIssue>
+          <Title>Test Journal</Title>
+          <ISOAbbreviation>Test J</ISOAbbreviation>
+        </Journal>
+        <ArticleTitle>Test <i>Article</i> Title</ArticleTitle>
+        <Pagination><MedlinePgn>100-110</MedlinePgn></Pagination>
+        <ELocationID EIdType="doi" ValidYN="Y">10.1000/elocation</ELocationID>
+        <Abstract>
+          <AbstractText Label="BACKGROUND">Background text.</AbstractText>
+          <AbstractText Label="CONCLUSIONS">Conclusion text.</AbstractText>
+        </Abstract>
+        <AuthorList CompleteYN="Y">
+          <Author ValidYN="Y">
+            <LastName>Smith</LastName>
+            <ForeName>John A</ForeName>
+            <Initials>JA</Initials>
+            <Identifier Source="ORCID">0000-0002-1825-0097</Identifier>
+            <AffiliationInfo><Affiliation>Test University.</Affiliation></AffiliationInfo>
+          </Author>
+          <Author ValidYN="Y">
+            <CollectiveName>Test Study Group</CollectiveName>
+          </Author>
+        </AuthorList>
+        <Language>eng</Language>
+        <DataBankList CompleteYN="Y">
+          <DataBank>
+            <DataBankName>ClinicalTrials.gov</DataBankName>
+            <AccessionNumberList><AccessionNumber>NCT01234567</AccessionNumber></AccessionNumberList>
+          </DataBank>
+          <DataBank>
+            <DataBankName>GENBANK</DataBankName>
+            <AccessionNumberList><AccessionNumber>AB123456</AccessionNumber></AccessionNumberList>
+          </DataBank>
+        </DataBankList>
+        <PublicationTypeList>
+          <PublicationType UI="D016428">Journal Article</PublicationType>
+          <PublicationType UI="D016454">Review</PublicationType>
+        </PublicationTypeList>
+        <ArticleDate DateType="Electronic"><Year>2022</Year><Month>12</Month><Day>30</Day></ArticleDate>
+      </Article>
+      <MedlineJournalInfo>
+        <MedlineTA>Test J</MedlineTA>
+        <ISSNLinking>1234-0000</ISSNLinking>
+      </MedlineJournalInfo>
+      <MeshHeadingList>
+        <MeshHeading><DescriptorName MajorTopicYN="N">Humans</DescriptorName></MeshHeading>
+      </MeshHeadingList>
+      <KeywordList Owner="NOTNLM">
+        <Keyword MajorTopicYN="N">alpha</Keyword>
+        <Keyword MajorTopicYN="N">beta</Keyword>
+      </KeywordList>
+      <InvestigatorList>
+        <Investigator ValidYN="Y">
+          <LastName>Doe</LastName>
+          <ForeName>Jane</ForeName>
+          <Initials>J</Initials>
+          <AffiliationInfo><Affiliation>Test Hospital.</Affiliation></AffiliationInfo>
+        </Investigator>
+      </InvestigatorList>
+    </MedlineCitation>
+    <PubmedData>
+      <ArticleIdList>
+        <ArticleId IdType="pubmed">12345678</ArticleId>
+        <ArticleId IdType="doi">10.1000/test</ArticleId>
+        <ArticleId IdType="pmc">PMC1234567</ArticleId>
+      </ArticleIdList>
+      <ReferenceList>
+        <Reference>
+          <Citation>Other work.</Citation>
+          <ArticleIdList><ArticleId IdType="doi">10.1000/reference</ArticleId></ArticleIdList>
+        </Reference>
+      </ReferenceList>
+    </PubmedData>
+  </PubmedArticle>
+</PubmedArticleSet>"#;
+
+    #[test]
+    fn test_parse_article() {
+        let citations = PubMedXmlParser::new().parse(ARTICLE).unwrap();
+        assert_eq!(citations.len(), 1);
+        let citation = &citations[0];
+
+        assert_eq!(citation.pmid.as_deref(), Some("12345678"));
+        assert_eq!(citation.pmc_id.as_deref(), Some("PMC1234567"));
+        assert_eq!(citation.doi.as_deref(), Some("10.1000/test"));
+        assert_eq!(citation.title, "Test Article Title");
+        assert_eq!(citation.journal.as_deref(), Some("Test Journal"));
+        assert_eq!(citation.journal_abbr.as_deref(), Some("Test J"));
+        assert_eq!(citation.citation_type, vec!["Journal Article", "Review"]);
+        assert_eq!(
+            citation.issn,
+            vec!["1234-5678 (Electronic)", "1234-0000 (Linking)"]
+        );
+        assert_eq!(citation.pages.as_deref(), Some("100-110"));
+        assert_eq!(
+            citation.abstract_text.as_deref(),
+            Some("BACKGROUND: Background text. CONCLUSIONS: Conclusion text.")
+        );
+        assert_eq!(citation.mesh_terms, vec!["Humans"]);
+        assert_eq!(citation.language.as_deref(), Some("eng"));
+
+        assert_eq!(citation.authors.len(), 2);
+        assert_eq!(citation.authors[0].name, "Smith");
+        assert_eq!(citation.authors[0].given_name.as_deref(), Some("John"));
+        assert_eq!(citation.authors[0].middle_name.as_deref(), Some("A"));
+        assert_eq!(citation.authors[0].affiliations, vec!["Test University."]);
+
+        assert_eq!(citation.extra_fields["OT"], vec!["alpha", "beta"]);
+        assert_eq!(citation.extra_fields["AID"], vec!["10.1000/test [doi]"]);
+        assert_eq!(
+            citation.authors[0].orcid.as_deref(),
+            Some("0000-0002-1825-0097")
+        );
+        assert!(citation.authors[1].is_organization);
+        assert!(!citation.extra_fields.contains_key("AUID"));
+        assert_eq!(citation.contributors.len(), 1);
+        assert_eq!(citation.contributors[0].name, "Doe");
+        assert_eq!(citation.contributors[0].role, ContributorRole::Investigator);
+        assert_eq!(
+            citation.contributors[0].affiliations,
+            vec!["Test Hospital."]
+        );
+        assert_eq!(citation.authors[1].name, "Test Study Group");
+        assert_eq!(citation.authors[1].role, ContributorRole::CorporateAuthor);
+        assert!(!citation.extra_fields.contains_key("CN"));
+        assert_eq!(citation.identifier(Scheme::Nct), Some("NCT01234567"));
+        assert_eq!(citation.extra_fields["SI"], vec!["GENBANK/AB123456"]);
+        assert_eq!(
+            citation
+                .date_of(DateKind::Epub)
+                .map(|date| (date.year, date.month, date.day)),
+            Some((2022, Some(12), Some(30)))
+        );
+        assert_eq!(
+            citation
+                .date_of(DateKind::LastUpdated)
+                .map(|date| (date.year, date.month, date.day)),
+            Some((2023, Some(3), Some(15)))
+        );
+    }
+
+    #[cfg(feature = "pubmed")]
+    #[test]
+    fn test_matches_medline_mapping() {
+        let medline = r#"PMID- 12345678
+IS  - 1234-5678 (Electronic)
+IS  - 1234-0000 (Linking)
+VI  - 10
+IP  - 2
+DP  - 2023 Jan 23
+TI  - Test Article Title
+PG  - 100-110
+LID - 10.1000/test [doi]
+AB  - BACKGROUND: Background text. CONCLUSIONS: Conclusion text.
+FAU - Smith, John A
+AU  - Smith JA
+AUID- ORCID: 0000-0002-1825-0097
+AD  - Test University.
+CN  - Test Study Group
+LA  - eng
+SI  - ClinicalTrials.gov/NCT01234567
+SI  - GENBANK/AB123456
+PT  - Journal Article
+PT  - Review
+TA  - Test J
+JT  - Test Journal
+MH  - Humans
+OT  - alpha
+OT  - beta
+PMC - PMC1234567
+FIR - Doe, Jane
+IR  - Doe J
+IRAD- Test Hospital.
+AID - 10.1000/test [doi]
+DEP - 20221230
+LR  - 20230315
+"#;
+        let expected = crate::PubMedParser::new().parse(medline).unwrap();
+        let actual = PubMedXmlParser::new().parse(ARTICLE).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parse_book_article() {
+        let input = r#"<PubmedArticleSet>
+  <PubmedBookArticle>
+    <BookDocument>
+      <PMID Version="1">20301295</PMID>
+      <ArticleIdList><ArticleId IdType="bookaccession">NBK1116</ArticleId></ArticleIdList>
+      <Book>
+        <Publisher><PublisherName>University of Washington, Seattle</PublisherName></Publisher>
+        <BookTitle book="gene">GeneReviews</BookTitle>
+        <PubDate><Year>1993</Year></PubDate>
+        <Isbn>978-0-306-40615-7</Isbn>
+      </Book>
+      <ArticleTitle book="gene" part="brca1">BRCA1- and BRCA2-Associated Cancer</ArticleTitle>
+      <Language>eng</Language>
+      <AuthorList Type="authors">
+        <Author><LastName>Petrucelli</LastName><ForeName>Nancie</ForeName></Author>
+      </AuthorList>
+      <PublicationType UI="D016454">Review</PublicationType>
+    </BookDocument>
+    <PubmedBookData>
+      <ArticleIdList><ArticleId IdType="pubmed">20301295</ArticleId></ArticleIdList>
+    </PubmedBookData>
+  </PubmedBookArticle>
+</PubmedArticleSet>"#;
+
+        let citation = PubMedXmlParser::new().parse(input).unwrap().remove(0);
+        assert_eq!(citation.pmid.as_deref(), Some("20301295"));
+        assert_eq!(citation.title, "BRCA1- and BRCA2-Associated Cancer");
+        assert_eq!(
+            citation.publisher.as_deref(),
+            Some("University of Washington, Seattle")
+        );
+        assert_eq!(citation.date.as_ref().map(|date| date.year), Some(1993));
+        assert_eq!(citation.authors[0].name, "Petrucelli");
+        assert_eq!(
+            citation.extra_fields["AID"],
+            vec!["NBK1116 [bookaccession]"]
+        );
+        assert_eq!(citation.identifier(Scheme::Isbn), Some("9780306406157"));
+    }
+
+    #[test]
+    fn test_missing_title_is_error() {
+        let input = r#"<PubmedArticleSet>
+  <PubmedArticle>
+    <MedlineCitation><PMID>1</PMID><Article></Article></MedlineCitation>
+  </PubmedArticle>
+</PubmedArticleSet>"#;
+
+        let err = PubMedXmlParser::new().parse(input).unwrap_err();
+        assert_eq!(err.format, CitationFormat::PubMedXml);
+        assert_eq!(err.line, Some(2));
+        assert!(matches!(
+            err.error,
+            ValueError::MissingValue {
+                key: "ArticleTitle",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_mismatched_tag_is_error() {
+        let input =
+            "<PubmedArticleSet>\n<PubmedArticle>\n<MedlineCitation></Article>\n</PubmedArticleSet>";
+        let err = PubMedXmlParser::new().parse(input).unwrap_err();
+        assert_eq!(err.format, CitationFormat::PubMedXml);
+        assert!(matches!(err.error, ValueError::Syntax(_)));
+    }
+
+    #[test]
+    fn test_parse_with_options_reports_warnings() {
+        use crate::{OnError, ParseOptions, WarningKind};
+
+        let input = r#"<PubmedArticleSet>
+  <PubmedArticle>
+    <MedlineCitation><PMID>1</PMID><Article><ArticleTitle>First</ArticleTitle></Article></MedlineCitation>
+  </PubmedArticle>
+  <PubmedArticle>
+    <MedlineCitation>
+      <PMID>2</PMID>
+      <DateRevised><Year>soon</Year></DateRevised>
+      <Article><ArticleTitle>Second</ArticleTitle></Article>
+    </MedlineCitation>
+    <PubmedData><ArticleIdList><ArticleId IdType="doi">not a doi</ArticleId></ArticleIdList></PubmedData>
+  </PubmedArticle>
+</PubmedArticleSet>"#;
+        for on_error in [OnError::Fail, OnError::Collect] {
+            let options = ParseOptions::new().with_on_error(on_error);
+            let report = PubMedXmlParser::new()
+                .parse_with_options(input, &options)
+                .unwrap();
+
+            assert_eq!(report.citations.len(), 2);
+            assert_eq!(report.citations[1].doi, None);
+            let kinds: Vec<_> = report.warnings.iter().map(|w| &w.kind).collect();
+            assert_eq!(
+                kinds,
+                vec![
+                    &WarningKind::UnparseableDate {
+                        key: "DateRevised".to_string(),
+                        value: "soon".to_string()
+                    },
+                    &WarningKind::InvalidDoi {
+                        key: "ArticleId".to_string(),
+                        value: "not a doi".to_string()
+                    },
+                ]
+            );
+            assert_eq!(report.warnings[0].line, Some(5));
+        }
+    }
+
+    #[test]
+    fn test_parse_reader_yields_one_result_per_article() {
+        let input = r#"<PubmedArticleSet>
+  <PubmedArticle>
+    <MedlineCitation><PMID>1</PMID><Article><ArticleTitle>First</ArticleTitle></Article></MedlineCitation>
+  </PubmedArticle>
+  <PubmedArticle>
+    <MedlineCitation><PMID>2</PMID><Article></Article></MedlineCitation>
+  </PubmedArticle>
+  <PubmedArticle>
+    <MedlineCitation><PMID>3</PMID><Article><ArticleTitle>Third</ArticleTitle></Article></MedlineCitation>
+  </PubmedArticle>
+</PubmedArticleSet>"#;
+        let streamed: Vec<_> = PubMedXmlParser::new()
+            .parse_reader(input.as_bytes())
+            .collect();
+
+        assert_eq!(streamed.len(), 3);
+        assert_eq!(streamed[0].as_ref().unwrap().pmid.as_deref(), Some("1"));
+        assert_eq!(streamed[1].as_ref().unwrap_err().line, Some(5));
+        assert_eq!(streamed[2].as_ref().unwrap().pmid.as_deref(), Some("3"));
+    }
+}
@@ -0,0 +1,642 @@
+//! PubMed XML parsing implementation.
+//!
+//! Each `<PubmedArticle>` or `<PubmedBookArticle>` is read into an element
+//! tree and mapped onto [`Citation`] fields the same way [`crate::PubMedParser`]
+//! maps the equivalent MEDLINE tags. Values the MEDLINE parser keeps in
+//! `extra_fields` are stored here under the same MEDLINE tag names.
+
+use crate::error::{ParseError, ParseWarning, SourceSpan, ValueError, WarningKind, fields};
+use crate::pubmed_xml::tree::{
+    XmlElement, buffer_position_to_line_number, read_element, xml_error,
+};
+use crate::utils::{
+    detect_identifier, format_doi, format_orcid, parse_bib_year_month, parse_pubmed_date,
+    partition_contributors, split_given_and_middle,
+};
+use crate::{
+    Author, Citation, CitationFormat, ContributorRole, Date, DateKind, Identifier, Scheme, Season,
+    TypedDate,
+};
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use std::collections::HashMap;
+
+/// Quick check used by format auto-detection.
+pub(crate) fn looks_like_pubmed_xml(content: &str) -> bool {
+    let trimmed = content.trim_start_matches('\u{feff}').trim_start();
+
+    trimmed.starts_with('<')
+        && (content.contains("<PubmedArticleSet")
+            || content.contains("<PubmedArticle>")
+            || content.contains("<PubmedBookArticle>"))
+}
+
+/// Parse PubMed XML content into citations.
+pub(crate) fn parse_pubmed_xml(content: &str) -> Result<Vec<Citation>, ParseError> {
+    pubmed_xml_records(content).into_iter().collect()
+}
+
+/// Parse PubMed XML content into one result per article. An XML syntax error
+/// ends parsing and is the last item.
+pub(crate) fn pubmed_xml_records(content: &str) -> Vec<Result<Citation, ParseError>> {
+    pubmed_xml_records_with_warnings(content)
+        .into_iter()
+        .map(|record| record.map(|(citation, _)| citation))
+        .collect()
+}
+
+/// [`pubmed_xml_records`], keeping the warnings for each citation.
+pub(crate) fn pubmed_xml_records_with_warnings(
+    content: &str,
+) -> Vec<Result<(Citation, Vec<ParseWarning>), ParseError>> {
+    let mut reader = Reader::from_str(content);
+    let mut citations = Vec::new();
+    let mut buf = Vec::new();
+
+    loop {
+        let pos = reader.buffer_position() as usize;
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e))
+                if matches!(e.name().as_ref(), b"PubmedArticle" | b"PubmedBookArticle") =>
+            {
+                let record = match read_element(&mut reader, e, content, pos) {
+                    Ok(record) => record,
+                    Err(err) => {
+                        citations.push(Err(err));
+                        break;
+                    }
+                };
+                let end_pos = reader.buffer_position() as usize;
+                let span = SourceSpan::new(pos, end_pos);
+                citations.push(build_citation(&record, content, span));
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                citations.push(Err(xml_error(
+                    content,
+                    pos,
+                    reader.buffer_position() as usize,
+                    e.to_string(),
+                )));
+                break;
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    citations
+}
+
+fn build_citation(
+    record: &XmlElement,
+    content: &str,
+    span: SourceSpan,
+) -> Result<(Citation, Vec<ParseWarning>), ParseError> {
+    let mut warnings = Vec::new();
+    let mut warn = |kind| {
+        warnings.push(
+            ParseWarning::at_line(
+                buffer_position_to_line_number(content, span.start),
+                CitationFormat::PubMedXml,
+                kind,
+            )
+            .with_span(span.clone()),
+        );
+    };
+    let is_book = record.name == "PubmedBookArticle";
+    let (document, pubmed_data) = if is_book {
+        (record.child("BookDocument"), record.child("PubmedBookData"))
+    } else {
+        (record.child("MedlineCitation"), record.child("PubmedData"))
+    };
+    let Some(document) = document else {
+        return Err(record_error(
+            content,
+            span,
+            ValueError::Syntax(format!(
+                "<{}> has no {} element",
+                record.name,
+                if is_book {
+                    "BookDocument"
+                } else {
+                    "MedlineCitation"
+                }
+            )),
+        ));
+    };
+    // Journal articles keep their bibliographic data in `Article`; book
+    // records keep it on the `BookDocument` itself and its `Book` child.
+    let article = if is_book {
+        document
+    } else {
+        document.child("Article").unwrap_or(document)
+    };
+    let book = document.child("Book");
+    let journal = article.child("Journal");
+
+    let title = article
+        .text_at(&["ArticleTitle"])
+        .or_else(|| book.and_then(|book| book.text_at(&["BookTitle"])))
+        .ok_or_else(|| {
+            record_error(
+                content,
+                span.clone(),
+                ValueError::MissingValue {
+                    field: fields::TITLE,
+                    key: "ArticleTitle",
+                },
+            )
+        })?;
+
+    let pub_date = journal
+        .and_then(|journal| journal.path(&["JournalIssue", "PubDate"]))
+        .or_else(|| book.and_then(|book| book.child("PubDate")));
+    let date = match pub_date {
+        Some(pub_date) => Some(parse_pub_date(pub_date).ok_or_else(|| {
+            record_error(
+                content,
+                span.clone(),
+                ValueError::BadValue {
+                    field: fields::DATE,
+                    key: "PubDate",
+                    value: pub_date.text(),
+                    reason: "expected a Year or MedlineDate".to_string(),
+                },
+            )
+        })?),
+        None => None,
+    };
+
+    // The electronic `ArticleDate` and `DateRevised` correspond to MEDLINE's
+    // `DEP` and `LR` tags.
+    let mut typed_date = |element: Option<&XmlElement>, kind| {
+        let element = element?;
+        let date = parse_pub_date(element);
+        if date.is_none() {
+            warn(WarningKind::UnparseableDate {
+                key: element.name.clone(),
+                value: element.text(),
+            });
+        }
+        date.map(|date| TypedDate { kind, date })
+    };
+    let epub = typed_date(
+        article.children_named("ArticleDate").find(|date| {
+            date.attr("DateType")
+                .is_none_or(|kind| kind == "Electronic")
+        }),
+        DateKind::Epub,
+    );
+    let last_updated = typed_date(document.child("DateRevised"), DateKind::LastUpdated);
+
+    let mut extra_fields: HashMap<String, Vec<String>> = HashMap::new();
+    // Book records list the book's editors in `Book/AuthorList`.
+    let author_lists = article
+        .children_named("AuthorList")
+        .chain(
+            book.into_iter()
+                .flat_map(|book| book.children_named("AuthorList")),
+        )
+        .map(|list| match list.attr("Type") {
+            Some("editors") => (list, ContributorRole::Editor),
+            _ => (list, ContributorRole::Author),
+        });
+    let investigator_lists = document
+        .children_named("InvestigatorList")
+        .map(|list| (list, ContributorRole::Investigator));
+    let mut people = Vec::new();
+    for (list, role) in author_lists.chain(investigator_lists) {
+        for person in list
+            .children_named("Author")
+            .chain(list.children_named("Investigator"))
+        {
+            if person.attr("ValidYN") == Some("N") {
+                continue;
+            }
+            let mut contributor = parse_person(person, role);
+            for identifier in person.children_named("Identifier") {
+                let value = identifier.text();
+                if value.is_empty() {
+                    continue;
+                }
+                let source = identifier.attr("Source").unwrap_or("ID");
+                // The first valid ORCID iD belongs to the contributor, as
+                // `PubMedParser` does with `AUID` lines.
+                let orcid = if source.eq_ignore_ascii_case("ORCID") {
+                    format_orcid(&value)
+                } else {
+                    None
+                };
+                match (contributor.as_mut().filter(|c| c.orcid.is_none()), orcid) {
+                    (Some(contributor), Some(orcid)) => contributor.orcid = Some(orcid),
+                    _ => push_extra(&mut extra_fields, "AUID", format!("{}: {}", source, value)),
+                }
+            }
+            people.extend(contributor);
+        }
+    }
+    let (authors, contributors) = partition_contributors(people);
+
+    let article_ids: Vec<(&str, String)> = pubmed_data
+        .into_iter()
+        .chain(Some(document))
+        .filter_map(|element| element.child("ArticleIdList"))
+        .flat_map(|list| list.children_named("ArticleId"))
+        .map(|id| (id.attr("IdType").unwrap_or(""), id.text()))
+        .filter(|(_, value)| !value.is_empty())
+        .collect();
+    let article_id = |id_type: &str| {
+        article_ids
+            .iter()
+            .find(|(kind, _)| *kind == id_type)
+            .map(|(_, value)| value.clone())
+    };
+    for (id_type, value) in &article_ids {
+        let aid = format!("{} [{}]", value, id_type);
+        let known = extra_fields
+            .get("AID")
+            .is_some_and(|ids| ids.contains(&aid));
+        if !matches!(*id_type, "pubmed" | "pmc") && !known {
+            push_extra(&mut extra_fields, "AID", aid);
+        }
+    }
+
+    let doi = article_id("doi")
+        .map(|doi| ("ArticleId", doi))
+        .or_else(|| {
+            article
+                .children_named("ELocationID")
+                .find(|location| location.attr("EIdType") == Some("doi"))
+                .map(XmlElement::text)
+                .filter(|doi| !doi.is_empty())
+                .map(|doi| ("ELocationID", doi))
+        })
+        .and_then(|(key, doi)| {
+            if format_doi(&doi).is_some() {
+                return Some(doi);
+            }
+            warn(WarningKind::InvalidDoi {
+                key: key.to_string(),
+                value: doi,
+            });
+            None
+        });
+
+    for keyword in document
+        .children_named("KeywordList")
+        .flat_map(|list| list.children_named("Keyword"))
+        .map(XmlElement::text)
+        .filter(|keyword| !keyword.is_empty())
+    {
+        push_extra(&mut extra_fields, "OT", keyword);
+    }
+
+    // Book ISBNs and trial registry numbers from `DataBankList`, which
+    // `PubMedParser` reads from `ISBN` and `SI` lines.
+    let mut identifiers = Vec::new();
+    for isbn in book
+        .into_iter()
+        .flat_map(|book| book.children_named("Isbn"))
+        .map(XmlElement::text)
+        .filter(|isbn| !isbn.is_empty())
+    {
+        match Identifier::new(Scheme::Isbn, &isbn) {
+            Some(identifier) => identifiers.push(identifier),
+            None => push_extra(&mut extra_fields, "ISBN", isbn),
+        }
+    }
+    for databank in article
+        .children_named("DataBankList")
+        .flat_map(|list| list.children_named("DataBank"))
+    {
+        let name = databank.text_at(&["DataBankName"]).unwrap_or_default();
+        for number in databank
+            .children_named("AccessionNumberList")
+            .flat_map(|list| list.children_named("AccessionNumber"))
+            .map(XmlElement::text)
+            .filter(|number| !number.is_empty())
+        {
+            match detect_identifier(&number) {
+                Some(identifier) => identifiers.push(identifier),
+                None => push_extra(&mut extra_fields, "SI", format!("{}/{}", name, number)),
+            }
+        }
+    }
+
+    let mut issn: Vec<String> = journal
+        .into_iter()
+        .flat_map(|journal| journal.children_named("ISSN"))
+        .filter_map(|element| {
+            let value = element.text();
+            (!value.is_empty()).then(|| match element.attr("IssnType") {
+                Some(kind) => format!("{} ({})", value, kind),
+                None => value,
+            })
+        })
+        .collect();
+    if let Some(linking) = document.text_at(&["MedlineJournalInfo", "ISSNLinking"]) {
+        issn.push(format!("{} (Linking)", linking));
+    }
+
+    let citation = Citation {
+        citation_type: article
+            .children_named("PublicationTypeList")
+            .flat_map(|list| list.children_named("PublicationType"))
+            .map(XmlElement::text)
+            .filter(|kind| !kind.is_empty())
+            .collect(),
+        title,
+        authors,
+        contributors,
+        journal: journal.and_then(|journal| journal.text_at(&["Title"])),
+        journal_abbr: journal
+            .and_then(|journal| journal.text_at(&["ISOAbbreviation"]))
+            .or_else(|| document.text_at(&["MedlineJournalInfo", "MedlineTA"])),
+        date,
+        dates: epub.into_iter().chain(last_updated).collect(),
+        volume: journal
+            .and_then(|journal| journal.text_at(&["JournalIssue", "Volume"]))
+            .or_else(|| book.and_then(|book| book.text_at(&["Volume"]))),
+        issue: journal.and_then(|journal| journal.text_at(&["JournalIssue", "Issue"])),
+        pages: article.child("Pagination").and_then(parse_pagination),
+        issn,
+        doi,
+        accession_number: None,
+        pmid: document.text_at(&["PMID"]),
+        pmc_id: article_id("pmc"),
+        identifiers,
+        abstract_text: article.child("Abstract").and_then(parse_abstract),
+        keywords: Vec::new(),
+        urls: Vec::new(),
+        language: join_if_some(
+            article
+                .children_named("Language")
+                .map(XmlElement::text)
+                .filter(|language| !language.is_empty())
+                .collect(),
+        ),
+        mesh_terms: document
+            .children_named("MeshHeadingList")
+            .flat_map(|list| list.children_named("MeshHeading"))
+            .filter_map(mesh_term)
+            .collect(),
+        publisher: book.and_then(|book| book.text_at(&["Publisher", "PublisherName"])),
+        extra_fields,
+    };
+    Ok((citation, warnings))
+}
+
+/// Parse an `Author` or `Investigator` element as a contributor with `role`.
+///
+/// A `CollectiveName` in an author list is a corporate author.
+fn parse_person(person: &XmlElement, role: ContributorRole) -> Option<Author> {
+    let affiliations = person
+        .children_named("AffiliationInfo")
+        .filter_map(|info| info.text_at(&["Affiliation"]))
+        .collect();
+    let Some(name) = person.text_at(&["LastName"]) else {
+        let name = person.text_at(&["CollectiveName"])?;
+        return Some(Author {
+            affiliations,
+            ..Author::organization(name, role)
+        });
+    };
+    let (given_name, middle_name) = person
+        .text_at(&["ForeName"])
+        .or_else(|| person.text_at(&["Initials"]))
+        .map(|given| split_given_and_middle(&given))
+        .unwrap_or((None, None));
+
+    Some(Author {
+        name,
+        given_name,
+        middle_name,
+        affiliations,
+        role,
+        orcid: None,
+        is_organization: false,
+    })
+}
+
+/// Parse a `PubDate`, which holds either `Year` with `Month`/`Day` or
+/// `Season`, or a free-text `MedlineDate` such as `2023 Jan-Feb`.
+fn parse_pub_date(pub_date: &XmlElement) -> Option<Date> {
+    let Some(year) = pub_date.text_at(&["Year"]) else {
+        return pub_date
+            .text_at(&["MedlineDate"])
+            .and_then(|date| parse_pubmed_date(&date));
+    };
+    let year: i32 = year.parse().ok()?;
+    let month = pub_date
+        .text_at(&["Month"])
+        .and_then(|month| parse_bib_year_month(&year.to_string(), &month))
+        .and_then(|date| date.month);
+    let day = month
+        .and(pub_date.text_at(&["Day"]))
+        .and_then(|day| day.parse().ok())
+        .filter(|day| (1..=31).contains(day));
+    let season = pub_date
+        .text_at(&["Season"])
+        .and_then(|season| Season::from_name(&season));
+
+    Some(Date {
+        year,
+        month,
+        day,
+        season: month.is_none().then_some(season).flatten(),
+        end: None,
+    })
+}
+
+/// `MedlinePgn`, or `StartPage-EndPage` when only those are given.
+fn parse_pagination(pagination: &XmlElement) -> Option<String> {
+    pagination.text_at(&["MedlinePgn"]).or_else(|| {
+        let start = pagination.text_at(&["StartPage"])?;
+        Some(match pagination.text_at(&["EndPage"]) {
+            Some(end) => format!("{}-{}", start, end),
+            None => start,
+        })
+    })
+}
+
+/// Join abstract sections as MEDLINE does, prefixing labelled sections of a
+/// structured abstract with `LABEL: `.
+fn parse_abstract(abstract_element: &XmlElement) -> Option<String> {
+    let sections: Vec<String> = abstract_element
+        .children_named("AbstractText")
+        .map(|section| {
+            let text = section.text();
+            match section.attr("Label").filter(|label| !label.is_empty()) {
+                Some(label) if !text.is_empty() => format!("{}: {}", label, text),
+                _ => text,
+            }
+        })
+        .filter(|section| !section.is_empty())
+        .collect();
+
+    (!sections.is_empty()).then(|| sections.join(" "))
+}
+
+/// Format a `MeshHeading` as a MEDLINE `MH` value, e.g. `*Neoplasms/therapy`.
+/// Major topics are marked with `*`.
+fn mesh_term(heading: &XmlElement) -> Option<String> {
+    let major = |element: &XmlElement| {
+        if element.attr("MajorTopicYN") == Some("Y") {
+            "*"
+        } else {
+            ""
+        }
+    };
+    let descriptor = heading.child("DescriptorName")?;
+    let name = descriptor.text();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut term = format!("{}{}", major(descriptor), name);
+    for qualifier in heading.children_named("QualifierName") {
+        term.push('/');
+        term.push_str(major(qualifier));
+        term.push_str(&qualifier.text());
+    }
+    Some(term)
+}
+
+fn push_extra(extra_fields: &mut HashMap<String, Vec<String>>, key: &str, value: String) {
+    extra_fields.entry(key.to_string()).or_default().push(value);
+}
+
+/// Multiple values for a single-valued field are joined the same way as in
+/// MEDLINE input.
+fn join_if_some(values: Vec<String>) -> Option<String> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.join(" AND "))
+    }
+}
+
+fn record_error(content: &str, span: SourceSpan, error: ValueError) -> ParseError {
+    ParseError::at_line(
+        buffer_position_to_line_number(content, span.start),
+        CitationFormat::PubMedXml,
+        error,
+    )
+    .with_span(span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn read(xml: &str) -> XmlElement {
+        let mut reader = Reader::from_str(xml);
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf).unwrap() {
+                Event::Start(e) => {
+                    let e = e.into_owned();
+                    return read_element(&mut reader, &e, xml, 0).unwrap();
+                }
+                Event::Eof => panic!("no element"),
+                _ => {}
+            }
+            buf.clear();
+        }
+    }
+
+    #[test]
+    fn test_parse_pub_date() {
+        let date = |xml: &str| parse_pub_date(&read(xml));
+        assert_eq!(
+            date("<PubDate><Year>2023</Year><Month>Mar</Month><Day>07</Day></PubDate>"),
+            Some(Date {
+                year: 2023,
+                month: Some(3),
+                day: Some(7),
+                season: None,
+                end: None
+            })
+        );
+        assert_eq!(
+            date("<PubDate><Year>2021</Year><Month>11</Month></PubDate>"),
+            Some(Date {
+                year: 2021,
+                month: Some(11),
+                day: None,
+                season: None,
+                end: None
+            })
+        );
+        assert_eq!(
+            date("<PubDate><MedlineDate>2019 Jan-Feb</MedlineDate></PubDate>"),
+            Some(Date {
+                year: 2019,
+                month: Some(1),
+                day: None,
+                season: None,
+                end: Some(Box::new(Date {
+                    year: 2019,
+                    month: Some(2),
+                    day: None,
+                    season: None,
+                    end: None
+                }))
+            })
+        );
+        assert_eq!(
+            date("<PubDate><Year>2020</Year><Season>Winter</Season></PubDate>"),
+            Some(Date {
+                year: 2020,
+                month: None,
+                day: None,
+                season: Some(Season::Winter),
+                end: None
+            })
+        );
+        assert_eq!(date("<PubDate><Season>Spring</Season></PubDate>"), None);
+    }
+
+    #[test]
+    fn test_mesh_term() {
+        let heading = read(
+            r#"<MeshHeading>
+  <DescriptorName UI="D009369" MajorTopicYN="Y">Neoplasms</DescriptorName>
+  <QualifierName UI="Q000188" MajorTopicYN="N">drug therapy</QualifierName>
+  <QualifierName UI="Q000628" MajorTopicYN="Y">therapy</QualifierName>
+</MeshHeading>"#,
+        );
+        assert_eq!(
+            mesh_term(&heading).as_deref(),
+            Some("*Neoplasms/drug therapy/*therapy")
+        );
+    }
+
+    #[test]
+    fn test_parse_structured_abstract() {
+        let element = read(
+            r#"<Abstract>
+  <AbstractText Label="BACKGROUND" NlmCategory="BACKGROUND">Some background.</AbstractText>
+  <AbstractText Label="RESULTS" NlmCategory="RESULTS">Some <i>results</i>.</AbstractText>
+  <CopyrightInformation>Copyright</CopyrightInformation>
+</Abstract>"#,
+        );
+        assert_eq!(
+            parse_abstract(&element).as_deref(),
+            Some("BACKGROUND: Some background. RESULTS: Some results.")
+        );
+    }
+
+    #[test]
+    fn test_looks_like_pubmed_xml() {
+        assert!(looks_like_pubmed_xml(
+            "<?xml version=\"1.0\" ?>\n<!DOCTYPE PubmedArticleSet>\n<PubmedArticleSet></PubmedArticleSet>"
+        ));
+        assert!(!looks_like_pubmed_xml(
+            "<?xml version=\"1.0\"?><xml><records><record></record></records></xml>"
+        ));
+        assert!(!looks_like_pubmed_xml("PMID- 1\nAB  - <PubmedArticle>"));
+    }
+}
@@ -0,0 +1,289 @@
+//! Minimal element tree for PubMed XML records.
+//!
+//! PubMed records are deeply nested and reuse element names at different
+//! depths (an `ArticleIdList` appears both in `PubmedData` and in every
+//! reference of a `ReferenceList`), so each record is read into a small tree
+//! and fields are looked up by path.
+
+use crate::CitationFormat;
+use crate::error::{ParseError, SourceSpan, ValueError};
+use quick_xml::Reader;
+use quick_xml::escape::unescape;
+use quick_xml::events::{BytesStart, Event};
+
+/// An XML element with its attributes and children.
+#[derive(Debug, Default)]
+pub(crate) struct XmlElement {
+    pub(crate) name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<XmlNode>,
+}
+
+#[derive(Debug)]
+enum XmlNode {
+    Element(XmlElement),
+    Text(String),
+}
+
+impl XmlElement {
+    /// The first child element with the given name.
+    pub(crate) fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find_map(|node| match node {
+            XmlNode::Element(element) if element.name == name => Some(element),
+            _ => None,
+        })
+    }
+
+    /// All child elements with the given name, in document order.
+    pub(crate) fn children_named<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a XmlElement> + 'a {
+        self.children.iter().filter_map(move |node| match node {
+            XmlNode::Element(element) if element.name == name => Some(element),
+            _ => None,
+        })
+    }
+
+    /// Follow a path of child element names.
+    pub(crate) fn path(&self, names: &[&str]) -> Option<&XmlElement> {
+        names
+            .iter()
+            .try_fold(self, |element, name| element.child(name))
+    }
+
+    /// The value of an attribute.
+    pub(crate) fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The text content of this element and its descendants, with runs of
+    /// whitespace collapsed. Inline markup such as `<i>` or `<sup>` is dropped
+    /// but its text is kept.
+    pub(crate) fn text(&self) -> String {
+        let mut raw = String::new();
+        self.collect_text(&mut raw);
+        raw.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Text of the child at `path`, if present and non-empty.
+    pub(crate) fn text_at(&self, path: &[&str]) -> Option<String> {
+        self.path(path)
+            .map(XmlElement::text)
+            .filter(|text| !text.is_empty())
+    }
+
+    fn collect_text(&self, out: &mut String) {
+        for node in &self.children {
+            match node {
+                XmlNode::Text(text) => out.push_str(text),
+                XmlNode::Element(element) => element.collect_text(out),
+            }
+        }
+    }
+}
+
+/// Read the element opened by `start` and everything up to its end tag.
+pub(crate) fn read_element(
+    reader: &mut Reader<&[u8]>,
+    start: &BytesStart,
+    content: &str,
+    start_pos: usize,
+) -> Result<XmlElement, ParseError> {
+    let mut stack = vec![open_element(start, content, start_pos)?];
+    let mut buf = Vec::new();
+
+    loop {
+        let event_pos = reader.buffer_position() as usize;
+        let event = reader.read_event_into(&mut buf).map_err(|e| {
+            xml_error(
+                content,
+                event_pos,
+                reader.buffer_position() as usize,
+                e.to_string(),
+            )
+        })?;
+        let text_error = |detail: String| {
+            xml_error(
+                content,
+                event_pos,
+                reader.buffer_position() as usize,
+                detail,
+            )
+        };
+
+        match event {
+            Event::Start(ref e) => stack.push(open_element(e, content, event_pos)?),
+            Event::Empty(ref e) => {
+                let element = open_element(e, content, event_pos)?;
+                push_child(&mut stack, XmlNode::Element(element));
+            }
+            Event::End(_) => {
+                let element = stack.pop().unwrap_or_default();
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(XmlNode::Element(element)),
+                    None => return Ok(element),
+                }
+            }
+            Event::Text(e) => {
+                let decoded = e.decode().map_err(|e| text_error(e.to_string()))?;
+                let text = unescape(&decoded).map_err(|e| text_error(e.to_string()))?;
+                push_child(&mut stack, XmlNode::Text(text.into_owned()));
+            }
+            Event::CData(e) => {
+                let text = e.decode().map_err(|e| text_error(e.to_string()))?;
+                push_child(&mut stack, XmlNode::Text(text.into_owned()));
+            }
+            Event::GeneralRef(e) => {
+                let text = match e
+                    .resolve_char_ref()
+                    .map_err(|e| text_error(e.to_string()))?
+                {
+                    Some(ch) => ch.to_string(),
+                    None => {
+                        let name = e.decode().map_err(|e| text_error(e.to_string()))?;
+                        match name.as_ref() {
+                            "lt" => "<".to_string(),
+                            "gt" => ">".to_string(),
+                            "amp" => "&".to_string(),
+                            "apos" => "'".to_string(),
+                            "quot" => "\"".to_string(),
+                            other => {
+                                return Err(text_error(format!(
+                                    "Unsupported entity reference: &{};",
+                                    other
+                                )));
+                            }
+                        }
+                    }
+                };
+                push_child(&mut stack, XmlNode::Text(text));
+            }
+            Event::Eof => {
+                return Err(ParseError::at_line(
+                    buffer_position_to_line_number(content, start_pos),
+                    CitationFormat::PubMedXml,
+                    ValueError::Syntax(format!(
+                        "Unexpected EOF while looking for closing tag '{}'",
+                        stack[0].name
+                    )),
+                )
+                .with_span(SourceSpan::new(
+                    start_pos,
+                    reader.buffer_position() as usize,
+                )));
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+fn open_element(start: &BytesStart, content: &str, pos: usize) -> Result<XmlElement, ParseError> {
+    let mut attributes = Vec::new();
+    for attr in start.attributes() {
+        let attr = attr.map_err(|e| xml_error(content, pos, pos, e.to_string()))?;
+        let value = attr
+            .unescape_value()
+            .map_err(|e| xml_error(content, pos, pos, e.to_string()))?;
+        attributes.push((
+            String::from_utf8_lossy(attr.key.as_ref()).into_owned(),
+            value.into_owned(),
+        ));
+    }
+    Ok(XmlElement {
+        name: String::from_utf8_lossy(start.name().as_ref()).into_owned(),
+        attributes,
+        children: Vec::new(),
+    })
+}
+
+fn push_child(stack: &mut [XmlElement], node: XmlNode) {
+    if let Some(parent) = stack.last_mut() {
+        parent.children.push(node);
+    }
+}
+
+pub(crate) fn xml_error(
+    content: &str,
+    start_pos: usize,
+    end_pos: usize,
+    detail: String,
+) -> ParseError {
+    ParseError::at_line(
+        buffer_position_to_line_number(content, start_pos),
+        CitationFormat::PubMedXml,
+        ValueError::Syntax(format!("XML parsing error: {}", detail)),
+    )
+    .with_span(SourceSpan::new(start_pos, end_pos))
+}
+
+pub(crate) fn buffer_position_to_line_number(content: &str, pos: usize) -> usize {
+    if pos >= content.len() {
+        return content.lines().count();
+    }
+
+    content[..pos].lines().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn read(xml: &str) -> XmlElement {
+        let mut reader = Reader::from_str(xml);
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf).unwrap() {
+                Event::Start(e) => {
+                    let e = e.into_owned();
+                    return read_element(&mut reader, &e, xml, 0).unwrap();
+                }
+                Event::Eof => panic!("no element"),
+                _ => {}
+            }
+            buf.clear();
+        }
+    }
+
+    #[test]
+    fn test_text_keeps_inline_markup_text() {
+        let element = read(
+            "<AbstractText Label=\"AIMS\">The <i>BRCA1</i>\n  gene &amp; H<sub>2</sub>O&#8217;s</AbstractText>",
+        );
+        assert_eq!(element.attr("Label"), Some("AIMS"));
+        assert_eq!(element.text(), "The BRCA1 gene & H2O\u{2019}s");
+    }
+
+    #[test]
+    fn test_path_only_follows_direct_children() {
+        let element = read(
+            "<Root><Refs><ArticleIdList><ArticleId>1</ArticleId></ArticleIdList></Refs>\
+             <ArticleIdList><ArticleId>2</ArticleId><Empty/></ArticleIdList></Root>",
+        );
+        assert_eq!(
+            element.text_at(&["ArticleIdList", "ArticleId"]).as_deref(),
+            Some("2")
+        );
+        assert!(element.path(&["ArticleIdList", "Empty"]).is_some());
+        assert_eq!(element.text_at(&["ArticleIdList", "Empty"]), None);
+    }
+
+    #[test]
+    fn test_unclosed_element_is_error() {
+        let xml = "<Root>\n<Child>text</Child>";
+        let mut reader = Reader::from_str(xml);
+        let mut buf = Vec::new();
+        let Event::Start(start) = reader.read_event_into(&mut buf).unwrap() else {
+            panic!("expected start tag");
+        };
+        let start = start.into_owned();
+        let err = read_element(&mut reader, &start, xml, 0).unwrap_err();
+        assert_eq!(err.format, CitationFormat::PubMedXml);
+        assert!(matches!(err.error, ValueError::Syntax(_)));
+    }
+}
@@ -18,13 +18,33 @@
 //! let citations = parser.parse(input).unwrap();
 //! assert_eq!(citations[0].title, "Example Title");
 //! ```
+//!
+//! Citations can be written back to RIS with [`RisWriter`]:
+//!
+//! ```
+//! use biblib::{CitationParser, CitationWriter, RisParser, RisWriter};
+//!
+//! let input = "TY  - JOUR\nTI  - Example Title\nAU  - Smith, John\nER  -\n";
+//! let citations = RisParser::new().parse(input).unwrap();
+//!
+//! let output = RisWriter::new().write(&citations);
+//! assert_eq!(RisParser::new().parse(&output).unwrap(), citations);
+//! ```
 
 mod parse;
 mod structure;
 mod tags;
-
-use crate::{Citation, CitationParser};
-use parse::ris_parse;
+mod write;
+
+use crate::error::{ParseError, ParseWarning};
+use crate::stream::chunk::parse_chunks;
+use crate::stream::line::LineChunks;
+use crate::{
+    Citation, CitationFormat, CitationParser, CitationWriter, OnError, ParseOptions, ParseReport,
+    collect_report,
+};
+use parse::{ris_boundary, ris_parse};
+use write::ris_write;
 
 /// Parser for RIS format citations.
 ///
@@ -62,7 +82,7 @@ impl CitationParser for RisParser {
     /// # Errors
     ///
     /// Returns `ParseError` if the input is malformed or contains no valid citations
-    fn parse(&self, input: &str) -> std::result::Result<Vec<Citation>, crate::error::ParseError> {
+    fn parse(&self, input: &str) -> std::result::Result<Vec<Citation>, ParseError> {
         let raw_citations = ris_parse(input)?;
 
         let mut citations = Vec::with_capacity(raw_citations.len());
@@ -73,13 +93,114 @@ impl CitationParser for RisParser {
 
         Ok(citations)
     }
+
+    /// Reads one `TY` … `ER` record at a time.
+    fn parse_reader<'r, R>(
+        &self,
+        reader: R,
+    ) -> Box<dyn Iterator<Item = std::result::Result<Citation, crate::error::ParseError>> + 'r>
+    where
+        R: std::io::Read + 'r,
+    {
+        let chunks = LineChunks::new(
+            std::io::BufReader::new(reader),
+            CitationFormat::Ris,
+            ris_boundary,
+        );
+        Box::new(parse_chunks(chunks, |chunk| RisParser.parse(chunk)))
+    }
+
+    /// Reports ignored lines, unknown tags, unparseable dates, invalid DOIs
+    /// and dropped repeats of single-valued tags as warnings.
+    fn parse_with_options(
+        &self,
+        input: &str,
+        options: &ParseOptions,
+    ) -> std::result::Result<ParseReport, ParseError> {
+        if options.on_error == OnError::Fail {
+            let records = parse_with_warnings(input)?;
+            return collect_report(records.into_iter().map(Ok), options);
+        }
+
+        let chunks = LineChunks::new(input.as_bytes(), CitationFormat::Ris, ris_boundary);
+        collect_report(parse_chunks(chunks, parse_with_warnings), options)
+    }
+}
+
+/// Parse RIS text, keeping the warnings for each citation.
+fn parse_with_warnings(
+    input: &str,
+) -> std::result::Result<Vec<(Citation, Vec<ParseWarning>)>, ParseError> {
+    ris_parse(input)?
+        .into_iter()
+        .map(|raw| raw.into_citation_with_warnings())
+        .collect()
+}
+
+/// Writer for RIS format citations.
+///
+/// Each citation becomes one `TY` … `ER` record. Fields are written with the
+/// same tags [`RisParser`] reads, so parsing the output yields the original
+/// citation for everything RIS can represent. `pmid` and `mesh_terms` have no
+/// RIS tag and are not written; a citation without a type is written as `GEN`.
+/// `extra_fields` entries are written when their key is a RIS tag that has no
+/// dedicated `Citation` field (for example `ID` or `N1`).
+#[derive(Debug, Clone, Default)]
+pub struct RisWriter;
+
+impl RisWriter {
+    /// Creates a new RIS writer instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::RisWriter;
+    /// let writer = RisWriter::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CitationWriter for RisWriter {
+    fn write(&self, citations: &[Citation]) -> String {
+        ris_write(citations)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Author, ContributorRole, DateKind, Scheme, Season};
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn test_parse_reader_matches_parse() {
+        let input = "Provider: Example\r\n\r\nTY  - JOUR\r\nTI  - First\r\nER  -\r\nTY  - JOUR\r\nTI  - Second\r\n  wrapped\r\nAU  - Smith, J\r\nER  -\r\n\r\nTY  - BOOK\r\nTI  - Third\r\n";
+        let parser = RisParser::new();
+
+        let streamed: Result<Vec<_>, _> = parser.parse_reader(input.as_bytes()).collect();
+        assert_eq!(streamed.unwrap(), parser.parse(input).unwrap());
+    }
+
+    #[test]
+    fn test_parse_reader_reports_positions_in_whole_input() {
+        let input = "TY  - JOUR\nTI  - First\nER  -\nTY  - JOUR\nAU  - Smith, J\nER  -\nTY  - JOUR\nTI  - Third\nER  -\n";
+        let parser = RisParser::new();
+        let expected = parser.parse(input).unwrap_err();
+
+        let streamed: Vec<_> = parser.parse_reader(input.as_bytes()).collect();
+        assert_eq!(streamed.len(), 3);
+        assert_eq!(streamed[0].as_ref().unwrap().title, "First");
+        assert_eq!(streamed[2].as_ref().unwrap().title, "Third");
+
+        let err = streamed[1].as_ref().unwrap_err();
+        assert_eq!(err.line, expected.line);
+        assert_eq!(err.span, expected.span);
+        assert_eq!(err.line, Some(4));
+    }
+
     #[test]
     fn test_parse_simple_ris() {
         let input = r#"TY  - JOUR
@@ -148,7 +269,7 @@ ER  -
 
 "#;
         let parser = RisParser::new();
-        let citations = parser.parse(&input).unwrap();
+        let citations = parser.parse(input).unwrap();
         assert_eq!(
             citations.len(),
             2,
@@ -192,6 +313,22 @@ ER  -"#;
 
         let citation = RisParser::new().parse(input).unwrap().remove(0);
         assert_eq!(citation.accession_number.as_deref(), Some("ACC-123"));
+        assert!(citation.identifiers.is_empty());
+    }
+
+    #[test]
+    fn test_typed_identifier_from_an() {
+        let input = "TY  - JOUR\nTI  - Test Article\nAN  - WOS:000123456700001\nER  -\n";
+
+        let citation = RisParser::new().parse(input).unwrap().remove(0);
+        assert_eq!(
+            citation.accession_number.as_deref(),
+            Some("WOS:000123456700001")
+        );
+        assert_eq!(
+            citation.identifier(Scheme::Wos),
+            Some("WOS:000123456700001")
+        );
     }
 
     #[test]
@@ -378,4 +515,194 @@ ER  -"#;
             "bad line should be tagged as line 3"
         );
     }
+
+    #[test]
+    fn test_write_round_trip() {
+        let input = r#"TY  - JOUR
+TI  - Round Trip Article
+AU  - Smith, John A
+AU  - Doe, Jane
+JF  - Journal of Tests
+JA  - J Tests
+PY  - 2023/05/09
+Y2  - 2024/01/15
+VL  - 12
+IS  - 3
+SP  - 100
+EP  - 110
+DO  - 10.1000/round
+AN  - ACC-1
+C2  - PMC123456
+AB  - First paragraph.
+AB  - Second paragraph.
+KW  - alpha
+KW  - beta
+SN  - 1234-5678
+UR  - https://example.com/a
+L1  - https://example.com/a.pdf
+LA  - eng
+PB  - Test Press
+M3  - Article
+ID  - ref-1
+N1  - A note
+ER  -
+
+TY  - CHAP
+TI  - Second Record
+AU  - Brown
+A2  - Editor, Ed
+A3  - Series, Sam
+A4  - Translator, Tina
+ER  -
+"#;
+        let citations = RisParser::new().parse(input).unwrap();
+        let output = RisWriter::new().write(&citations);
+        let reparsed = RisParser::new().parse(&output).unwrap();
+
+        assert_eq!(reparsed, citations);
+        assert!(output.contains("SP  - 100\nEP  - 110\n"));
+        assert!(output.contains("N1  - A note\n"));
+    }
+
+    #[test]
+    fn test_write_round_trip_empty_title() {
+        let citation = Citation {
+            citation_type: vec!["Journal Article".to_string()],
+            authors: vec![Author {
+                name: "Smith".to_string(),
+                given_name: Some("John".to_string()),
+                middle_name: None,
+                affiliations: Vec::new(),
+                role: ContributorRole::Author,
+                orcid: None,
+                is_organization: false,
+            }],
+            ..Default::default()
+        };
+        let output = RisWriter::new().write(std::slice::from_ref(&citation));
+        let reparsed = RisParser::new().parse(&output).unwrap();
+
+        assert_eq!(reparsed, vec![citation]);
+    }
+
+    #[test]
+    fn test_parse_contributor_roles() {
+        let input = r#"TY  - CHAP
+TI  - A Chapter
+AU  - Smith, John
+A1  - Doe, Jane
+A2  - Editor, Ed
+A2  - Other, Olga
+A3  - Series, Sam
+A4  - Translator, Tina
+ER  -
+"#;
+        let citation = RisParser::new().parse(input).unwrap().remove(0);
+
+        let authors: Vec<_> = citation.authors.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(authors, ["Smith", "Doe"]);
+        let contributors: Vec<_> = citation
+            .contributors
+            .iter()
+            .map(|c| (c.name.as_str(), c.role))
+            .collect();
+        assert_eq!(
+            contributors,
+            [
+                ("Editor", ContributorRole::Editor),
+                ("Other", ContributorRole::Editor),
+                ("Series", ContributorRole::SeriesEditor),
+                ("Translator", ContributorRole::Translator),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_access_date_and_season() {
+        let input = "TY  - JOUR\nTI  - Example\nPY  - 2023///Winter\nY2  - 2024/01/15\nER  -\n";
+        let citation = RisParser::new().parse(input).unwrap().remove(0);
+
+        let date = citation.date.as_ref().unwrap();
+        assert_eq!(
+            (date.year, date.month, date.season),
+            (2023, None, Some(Season::Winter))
+        );
+        let accessed = citation.date_of(DateKind::Accessed).unwrap();
+        assert_eq!(
+            (accessed.year, accessed.month, accessed.day),
+            (2024, Some(1), Some(15))
+        );
+        assert!(!citation.extra_fields.contains_key("Y2"));
+    }
+
+    #[test]
+    fn test_write_empty() {
+        assert_eq!(RisWriter::new().write(&[]), "");
+    }
+
+    #[test]
+    fn test_serial_numbers_sorted_into_issn_and_isbn() {
+        use crate::{ParseOptions, WarningKind};
+
+        let input = "TY  - BOOK\nTI  - Example\nSN  - 0028-0836 (Print)\nSN  - 978-0-306-40615-7\nSN  - 0028-0837\nER  -\n";
+        let report = RisParser::new()
+            .parse_with_options(input, &ParseOptions::new())
+            .unwrap();
+
+        let citation = &report.citations[0];
+        assert_eq!(citation.issn, vec!["0028-0836 (Print)", "0028-0837"]);
+        assert_eq!(citation.identifier(Scheme::Isbn), Some("9780306406157"));
+        assert_eq!(
+            report.warnings.iter().map(|w| &w.kind).collect::<Vec<_>>(),
+            vec![&WarningKind::InvalidCheckDigit {
+                key: "SN".to_string(),
+                value: "0028-0837".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_with_options_reports_warnings() {
+        use crate::{OnError, ParseOptions, WarningKind};
+
+        let input = "TY  - JOUR\nTI  - First\nER  -\nTY  - JOUR\nTI  - Second\n!! stray\nPY  - someday\nDO  - not a doi\nZZ  - custom\nVL  - 1\nVL  - 2\nER  -\n";
+        for on_error in [OnError::Fail, OnError::Collect] {
+            let options = ParseOptions::new().with_on_error(on_error);
+            let report = RisParser::new()
+                .parse_with_options(input, &options)
+                .unwrap();
+
+            assert_eq!(report.citations.len(), 2);
+            let kinds: Vec<_> = report.warnings.iter().map(|w| &w.kind).collect();
+            assert_eq!(
+                kinds,
+                vec![
+                    &WarningKind::IgnoredLine {
+                        line: "!! stray".to_string()
+                    },
+                    &WarningKind::UnknownTag {
+                        tag: "ZZ".to_string()
+                    },
+                    &WarningKind::UnparseableDate {
+                        key: "PY".to_string(),
+                        value: "someday".to_string()
+                    },
+                    &WarningKind::DroppedValue {
+                        key: "VL".to_string(),
+                        value: "2".to_string()
+                    },
+                    &WarningKind::InvalidDoi {
+                        key: "DO".to_string(),
+                        value: "not a doi".to_string()
+                    },
+                ]
+            );
+
+            let ignored = &report.warnings[0];
+            assert_eq!(ignored.line, Some(6));
+            let span = ignored.span.as_ref().unwrap();
+            assert_eq!(&input[span.start..span.end], "!! stray");
+            assert_eq!(report.warnings[2].line, Some(4));
+        }
+    }
 }
@@ -4,10 +4,11 @@
 
 use crate::ris::structure::RawRisData;
 use crate::ris::tags::RisTag;
+use crate::stream::line::Boundary;
 use crate::utils::parse_author_name;
 use crate::{
-    Author, CitationFormat,
-    error::{ParseError, SourceSpan, ValueError},
+    Author, CitationFormat, ContributorRole,
+    error::{ParseError, ParseWarning, SourceSpan, ValueError, WarningKind},
 };
 
 /// Parse the content of a RIS formatted file, returning structured data.
@@ -54,7 +55,11 @@ pub(crate) fn ris_parse<S: AsRef<str>>(ris_text: S) -> Result<Vec<RawRisData>, P
                 }
             } else {
                 // No prior tag to attach to — treat as ignored
-                current_citation.add_ignored_line(line_number, raw_line.trim().to_string());
+                current_citation.add_ignored_line(
+                    line_number,
+                    raw_line.trim().to_string(),
+                    SourceSpan::new(line_byte_start, line_byte_end),
+                );
             }
             continue;
         }
@@ -98,8 +103,9 @@ pub(crate) fn ris_parse<S: AsRef<str>>(ris_text: S) -> Result<Vec<RawRisData>, P
                             span.end = line_byte_end;
                         }
                         last_tag = None;
-                        let authors = split_and_parse_authors(&content);
-                        for author in authors {
+                        let role = tag.contributor_role().unwrap_or_default();
+                        for mut author in split_and_parse_authors(&content) {
+                            author.role = role;
                             current_citation.add_author(author);
                         }
                     }
@@ -107,6 +113,18 @@ pub(crate) fn ris_parse<S: AsRef<str>>(ris_text: S) -> Result<Vec<RawRisData>, P
                         if let Some(ref mut span) = current_citation.record_span {
                             span.end = line_byte_end;
                         }
+                        if let RisTag::Unknown(unknown) = &tag {
+                            current_citation.warnings.push(
+                                ParseWarning::at_line(
+                                    line_number,
+                                    CitationFormat::Ris,
+                                    WarningKind::UnknownTag {
+                                        tag: unknown.clone(),
+                                    },
+                                )
+                                .with_span(SourceSpan::new(line_byte_start, line_byte_end)),
+                            );
+                        }
                         last_tag = Some(tag.clone());
                         current_citation.add_data(tag, content);
                     }
@@ -118,7 +136,11 @@ pub(crate) fn ris_parse<S: AsRef<str>>(ris_text: S) -> Result<Vec<RawRisData>, P
                 }
                 last_tag = None;
                 // Add invalid lines to ignored lines with context
-                current_citation.add_ignored_line(line_number, line.to_string());
+                current_citation.add_ignored_line(
+                    line_number,
+                    line.to_string(),
+                    SourceSpan::new(line_byte_start, line_byte_end),
+                );
             }
         }
     }
@@ -269,6 +291,9 @@ fn parse_author(author_str: &str) -> Author {
         given_name: given_opt,
         middle_name: middle_opt,
         affiliations: Vec::new(),
+        role: ContributorRole::Author,
+        orcid: None,
+        is_organization: false,
     }
 }
 
@@ -309,6 +334,20 @@ fn is_continuation_line(line: &str) -> bool {
     true
 }
 
+/// Record boundaries for [`RisParser::parse_reader`](crate::RisParser): `TY`
+/// starts a record and `ER` ends it, as in [`ris_parse`].
+pub(crate) fn ris_boundary(_previous: Option<&str>, line: &str) -> Boundary {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || is_continuation_line(line) || is_metadata_line(trimmed) {
+        return Boundary::Inside;
+    }
+    match parse_ris_line(trimmed, 0) {
+        Ok((RisTag::Type, _)) => Boundary::Before,
+        Ok((RisTag::EndOfReference, _)) => Boundary::After,
+        _ => Boundary::Inside,
+    }
+}
+
 /// Check if a line is RIS metadata that should be ignored.
 fn is_metadata_line(line: &str) -> bool {
     line.starts_with("Record #")
@@ -10,9 +10,10 @@
 //! - **Two-pass**: DOI extraction checks dedicated fields first, then URLs
 //! - **Validation**: Date parsing includes error logging for invalid formats
 
-use crate::Author;
-use crate::error::SourceSpan;
+use crate::error::{ParseWarning, SourceSpan, WarningKind};
 use crate::ris::tags::RisTag;
+use crate::utils::{detect_identifier, partition_contributors, sort_serial_numbers};
+use crate::{Author, CitationFormat, Identifier};
 use std::collections::HashMap;
 
 /// Structured raw data from a RIS formatted file.
@@ -20,7 +21,7 @@ use std::collections::HashMap;
 pub(crate) struct RawRisData {
     /// Key-value pair data from the RIS file data.
     pub(crate) data: HashMap<RisTag, Vec<String>>,
-    /// Authors of the cited work.
+    /// Authors and other contributors of the cited work, in source order.
     pub(crate) authors: Vec<Author>,
     /// Invalid lines found in the RIS file data with line number context for error reporting.
     pub(crate) ignored_lines: Vec<(usize, String)>,
@@ -28,6 +29,8 @@ pub(crate) struct RawRisData {
     pub(crate) start_line: Option<usize>,
     /// Byte-offset span of the citation record in the source text (TY … ER).
     pub(crate) record_span: Option<SourceSpan>,
+    /// Data dropped or coerced while parsing and converting this record.
+    pub(crate) warnings: Vec<ParseWarning>,
 }
 
 impl RawRisData {
@@ -39,6 +42,7 @@ impl RawRisData {
             ignored_lines: Vec::new(),
             start_line: None,
             record_span: None,
+            warnings: Vec::new(),
         }
     }
 
@@ -47,16 +51,49 @@ impl RawRisData {
         self.data.entry(tag).or_default().push(value);
     }
 
-    /// Add an author to the authors list.
+    /// Add an author or other contributor to the authors list.
     pub(crate) fn add_author(&mut self, author: Author) {
         self.authors.push(author);
     }
 
     /// Add an ignored line with context.
-    pub(crate) fn add_ignored_line(&mut self, line_number: usize, line: String) {
+    pub(crate) fn add_ignored_line(&mut self, line_number: usize, line: String, span: SourceSpan) {
+        self.warnings.push(
+            ParseWarning::at_line(
+                line_number,
+                CitationFormat::Ris,
+                WarningKind::IgnoredLine { line: line.clone() },
+            )
+            .with_span(span),
+        );
         self.ignored_lines.push((line_number, line));
     }
 
+    /// Add a warning located at the start of this record.
+    pub(crate) fn warn(&mut self, kind: WarningKind) {
+        let warning = ParseWarning::new(self.start_line, CitationFormat::Ris, kind);
+        self.warnings.push(match self.record_span.clone() {
+            Some(span) => warning.with_span(span),
+            None => warning,
+        });
+    }
+
+    /// Remove all values for a tag and return the first, warning about any
+    /// other values that are dropped.
+    pub(crate) fn take_single(&mut self, tag: &RisTag) -> Option<String> {
+        let mut values = self.remove(tag)?.into_iter();
+        let first = values.next()?;
+        for value in values {
+            if !value.trim().is_empty() && value != first {
+                self.warn(WarningKind::DroppedValue {
+                    key: tag.as_tag().to_string(),
+                    value,
+                });
+            }
+        }
+        Some(first)
+    }
+
     /// Get the first value for a tag, if it exists.
     pub(crate) fn get_first(&self, tag: &RisTag) -> Option<&String> {
         self.data.get(tag).and_then(|values| values.first())
@@ -112,7 +149,19 @@ impl RawRisData {
 impl TryFrom<RawRisData> for crate::Citation {
     type Error = crate::error::ParseError;
 
-    fn try_from(mut raw: RawRisData) -> Result<Self, Self::Error> {
+    fn try_from(raw: RawRisData) -> Result<Self, Self::Error> {
+        raw.into_citation_with_warnings()
+            .map(|(citation, _)| citation)
+    }
+}
+
+impl RawRisData {
+    /// Convert to a citation, returning the warnings for data that was
+    /// dropped or coerced along the way.
+    pub(crate) fn into_citation_with_warnings(
+        mut self,
+    ) -> Result<(crate::Citation, Vec<ParseWarning>), crate::error::ParseError> {
+        let raw = &mut self;
         let mut citation_type: Vec<String> = raw
             .remove(&RisTag::Type)
             .unwrap_or_default()
@@ -126,26 +175,42 @@ impl TryFrom<RawRisData> for crate::Citation {
                 }
             }
         }
-        let title = Self::extract_title(&mut raw)?;
-        let (journal, journal_abbr) = Self::extract_journal_info(&mut raw);
-        let date = Self::extract_date(&mut raw);
-        let (volume, issue, pages) = Self::extract_publication_details(&mut raw);
-        let (doi, urls) = Self::extract_doi_and_urls(&mut raw);
-        let accession_number = Self::extract_accession_number(&mut raw);
-        let (pmid, pmc_id) = Self::extract_identifiers(&mut raw);
-        let abstract_text = Self::extract_abstract(&mut raw);
+        let title = crate::Citation::extract_title(raw)?;
+        let (journal, journal_abbr) = crate::Citation::extract_journal_info(raw);
+        let (date, dates) = crate::Citation::extract_date(raw);
+        let (volume, issue, pages) = crate::Citation::extract_publication_details(raw);
+        let (doi, urls) = crate::Citation::extract_doi_and_urls(raw);
+        let accession_number = crate::Citation::extract_accession_number(raw);
+        let mut identifiers: Vec<Identifier> = accession_number
+            .as_deref()
+            .and_then(detect_identifier)
+            .into_iter()
+            .collect();
+        let (pmid, pmc_id) = crate::Citation::extract_identifiers(raw);
+        let abstract_text = crate::Citation::extract_abstract(raw);
         let keywords = raw.remove(&RisTag::Keywords).unwrap_or_default();
-        let issn = raw.remove(&RisTag::SerialNumber).unwrap_or_default();
-        let (language, publisher) = Self::extract_metadata(&mut raw);
-        let extra_fields = Self::extract_extra_fields(&mut raw);
+        let mut issn = Vec::new();
+        for value in raw.remove(&RisTag::SerialNumber).unwrap_or_default() {
+            for invalid in sort_serial_numbers(&value, &mut issn, &mut identifiers) {
+                raw.warn(WarningKind::InvalidCheckDigit {
+                    key: "SN".to_string(),
+                    value: invalid,
+                });
+            }
+        }
+        let (language, publisher) = crate::Citation::extract_metadata(raw);
+        let extra_fields = crate::Citation::extract_extra_fields(raw);
+        let (authors, contributors) = partition_contributors(std::mem::take(&mut raw.authors));
 
-        Ok(crate::Citation {
+        let citation = crate::Citation {
             citation_type,
             title,
-            authors: raw.authors,
+            authors,
+            contributors,
             journal,
             journal_abbr,
             date: date.clone(),
+            dates,
             volume,
             issue,
             pages,
@@ -154,6 +219,7 @@ impl TryFrom<RawRisData> for crate::Citation {
             accession_number,
             pmid,
             pmc_id,
+            identifiers,
             abstract_text,
             keywords,
             urls,
@@ -161,7 +227,8 @@ impl TryFrom<RawRisData> for crate::Citation {
             mesh_terms: Vec::new(), // RIS doesn't typically have MeSH terms
             publisher,
             extra_fields,
-        })
+        };
+        Ok((citation, self.warnings))
     }
 }
 
@@ -178,6 +245,8 @@ impl crate::Citation {
                     .filter(|s| !s.trim().is_empty())
             })
             .cloned()
+            // A blank `TI` line, as written for a citation without a title.
+            .or_else(|| raw.get_first(&RisTag::Title).map(|_| String::new()))
             .ok_or_else(|| {
                 let err = crate::error::ParseError::new(
                     start_line,
@@ -217,43 +286,59 @@ impl crate::Citation {
         (journal, journal_abbr)
     }
 
-    /// Extract date from RIS data with validation.
-    fn extract_date(raw: &mut RawRisData) -> Option<crate::Date> {
+    /// Extract the publication date and the access date (`Y2`) from RIS
+    /// data with validation.
+    fn extract_date(raw: &mut RawRisData) -> (Option<crate::Date>, Vec<crate::TypedDate>) {
         // Parse date from available date fields with validation
-        let date = raw
-            .get_first(&RisTag::PublicationYear)
-            .or_else(|| raw.get_first(&RisTag::DatePrimary))
-            .and_then(|date_str| {
-                crate::utils::parse_ris_date(date_str)
-                // Note: Invalid dates are silently ignored to avoid breaking parsing
-                // TODO: Collect warnings
-            });
+        let (key, value) = match raw.get_first(&RisTag::PublicationYear) {
+            Some(value) => ("PY", Some(value.clone())),
+            None => ("DA", raw.get_first(&RisTag::DatePrimary).cloned()),
+        };
+        let date = value.and_then(|value| {
+            let date = crate::utils::parse_ris_date(&value);
+            if date.is_none() && !value.trim().is_empty() {
+                raw.warn(WarningKind::UnparseableDate {
+                    key: key.to_string(),
+                    value,
+                });
+            }
+            date
+        });
+
+        let accessed = raw.take_single(&RisTag::DateAccess).and_then(|value| {
+            let date = crate::utils::parse_ris_date(&value);
+            if date.is_none() && !value.trim().is_empty() {
+                raw.warn(WarningKind::UnparseableDate {
+                    key: "Y2".to_string(),
+                    value,
+                });
+            }
+            date
+        });
+        let dates = accessed
+            .map(|date| crate::TypedDate {
+                kind: crate::DateKind::Accessed,
+                date,
+            })
+            .into_iter()
+            .collect();
 
         raw.remove(&RisTag::PublicationYear);
         raw.remove(&RisTag::DatePrimary);
-        raw.remove(&RisTag::DateAccess);
 
-        date
+        (date, dates)
     }
 
     /// Extract publication details: volume, issue, and formatted pages.
     fn extract_publication_details(
         raw: &mut RawRisData,
     ) -> (Option<String>, Option<String>, Option<String>) {
-        let volume = raw
-            .remove(&RisTag::Volume)
-            .and_then(|v| v.into_iter().next());
-        let issue = raw
-            .remove(&RisTag::Issue)
-            .and_then(|v| v.into_iter().next());
+        let volume = raw.take_single(&RisTag::Volume);
+        let issue = raw.take_single(&RisTag::Issue);
 
         // Handle pages
-        let start_page = raw
-            .remove(&RisTag::StartPage)
-            .and_then(|v| v.into_iter().next());
-        let end_page = raw
-            .remove(&RisTag::EndPage)
-            .and_then(|v| v.into_iter().next());
+        let start_page = raw.take_single(&RisTag::StartPage);
+        let end_page = raw.take_single(&RisTag::EndPage);
         let pages = match (start_page, end_page) {
             (Some(start), Some(end)) => Some(crate::utils::format_page_numbers(&format!(
                 "{}-{}",
@@ -270,10 +355,16 @@ impl crate::Citation {
     /// Extract DOI and URLs with two-pass DOI extraction strategy.
     fn extract_doi_and_urls(raw: &mut RawRisData) -> (Option<String>, Vec<String>) {
         // First pass: Extract DOI from dedicated DOI field
-        let mut doi = raw
-            .remove(&RisTag::Doi)
-            .and_then(|v| v.into_iter().next())
-            .and_then(|doi_str| crate::utils::format_doi(&doi_str));
+        let mut doi = raw.take_single(&RisTag::Doi).and_then(|doi_str| {
+            let doi = crate::utils::format_doi(&doi_str);
+            if doi.is_none() && !doi_str.trim().is_empty() {
+                raw.warn(WarningKind::InvalidDoi {
+                    key: "DO".to_string(),
+                    value: doi_str,
+                });
+            }
+            doi
+        });
 
         // Collect URLs from various link fields and extract DOI if not already found
         let mut urls = Vec::new();
@@ -306,8 +397,7 @@ impl crate::Citation {
 
     /// Extract accession number from the RIS AN tag.
     fn extract_accession_number(raw: &mut RawRisData) -> Option<String> {
-        raw.remove(&RisTag::AccessionNumber)
-            .and_then(|v| v.into_iter().next())
+        raw.take_single(&RisTag::AccessionNumber)
     }
 
     /// Extract normalized identifiers while preserving raw reference IDs.
@@ -351,12 +441,8 @@ impl crate::Citation {
 
     /// Extract language and publisher metadata.
     fn extract_metadata(raw: &mut RawRisData) -> (Option<String>, Option<String>) {
-        let language = raw
-            .remove(&RisTag::Language)
-            .and_then(|v| v.into_iter().next());
-        let publisher = raw
-            .remove(&RisTag::Publisher)
-            .and_then(|v| v.into_iter().next());
+        let language = raw.take_single(&RisTag::Language);
+        let publisher = raw.take_single(&RisTag::Publisher);
 
         (language, publisher)
     }
@@ -421,6 +507,7 @@ fn map_ris_type(abbr: &str) -> &str {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ContributorRole;
     use crate::ris::tags::RisTag;
 
     #[test]
@@ -465,6 +552,9 @@ mod tests {
             given_name: Some("John".to_string()),
             middle_name: None,
             affiliations: Vec::new(),
+            role: ContributorRole::Author,
+            orcid: None,
+            is_organization: false,
         });
 
         let citation: crate::Citation = raw.try_into().unwrap();
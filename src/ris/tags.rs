@@ -3,6 +3,8 @@
 //! This module defines all the standard RIS tags used in bibliographic citations.
 //! See: http://en.wikipedia.org/wiki/RIS_(file_format)
 
+use crate::ContributorRole;
+
 /// RIS format tags.
 ///
 /// RIS (Research Information Systems) is a standardized tag format developed by
@@ -195,6 +197,20 @@ impl RisTag {
         )
     }
 
+    /// The role of the contributors listed under this tag, for author tags.
+    ///
+    /// `A2` holds editors, `A3` series editors and `A4` translators, as in
+    /// EndNote's secondary, tertiary and subsidiary authors.
+    pub fn contributor_role(&self) -> Option<ContributorRole> {
+        match self {
+            RisTag::Author | RisTag::AuthorPrimary => Some(ContributorRole::Author),
+            RisTag::AuthorSecondary => Some(ContributorRole::Editor),
+            RisTag::AuthorTertiary => Some(ContributorRole::SeriesEditor),
+            RisTag::AuthorSubsidiary => Some(ContributorRole::Translator),
+            _ => None,
+        }
+    }
+
     /// Get the priority of this tag for journal name selection.
     /// Lower numbers have higher priority.
     ///
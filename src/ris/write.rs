@@ -0,0 +1,285 @@
+//! RIS format writing implementation.
+//!
+//! This module serializes [`Citation`] values back into RIS tagged lines. The
+//! tag choices mirror what [`crate::ris::parse`] understands so that a written
+//! record parses back into the same citation.
+
+use crate::ris::tags::RisTag;
+use crate::utils::{format_author_name, format_ris_date};
+use crate::{Citation, ContributorRole, DateKind, Scheme};
+
+/// Serialize citations into RIS formatted text.
+pub(crate) fn ris_write(citations: &[Citation]) -> String {
+    let mut out = String::new();
+    for citation in citations {
+        write_record(&mut out, citation);
+    }
+    out
+}
+
+/// The author tag for a contributor role, the inverse of
+/// [`RisTag::contributor_role`]. RIS has no tag for investigators.
+fn contributor_tag(role: ContributorRole) -> Option<RisTag> {
+    match role {
+        ContributorRole::Author | ContributorRole::CorporateAuthor => Some(RisTag::Author),
+        ContributorRole::Editor => Some(RisTag::AuthorSecondary),
+        ContributorRole::SeriesEditor => Some(RisTag::AuthorTertiary),
+        ContributorRole::Translator => Some(RisTag::AuthorSubsidiary),
+        ContributorRole::Investigator => None,
+    }
+}
+
+/// Write a single citation as a `TY` … `ER` record followed by a blank line.
+fn write_record(out: &mut String, citation: &Citation) {
+    let mut types = citation.citation_type.iter();
+    let ris_type = types
+        .next()
+        .map(|t| ris_type_abbr(t))
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or("GEN");
+    push_line(out, &RisTag::Type, ris_type);
+
+    if citation.title.trim().is_empty() {
+        // The parser needs a TI line, even an empty one, to read the record.
+        out.push_str(RisTag::Title.as_tag());
+        out.push_str("  - \n");
+    } else {
+        push_line(out, &RisTag::Title, &citation.title);
+    }
+    for author in &citation.authors {
+        push_line(out, &RisTag::Author, &format_author_name(author));
+    }
+    for contributor in &citation.contributors {
+        if let Some(tag) = contributor_tag(contributor.role) {
+            push_line(out, &tag, &format_author_name(contributor));
+        }
+    }
+    push_opt(out, &RisTag::JournalFull, citation.journal.as_deref());
+    push_opt(
+        out,
+        &RisTag::JournalAbbreviation,
+        citation.journal_abbr.as_deref(),
+    );
+    if let Some(date) = &citation.date {
+        push_line(out, &RisTag::PublicationYear, &format_ris_date(date));
+    }
+    if let Some(date) = citation.date_of(DateKind::Accessed) {
+        push_line(out, &RisTag::DateAccess, &format_ris_date(date));
+    }
+    push_opt(out, &RisTag::Volume, citation.volume.as_deref());
+    push_opt(out, &RisTag::Issue, citation.issue.as_deref());
+    if let Some(pages) = citation.pages.as_deref() {
+        match pages.split_once('-') {
+            Some((start, end)) if !start.trim().is_empty() && !end.trim().is_empty() => {
+                push_line(out, &RisTag::StartPage, start);
+                push_line(out, &RisTag::EndPage, end);
+            }
+            _ => push_line(out, &RisTag::StartPage, pages),
+        }
+    }
+    push_opt(out, &RisTag::Doi, citation.doi.as_deref());
+    push_opt(
+        out,
+        &RisTag::AccessionNumber,
+        citation.accession_number.as_deref(),
+    );
+    push_opt(out, &RisTag::PmcId, citation.pmc_id.as_deref());
+    if let Some(abstract_text) = citation.abstract_text.as_deref() {
+        // The parser joins repeated abstract lines with a blank line, so each
+        // paragraph gets its own AB line.
+        for paragraph in abstract_text.split("\n\n") {
+            push_line(out, &RisTag::Abstract, paragraph);
+        }
+    }
+    for keyword in &citation.keywords {
+        push_line(out, &RisTag::Keywords, keyword);
+    }
+    for issn in &citation.issn {
+        push_line(out, &RisTag::SerialNumber, issn);
+    }
+    for isbn in citation.identifiers_of(Scheme::Isbn) {
+        push_line(out, &RisTag::SerialNumber, isbn);
+    }
+    for url in &citation.urls {
+        push_line(out, &RisTag::Url, url);
+    }
+    push_opt(out, &RisTag::Language, citation.language.as_deref());
+    push_opt(out, &RisTag::Publisher, citation.publisher.as_deref());
+    for work_type in types {
+        push_line(out, &RisTag::WorkType, work_type);
+    }
+
+    // Sort keys so output is stable across runs despite HashMap ordering.
+    let mut extra_keys: Vec<&String> = citation
+        .extra_fields
+        .keys()
+        .filter(|key| is_passthrough_tag(key))
+        .collect();
+    extra_keys.sort();
+    for key in extra_keys {
+        let tag = RisTag::from_tag(key);
+        for value in &citation.extra_fields[key] {
+            push_line(out, &tag, value);
+        }
+    }
+
+    out.push_str(RisTag::EndOfReference.as_tag());
+    out.push_str("  -\n\n");
+}
+
+/// Append a `TAG  - value` line, skipping blank values.
+///
+/// Embedded line breaks are folded into spaces, which is how the parser
+/// treats continuation lines anyway.
+fn push_line(out: &mut String, tag: &RisTag, value: &str) {
+    let value = value.trim();
+    if value.is_empty() {
+        return;
+    }
+    out.push_str(tag.as_tag());
+    out.push_str("  - ");
+    if value.contains(['\n', '\r']) {
+        out.push_str(&value.split_whitespace().collect::<Vec<_>>().join(" "));
+    } else {
+        out.push_str(value);
+    }
+    out.push('\n');
+}
+
+fn push_opt(out: &mut String, tag: &RisTag, value: Option<&str>) {
+    if let Some(value) = value {
+        push_line(out, tag, value);
+    }
+}
+
+/// Whether an `extra_fields` key should be written back as a RIS tag.
+///
+/// Only well-formed two-character tags the parser leaves untouched are
+/// written; tags backed by a `Citation` field are emitted from that field
+/// instead, and keys from other formats are not valid RIS.
+fn is_passthrough_tag(key: &str) -> bool {
+    key.len() == 2
+        && key.chars().all(|c| c.is_ascii_alphanumeric())
+        && matches!(
+            RisTag::from_tag(key),
+            RisTag::Unknown(_) | RisTag::ReferenceId
+        )
+}
+
+/// Maps human-readable citation types back to RIS type abbreviations.
+///
+/// This is the inverse of the parser's type mapping; unknown types are
+/// written unchanged.
+fn ris_type_abbr(name: &str) -> &str {
+    match name {
+        "Abstract" => "ABST",
+        "Audiovisual Material" => "ADVS",
+        "Art Work" => "ART",
+        "Bill/Resolution" => "BILL",
+        "Book" => "BOOK",
+        "Case" => "CASE",
+        "Book Chapter" => "CHAP",
+        "Computer Program" => "COMP",
+        "Conference Proceeding" => "CONF",
+        "Catalog" => "CTLG",
+        "Data File" => "DATA",
+        "Electronic Citation" => "ELEC",
+        "Generic" => "GEN",
+        "Hearing" => "HEAR",
+        "Internet Communication" => "ICOMM",
+        "In Press" => "INPR",
+        "Journal/Periodical (Full)" => "JFULL",
+        "Journal Article" => "JOUR",
+        "Map" => "MAP",
+        "Magazine Article" => "MGZN",
+        "Motion Picture" => "MPCT",
+        "Music Score" => "MUSIC",
+        "Newspaper" => "NEWS",
+        "Pamphlet" => "PAMP",
+        "Patent" => "PAT",
+        "Personal Communication" => "PCOMM",
+        "Report" => "RPRT",
+        "Serial Publication" => "SER",
+        "Slide" => "SLIDE",
+        "Sound Recording" => "SOUND",
+        "Statute" => "STAT",
+        "Thesis/Dissertation" => "THES",
+        "Unenacted Bill/Resolution" => "UNBILL",
+        "Unpublished Work" => "UNPB",
+        "Video Recording" => "VIDEO",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Author, ContributorRole};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Journal Article", "JOUR")]
+    #[case("Book Chapter", "CHAP")]
+    #[case("article", "article")]
+    fn test_ris_type_abbr(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(ris_type_abbr(name), expected);
+    }
+
+    #[rstest]
+    #[case("ID", true)]
+    #[case("N1", true)]
+    #[case("TI", false)]
+    #[case("%F", false)]
+    #[case("note", false)]
+    fn test_is_passthrough_tag(#[case] key: &str, #[case] expected: bool) {
+        assert_eq!(is_passthrough_tag(key), expected);
+    }
+
+    #[test]
+    fn test_write_minimal_record() {
+        let citation = Citation {
+            citation_type: vec!["Journal Article".to_string()],
+            title: "Example".to_string(),
+            authors: vec![Author {
+                name: "Smith".to_string(),
+                given_name: Some("John".to_string()),
+                middle_name: Some("A".to_string()),
+                affiliations: Vec::new(),
+                role: ContributorRole::Author,
+                orcid: None,
+                is_organization: false,
+            }],
+            pages: Some("100-110".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ris_write(&[citation]),
+            "TY  - JOUR\nTI  - Example\nAU  - Smith, John A\nSP  - 100\nEP  - 110\nER  -\n\n"
+        );
+    }
+
+    #[test]
+    fn test_write_empty_title() {
+        let citation = Citation {
+            title: " ".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(ris_write(&[citation]), "TY  - GEN\nTI  - \nER  -\n\n");
+    }
+
+    #[test]
+    fn test_write_folds_line_breaks() {
+        let citation = Citation {
+            title: "Multi\nline\r\n title".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ris_write(&[citation]),
+            "TY  - GEN\nTI  - Multi line title\nER  -\n\n"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Chunks of the input and moving positions from a chunk to the whole
+//! input.
+
+use crate::error::{ParseError, ParseWarning, SourceSpan, ValueError};
+use crate::{Citation, CitationFormat};
+use either::Either;
+
+/// A slice of the input holding one record.
+#[derive(Debug)]
+pub(crate) struct Chunk {
+    pub(super) text: String,
+    /// 1-based line number of the first line of `text`.
+    pub(super) start_line: usize,
+    /// Byte offset of `text` in the whole input.
+    pub(super) start_byte: usize,
+}
+
+/// Output of a chunk parser whose positions can be moved from the chunk to
+/// the whole input.
+pub(crate) trait Relocate {
+    fn relocate(self, chunk: &Chunk) -> Self;
+}
+
+impl Relocate for Citation {
+    fn relocate(self, _chunk: &Chunk) -> Self {
+        self
+    }
+}
+
+impl Relocate for (Citation, Vec<ParseWarning>) {
+    fn relocate(self, chunk: &Chunk) -> Self {
+        let (citation, mut warnings) = self;
+        for warning in &mut warnings {
+            shift(&mut warning.line, &mut warning.span, chunk);
+        }
+        (citation, warnings)
+    }
+}
+
+/// Parse each chunk with `parse`, moving error positions to the whole input.
+pub(crate) fn parse_chunks<'r, I, F, T>(
+    chunks: I,
+    parse: F,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'r
+where
+    I: Iterator<Item = Result<Chunk, ParseError>> + 'r,
+    F: Fn(&str) -> Result<Vec<T>, ParseError> + 'r,
+    T: Relocate + 'r,
+{
+    chunks.flat_map(move |chunk| {
+        let result = chunk.and_then(|chunk| match parse(&chunk.text) {
+            Ok(records) => Ok(records
+                .into_iter()
+                .map(|record| record.relocate(&chunk))
+                .collect::<Vec<_>>()),
+            Err(err) => Err(relocate(err, &chunk)),
+        });
+        match result {
+            Ok(records) => Either::Left(records.into_iter().map(Ok)),
+            Err(err) => Either::Right(std::iter::once(Err(err))),
+        }
+    })
+}
+
+fn relocate(mut err: ParseError, chunk: &Chunk) -> ParseError {
+    shift(&mut err.line, &mut err.span, chunk);
+    err
+}
+
+fn shift(line: &mut Option<usize>, span: &mut Option<SourceSpan>, chunk: &Chunk) {
+    if let Some(line) = line.as_mut() {
+        *line = chunk.start_line + (*line).max(1) - 1;
+    }
+    if let Some(span) = span.as_mut() {
+        span.start += chunk.start_byte;
+        span.end += chunk.start_byte;
+    }
+}
+
+pub(super) fn read_error(format: &CitationFormat, line: usize, err: std::io::Error) -> ParseError {
+    ParseError::at_line(line, format.clone(), ValueError::Io(err))
+}
+
+pub(super) fn utf8_error(format: &CitationFormat, line: usize, span: SourceSpan) -> ParseError {
+    ParseError::at_line(
+        line,
+        format.clone(),
+        ValueError::Syntax("Input is not valid UTF-8".to_string()),
+    )
+    .with_span(span)
+}
+
+/// `(start_line, start_byte, text)` of each chunk, for tests.
+#[cfg(test)]
+pub(super) fn texts<I: Iterator<Item = Result<Chunk, ParseError>>>(
+    chunks: I,
+) -> Vec<(usize, usize, String)> {
+    chunks
+        .map(|chunk| {
+            let chunk = chunk.unwrap();
+            (chunk.start_line, chunk.start_byte, chunk.text)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_relocate_offsets_line_and_span() {
+        let chunk = Chunk {
+            text: String::new(),
+            start_line: 10,
+            start_byte: 100,
+        };
+        let err = ParseError::at_line(3, CitationFormat::Ris, ValueError::Syntax("x".into()))
+            .with_span(SourceSpan::new(5, 9));
+
+        let err = relocate(err, &chunk);
+        assert_eq!(err.line, Some(12));
+        assert_eq!(err.span, Some(SourceSpan::new(105, 109)));
+    }
+}
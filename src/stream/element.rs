@@ -0,0 +1,185 @@
+//! Splitting XML input into records.
+
+use super::chunk::{Chunk, read_error, utf8_error};
+use crate::CitationFormat;
+use crate::error::{ParseError, SourceSpan};
+use std::io::Read;
+
+/// Size of the blocks read by [`ElementChunks`].
+const READ_BLOCK_SIZE: usize = 64 * 1024;
+
+/// Splits XML input into one chunk per `<tag>...</tag>` element. Text between
+/// elements, including the XML declaration and the root element, is skipped.
+pub(crate) struct ElementChunks<R> {
+    reader: R,
+    format: CitationFormat,
+    open: Vec<u8>,
+    close: Vec<u8>,
+    window: Vec<u8>,
+    window_line: usize,
+    window_byte: usize,
+    done: bool,
+}
+
+impl<R: Read> ElementChunks<R> {
+    pub(crate) fn new(reader: R, format: CitationFormat, tag: &str) -> Self {
+        Self {
+            reader,
+            format,
+            open: format!("<{}", tag).into_bytes(),
+            close: format!("</{}>", tag).into_bytes(),
+            window: Vec::new(),
+            window_line: 1,
+            window_byte: 0,
+            done: false,
+        }
+    }
+
+    /// Drop the first `len` bytes of the window.
+    fn discard(&mut self, len: usize) -> Vec<u8> {
+        let drained: Vec<u8> = self.window.drain(..len).collect();
+        self.window_line += drained.iter().filter(|&&b| b == b'\n').count();
+        self.window_byte += len;
+        drained
+    }
+
+    /// Position of the next opening tag, if the window holds enough bytes to
+    /// tell it apart from a longer name such as `<records>`.
+    fn find_open(&self) -> Option<usize> {
+        let len = self.open.len();
+        (0..self.window.len().saturating_sub(len)).find(|&i| {
+            self.window[i..].starts_with(&self.open)
+                && matches!(
+                    self.window[i + len],
+                    b'>' | b'/' | b' ' | b'\t' | b'\r' | b'\n'
+                )
+        })
+    }
+
+    fn find_bytes(&self, needle: &[u8], from: usize) -> Option<usize> {
+        self.window
+            .get(from..)?
+            .windows(needle.len())
+            .position(|window| window == needle)
+            .map(|i| i + from)
+    }
+
+    fn chunk(&mut self, len: usize) -> Result<Chunk, ParseError> {
+        let start_line = self.window_line;
+        let start_byte = self.window_byte;
+        let bytes = self.discard(len);
+        let text = String::from_utf8(bytes).map_err(|_| {
+            self.done = true;
+            utf8_error(
+                &self.format,
+                start_line,
+                SourceSpan::new(start_byte, start_byte + len),
+            )
+        })?;
+        Ok(Chunk {
+            text,
+            start_line,
+            start_byte,
+        })
+    }
+
+    /// Read another block into the window, returning `false` at EOF.
+    fn fill(&mut self) -> Result<bool, ParseError> {
+        let start = self.window.len();
+        self.window.resize(start + READ_BLOCK_SIZE, 0);
+        match self.reader.read(&mut self.window[start..]) {
+            Ok(read) => {
+                self.window.truncate(start + read);
+                Ok(read > 0)
+            }
+            Err(err) => {
+                self.window.truncate(start);
+                if err.kind() == std::io::ErrorKind::Interrupted {
+                    Ok(true)
+                } else {
+                    Err(read_error(&self.format, self.window_line, err))
+                }
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for ElementChunks<R> {
+    type Item = Result<Chunk, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.find_open() {
+                Some(start) => {
+                    self.discard(start);
+                    if let Some(tag_end) = self.find_bytes(b">", self.open.len()) {
+                        if self.window[tag_end - 1] == b'/' {
+                            // Self-closing element with no content.
+                            self.discard(tag_end + 1);
+                            continue;
+                        }
+                        if let Some(close) = self.find_bytes(&self.close, tag_end) {
+                            return Some(self.chunk(close + self.close.len()));
+                        }
+                    }
+                }
+                None => {
+                    // Keep enough bytes to match an opening tag split across
+                    // two reads.
+                    let keep = self.open.len();
+                    self.discard(self.window.len().saturating_sub(keep));
+                }
+            }
+
+            match self.fill() {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.done = true;
+                    // An element left open at EOF is passed on so that the
+                    // parser reports it.
+                    if self.window.starts_with(&self.open) {
+                        let len = self.window.len();
+                        return Some(self.chunk(len));
+                    }
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::chunk::texts;
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_element_chunks_skip_wrappers_and_empty_elements() {
+        let input = "<?xml version=\"1.0\"?>\n<records><record/>\n  <record>a</record><record\n>b</record></records>";
+        let chunks = ElementChunks::new(input.as_bytes(), CitationFormat::EndNoteXml, "record");
+
+        assert_eq!(
+            texts(chunks),
+            vec![
+                (3, 43, "<record>a</record>".to_string()),
+                (3, 61, "<record\n>b</record>".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_element_chunks_pass_on_unclosed_element() {
+        let input = "<records><record><title>a</title>";
+        let chunks = ElementChunks::new(input.as_bytes(), CitationFormat::EndNoteXml, "record");
+
+        assert_eq!(
+            texts(chunks),
+            vec![(1, 9, "<record><title>a</title>".to_string())]
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Splitting line-oriented input into records.
+
+use super::chunk::{Chunk, read_error, utf8_error};
+use crate::CitationFormat;
+use crate::error::{ParseError, SourceSpan};
+use std::io::BufRead;
+
+/// Where a line falls relative to record boundaries.
+pub(crate) enum Boundary {
+    /// The line belongs to the current record.
+    Inside,
+    /// The line starts a new record.
+    #[cfg_attr(
+        not(any(feature = "ris", feature = "pubmed", feature = "enw", feature = "ovid")),
+        allow(dead_code)
+    )]
+    Before,
+    /// The line ends the current record.
+    #[cfg_attr(not(any(feature = "ris", feature = "wos")), allow(dead_code))]
+    After,
+}
+
+/// Splits line-oriented input into records.
+///
+/// `boundary` is called with the previous line and the current line, both
+/// without their line terminator.
+pub(crate) struct LineChunks<R> {
+    reader: R,
+    format: CitationFormat,
+    boundary: fn(Option<&str>, &str) -> Boundary,
+    line_buf: Vec<u8>,
+    previous: Option<String>,
+    pending: String,
+    pending_line: usize,
+    pending_byte: usize,
+    line_number: usize,
+    byte: usize,
+    done: bool,
+}
+
+impl<R: BufRead> LineChunks<R> {
+    pub(crate) fn new(
+        reader: R,
+        format: CitationFormat,
+        boundary: fn(Option<&str>, &str) -> Boundary,
+    ) -> Self {
+        Self {
+            reader,
+            format,
+            boundary,
+            line_buf: Vec::new(),
+            previous: None,
+            pending: String::new(),
+            pending_line: 1,
+            pending_byte: 0,
+            line_number: 0,
+            byte: 0,
+            done: false,
+        }
+    }
+
+    fn take_pending(&mut self) -> Option<Chunk> {
+        take_chunk(&mut self.pending, self.pending_line, self.pending_byte)
+    }
+}
+
+impl<R: BufRead> Iterator for LineChunks<R> {
+    type Item = Result<Chunk, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.done {
+                return self.take_pending().map(Ok);
+            }
+
+            self.line_buf.clear();
+            let read = match self.reader.read_until(b'\n', &mut self.line_buf) {
+                Ok(read) => read,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(read_error(&self.format, self.line_number + 1, err)));
+                }
+            };
+            if read == 0 {
+                self.done = true;
+                continue;
+            }
+
+            let line_start = self.byte;
+            self.line_number += 1;
+            self.byte += read;
+
+            let Ok(line) = std::str::from_utf8(&self.line_buf) else {
+                self.done = true;
+                self.pending.clear();
+                return Some(Err(utf8_error(
+                    &self.format,
+                    self.line_number,
+                    SourceSpan::new(line_start, self.byte),
+                )));
+            };
+            let content = line.trim_end_matches(['\n', '\r']);
+
+            let boundary = (self.boundary)(self.previous.as_deref(), content);
+            let finished = match boundary {
+                Boundary::Before => {
+                    take_chunk(&mut self.pending, self.pending_line, self.pending_byte)
+                }
+                _ => None,
+            };
+
+            if self.pending.is_empty() {
+                self.pending_line = self.line_number;
+                self.pending_byte = line_start;
+            }
+            self.pending.push_str(line);
+            self.previous = Some(content.to_string());
+
+            if let Some(chunk) = finished {
+                return Some(Ok(chunk));
+            }
+            if matches!(boundary, Boundary::After)
+                && let Some(chunk) = self.take_pending()
+            {
+                return Some(Ok(chunk));
+            }
+        }
+    }
+}
+
+fn take_chunk(pending: &mut String, start_line: usize, start_byte: usize) -> Option<Chunk> {
+    (!pending.is_empty()).then(|| Chunk {
+        text: std::mem::take(pending),
+        start_line,
+        start_byte,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::chunk::texts;
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn blank_line_boundary(previous: Option<&str>, line: &str) -> Boundary {
+        if previous.is_some_and(str::is_empty) && !line.is_empty() {
+            Boundary::Before
+        } else {
+            Boundary::Inside
+        }
+    }
+
+    #[test]
+    fn test_line_chunks_track_lines_and_bytes() {
+        let input = "a\r\nb\r\n\r\nc\n";
+        let chunks = LineChunks::new(
+            input.as_bytes(),
+            CitationFormat::Unknown,
+            blank_line_boundary,
+        );
+
+        assert_eq!(
+            texts(chunks),
+            vec![
+                (1, 0, "a\r\nb\r\n\r\n".to_string()),
+                (4, 8, "c\n".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_chunks_reject_invalid_utf8() {
+        let input: &[u8] = b"a\n\xff\n";
+        let err = LineChunks::new(input, CitationFormat::Ris, blank_line_boundary)
+            .find_map(Result::err)
+            .unwrap();
+
+        assert_eq!(err.line, Some(2));
+        assert_eq!(err.span, Some(SourceSpan::new(2, 4)));
+    }
+}
@@ -0,0 +1,61 @@
+//! Record-at-a-time reading for [`CitationParser::parse_reader`].
+//!
+//! Input is split into chunks that each hold one record, and each chunk is
+//! parsed with the format's ordinary `&str` parser. Errors are then moved from
+//! chunk-relative positions to positions in the whole input.
+//!
+//! [`CitationParser::parse_reader`]: crate::CitationParser::parse_reader
+
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "enw",
+    feature = "wos",
+    feature = "ovid",
+    feature = "xml"
+))]
+pub(crate) mod chunk;
+#[cfg(feature = "xml")]
+pub(crate) mod element;
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "enw",
+    feature = "wos",
+    feature = "ovid"
+))]
+pub(crate) mod line;
+
+use crate::CitationFormat;
+use crate::error::{ParseError, ValueError};
+use std::io::Read;
+
+/// Read the whole input, for parsers that need all of it before they can
+/// split it into records.
+pub(crate) fn read_all<R: Read>(
+    mut reader: R,
+    format: CitationFormat,
+) -> Result<String, ParseError> {
+    let mut input = String::new();
+    reader
+        .read_to_string(&mut input)
+        .map_err(|err| ParseError::without_position(format, ValueError::Io(err)))?;
+    Ok(input)
+}
+
+/// Read the whole input and yield the per-record results of `parse`.
+#[cfg(any(feature = "bib", feature = "csl", feature = "wos", feature = "xml"))]
+pub(crate) fn parse_whole<'r, R, F>(
+    reader: R,
+    format: CitationFormat,
+    parse: F,
+) -> Box<dyn Iterator<Item = Result<crate::Citation, ParseError>> + 'r>
+where
+    R: Read,
+    F: FnOnce(&str) -> Vec<Result<crate::Citation, ParseError>>,
+{
+    match read_all(reader, format) {
+        Ok(input) => Box::new(parse(&input).into_iter()),
+        Err(err) => Box::new(std::iter::once(Err(err))),
+    }
+}
@@ -0,0 +1,428 @@
+//! Cleaning of citation text.
+//!
+//! Exports often carry text that went through one encoding or escaping step
+//! too many: `Ã©tude` instead of `étude`, `&amp;#x2013;` instead of `–`,
+//! `<U+00E9>` escapes from R-based tools, and inline markup such as
+//! `H<sub>2</sub>O`. The functions here undo each of these, and [`clean`]
+//! applies all of them.
+//!
+//! Parsers leave text as it appears in the input. Set
+//! [`ParseOptions::clean_text`](crate::ParseOptions::clean_text) to clean the
+//! titles, abstracts, journals and author names of parsed citations, or call
+//! [`clean_citation`] directly.
+//!
+//! # Example
+//!
+//! ```
+//! use biblib::text::clean;
+//!
+//! assert_eq!(clean("Ã‰tude of H<sub>2</sub>O &amp;#x2013; 2<U+03B1>"), "Étude of H2O – 2α");
+//! ```
+
+use crate::regex::{Captures, Regex};
+use crate::{Author, Citation};
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+static UNICODE_ESCAPE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<U\+([0-9A-Fa-f]+)>").unwrap());
+
+static ENTITY_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"&(#[0-9]+|#[xX][0-9A-Fa-f]+|[A-Za-z][A-Za-z0-9]*);").unwrap());
+
+static MARKUP_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)</?(?:sup|sub|inf|i|b|em|strong|u|sc|scp|small|span|italic|bold|underline)(?:\s[^<>]*)?/?>",
+    )
+    .unwrap()
+});
+
+/// Entities decoded more than once handle input that was escaped twice,
+/// such as `&amp;#x2013;`.
+const MAX_ENTITY_PASSES: usize = 3;
+
+/// Text is re-decoded at most this many times, for input that was
+/// double-encoded more than once.
+const MAX_MOJIBAKE_PASSES: usize = 3;
+
+/// HTML 4 names for U+00A0 to U+00FF, in code point order.
+const LATIN1_ENTITIES: [&str; 96] = [
+    "nbsp", "iexcl", "cent", "pound", "curren", "yen", "brvbar", "sect", "uml", "copy", "ordf",
+    "laquo", "not", "shy", "reg", "macr", "deg", "plusmn", "sup2", "sup3", "acute", "micro",
+    "para", "middot", "cedil", "sup1", "ordm", "raquo", "frac14", "frac12", "frac34", "iquest",
+    "Agrave", "Aacute", "Acirc", "Atilde", "Auml", "Aring", "AElig", "Ccedil", "Egrave", "Eacute",
+    "Ecirc", "Euml", "Igrave", "Iacute", "Icirc", "Iuml", "ETH", "Ntilde", "Ograve", "Oacute",
+    "Ocirc", "Otilde", "Ouml", "times", "Oslash", "Ugrave", "Uacute", "Ucirc", "Uuml", "Yacute",
+    "THORN", "szlig", "agrave", "aacute", "acirc", "atilde", "auml", "aring", "aelig", "ccedil",
+    "egrave", "eacute", "ecirc", "euml", "igrave", "iacute", "icirc", "iuml", "eth", "ntilde",
+    "ograve", "oacute", "ocirc", "otilde", "ouml", "divide", "oslash", "ugrave", "uacute", "ucirc",
+    "uuml", "yacute", "thorn", "yuml",
+];
+
+/// HTML 4 names for the Greek letters from U+0391, in code point order.
+/// U+03A2 has no capital letter and no name.
+const GREEK_ENTITIES: [&str; 57] = [
+    "Alpha", "Beta", "Gamma", "Delta", "Epsilon", "Zeta", "Eta", "Theta", "Iota", "Kappa",
+    "Lambda", "Mu", "Nu", "Xi", "Omicron", "Pi", "Rho", "", "Sigma", "Tau", "Upsilon", "Phi",
+    "Chi", "Psi", "Omega", "", "", "", "", "", "", "", "alpha", "beta", "gamma", "delta",
+    "epsilon", "zeta", "eta", "theta", "iota", "kappa", "lambda", "mu", "nu", "xi", "omicron",
+    "pi", "rho", "sigmaf", "sigma", "tau", "upsilon", "phi", "chi", "psi", "omega",
+];
+
+/// Other named entities common in titles and abstracts.
+const OTHER_ENTITIES: [(&str, char); 42] = [
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("OElig", 'Œ'),
+    ("oelig", 'œ'),
+    ("Scaron", 'Š'),
+    ("scaron", 'š'),
+    ("Yuml", 'Ÿ'),
+    ("fnof", 'ƒ'),
+    ("circ", 'ˆ'),
+    ("tilde", '˜'),
+    ("ensp", '\u{2002}'),
+    ("emsp", '\u{2003}'),
+    ("thinsp", '\u{2009}'),
+    ("ndash", '–'),
+    ("mdash", '—'),
+    ("lsquo", '‘'),
+    ("rsquo", '’'),
+    ("sbquo", '‚'),
+    ("ldquo", '“'),
+    ("rdquo", '”'),
+    ("bdquo", '„'),
+    ("dagger", '†'),
+    ("Dagger", '‡'),
+    ("bull", '•'),
+    ("hellip", '…'),
+    ("permil", '‰'),
+    ("prime", '′'),
+    ("Prime", '″'),
+    ("euro", '€'),
+    ("trade", '™'),
+    ("larr", '←'),
+    ("rarr", '→'),
+    ("harr", '↔'),
+    ("minus", '−'),
+    ("infin", '∞'),
+    ("asymp", '≈'),
+    ("ne", '≠'),
+    ("le", '≤'),
+    ("ge", '≥'),
+];
+
+/// The characters Windows-1252 puts at 0x80 to 0x9F. Bytes that
+/// Windows-1252 leaves undefined decode to the C1 control with the same
+/// value.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+];
+
+/// Apply every cleaning step to `input`.
+///
+/// Steps run in this order: [`decode_unicode_escapes`], [`decode_entities`],
+/// [`repair_mojibake`] and [`strip_markup`], so entities that spell out
+/// mojibake or markup are repaired too.
+pub fn clean(input: &str) -> String {
+    let text = decode_unicode_escapes(input);
+    let text = decode_entities(&text);
+    let text = repair_mojibake(&text);
+    strip_markup(&text)
+}
+
+/// Clean the title, abstract, journal names and author and contributor names
+/// of a citation with [`clean`].
+pub fn clean_citation(citation: &mut Citation) {
+    clean_in_place(&mut citation.title);
+    [
+        &mut citation.abstract_text,
+        &mut citation.journal,
+        &mut citation.journal_abbr,
+    ]
+    .into_iter()
+    .flatten()
+    .for_each(clean_in_place);
+    citation
+        .authors
+        .iter_mut()
+        .chain(&mut citation.contributors)
+        .for_each(clean_author);
+}
+
+fn clean_author(author: &mut Author) {
+    clean_in_place(&mut author.name);
+    [&mut author.given_name, &mut author.middle_name]
+        .into_iter()
+        .flatten()
+        .for_each(clean_in_place);
+}
+
+fn clean_in_place(value: &mut String) {
+    let cleaned = clean(value);
+    if cleaned != *value {
+        *value = cleaned;
+    }
+}
+
+/// Replace `<U+XXXX>` escapes with the character they name.
+///
+/// Escapes that do not name a valid character are kept.
+///
+/// ```
+/// use biblib::text::decode_unicode_escapes;
+///
+/// assert_eq!(decode_unicode_escapes("2<U+0391>-amino"), "2Α-amino");
+/// ```
+pub fn decode_unicode_escapes(input: &str) -> String {
+    UNICODE_ESCAPE_REGEX
+        .replace_all(input, |caps: &Captures| {
+            u32::from_str_radix(&caps[1], 16)
+                .ok()
+                .and_then(char::from_u32)
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Decode HTML and XML character references.
+///
+/// Decimal (`&#8211;`) and hexadecimal (`&#x2013;`) references are decoded,
+/// as are the HTML 4 named entities for Latin-1, Greek and common
+/// punctuation. Input that was escaped twice, such as `&amp;#x2013;`, is
+/// decoded fully. Unknown names are kept.
+///
+/// ```
+/// use biblib::text::decode_entities;
+///
+/// assert_eq!(decode_entities("Fish &amp; Chips &#x2013; caf&eacute;"), "Fish & Chips – café");
+/// assert_eq!(decode_entities("1990&amp;#x2013;2000"), "1990–2000");
+/// ```
+pub fn decode_entities(input: &str) -> String {
+    let mut text = Cow::Borrowed(input);
+    for _ in 0..MAX_ENTITY_PASSES {
+        let decoded = ENTITY_REGEX.replace_all(&text, |caps: &Captures| {
+            entity_char(&caps[1])
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| caps[0].to_string())
+        });
+        if decoded == text {
+            break;
+        }
+        text = Cow::Owned(decoded.into_owned());
+    }
+    text.into_owned()
+}
+
+/// The character for an entity body, the part between `&` and `;`.
+fn entity_char(entity: &str) -> Option<char> {
+    if let Some(number) = entity.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code).filter(|c| *c != '\0');
+    }
+
+    if let Some(index) = LATIN1_ENTITIES.iter().position(|name| *name == entity) {
+        return char::from_u32(0xA0 + index as u32);
+    }
+    if let Some(index) = GREEK_ENTITIES
+        .iter()
+        .position(|name| !name.is_empty() && *name == entity)
+    {
+        return char::from_u32(0x391 + index as u32);
+    }
+    OTHER_ENTITIES
+        .iter()
+        .find(|(name, _)| *name == entity)
+        .map(|(_, c)| *c)
+}
+
+/// Repair UTF-8 text that was decoded as Windows-1252 or Latin-1, such as
+/// `Ã©` for `é` or `â€“` for `–`.
+///
+/// Each run of non-ASCII characters that Windows-1252 can encode is turned
+/// back into bytes; when those bytes are valid UTF-8 that spells out non-ASCII
+/// characters, the run is replaced. Text that was double-encoded more than
+/// once is repaired by repeating this. Correct Latin-1 text is left alone,
+/// because its bytes are not valid UTF-8.
+///
+/// ```
+/// use biblib::text::repair_mojibake;
+///
+/// assert_eq!(repair_mojibake("Ã©tude â€“ naÃ¯ve"), "étude – naïve");
+/// assert_eq!(repair_mojibake("Étude – naïve"), "Étude – naïve");
+/// ```
+pub fn repair_mojibake(input: &str) -> String {
+    let mut text = input.to_string();
+    for _ in 0..MAX_MOJIBAKE_PASSES {
+        match repair_mojibake_once(&text) {
+            Some(repaired) => text = repaired,
+            None => break,
+        }
+    }
+    text
+}
+
+/// One repair pass, or `None` if nothing was repaired.
+fn repair_mojibake_once(input: &str) -> Option<String> {
+    let mut output = String::with_capacity(input.len());
+    let mut run = Vec::new();
+    let mut repaired = false;
+
+    for c in input.chars() {
+        // ASCII never occurs inside a multi-byte UTF-8 sequence, so runs
+        // only hold non-ASCII characters.
+        match windows_1252_byte(c).filter(|byte| !byte.is_ascii()) {
+            Some(byte) => run.push(byte),
+            None => {
+                repaired |= flush_run(&mut run, &mut output);
+                output.push(c);
+            }
+        }
+    }
+    repaired |= flush_run(&mut run, &mut output);
+
+    repaired.then_some(output)
+}
+
+/// Append a run of Windows-1252 bytes to `output`, decoded as UTF-8 if that
+/// repairs it and as Windows-1252 otherwise. Returns whether it was repaired.
+fn flush_run(run: &mut Vec<u8>, output: &mut String) -> bool {
+    let repaired = match std::str::from_utf8(run) {
+        Ok(text) if !run.is_empty() => {
+            output.push_str(text);
+            true
+        }
+        _ => {
+            output.extend(run.iter().map(|&byte| windows_1252_char(byte)));
+            false
+        }
+    };
+    run.clear();
+    repaired
+}
+
+/// The Windows-1252 byte for a character, if it has one.
+fn windows_1252_byte(c: char) -> Option<u8> {
+    match c as u32 {
+        code @ (0..=0x7F | 0xA0..=0xFF) => Some(code as u8),
+        _ => WINDOWS_1252_HIGH
+            .iter()
+            .position(|&high| high == c)
+            .map(|index| 0x80 + index as u8),
+    }
+}
+
+fn windows_1252_char(byte: u8) -> char {
+    match byte {
+        0x80..=0x9F => WINDOWS_1252_HIGH[(byte - 0x80) as usize],
+        _ => byte as char,
+    }
+}
+
+/// Remove inline formatting tags such as `<sup>`, `<sub>`, `<inf>`, `<i>`,
+/// `<b>` and `<span …>`, keeping their content.
+///
+/// Only known formatting tags are removed, so comparisons like `p < 0.05`
+/// and other angle-bracketed text are kept.
+///
+/// ```
+/// use biblib::text::strip_markup;
+///
+/// assert_eq!(strip_markup("H<sub>2</sub>O and <i>E. coli</i>"), "H2O and E. coli");
+/// ```
+pub fn strip_markup(input: &str) -> String {
+    MARKUP_REGEX.replace_all(input, "").into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ContributorRole;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Ã©tude", "étude")]
+    #[case("Ã‰tude", "Étude")]
+    #[case("Schr\u{c3}\u{b6}dinger", "Schrödinger")]
+    #[case("1990â€“2000", "1990–2000")]
+    #[case("â€œquotedâ€\u{9d}", "“quoted”")]
+    #[case("ÃƒÂ©tude", "étude")]
+    #[case("Î±-synuclein and Ã©tude", "α-synuclein and étude")]
+    #[case("α-synuclein Ã©tude", "α-synuclein étude")]
+    #[case("Étude", "Étude")]
+    #[case("naïve café", "naïve café")]
+    #[case("Plain ASCII", "Plain ASCII")]
+    fn test_repair_mojibake(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(repair_mojibake(input), expected);
+    }
+
+    #[rstest]
+    #[case("&lt;i&gt;", "<i>")]
+    #[case("&#233;&#xE9;&#XE9;", "ééé")]
+    #[case("&amp;#x2013;", "–")]
+    #[case("&alpha;&Omega;&sigmaf;", "αΩς")]
+    #[case("&Uuml;ber &ndash; &hellip;", "Über – …")]
+    #[case("&unknown; & AT&T;", "&unknown; & AT&T;")]
+    #[case("&#0;&#xFFFFFFFF;", "&#0;&#xFFFFFFFF;")]
+    fn test_decode_entities(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(decode_entities(input), expected);
+    }
+
+    #[rstest]
+    #[case("x<sup>2</sup>", "x2")]
+    #[case("CO<INF>2</INF>", "CO2")]
+    #[case("<span class=\"gene\">BRCA1</span>", "BRCA1")]
+    #[case("a<br/>b", "a<br/>b")]
+    #[case("p < 0.05 and q > 1", "p < 0.05 and q > 1")]
+    #[case("<italic>in vivo</italic>", "in vivo")]
+    fn test_strip_markup(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(strip_markup(input), expected);
+    }
+
+    #[test]
+    fn test_decode_unicode_escapes() {
+        assert_eq!(
+            decode_unicode_escapes("Hello <U+03A9>orld <U+110000>"),
+            "Hello Ωorld <U+110000>"
+        );
+    }
+
+    #[test]
+    fn test_clean_citation() {
+        let mut citation = Citation {
+            title: "Caf&eacute; <i>study</i>".to_string(),
+            abstract_text: Some("Ã©tude".to_string()),
+            journal: Some("J Chem &amp; Phys".to_string()),
+            authors: vec![Author {
+                name: "M<U+00FC>ller".to_string(),
+                given_name: Some("JosÃ©".to_string()),
+                middle_name: None,
+                affiliations: vec!["Caf&eacute;".to_string()],
+                role: ContributorRole::Author,
+                orcid: None,
+                is_organization: false,
+            }],
+            keywords: vec!["&amp;".to_string()],
+            ..Default::default()
+        };
+        clean_citation(&mut citation);
+
+        assert_eq!(citation.title, "Café study");
+        assert_eq!(citation.abstract_text.as_deref(), Some("étude"));
+        assert_eq!(citation.journal.as_deref(), Some("J Chem & Phys"));
+        assert_eq!(citation.authors[0].name, "Müller");
+        assert_eq!(citation.authors[0].given_name.as_deref(), Some("José"));
+        // Only the documented fields are cleaned.
+        assert_eq!(citation.authors[0].affiliations[0], "Caf&eacute;");
+        assert_eq!(citation.keywords[0], "&amp;");
+    }
+}
@@ -1,12 +1,29 @@
-use crate::Date;
+// Helpers are shared across parsers, so builds without every parser feature
+// leave some of them unused.
+#![cfg_attr(
+    not(all(
+        feature = "csv",
+        feature = "pubmed",
+        feature = "xml",
+        feature = "ris",
+        feature = "enw",
+        feature = "bib",
+        feature = "csl",
+        feature = "wos",
+        feature = "ovid"
+    )),
+    allow(dead_code)
+)]
+
 use crate::regex::Regex;
+use crate::{Author, Date, Identifier, Scheme, Season};
 use std::sync::LazyLock;
 
 static DOI_URL_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^https?://(?:dx\.)?doi\.org/(.+)$").unwrap());
 
-static ISSN_SPLIT_REGEX: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\d{4}-\d{3}[\dX](?:\s*\([^)]+\))?").unwrap());
+static SERIAL_NUMBER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\d[\d-]{6,}[\dXx](?:\s*\([^)]+\))?").unwrap());
 
 /// Formats page numbers consistently, handling partial end page numbers
 ///
@@ -112,35 +129,225 @@ pub fn format_doi(doi_str: &str) -> Option<String> {
     }
 }
 
-/// Splits a string containing multiple ISSNs into a vector of individual ISSNs
+/// Recognise an identifier without a dedicated `Citation` field in `value`,
+/// such as a Web of Science `WOS:` number or a trial registry number in an
+/// accession number field.
+pub(crate) fn detect_identifier(value: &str) -> Option<Identifier> {
+    Identifier::detect(value).filter(|identifier| !identifier.scheme.has_dedicated_field())
+}
+
+/// Formats an ORCID iD as `0000-0002-1825-0097`, removing any `orcid.org`
+/// URL prefix, or returns `None` when it is malformed or its ISO 7064 MOD 11-2
+/// check digit is wrong.
 ///
 /// # Arguments
 ///
-/// * `issns` - String containing one or more ISSNs, possibly separated by newlines
-pub fn split_issns(issns: &str) -> Vec<String> {
-    let normalized = issns
+/// * `orcid_str` - The ORCID iD, with or without hyphens
+#[cfg(any(feature = "pubmed", feature = "wos", feature = "xml"))]
+pub fn format_orcid(orcid_str: &str) -> Option<String> {
+    let orcid = orcid_str.trim();
+    let orcid = orcid
+        .rfind("orcid.org/")
+        .map_or(orcid, |pos| &orcid[pos + "orcid.org/".len()..]);
+    let chars: Vec<char> = orcid
+        .chars()
+        .filter(|c| *c != '-')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if chars.len() != 16 || !chars[..15].iter().all(char::is_ascii_digit) {
+        return None;
+    }
+
+    let total = chars[..15]
+        .iter()
+        .fold(0, |total, c| (total + c.to_digit(10).unwrap_or(0)) * 2);
+    let check = match (12 - total % 11) % 11 {
+        10 => 'X',
+        digit => char::from_digit(digit, 10)?,
+    };
+    if chars[15] != check {
+        return None;
+    }
+
+    let digits: String = chars.into_iter().collect();
+    Some(format!(
+        "{}-{}-{}-{}",
+        &digits[0..4],
+        &digits[4..8],
+        &digits[8..12],
+        &digits[12..16]
+    ))
+}
+
+/// A value from a serial number field such as RIS `SN` or ENW `%@`, which
+/// mix ISSNs and ISBNs.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SerialNumber {
+    /// An ISSN. Valid ISSNs are written as `1234-5679` followed by any
+    /// qualifier such as `(Print)`; others are kept as written.
+    Issn { value: String, valid: bool },
+    /// An ISBN. Valid ISBNs are normalized to ISBN-13; others are kept as
+    /// written.
+    Isbn { value: String, valid: bool },
+    /// Text that is neither an ISSN nor an ISBN, kept as written.
+    Unknown(String),
+}
+
+/// Splits a serial number field into ISSNs, ISBNs and other values.
+///
+/// Values may be separated by newlines (including escaped `\n`), spaces,
+/// commas or semicolons. Labels such as `ISSN` are dropped, and a line with
+/// no ISSN- or ISBN-shaped value is returned whole as [`SerialNumber::Unknown`].
+pub(crate) fn split_serial_numbers(value: &str) -> Vec<SerialNumber> {
+    let normalized = value
         .replace("\\r\\n", "\n")
         .replace("\\r", "\n")
         .replace("\\n", "\n");
 
     let mut result = Vec::new();
     for line in normalized.split('\n') {
-        if line.trim().is_empty() {
+        let line = line.trim();
+        if line.is_empty() {
             continue;
         }
 
-        let matches: Vec<_> = ISSN_SPLIT_REGEX
-            .find_iter(line)
-            .map(|m| m.as_str().trim())
-            .collect();
-
-        if !matches.is_empty() {
-            result.extend(matches.into_iter().map(String::from));
+        let before = result.len();
+        result.extend(
+            SERIAL_NUMBER_REGEX
+                .find_iter(line)
+                .map(|m| classify_serial_number(m.as_str().trim())),
+        );
+        if result.len() == before {
+            result.push(SerialNumber::Unknown(line.to_string()));
         }
     }
     result
 }
 
+fn classify_serial_number(value: &str) -> SerialNumber {
+    let (number, qualifier) = match value.find('(') {
+        Some(pos) => (value[..pos].trim(), Some(value[pos..].trim())),
+        None => (value, None),
+    };
+    if let Some(issn) = normalize_issn(number) {
+        let valid = issn_check_digit_is_valid(&issn);
+        let value = match qualifier {
+            Some(qualifier) if valid => format!("{} {}", issn, qualifier),
+            _ if valid => issn,
+            _ => value.to_string(),
+        };
+        return SerialNumber::Issn { value, valid };
+    }
+
+    let compact: Vec<char> = number.chars().filter(|c| *c != '-').collect();
+    let isbn_shaped = match compact.len() {
+        10 => compact[..9].iter().all(char::is_ascii_digit),
+        13 => compact.iter().all(char::is_ascii_digit),
+        _ => false,
+    };
+    if !isbn_shaped {
+        return SerialNumber::Unknown(value.to_string());
+    }
+    match Scheme::Isbn.normalize(number) {
+        Some(isbn) => SerialNumber::Isbn {
+            value: isbn,
+            valid: true,
+        },
+        None => SerialNumber::Isbn {
+            value: value.to_string(),
+            valid: false,
+        },
+    }
+}
+
+/// Formats an ISSN as `1234-567X`, dropping an `ISSN` label and a qualifier
+/// such as `(Print)`, or returns `None` when the value is not shaped like an
+/// ISSN. The check digit is not verified.
+pub(crate) fn normalize_issn(value: &str) -> Option<String> {
+    let value = value.split('(').next().unwrap_or_default().trim();
+    let value = match value.get(..4) {
+        Some(label) if label.eq_ignore_ascii_case("ISSN") => value[4..].trim_start(),
+        _ => value,
+    };
+    let value = value.trim_start_matches(':').trim();
+    let compact = match value.as_bytes() {
+        [_, _, _, _, b'-', ..] if value.len() == 9 => format!("{}{}", &value[..4], &value[5..]),
+        _ => value.to_string(),
+    };
+    let bytes = compact.as_bytes();
+    (bytes.len() == 8
+        && bytes[..7].iter().all(u8::is_ascii_digit)
+        && (bytes[7].is_ascii_digit() || bytes[7].eq_ignore_ascii_case(&b'X')))
+    .then(|| format!("{}-{}", &compact[..4], compact[4..].to_ascii_uppercase()))
+}
+
+/// Checks the mod-11 check digit of an ISSN formatted by [`normalize_issn`].
+fn issn_check_digit_is_valid(issn: &str) -> bool {
+    let digits: Vec<u32> = issn.chars().filter_map(|c| c.to_digit(10)).collect();
+    let total: u32 = digits[..7]
+        .iter()
+        .zip((2..=8).rev())
+        .map(|(digit, weight)| digit * weight)
+        .sum();
+    let check = match issn.as_bytes()[8] {
+        b'X' => 10,
+        digit => u32::from(digit - b'0'),
+    };
+    (total + check).is_multiple_of(11)
+}
+
+/// Splits a string containing multiple ISSNs into a vector of individual
+/// ISSNs, ignoring ISBNs and other text.
+///
+/// ISSNs are returned as written, whether or not their check digit is valid.
+///
+/// # Arguments
+///
+/// * `issns` - String containing one or more ISSNs, possibly separated by newlines
+pub fn split_issns(issns: &str) -> Vec<String> {
+    split_serial_numbers(issns)
+        .into_iter()
+        .filter_map(|serial| match serial {
+            SerialNumber::Issn { value, .. } => Some(value),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Sorts a serial number field into ISSNs and ISBN identifiers.
+///
+/// ISBNs with a valid check digit are added to `identifiers`; everything
+/// else, including unrecognised text, is kept in `issn`. The values with a
+/// wrong check digit are returned so that parsers can warn about them.
+pub(crate) fn sort_serial_numbers(
+    value: &str,
+    issn: &mut Vec<String>,
+    identifiers: &mut Vec<Identifier>,
+) -> Vec<String> {
+    let mut invalid = Vec::new();
+    for serial in split_serial_numbers(value) {
+        match serial {
+            SerialNumber::Isbn { value, valid: true } => {
+                let isbn = Identifier {
+                    scheme: Scheme::Isbn,
+                    value,
+                };
+                if !identifiers.contains(&isbn) {
+                    identifiers.push(isbn);
+                }
+            }
+            SerialNumber::Issn { value, valid } | SerialNumber::Isbn { value, valid } => {
+                if !valid {
+                    invalid.push(value.clone());
+                }
+                issn.push(value);
+            }
+            SerialNumber::Unknown(value) => issn.push(value),
+        }
+    }
+    invalid
+}
+
 /// Helper function to parse author names in various formats
 pub fn parse_author_name(name: &str) -> (String, String) {
     // Handle formats like "Lastname, Firstname", "Lastname, FN", or "Lastname FN"
@@ -166,6 +373,75 @@ pub fn parse_author_name(name: &str) -> (String, String) {
     }
 }
 
+/// Format an author as `Family, Given Middle`, the inverse of [`parse_author_name`].
+///
+/// Authors without given or middle names are written as the bare family name.
+pub(crate) fn format_author_name(author: &Author) -> String {
+    let given = [author.given_name.as_deref(), author.middle_name.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if given.is_empty() {
+        author.name.trim().to_string()
+    } else {
+        format!("{}, {}", author.name.trim(), given)
+    }
+}
+
+/// Parse an EndNote contributor name with `role`.
+///
+/// EndNote marks corporate names with a trailing comma (`World Health
+/// Organization,`); these are kept whole as organizations, and an
+/// organization in the author role becomes a corporate author.
+#[cfg(any(feature = "enw", feature = "xml"))]
+pub(crate) fn parse_endnote_name(value: &str, role: crate::ContributorRole) -> Author {
+    let trimmed = value.trim();
+    if let Some(name) = trimmed.strip_suffix(',')
+        && !name.trim().is_empty()
+    {
+        return Author::organization(name.trim(), role);
+    }
+
+    let (family, given) = parse_author_name(trimmed);
+    let (given_name, middle_name) = if given.is_empty() {
+        (None, None)
+    } else {
+        split_given_and_middle(&given)
+    };
+    Author {
+        name: family,
+        given_name,
+        middle_name,
+        affiliations: Vec::new(),
+        role,
+        orcid: None,
+        is_organization: false,
+    }
+}
+
+/// Format a contributor for EndNote, marking organizations with a trailing
+/// comma so they are not split into family and given names on re-import.
+#[cfg(any(feature = "enw", feature = "xml"))]
+pub(crate) fn format_endnote_name(author: &Author) -> String {
+    if author.is_organization {
+        format!("{},", author.name.trim())
+    } else {
+        format_author_name(author)
+    }
+}
+
+/// Split contributors in source order into authors and other contributors,
+/// by [`ContributorRole::is_author`](crate::ContributorRole::is_author).
+pub(crate) fn partition_contributors(contributors: Vec<Author>) -> (Vec<Author>, Vec<Author>) {
+    contributors
+        .into_iter()
+        .partition(|contributor| contributor.role.is_author())
+}
+
 /// Split a full given name string into given name and middle name parts.
 ///
 /// Returns a tuple of (given_name, middle_name), where each is Option<String>.
@@ -189,9 +465,14 @@ pub fn split_given_and_middle(full_given: &str) -> (Option<String>, Option<Strin
 
 /// Parses PubMed format dates (e.g., "2020 Jun 9", "2023 May 30", "2023 Jan 3", "2023")
 ///
+/// Seasons (`2023 Winter`) and ranges (`2022 Dec-2023 Jan`, `2023 Jan-Feb`,
+/// `2021 Jun 5-12`) are also read; the end of a range leaves out the parts it
+/// shares with the start.
+///
 /// # Arguments
 ///
 /// * `date_str` - The date string to parse
+#[cfg(any(feature = "enw", feature = "ovid", feature = "pubmed", feature = "xml"))]
 pub fn parse_pubmed_date(date_str: &str) -> Option<Date> {
     let date_str = date_str.trim();
 
@@ -199,8 +480,13 @@ pub fn parse_pubmed_date(date_str: &str) -> Option<Date> {
         return None;
     }
 
+    let (start, end) = match date_str.split_once('-') {
+        Some((start, end)) => (start, Some(end)),
+        None => (date_str, None),
+    };
+
     // Split the date string into parts
-    let parts: Vec<&str> = date_str.split_whitespace().collect();
+    let parts: Vec<&str> = start.split_whitespace().collect();
 
     // First part should be year
     let year = if let Some(year_str) = parts.first() {
@@ -210,11 +496,16 @@ pub fn parse_pubmed_date(date_str: &str) -> Option<Date> {
     };
 
     let mut month = None;
+    let mut season = None;
     let mut day = None;
 
-    // Second part should be month (if present)
+    // Second part should be month or season (if present)
     if let Some(month_str) = parts.get(1) {
         month = parse_month_name(month_str);
+        season = month
+            .is_none()
+            .then(|| Season::from_name(month_str))
+            .flatten();
     }
 
     // Third part should be day (if present)
@@ -225,7 +516,108 @@ pub fn parse_pubmed_date(date_str: &str) -> Option<Date> {
         day = Some(parsed_day);
     }
 
-    Some(Date { year, month, day })
+    let mut date = Date {
+        year,
+        month,
+        day,
+        season,
+        end: None,
+    };
+    date.end = end
+        .and_then(|end| parse_pubmed_range_end(end, &date))
+        .map(Box::new);
+    Some(date)
+}
+
+/// Parses the end of a PubMed date range, taking the year and month it
+/// leaves out from `start`. Every part must be recognised.
+#[cfg(any(feature = "enw", feature = "ovid", feature = "pubmed", feature = "xml"))]
+fn parse_pubmed_range_end(end: &str, start: &Date) -> Option<Date> {
+    let mut parts = end.split_whitespace().peekable();
+    let year = match parts.peek() {
+        Some(year) if year.len() == 4 => {
+            let year = year.parse::<i32>().ok()?;
+            parts.next();
+            year
+        }
+        _ => start.year,
+    };
+
+    let mut date = Date {
+        year,
+        month: None,
+        day: None,
+        season: None,
+        end: None,
+    };
+    if let Some(part) = parts.peek() {
+        if let Some(month) = parse_month_name(part) {
+            date.month = Some(month);
+            parts.next();
+        } else if let Some(season) = Season::from_name(part) {
+            date.season = Some(season);
+            parts.next();
+        } else if year == start.year {
+            date.month = start.month;
+        }
+    }
+    if let Some(part) = parts.next() {
+        let day = part
+            .parse::<u8>()
+            .ok()
+            .filter(|day| (1..=31).contains(day))?;
+        date.month?;
+        date.day = Some(day);
+    }
+
+    let empty = date.month.is_none() && date.season.is_none() && year == start.year;
+    (parts.next().is_none() && !empty).then_some(date)
+}
+
+/// Format a date as `YYYY Mon D`, the inverse of [`parse_pubmed_date`].
+///
+/// PubMed dates cannot express a day without a month, so such dates are
+/// written as the bare year. Seasons are written by name and ranges as
+/// `start-end`, leaving out the parts of the end shared with the start.
+#[cfg(feature = "pubmed")]
+pub(crate) fn format_pubmed_date(date: &Date) -> String {
+    let start = format_pubmed_date_part(date, None);
+    match &date.end {
+        Some(end) => format!("{}-{}", start, format_pubmed_date_part(end, Some(date))),
+        None => start,
+    }
+}
+
+#[cfg(feature = "pubmed")]
+fn format_pubmed_date_part(date: &Date, start: Option<&Date>) -> String {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let month = date
+        .month
+        .and_then(|m| MONTHS.get(usize::from(m).checked_sub(1)?).copied())
+        .or_else(|| date.season.map(|season| season.as_str()));
+    let same_year = start.is_some_and(|start| start.year == date.year);
+    let same_month = same_year
+        && date.day.is_some()
+        && start.is_some_and(|start| start.month.is_some() && start.month == date.month);
+
+    let mut parts = Vec::new();
+    if !same_year {
+        parts.push(date.year.to_string());
+    }
+    if let Some(month) = month {
+        if !same_month {
+            parts.push(month.to_string());
+        }
+        if let Some(day) = date.day.filter(|_| date.month.is_some()) {
+            parts.push(day.to_string());
+        }
+    }
+    if parts.is_empty() {
+        parts.push(date.year.to_string());
+    }
+    parts.join(" ")
 }
 
 /// Parses RIS format dates (e.g., "1999/12/25/Christmas edition", "2023/05/30", "2023")
@@ -275,7 +667,74 @@ pub fn parse_ris_date(date_str: &str) -> Option<Date> {
         day = Some(parsed_day);
     }
 
-    Some(Date { year, month, day })
+    // The free-text fourth part may name a season
+    let season = parts
+        .get(3)
+        .filter(|_| month.is_none())
+        .and_then(|other| Season::from_name(other));
+
+    Some(Date {
+        year,
+        month,
+        day,
+        season,
+        end: None,
+    })
+}
+
+/// Format a date as `YYYY/MM/DD`, the inverse of [`parse_ris_date`].
+///
+/// Unknown components are left empty, e.g. `2023//09` for a date without month.
+/// A season is written in the free-text fourth part, e.g. `2023///Winter`.
+pub(crate) fn format_ris_date(date: &Date) -> String {
+    match (date.month, date.day) {
+        (None, None) => match date.season {
+            Some(season) => format!("{}///{}", date.year, season.as_str()),
+            None => date.year.to_string(),
+        },
+        (Some(month), None) => format!("{}/{:02}", date.year, month),
+        (month, Some(day)) => format!(
+            "{}/{}/{:02}",
+            date.year,
+            month.map(|m| format!("{:02}", m)).unwrap_or_default(),
+            day
+        ),
+    }
+}
+
+/// Parses compact `YYYYMMDD` dates, as used by PubMed's `DEP` and `LR` tags
+/// and ICTRP's `Date_registration3`.
+#[cfg(any(feature = "csv", feature = "pubmed", feature = "xml"))]
+pub(crate) fn parse_compact_date(value: &str) -> Option<Date> {
+    let trimmed = value.trim();
+    if trimmed.len() != 8 || !trimmed.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let month = trimmed[4..6]
+        .parse()
+        .ok()
+        .filter(|m| (1..=12).contains(m))?;
+    let day = trimmed[6..8]
+        .parse()
+        .ok()
+        .filter(|d| (1..=31).contains(d))?;
+    Some(Date {
+        year: trimmed[0..4].parse().ok()?,
+        month: Some(month),
+        day: Some(day),
+        season: None,
+        end: None,
+    })
+}
+
+/// Format a full date as `YYYYMMDD`, the inverse of [`parse_compact_date`].
+#[cfg(feature = "pubmed")]
+pub(crate) fn format_compact_date(date: &Date) -> Option<String> {
+    Some(format!(
+        "{:04}{:02}{:02}",
+        date.year, date.month?, date.day?
+    ))
 }
 
 /// Parses EndNote XML format dates from year attributes
@@ -287,7 +746,13 @@ pub fn parse_ris_date(date_str: &str) -> Option<Date> {
 /// * `day` - Day value (optional)
 pub fn parse_endnote_date(year: Option<i32>, month: Option<u8>, day: Option<u8>) -> Option<Date> {
     let year = year?;
-    Some(Date { year, month, day })
+    Some(Date {
+        year,
+        month,
+        day,
+        season: None,
+        end: None,
+    })
 }
 
 /// Parses EndNote Tagged / ENW dates from several common export shapes.
@@ -298,6 +763,7 @@ pub fn parse_endnote_date(year: Option<i32>, month: Option<u8>, day: Option<u8>)
 /// - `YYYY-MM-DD`
 /// - `YYYY Mon DD`
 /// - `Mon DD, YYYY`
+#[cfg(feature = "enw")]
 pub fn parse_enw_date(date_str: &str) -> Option<Date> {
     let date_str = date_str.trim();
     if date_str.is_empty() {
@@ -319,12 +785,25 @@ pub fn parse_enw_date(date_str: &str) -> Option<Date> {
 /// - `YYYY/MM`
 /// - `YYYY-MM-DD`
 /// - `YYYY/MM/DD`
+/// - `YYYY-21` to `YYYY-24`, the BibLaTeX codes for spring to winter
+/// - `start/end` ranges of the forms above, such as `2022-12/2023-01`
+#[cfg(any(feature = "bib", feature = "csl"))]
 pub fn parse_bib_date(date_str: &str) -> Option<Date> {
     let trimmed = date_str.trim();
     if trimmed.is_empty() {
         return None;
     }
 
+    if let Some((start, end)) = trimmed.split_once('/')
+        && end.len() >= 4
+        && end.as_bytes()[..4].iter().all(u8::is_ascii_digit)
+        && !end.contains('/')
+    {
+        let mut date = parse_bib_date(start)?;
+        date.end = Some(Box::new(parse_bib_date(end)?));
+        return Some(date);
+    }
+
     let delimiter = if trimmed.contains('-') {
         Some('-')
     } else if trimmed.contains('/') {
@@ -343,7 +822,19 @@ pub fn parse_bib_date(date_str: &str) -> Option<Date> {
     }
 
     let year = parts.first()?.trim().parse::<i32>().ok()?;
-    let month = parse_bib_month_token(parts.get(1)?.trim())?;
+    let month_str = parts.get(1)?.trim();
+    if parts.len() == 2
+        && let Some(season) = parse_bib_season_code(month_str)
+    {
+        return Some(Date {
+            year,
+            month: None,
+            day: None,
+            season: Some(season),
+            end: None,
+        });
+    }
+    let month = parse_bib_month_token(month_str)?;
 
     let day = if let Some(day_str) = parts.get(2) {
         let parsed = day_str.trim().parse::<u8>().ok()?;
@@ -356,6 +847,8 @@ pub fn parse_bib_date(date_str: &str) -> Option<Date> {
         year,
         month: Some(month),
         day,
+        season: None,
+        end: None,
     })
 }
 
@@ -368,6 +861,8 @@ pub fn parse_bib_year_month(year_str: &str, month_str: &str) -> Option<Date> {
         year,
         month: Some(month),
         day: None,
+        season: None,
+        end: None,
     })
 }
 
@@ -392,11 +887,13 @@ pub fn parse_year_only(year_str: &str) -> Option<Date> {
         year,
         month: None,
         day: None,
+        season: None,
+        end: None,
     })
 }
 
 /// Helper function to parse month names to month numbers
-fn parse_month_name(month_str: &str) -> Option<u8> {
+pub(crate) fn parse_month_name(month_str: &str) -> Option<u8> {
     match month_str.to_lowercase().as_str() {
         "jan" | "january" => Some(1),
         "feb" | "february" => Some(2),
@@ -414,6 +911,18 @@ fn parse_month_name(month_str: &str) -> Option<u8> {
     }
 }
 
+/// The season for a BibLaTeX season code, `21` (spring) to `24` (winter).
+#[cfg(any(feature = "bib", feature = "csl"))]
+fn parse_bib_season_code(code: &str) -> Option<Season> {
+    match code {
+        "21" => Some(Season::Spring),
+        "22" => Some(Season::Summer),
+        "23" => Some(Season::Autumn),
+        "24" => Some(Season::Winter),
+        _ => None,
+    }
+}
+
 fn parse_bib_month_token(month_str: &str) -> Option<u8> {
     if let Ok(month) = month_str.parse::<u8>() {
         return (1..=12).contains(&month).then_some(month);
@@ -451,6 +960,8 @@ fn parse_iso_like_date(date_str: &str) -> Option<Date> {
         year,
         month: Some(month),
         day: Some(day),
+        season: None,
+        end: None,
     })
 }
 
@@ -473,9 +984,52 @@ fn parse_month_day_year_date(date_str: &str) -> Option<Date> {
         year,
         month: Some(month),
         day: Some(day),
+        season: None,
+        end: None,
     })
 }
 
+/// Maps a citation type to an EndNote reference type name and `ref-type` number.
+///
+/// Accepts EndNote names as well as the type names produced by the RIS, PubMed
+/// and `.bib` parsers. Returns `None` for types without an EndNote equivalent.
+#[cfg(any(feature = "enw", feature = "xml"))]
+pub(crate) fn endnote_ref_type(citation_type: &str) -> Option<(&'static str, u8)> {
+    let ref_type = match citation_type.trim().to_ascii_lowercase().as_str() {
+        "artwork" | "art work" => ("Artwork", 2),
+        "audiovisual material" => ("Audiovisual Material", 3),
+        "bill" | "bill/resolution" => ("Bill", 4),
+        "book section" | "book chapter" | "incollection" | "inbook" => ("Book Section", 5),
+        "book" => ("Book", 6),
+        "case" => ("Case", 7),
+        "computer program" | "software" => ("Computer Program", 9),
+        "conference proceedings" | "conference proceeding" | "proceedings" => {
+            ("Conference Proceedings", 10)
+        }
+        "web page" | "electronic citation" | "online" => ("Web Page", 12),
+        "generic" | "misc" => ("Generic", 13),
+        "journal article" | "article" => ("Journal Article", 17),
+        "magazine article" => ("Magazine Article", 19),
+        "map" => ("Map", 20),
+        "film or broadcast" | "motion picture" => ("Film or Broadcast", 21),
+        "newspaper article" | "newspaper" => ("Newspaper Article", 23),
+        "patent" => ("Patent", 25),
+        "personal communication" => ("Personal Communication", 26),
+        "report" | "techreport" => ("Report", 27),
+        "edited book" => ("Edited Book", 28),
+        "statute" => ("Statute", 31),
+        "thesis" | "thesis/dissertation" | "phdthesis" | "mastersthesis" => ("Thesis", 32),
+        "unpublished work" | "unpublished" => ("Unpublished Work", 34),
+        "manuscript" => ("Manuscript", 36),
+        "electronic article" => ("Electronic Article", 43),
+        "electronic book" => ("Electronic Book", 44),
+        "conference paper" | "inproceedings" => ("Conference Paper", 47),
+        "dataset" | "data file" => ("Dataset", 59),
+        _ => return None,
+    };
+    Some(ref_type)
+}
+
 /// get the newline delimiter (e.g. CRLF for Windows, LF for Linux). of multi-line text.
 pub(crate) fn newline_delimiter_of(text: &str) -> &'static str {
     // find the first '\n', then check whether the character before it is '\r'
@@ -494,6 +1048,7 @@ pub(crate) fn newline_delimiter_of(text: &str) -> &'static str {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
     #[test]
     fn test_format_page_numbers() {
@@ -556,6 +1111,51 @@ mod tests {
         }
     }
 
+    #[rstest]
+    #[case("0028-0836", vec![SerialNumber::Issn { value: "0028-0836".to_string(), valid: true }])]
+    #[case("00280836(Print)", vec![SerialNumber::Issn { value: "0028-0836 (Print)".to_string(), valid: true }])]
+    #[case("1476-4687 (Electronic)", vec![SerialNumber::Issn { value: "1476-4687 (Electronic)".to_string(), valid: true }])]
+    #[case("2049-369x", vec![SerialNumber::Issn { value: "2049-369X".to_string(), valid: true }])]
+    #[case("0028-0837", vec![SerialNumber::Issn { value: "0028-0837".to_string(), valid: false }])]
+    #[case("978-3-8309-1689-5", vec![SerialNumber::Isbn { value: "9783830916895".to_string(), valid: true }])]
+    #[case("ISBN 0-306-40615-2 (pbk.)", vec![SerialNumber::Isbn { value: "9780306406157".to_string(), valid: true }])]
+    #[case("0-306-40615-1", vec![SerialNumber::Isbn { value: "0-306-40615-1".to_string(), valid: false }])]
+    #[case(
+        "0028-0836; 978-0-306-40615-7",
+        vec![
+            SerialNumber::Issn { value: "0028-0836".to_string(), valid: true },
+            SerialNumber::Isbn { value: "9780306406157".to_string(), valid: true },
+        ]
+    )]
+    #[case("Report 12", vec![SerialNumber::Unknown("Report 12".to_string())])]
+    #[case("123456789", vec![SerialNumber::Unknown("123456789".to_string())])]
+    fn test_split_serial_numbers(#[case] input: &str, #[case] expected: Vec<SerialNumber>) {
+        assert_eq!(split_serial_numbers(input), expected);
+    }
+
+    #[test]
+    fn test_split_issns_ignores_isbns() {
+        assert_eq!(
+            split_issns("978-3-8309-1689-5\n0028-0836"),
+            vec!["0028-0836"]
+        );
+    }
+
+    #[cfg(any(feature = "pubmed", feature = "wos", feature = "xml"))]
+    #[rstest]
+    #[case("0000-0002-1825-0097", Some("0000-0002-1825-0097"))]
+    #[case("https://orcid.org/0000-0002-1825-0097", Some("0000-0002-1825-0097"))]
+    #[case("http://orcid.org/0000000218250097", Some("0000-0002-1825-0097"))]
+    #[case(" 0000-0002-1694-233x ", Some("0000-0002-1694-233X"))]
+    #[case("0000-0001-5109-3700", Some("0000-0001-5109-3700"))]
+    #[case("0000-0002-1825-0098", None)]
+    #[case("0000-0002-1825", None)]
+    #[case("ABCD-0002-1825-0097", None)]
+    #[case("", None)]
+    fn test_format_orcid(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(format_orcid(input).as_deref(), expected);
+    }
+
     #[test]
     fn test_parse_author_name() {
         // Test standard format "LastName, FirstName"
@@ -648,6 +1248,7 @@ mod tests {
         // Test empty page_str
         assert_eq!(split_issns(""), Vec::<String>::new());
     }
+    #[cfg(any(feature = "enw", feature = "ovid", feature = "pubmed", feature = "xml"))]
     #[test]
     fn test_parse_pubmed_date() {
         // Test full date
@@ -671,6 +1272,43 @@ mod tests {
         // Test empty string
         let date = parse_pubmed_date("");
         assert!(date.is_none());
+
+        // Test season
+        let date = parse_pubmed_date("2023 Winter").unwrap();
+        assert_eq!(date.year, 2023);
+        assert_eq!(date.month, None);
+        assert_eq!(date.season, Some(Season::Winter));
+    }
+
+    #[cfg(any(feature = "enw", feature = "ovid", feature = "pubmed", feature = "xml"))]
+    #[rstest]
+    #[case("2023 Jan-Feb", (2023, Some(1), None), (2023, Some(2), None))]
+    #[case("2022 Dec-2023 Jan", (2022, Some(12), None), (2023, Some(1), None))]
+    #[case("2021 Jun 5-12", (2021, Some(6), Some(5)), (2021, Some(6), Some(12)))]
+    #[case("2021 Jun 28-Jul 3", (2021, Some(6), Some(28)), (2021, Some(7), Some(3)))]
+    #[case("2020-2021", (2020, None, None), (2021, None, None))]
+    fn test_parse_pubmed_date_range(
+        #[case] input: &str,
+        #[case] start: (i32, Option<u8>, Option<u8>),
+        #[case] end: (i32, Option<u8>, Option<u8>),
+    ) {
+        let date = parse_pubmed_date(input).unwrap();
+        assert_eq!((date.year, date.month, date.day), start);
+        let date_end = date.end.unwrap();
+        assert_eq!((date_end.year, date_end.month, date_end.day), end);
+    }
+
+    #[cfg(any(feature = "enw", feature = "ovid", feature = "pubmed", feature = "xml"))]
+    #[test]
+    fn test_parse_pubmed_date_season_range() {
+        let date = parse_pubmed_date("2023 Spring-Summer").unwrap();
+        assert_eq!(date.season, Some(Season::Spring));
+        assert_eq!(date.end.unwrap().season, Some(Season::Summer));
+
+        // An unrecognised range end is dropped, keeping the start
+        let date = parse_pubmed_date("2023 May-unknown").unwrap();
+        assert_eq!(date.month, Some(5));
+        assert_eq!(date.end, None);
     }
     #[test]
     fn test_parse_ris_date() {
@@ -715,6 +1353,8 @@ mod tests {
                     year: 2023,
                     month: Some(5),
                     day: Some(30),
+                    season: None,
+                    end: None,
                 }),
             ),
             (
@@ -725,6 +1365,8 @@ mod tests {
                     year: 2023,
                     month: None,
                     day: None,
+                    season: None,
+                    end: None,
                 }),
             ),
             (None, Some(12), Some(25), None),
@@ -735,6 +1377,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "enw")]
     #[test]
     fn test_parse_enw_date() {
         assert_eq!(
@@ -743,6 +1386,8 @@ mod tests {
                 year: 2023,
                 month: None,
                 day: None,
+                season: None,
+                end: None,
             })
         );
         assert_eq!(
@@ -751,6 +1396,8 @@ mod tests {
                 year: 2023,
                 month: Some(5),
                 day: Some(30),
+                season: None,
+                end: None,
             })
         );
         assert_eq!(
@@ -759,6 +1406,8 @@ mod tests {
                 year: 2023,
                 month: Some(5),
                 day: Some(30),
+                season: None,
+                end: None,
             })
         );
         assert_eq!(
@@ -767,11 +1416,14 @@ mod tests {
                 year: 2023,
                 month: Some(5),
                 day: Some(30),
+                season: None,
+                end: None,
             })
         );
         assert_eq!(parse_enw_date("not-a-date"), None);
     }
 
+    #[cfg(any(feature = "bib", feature = "csl"))]
     #[test]
     fn test_parse_bib_date() {
         assert_eq!(
@@ -780,6 +1432,8 @@ mod tests {
                 year: 2024,
                 month: Some(5),
                 day: Some(2),
+                season: None,
+                end: None,
             })
         );
         assert_eq!(
@@ -788,6 +1442,8 @@ mod tests {
                 year: 2024,
                 month: Some(5),
                 day: None,
+                season: None,
+                end: None,
             })
         );
         assert_eq!(
@@ -796,9 +1452,20 @@ mod tests {
                 year: 2024,
                 month: None,
                 day: None,
+                season: None,
+                end: None,
             })
         );
         assert_eq!(parse_bib_date("bad-date"), None);
+
+        let date = parse_bib_date("2023-24").unwrap();
+        assert_eq!((date.month, date.season), (None, Some(Season::Winter)));
+
+        let date = parse_bib_date("2022-12/2023-01").unwrap();
+        assert_eq!((date.year, date.month), (2022, Some(12)));
+        let end = date.end.unwrap();
+        assert_eq!((end.year, end.month), (2023, Some(1)));
+        assert_eq!(parse_bib_date("2022-12/bad"), None);
     }
 
     #[test]
@@ -809,6 +1476,8 @@ mod tests {
                 year: 2024,
                 month: Some(1),
                 day: None,
+                season: None,
+                end: None,
             })
         );
         assert_eq!(
@@ -817,6 +1486,8 @@ mod tests {
                 year: 2024,
                 month: Some(11),
                 day: None,
+                season: None,
+                end: None,
             })
         );
         assert_eq!(parse_bib_year_month("2024", "bogus"), None);
@@ -858,4 +1529,74 @@ mod tests {
         assert_eq!(newline_delimiter_of("hello\r\nworld"), "\r\n");
         assert_eq!(newline_delimiter_of("hello\r\nworld\r\n"), "\r\n");
     }
+
+    #[rstest]
+    #[case(Date { year: 2023, month: None, day: None, season: None, end: None }, "2023")]
+    #[case(Date { year: 2023, month: Some(5), day: None, season: None, end: None }, "2023/05")]
+    #[case(Date { year: 2023, month: Some(5), day: Some(9), season: None, end: None }, "2023/05/09")]
+    #[case(Date { year: 2023, month: None, day: Some(9), season: None, end: None }, "2023//09")]
+    #[case(Date { year: 2023, month: None, day: None, season: Some(Season::Winter), end: None }, "2023///Winter")]
+    fn test_format_ris_date(#[case] date: Date, #[case] expected: &str) {
+        assert_eq!(format_ris_date(&date), expected);
+        assert_eq!(parse_ris_date(&format_ris_date(&date)), Some(date));
+    }
+
+    #[cfg(feature = "pubmed")]
+    #[rstest]
+    #[case(Date { year: 2023, month: None, day: None, season: None, end: None }, "2023")]
+    #[case(Date { year: 2023, month: Some(5), day: None, season: None, end: None }, "2023 May")]
+    #[case(Date { year: 2020, month: Some(6), day: Some(9), season: None, end: None }, "2020 Jun 9")]
+    #[case(Date { year: 2023, month: None, day: None, season: Some(Season::Autumn), end: None }, "2023 Autumn")]
+    fn test_format_pubmed_date(#[case] date: Date, #[case] expected: &str) {
+        assert_eq!(format_pubmed_date(&date), expected);
+        assert_eq!(parse_pubmed_date(&format_pubmed_date(&date)), Some(date));
+    }
+
+    #[cfg(feature = "pubmed")]
+    #[rstest]
+    #[case("2023 Jan-Feb")]
+    #[case("2022 Dec-2023 Jan")]
+    #[case("2021 Jun 5-12")]
+    #[case("2021 Jun 28-Jul 3")]
+    #[case("2023 Spring-Summer")]
+    #[case("2022 Winter-2023 Spring")]
+    fn test_format_pubmed_date_range(#[case] input: &str) {
+        assert_eq!(
+            format_pubmed_date(&parse_pubmed_date(input).unwrap()),
+            input
+        );
+    }
+
+    #[cfg(any(feature = "csv", feature = "pubmed", feature = "xml"))]
+    #[test]
+    fn test_parse_compact_date() {
+        assert_eq!(
+            parse_compact_date("20260501"),
+            Some(Date {
+                year: 2026,
+                month: Some(5),
+                day: Some(1),
+                season: None,
+                end: None,
+            })
+        );
+        assert_eq!(parse_compact_date("20261301"), None);
+        assert_eq!(parse_compact_date("2026-05-01"), None);
+    }
+
+    #[cfg(any(feature = "enw", feature = "xml"))]
+    #[test]
+    fn test_endnote_ref_type() {
+        assert_eq!(
+            endnote_ref_type("Journal Article"),
+            Some(("Journal Article", 17))
+        );
+        assert_eq!(endnote_ref_type("article"), Some(("Journal Article", 17)));
+        assert_eq!(endnote_ref_type("Book Chapter"), Some(("Book Section", 5)));
+        assert_eq!(
+            endnote_ref_type("inproceedings"),
+            Some(("Conference Paper", 47))
+        );
+        assert_eq!(endnote_ref_type("Clinical Trial"), None);
+    }
 }
@@ -0,0 +1,386 @@
+//! Web of Science (ISI) parser implementation.
+//!
+//! Parses the Web of Science "Plain text file" export, where each field starts
+//! with a two-letter tag and records end with `ER`, and the "Tab delimited
+//! file" export, which uses the same tags as column headers.
+//!
+//! # Example
+//!
+//! ```
+//! use biblib::{CitationParser, WosParser};
+//!
+//! let input = r#"FN Clarivate Analytics Web of Science
+//! VR 1.0
+//! PT J
+//! AU Smith, J
+//!    Doe, JA
+//! AF Smith, John
+//!    Doe, Jane A.
+//! TI Example title that wraps
+//!    onto a second line
+//! SO JOURNAL OF EXAMPLES
+//! JI J. Examples
+//! PY 2020
+//! DI 10.1000/example
+//! UT WOS:000123456700001
+//! ER
+//!
+//! EF
+//! "#;
+//!
+//! let citations = WosParser::new().parse(input).unwrap();
+//! assert_eq!(citations[0].title, "Example title that wraps onto a second line");
+//! assert_eq!(citations[0].authors.len(), 2);
+//! assert_eq!(citations[0].accession_number.as_deref(), Some("WOS:000123456700001"));
+//! ```
+
+mod parse;
+
+use crate::error::{ParseError, ValueError};
+use crate::stream::chunk::parse_chunks;
+use crate::stream::line::LineChunks;
+use crate::stream::parse_whole;
+use crate::{
+    Citation, CitationFormat, CitationParser, OnError, ParseOptions, ParseReport, collect_report,
+};
+pub(crate) use parse::looks_like_wos;
+use parse::{
+    is_tab_delimited, parse_wos, parse_wos_with_warnings, wos_boundary, wos_records,
+    wos_records_with_warnings,
+};
+use std::io::{BufRead, BufReader, Cursor, Read};
+
+/// Parser for Web of Science plain-text and tab-delimited exports.
+///
+/// Tags are mapped as follows:
+///
+/// - `TI` → `title`, with wrapped lines joined by a space
+/// - `AF` (or `AU` when there are no full names) → `authors`; `C1` addresses
+///   that list their authors in brackets become affiliations, and `OI`
+///   entries set the ORCID iD of the matching author
+/// - `CA` → `authors` as organizations with role `CorporateAuthor`
+/// - `SO` → `journal`; `JI` (or `J9`) → `journal_abbr`
+/// - `PY` and `PD` → `date`
+/// - `DT` → `citation_type`, split on `;`
+/// - `VL`, `IS`, `BP`-`EP` → `volume`, `issue`, `pages`
+/// - `SN`, `EI` → `issn`; `BN` → `identifiers` as ISBNs
+/// - `DI` → `doi`, `PM` → `pmid`, `UT` → `accession_number`, and also to
+///   `identifiers` as a Web of Science accession number
+/// - `AB`, `DE`, `LA`, `PU` → `abstract_text`, `keywords`, `language`,
+///   `publisher`
+///
+/// All other tags, such as `TC` (times cited), `NR` and `CR` (cited
+/// references), are kept in `extra_fields` under their tag.
+#[derive(Debug, Clone, Default)]
+pub struct WosParser;
+
+impl WosParser {
+    /// Creates a new Web of Science parser instance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CitationParser for WosParser {
+    fn parse(&self, input: &str) -> Result<Vec<Citation>, ParseError> {
+        if input.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        parse_wos(input)
+    }
+
+    /// Reads one `PT` … `ER` record at a time from plain-text exports.
+    /// Tab-delimited exports are read whole and then yield one result per row.
+    fn parse_reader<'r, R>(
+        &self,
+        reader: R,
+    ) -> Box<dyn Iterator<Item = Result<Citation, ParseError>> + 'r>
+    where
+        R: Read + 'r,
+    {
+        // The first non-blank line tells the two layouts apart.
+        let mut reader = BufReader::new(reader);
+        let mut head = String::new();
+        loop {
+            match reader.read_line(&mut head) {
+                Ok(0) => break,
+                Ok(_) if head.trim().is_empty() => {}
+                Ok(_) => break,
+                Err(err) => {
+                    return Box::new(std::iter::once(Err(ParseError::at_line(
+                        head.lines().count() + 1,
+                        CitationFormat::WebOfScience,
+                        ValueError::Io(err),
+                    ))));
+                }
+            }
+        }
+
+        let tab_delimited = is_tab_delimited(&head);
+        let reader = Cursor::new(head.into_bytes()).chain(reader);
+        if tab_delimited {
+            parse_whole(reader, CitationFormat::WebOfScience, wos_records)
+        } else {
+            let chunks = LineChunks::new(reader, CitationFormat::WebOfScience, wos_boundary);
+            Box::new(parse_chunks(chunks, parse_wos))
+        }
+    }
+
+    /// Reports unparseable `PY`/`PD` dates and invalid `DI` DOIs as warnings.
+    fn parse_with_options(
+        &self,
+        input: &str,
+        options: &ParseOptions,
+    ) -> Result<ParseReport, ParseError> {
+        if options.on_error == OnError::Fail || is_tab_delimited(input) {
+            return collect_report(wos_records_with_warnings(input), options);
+        }
+
+        let chunks = LineChunks::new(input.as_bytes(), CitationFormat::WebOfScience, wos_boundary);
+        collect_report(parse_chunks(chunks, parse_wos_with_warnings), options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContributorRole, Scheme};
+    use pretty_assertions::assert_eq;
+
+    const PLAIN_TEXT: &str = r#"FN Clarivate Analytics Web of Science
+VR 1.0
+PT J
+AU Smith, J
+   Doe, JA
+AF Smith, John
+   Doe, Jane A.
+BE Brown, Alex
+CA Example Study Group
+TI A study of
+   wrapped titles
+SO JOURNAL OF EXAMPLES
+LA English
+DT Article; Proceedings Paper
+DE alpha; beta;
+   gamma
+ID KEYWORD PLUS
+AB An abstract that
+   wraps.
+C1 [Smith, John; Doe, Jane A.] Univ Example, Dept Tests, Springfield, USA.
+   Other Inst, Shelbyville, USA.
+OI Doe, Jane A./0000-0002-1825-0097; Roe, Rick/0000-0001-5109-3700
+CR Roe R, 2019, J EXAMPLES, V1, P1
+   Poe P, 2018, J TESTS, V2, P2
+NR 2
+TC 5
+PU EXAMPLE PUBLISHER
+SN 1234-5678
+EI 8765-4321
+J9 J EXAMPLES
+JI J. Examples
+BN 978-0-306-40615-7; not-an-isbn
+PD MAR 15
+PY 2020
+VL 12
+IS 3
+BP 100
+EP 110
+DI 10.1000/EXAMPLE
+PM 12345678
+UT WOS:000123456700001
+ER
+
+PT J
+AU Roe, R
+TI Second record
+PY 2019
+UT WOS:000123456700002
+ER
+
+EF
+"#;
+
+    #[test]
+    fn test_parse_plain_text() {
+        let citations = WosParser::new().parse(PLAIN_TEXT).unwrap();
+        assert_eq!(citations.len(), 2);
+
+        let citation = &citations[0];
+        assert_eq!(citation.title, "A study of wrapped titles");
+        assert_eq!(citation.citation_type, vec!["Article", "Proceedings Paper"]);
+        assert_eq!(citation.journal.as_deref(), Some("JOURNAL OF EXAMPLES"));
+        assert_eq!(citation.journal_abbr.as_deref(), Some("J. Examples"));
+        assert_eq!(citation.volume.as_deref(), Some("12"));
+        assert_eq!(citation.issue.as_deref(), Some("3"));
+        assert_eq!(citation.pages.as_deref(), Some("100-110"));
+        assert_eq!(citation.issn, vec!["1234-5678", "8765-4321"]);
+        assert_eq!(citation.identifier(Scheme::Isbn), Some("9780306406157"));
+        assert_eq!(
+            citation.identifier(Scheme::Wos),
+            Some("WOS:000123456700001")
+        );
+        assert_eq!(citation.extra_fields["BN"], vec!["not-an-isbn"]);
+        assert_eq!(citation.doi.as_deref(), Some("10.1000/example"));
+        assert_eq!(citation.pmid.as_deref(), Some("12345678"));
+        assert_eq!(
+            citation.accession_number.as_deref(),
+            Some("WOS:000123456700001")
+        );
+        assert_eq!(
+            citation.abstract_text.as_deref(),
+            Some("An abstract that wraps.")
+        );
+        assert_eq!(citation.keywords, vec!["alpha", "beta", "gamma"]);
+        assert_eq!(citation.language.as_deref(), Some("English"));
+        assert_eq!(citation.publisher.as_deref(), Some("EXAMPLE PUBLISHER"));
+
+        let date = citation.date.as_ref().unwrap();
+        assert_eq!((date.year, date.month, date.day), (2020, Some(3), Some(15)));
+
+        assert_eq!(citation.authors.len(), 3);
+        assert_eq!(citation.authors[1].name, "Doe");
+        assert_eq!(citation.authors[1].given_name.as_deref(), Some("Jane"));
+        assert_eq!(
+            citation.authors[1].affiliations,
+            vec!["Univ Example, Dept Tests, Springfield, USA."]
+        );
+        assert_eq!(citation.authors[0].orcid, None);
+        assert_eq!(
+            citation.authors[1].orcid.as_deref(),
+            Some("0000-0002-1825-0097")
+        );
+        assert_eq!(citation.authors[2].name, "Example Study Group");
+        assert_eq!(citation.authors[2].role, ContributorRole::CorporateAuthor);
+        assert!(citation.authors[2].is_organization);
+        assert_eq!(
+            citation.extra_fields["OI"],
+            vec!["Roe, Rick/0000-0001-5109-3700"]
+        );
+        assert!(!citation.extra_fields.contains_key("CA"));
+
+        assert_eq!(citation.extra_fields["TC"], vec!["5"]);
+        assert_eq!(citation.extra_fields["CR"].len(), 2);
+        assert_eq!(citation.extra_fields["J9"], vec!["J EXAMPLES"]);
+        assert_eq!(citation.extra_fields["ID"], vec!["KEYWORD PLUS"]);
+        assert_eq!(
+            citation.extra_fields["C1"],
+            vec!["Other Inst, Shelbyville, USA."]
+        );
+        assert!(!citation.extra_fields.contains_key("AU"));
+        assert!(!citation.extra_fields.contains_key("FN"));
+
+        assert_eq!(citation.contributors.len(), 1);
+        assert_eq!(citation.contributors[0].name, "Brown");
+        assert_eq!(citation.contributors[0].role, ContributorRole::Editor);
+        assert!(!citation.extra_fields.contains_key("BE"));
+
+        assert_eq!(citations[1].title, "Second record");
+        assert_eq!(citations[1].authors[0].name, "Roe");
+        assert_eq!(citations[1].citation_type, vec!["Journal"]);
+    }
+
+    #[test]
+    fn test_parse_tab_delimited() {
+        let input = concat!(
+            "\u{feff}PT\tAU\tAF\tTI\tSO\tC1\tDE\tPY\tDI\tUT\tTC\n",
+            "J\tSmith, J; Doe, JA\tSmith, John; Doe, Jane A.\tTab title\tJOURNAL\t",
+            "[Smith, John; Doe, Jane A.] Univ A; [Doe, Jane A.] Univ B\talpha; beta\t",
+            "2021\t10.1000/tab\tWOS:000000000000001\t7\n",
+        );
+
+        let citation = WosParser::new().parse(input).unwrap().remove(0);
+        assert_eq!(citation.title, "Tab title");
+        assert_eq!(citation.authors.len(), 2);
+        assert_eq!(citation.authors[0].affiliations, vec!["Univ A"]);
+        assert_eq!(citation.authors[1].affiliations, vec!["Univ A", "Univ B"]);
+        assert_eq!(citation.keywords, vec!["alpha", "beta"]);
+        assert_eq!(citation.date.as_ref().map(|date| date.year), Some(2021));
+        assert_eq!(
+            citation.accession_number.as_deref(),
+            Some("WOS:000000000000001")
+        );
+        assert_eq!(citation.extra_fields["TC"], vec!["7"]);
+    }
+
+    #[test]
+    fn test_missing_title_reports_line_and_span() {
+        let input = "FN Clarivate Analytics Web of Science\nVR 1.0\nPT J\nAU Smith, J\nER\n";
+        let err = WosParser::new().parse(input).unwrap_err();
+
+        assert_eq!(err.line, Some(3));
+        assert_eq!(err.format, CitationFormat::WebOfScience);
+        assert!(matches!(
+            err.error,
+            ValueError::MissingValue { key: "TI", .. }
+        ));
+        let span = err.span.expect("expected span");
+        assert!(span.end > span.start);
+    }
+
+    #[test]
+    fn test_malformed_line_is_error() {
+        let input = "PT J\nTI Title\nnot a tag\nER\n";
+        let err = WosParser::new().parse(input).unwrap_err();
+
+        assert_eq!(err.line, Some(3));
+        assert!(matches!(err.error, ValueError::Syntax(_)));
+    }
+
+    #[test]
+    fn test_parse_reader_continues_after_bad_record() {
+        let input = "FN Clarivate Analytics Web of Science\nVR 1.0\nPT J\nTI First\nER\n\nPT J\nAU Smith, J\nER\n\nPT J\nnot a tag\nER\n\nPT J\nTI Fourth\nER\n\nEF\n";
+        let streamed: Vec<_> = WosParser::new().parse_reader(input.as_bytes()).collect();
+
+        assert_eq!(streamed.len(), 4);
+        assert_eq!(streamed[0].as_ref().unwrap().title, "First");
+        assert_eq!(streamed[1].as_ref().unwrap_err().line, Some(7));
+        assert_eq!(streamed[2].as_ref().unwrap_err().line, Some(12));
+        assert_eq!(streamed[3].as_ref().unwrap().title, "Fourth");
+    }
+
+    #[test]
+    fn test_parse_reader_matches_parse() {
+        let tab_delimited =
+            "\nPT\tAU\tTI\tUT\nJ\tSmith, J\tFirst\tWOS:1\nJ\tDoe, J\tSecond\tWOS:2\n";
+        for input in [PLAIN_TEXT, tab_delimited] {
+            let parser = WosParser::new();
+            let streamed: Result<Vec<_>, _> = parser.parse_reader(input.as_bytes()).collect();
+            assert_eq!(streamed.unwrap(), parser.parse(input).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_parse_with_options_reports_warnings() {
+        use crate::{OnError, ParseOptions, WarningKind};
+
+        let plain_text = "FN Clarivate Analytics Web of Science\nVR 1.0\nPT J\nTI First\nPY 2020\nER\n\nPT J\nTI Second\nPY someday\nDI not a doi\nER\n\nEF\n";
+        let tab_delimited = "PT\tTI\tPY\tDI\nJ\tFirst\t2020\t\nJ\tSecond\tsomeday\tnot a doi\n";
+        for (input, line) in [(plain_text, 8), (tab_delimited, 3)] {
+            for on_error in [OnError::Fail, OnError::Collect] {
+                let options = ParseOptions::new().with_on_error(on_error);
+                let report = WosParser::new()
+                    .parse_with_options(input, &options)
+                    .unwrap();
+
+                assert_eq!(report.citations.len(), 2);
+                let kinds: Vec<_> = report.warnings.iter().map(|w| &w.kind).collect();
+                assert_eq!(
+                    kinds,
+                    vec![
+                        &WarningKind::UnparseableDate {
+                            key: "PY".to_string(),
+                            value: "someday".to_string()
+                        },
+                        &WarningKind::InvalidDoi {
+                            key: "DI".to_string(),
+                            value: "not a doi".to_string()
+                        },
+                    ]
+                );
+                assert_eq!(report.warnings[0].line, Some(line));
+            }
+        }
+    }
+}
@@ -0,0 +1,666 @@
+//! Web of Science parsing implementation.
+//!
+//! Both export layouts share the same two-letter field tags:
+//!
+//! - the plain-text export writes one `TAG value` line per field, with
+//!   continuation lines indented by three spaces, and ends records with `ER`
+//! - the tab-delimited export has a header row of tags and one record per row,
+//!   with multiple values in a cell separated by `; `
+//!
+//! Records from either layout are collected into the same raw tag map before
+//! being mapped onto [`Citation`] fields.
+
+use crate::error::{ParseError, ParseWarning, SourceSpan, ValueError, WarningKind, fields};
+use crate::stream::line::Boundary;
+use crate::utils::{
+    format_doi, format_orcid, format_page_numbers, parse_author_name, parse_bib_year_month,
+    parse_year_only, split_given_and_middle,
+};
+use crate::{Author, Citation, CitationFormat, ContributorRole, Date, Identifier, Scheme};
+use std::collections::HashMap;
+
+/// Tags whose continuation lines are separate values rather than wrapped text.
+const LIST_TAGS: &[&str] = &[
+    "AU", "AF", "BA", "BF", "BE", "CA", "GP", "ED", "C1", "C3", "CR",
+];
+
+/// File-level tags that are not part of any record.
+const FILE_TAGS: &[&str] = &["FN", "VR", "EF"];
+
+#[derive(Debug, Clone)]
+struct RawWosRecord {
+    data: HashMap<String, Vec<String>>,
+    start_line: usize,
+    record_span: SourceSpan,
+    warnings: Vec<ParseWarning>,
+}
+
+impl RawWosRecord {
+    fn new(start_line: usize, record_span: SourceSpan) -> Self {
+        Self {
+            data: HashMap::new(),
+            start_line,
+            record_span,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Add a warning located at the start of this record.
+    fn warn(&mut self, kind: WarningKind) {
+        self.warnings.push(
+            ParseWarning::at_line(self.start_line, CitationFormat::WebOfScience, kind)
+                .with_span(self.record_span.clone()),
+        );
+    }
+
+    fn add_data(&mut self, tag: &str, value: &str) {
+        let value = value.trim();
+        if !value.is_empty() {
+            self.data
+                .entry(tag.to_string())
+                .or_default()
+                .push(value.to_string());
+        }
+    }
+
+    /// Append a continuation line to the last value of `tag`.
+    fn continue_data(&mut self, tag: &str, value: &str) {
+        if LIST_TAGS.contains(&tag) {
+            self.add_data(tag, value);
+        } else if let Some(last) = self.data.get_mut(tag).and_then(|values| values.last_mut()) {
+            last.push(' ');
+            last.push_str(value.trim());
+        } else {
+            self.add_data(tag, value);
+        }
+    }
+
+    fn remove_all(&mut self, tag: &str) -> Vec<String> {
+        self.data.remove(tag).unwrap_or_default()
+    }
+
+    fn take_first(&mut self, tag: &str) -> Option<String> {
+        let mut values = self.data.remove(tag)?;
+        let value = values.remove(0);
+        if !values.is_empty() {
+            self.data.insert(tag.to_string(), values);
+        }
+        Some(value)
+    }
+}
+
+/// Quick check used by format auto-detection.
+pub(crate) fn looks_like_wos(content: &str) -> bool {
+    let Some(first_line) = content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .find(|line| !line.trim().is_empty())
+    else {
+        return false;
+    };
+
+    first_line.starts_with("FN ")
+        || is_tab_header(first_line)
+        || (first_line.starts_with("PT ") && content.lines().any(|line| line.trim_end() == "ER"))
+}
+
+/// Parse a Web of Science export into citations.
+pub(crate) fn parse_wos(content: &str) -> Result<Vec<Citation>, ParseError> {
+    wos_records(content).into_iter().collect()
+}
+
+/// Parse a Web of Science export into citations, keeping the warnings for
+/// each citation.
+pub(crate) fn parse_wos_with_warnings(
+    content: &str,
+) -> Result<Vec<(Citation, Vec<ParseWarning>)>, ParseError> {
+    wos_records_with_warnings(content).into_iter().collect()
+}
+
+/// Parse a Web of Science export into one result per record. An error that
+/// stops parsing altogether is the last item.
+pub(crate) fn wos_records(content: &str) -> Vec<Result<Citation, ParseError>> {
+    wos_records_with_warnings(content)
+        .into_iter()
+        .map(|record| record.map(|(citation, _)| citation))
+        .collect()
+}
+
+/// [`wos_records`], keeping the warnings for each citation.
+pub(crate) fn wos_records_with_warnings(
+    content: &str,
+) -> Vec<Result<(Citation, Vec<ParseWarning>), ParseError>> {
+    let records = if is_tab_delimited(content) {
+        parse_tab_delimited(content)
+    } else {
+        match parse_tagged(content) {
+            Ok(records) => records,
+            Err(err) => return vec![Err(err)],
+        }
+    };
+
+    records
+        .into_iter()
+        .map(into_citation_with_warnings)
+        .collect()
+}
+
+/// Whether the first non-blank line is a tab-delimited header row.
+pub(crate) fn is_tab_delimited(content: &str) -> bool {
+    content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .is_some_and(is_tab_header)
+}
+
+/// Record boundaries for [`WosParser::parse_reader`](crate::WosParser) on
+/// plain-text exports: `ER` ends a record.
+pub(crate) fn wos_boundary(_previous: Option<&str>, line: &str) -> Boundary {
+    if matches!(split_tag(line.trim_end()), Some(("ER", _))) {
+        Boundary::After
+    } else {
+        Boundary::Inside
+    }
+}
+
+fn is_tab_header(line: &str) -> bool {
+    let line = line.trim_start_matches('\u{feff}');
+    line.starts_with("PT\t") && line.split('\t').any(|tag| tag.trim() == "TI")
+}
+
+/// Parse the plain-text (tagged) export.
+fn parse_tagged(content: &str) -> Result<Vec<RawWosRecord>, ParseError> {
+    let text_ptr = content.as_ptr() as usize;
+    let mut records = Vec::new();
+    let mut current: Option<RawWosRecord> = None;
+    let mut last_tag: Option<String> = None;
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let line_start = raw_line.as_ptr() as usize - text_ptr;
+        let line_end = line_start + raw_line.len();
+        let line = raw_line.trim_start_matches('\u{feff}').trim_end();
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if line.starts_with(' ') {
+            let Some(record) = current.as_mut() else {
+                continue;
+            };
+            if let Some(tag) = &last_tag {
+                record.continue_data(tag, line);
+            }
+            record.record_span.end = line_end;
+            continue;
+        }
+
+        let Some((tag, value)) = split_tag(line) else {
+            return Err(ParseError::at_line(
+                line_number,
+                CitationFormat::WebOfScience,
+                ValueError::Syntax(format!("Malformed Web of Science line: '{}'", line)),
+            )
+            .with_span(SourceSpan::new(line_start, line_end)));
+        };
+
+        if tag == "ER" {
+            if let Some(mut record) = current.take() {
+                record.record_span.end = line_end;
+                records.push(record);
+            }
+            last_tag = None;
+            continue;
+        }
+        if FILE_TAGS.contains(&tag) {
+            continue;
+        }
+
+        let record = current.get_or_insert_with(|| {
+            RawWosRecord::new(line_number, SourceSpan::new(line_start, line_end))
+        });
+        record.add_data(tag, value);
+        record.record_span.end = line_end;
+        last_tag = Some(tag.to_string());
+    }
+
+    // A final record without an `ER` line is still accepted.
+    if let Some(record) = current {
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+/// Split `TAG value` where the tag is two uppercase letters or digits.
+fn split_tag(line: &str) -> Option<(&str, &str)> {
+    let tag = line.get(..2)?;
+    let valid_tag = tag.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+        && tag
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+    let rest = &line[2..];
+    (valid_tag && (rest.is_empty() || rest.starts_with(' '))).then(|| (tag, rest.trim()))
+}
+
+/// Parse the tab-delimited export, where the first row names the tags.
+fn parse_tab_delimited(content: &str) -> Vec<RawWosRecord> {
+    let text_ptr = content.as_ptr() as usize;
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let Some((_, header)) = lines.next() else {
+        return Vec::new();
+    };
+    let tags: Vec<&str> = header
+        .trim_start_matches('\u{feff}')
+        .split('\t')
+        .map(str::trim)
+        .collect();
+
+    lines
+        .map(|(index, line)| {
+            let line_start = line.as_ptr() as usize - text_ptr;
+            let mut record = RawWosRecord::new(
+                index + 1,
+                SourceSpan::new(line_start, line_start + line.len()),
+            );
+            for (tag, cell) in tags.iter().zip(line.split('\t')) {
+                if tag.is_empty() || FILE_TAGS.contains(tag) {
+                    continue;
+                }
+                if LIST_TAGS.contains(tag) {
+                    for value in split_outside_brackets(cell) {
+                        record.add_data(tag, value);
+                    }
+                } else {
+                    record.add_data(tag, cell);
+                }
+            }
+            record
+        })
+        .collect()
+}
+
+/// Split on `;` except inside `[...]`, so that `C1` cells such as
+/// `[Smith, J; Doe, J] Univ A; [Roe, R] Univ B` keep their author lists intact.
+fn split_outside_brackets(value: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, ch) in value.char_indices() {
+        match ch {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => {
+                parts.push(value[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(value[start..].trim());
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Convert a record to a citation, returning the warnings for dates and DOIs
+/// that could not be read.
+fn into_citation_with_warnings(
+    mut raw: RawWosRecord,
+) -> Result<(Citation, Vec<ParseWarning>), ParseError> {
+    let title = raw.take_first("TI").ok_or_else(|| {
+        ParseError::at_line(
+            raw.start_line,
+            CitationFormat::WebOfScience,
+            ValueError::MissingValue {
+                field: fields::TITLE,
+                key: "TI",
+            },
+        )
+        .with_span(raw.record_span.clone())
+    })?;
+
+    let mut citation_type: Vec<String> = raw
+        .remove_all("DT")
+        .iter()
+        .flat_map(|types| types.split(';'))
+        .map(str::trim)
+        .filter(|kind| !kind.is_empty())
+        .map(String::from)
+        .collect();
+    if citation_type.is_empty()
+        && let Some(kind) = raw.data.get("PT").and_then(|values| values.first())
+    {
+        citation_type.push(publication_type_name(kind).to_string());
+    }
+
+    let authors = extract_authors(&mut raw);
+    let contributors = raw
+        .remove_all("BE")
+        .iter()
+        .map(|name| parse_person(name, ContributorRole::Editor))
+        .collect();
+    let date = extract_date(&mut raw);
+    if date.is_none()
+        && let Some((key, value)) = ["PY", "PD"].into_iter().find_map(|key| {
+            let value = raw.data.get(key)?.first()?;
+            Some((key, value.clone()))
+        })
+    {
+        raw.warn(WarningKind::UnparseableDate {
+            key: key.to_string(),
+            value,
+        });
+    }
+    let pages = match (raw.take_first("BP"), raw.take_first("EP")) {
+        (Some(begin), Some(end)) => Some(format_page_numbers(&format!("{}-{}", begin, end))),
+        (Some(begin), None) => Some(begin),
+        (None, end) => end,
+    };
+    let doi = raw.take_first("DI").and_then(|value| {
+        let doi = format_doi(&value);
+        if doi.is_none() {
+            raw.warn(WarningKind::InvalidDoi {
+                key: "DI".to_string(),
+                value,
+            });
+        }
+        doi
+    });
+
+    let mut issn = raw.remove_all("SN");
+    issn.extend(raw.remove_all("EI"));
+    let accession_number = raw.take_first("UT");
+    let identifiers = extract_identifiers(&mut raw, accession_number.as_deref());
+
+    let keywords = raw
+        .remove_all("DE")
+        .iter()
+        .flat_map(|keywords| keywords.split(';'))
+        .map(str::trim)
+        .filter(|keyword| !keyword.is_empty())
+        .map(String::from)
+        .collect();
+
+    let citation = Citation {
+        citation_type,
+        title,
+        authors,
+        contributors,
+        journal: raw.take_first("SO"),
+        journal_abbr: raw.take_first("JI").or_else(|| raw.take_first("J9")),
+        date,
+        dates: Vec::new(),
+        volume: raw.take_first("VL"),
+        issue: raw.take_first("IS"),
+        pages,
+        issn,
+        doi,
+        accession_number,
+        pmid: raw.take_first("PM"),
+        pmc_id: None,
+        identifiers,
+        abstract_text: raw.take_first("AB"),
+        keywords,
+        urls: Vec::new(),
+        language: raw.take_first("LA"),
+        mesh_terms: Vec::new(),
+        publisher: raw.take_first("PU"),
+        extra_fields: raw.data,
+    };
+    Ok((citation, raw.warnings))
+}
+
+/// Authors from `AF` (full names), falling back to `AU`, followed by group
+/// authors from `CA`. Addresses in `C1` that name their authors in brackets
+/// are added as affiliations, and `OI` entries (`Name/ORCID`) set the ORCID
+/// iD of the matching author; other `C1` and `OI` values are left in
+/// `extra_fields`.
+fn extract_authors(raw: &mut RawWosRecord) -> Vec<Author> {
+    let full_names = raw.remove_all("AF");
+    let short_names = raw.remove_all("AU");
+    let names = if full_names.is_empty() {
+        short_names
+    } else {
+        full_names
+    };
+
+    let mut authors: Vec<Author> = names
+        .iter()
+        .map(|name| parse_person(name, ContributorRole::Author))
+        .collect();
+
+    let mut unmatched = Vec::new();
+    for address in raw.remove_all("C1") {
+        let Some((listed, location)) = address
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once(']'))
+        else {
+            unmatched.push(address);
+            continue;
+        };
+        let location = location.trim();
+        for listed_name in listed.split(';').map(str::trim) {
+            if let Some(index) = names.iter().position(|name| name == listed_name) {
+                authors[index].affiliations.push(location.to_string());
+            }
+        }
+    }
+    if !unmatched.is_empty() {
+        raw.data.insert("C1".to_string(), unmatched);
+    }
+
+    let mut unmatched = Vec::new();
+    for entry in raw
+        .remove_all("OI")
+        .iter()
+        .flat_map(|ids| ids.split(';'))
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let matched = entry.rsplit_once('/').and_then(|(name, id)| {
+            let orcid = format_orcid(id)?;
+            let person = parse_person(name, ContributorRole::Author);
+            let author = authors
+                .iter_mut()
+                .find(|author| author.orcid.is_none() && author.is_same_person(&person))?;
+            author.orcid = Some(orcid);
+            Some(())
+        });
+        if matched.is_none() {
+            unmatched.push(entry.to_string());
+        }
+    }
+    if !unmatched.is_empty() {
+        raw.data
+            .insert("OI".to_string(), vec![unmatched.join("; ")]);
+    }
+
+    authors.extend(
+        raw.remove_all("CA")
+            .into_iter()
+            .map(|name| Author::organization(name, ContributorRole::CorporateAuthor)),
+    );
+
+    authors
+}
+
+/// The `UT` accession number as a Web of Science identifier, and ISBNs from
+/// `BN`. `BN` values that are not valid ISBNs are left in `extra_fields`.
+fn extract_identifiers(raw: &mut RawWosRecord, accession_number: Option<&str>) -> Vec<Identifier> {
+    let mut identifiers: Vec<Identifier> = accession_number
+        .and_then(|ut| Identifier::new(Scheme::Wos, ut))
+        .into_iter()
+        .collect();
+    let mut unmatched = Vec::new();
+    for isbn in raw
+        .remove_all("BN")
+        .iter()
+        .flat_map(|isbns| isbns.split(';'))
+        .map(str::trim)
+        .filter(|isbn| !isbn.is_empty())
+    {
+        match Identifier::new(Scheme::Isbn, isbn) {
+            Some(identifier) => identifiers.push(identifier),
+            None => unmatched.push(isbn.to_string()),
+        }
+    }
+    if !unmatched.is_empty() {
+        raw.data.insert("BN".to_string(), unmatched);
+    }
+    identifiers
+}
+
+fn parse_person(name: &str, role: ContributorRole) -> Author {
+    let (family, given) = parse_author_name(name);
+    let (given_name, middle_name) = if given.is_empty() {
+        (None, None)
+    } else {
+        split_given_and_middle(&given)
+    };
+
+    Author {
+        name: family,
+        given_name,
+        middle_name,
+        affiliations: Vec::new(),
+        role,
+        orcid: None,
+        is_organization: false,
+    }
+}
+
+/// Combine `PY` with the month and day in `PD` (for example `JAN 15`,
+/// `JAN-FEB` or `2019 DEC`). Unparseable `PD` values are kept in
+/// `extra_fields`.
+fn extract_date(raw: &mut RawWosRecord) -> Option<Date> {
+    let publication_date = raw
+        .data
+        .get("PD")
+        .and_then(|values| values.first())
+        .cloned();
+    let tokens: Vec<&str> = publication_date
+        .as_deref()
+        .map(|pd| pd.split_whitespace().collect())
+        .unwrap_or_default();
+
+    let year_text = raw
+        .data
+        .get("PY")
+        .and_then(|values| values.first())
+        .cloned();
+    let year = year_text
+        .as_deref()
+        .or_else(|| {
+            tokens
+                .iter()
+                .copied()
+                .find(|token| token.len() == 4 && token.parse::<i32>().is_ok())
+        })
+        .and_then(parse_year_only)?
+        .year;
+    raw.remove_all("PY");
+
+    let month = tokens.iter().find_map(|token| {
+        let first_month = token.split('-').next().unwrap_or(token);
+        parse_bib_year_month(&year.to_string(), first_month).and_then(|date| date.month)
+    });
+    let day = month.and_then(|_| {
+        tokens.iter().find_map(|token| {
+            token
+                .parse::<u8>()
+                .ok()
+                .filter(|day| token.len() <= 2 && (1..=31).contains(day))
+        })
+    });
+    if month.is_some() {
+        raw.remove_all("PD");
+    }
+
+    Some(Date {
+        year,
+        month,
+        day,
+        season: None,
+        end: None,
+    })
+}
+
+/// Describe a one-letter `PT` publication type code.
+fn publication_type_name(code: &str) -> &str {
+    match code.trim() {
+        "J" => "Journal",
+        "B" => "Book",
+        "S" => "Series",
+        "P" => "Patent",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("PT J", Some(("PT", "J")))]
+    #[case("C1 [Smith, J] Univ", Some(("C1", "[Smith, J] Univ")))]
+    #[case("ER", Some(("ER", "")))]
+    #[case("Z9 5", Some(("Z9", "5")))]
+    #[case("pt J", None)]
+    #[case("PTJ", None)]
+    fn test_split_tag(#[case] line: &str, #[case] expected: Option<(&str, &str)>) {
+        assert_eq!(split_tag(line), expected);
+    }
+
+    #[test]
+    fn test_split_outside_brackets() {
+        assert_eq!(
+            split_outside_brackets("[Smith, J; Doe, J] Univ A; [Roe, R] Univ B"),
+            vec!["[Smith, J; Doe, J] Univ A", "[Roe, R] Univ B"]
+        );
+        assert_eq!(
+            split_outside_brackets("Smith, J; Doe, J;"),
+            vec!["Smith, J", "Doe, J"]
+        );
+    }
+
+    #[rstest]
+    #[case("JAN 15", "2020", Some(1), Some(15))]
+    #[case("JUL-AUG", "2020", Some(7), None)]
+    #[case("SPR", "2020", None, None)]
+    fn test_extract_date(
+        #[case] pd: &str,
+        #[case] py: &str,
+        #[case] month: Option<u8>,
+        #[case] day: Option<u8>,
+    ) {
+        let mut raw = RawWosRecord::new(1, SourceSpan::new(0, 0));
+        raw.add_data("PD", pd);
+        raw.add_data("PY", py);
+
+        let date = extract_date(&mut raw).unwrap();
+        assert_eq!(date.year, 2020);
+        assert_eq!(date.month, month);
+        assert_eq!(date.day, day);
+        assert_eq!(raw.data.contains_key("PD"), month.is_none());
+    }
+
+    #[test]
+    fn test_looks_like_wos() {
+        assert!(looks_like_wos(
+            "FN Clarivate Analytics Web of Science\nVR 1.0\n"
+        ));
+        assert!(looks_like_wos(
+            "\u{feff}PT\tAU\tTI\tUT\nJ\tSmith, J\tTitle\tWOS:1\n"
+        ));
+        assert!(looks_like_wos("PT J\nTI Title\nER\n"));
+        assert!(!looks_like_wos("TY  - JOUR\nTI  - Title\nER  -\n"));
+        assert!(!looks_like_wos("PT J\nTI Title\n"));
+    }
+}